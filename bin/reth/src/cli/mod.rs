@@ -8,6 +8,8 @@ use crate::{
 use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
+#[cfg(unix)]
+use reth_cli_commands::exex;
 use reth_cli_commands::{
     config_cmd, db, dump_genesis, import, init_cmd, init_state,
     node::{self, NoArgs},
@@ -171,6 +173,10 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| {
                 command.execute::<EthereumNode, _, _>(ctx, EthExecutorProvider::ethereum)
             }),
+            #[cfg(unix)]
+            Commands::Exex(command) => runner.run_command_until_exit(|ctx| {
+                command.execute::<EthereumNode, _, _>(ctx, EthExecutorProvider::ethereum)
+            }),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
@@ -218,6 +224,10 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command<C>),
+    /// Execution extension utilities
+    #[cfg(unix)]
+    #[command(name = "exex")]
+    Exex(exex::Command<C>),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command<C>),
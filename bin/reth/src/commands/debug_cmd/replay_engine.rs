@@ -1,8 +1,11 @@
 use crate::args::NetworkArgs;
+use alloy_primitives::U256;
 use clap::Parser;
 use eyre::Context;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
-use reth_beacon_consensus::{hooks::EngineHooks, BeaconConsensusEngine, EthBeaconConsensus};
+use reth_beacon_consensus::{
+    hooks::EngineHooks, BeaconConsensusEngine, BeaconForkChoiceUpdateError, EthBeaconConsensus,
+};
 use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
@@ -14,29 +17,45 @@ use reth_cli_util::get_secret_key;
 use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_db::DatabaseEnv;
-use reth_engine_util::engine_store::{EngineMessageStore, StoredEngineApiMessage};
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
 use reth_fs_util as fs;
+use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network::{BlockDownloaderProvider, NetworkHandle};
 use reth_network_api::NetworkInfo;
-use reth_node_api::{EngineApiMessageVersion, NodeTypesWithDBAdapter};
+use reth_node_api::{EngineApiMessageVersion, InvalidBlockHook, NodeTypesWithDBAdapter};
 use reth_node_ethereum::{EthEngineTypes, EthEvmConfig, EthExecutorProvider};
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_primitives::{SealedBlockWithSenders, SealedHeader};
 use reth_provider::{
     providers::{BlockchainProvider, ProviderNodeTypes},
-    CanonStateSubscriptions, ChainSpecProvider, ProviderFactory,
+    BlockReader, CanonStateSubscriptions, ChainSpecProvider, HeaderProvider, ProviderFactory,
+    StateProviderFactory, TransactionVariant,
 };
 use reth_prune::PruneModes;
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
 use reth_stages::Pipeline;
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::noop::NoopTransactionPool;
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::oneshot;
 use tracing::*;
 
 /// `reth debug replay-engine` command
 /// This script will read stored engine API messages and replay them by the timestamp.
-/// It does not require
+///
+/// The responses are compared with the recorded ones, if the responses have been recorded. The
+/// replay stops at the first divergence, and writes a report together with the witness and the
+/// state diff of the divergent block to the output directory. For the replay to be reproducible,
+/// the datadir should be a snapshot of the node at the time the first message was recorded.
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
     #[command(flatten)]
@@ -52,6 +71,12 @@ pub struct Command<C: ChainSpecParser> {
     /// The number of milliseconds between Engine API messages.
     #[arg(long = "interval", default_value_t = 1_000)]
     interval: u64,
+
+    /// The path to write the divergence report to.
+    ///
+    /// Defaults to `<DATADIR>/replay-engine`.
+    #[arg(long = "output-dir", value_name = "PATH")]
+    output_dir: Option<PathBuf>,
 }
 
 impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
@@ -76,6 +101,76 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
         Ok(network)
     }
 
+    /// Writes the report of a response that diverges from the recorded one to the output
+    /// directory.
+    ///
+    /// If the block the message refers to is known, it's re-executed on top of its parent to write
+    /// its state diff and witness next to the report.
+    ///
+    /// Returns the path of the report.
+    fn report_divergence<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
+        &self,
+        blockchain_db: &BlockchainProvider<N>,
+        output_directory: &Path,
+        message_path: &Path,
+        recorded_response: &StoredEngineApiResponse,
+        response: &StoredEngineApiResponse,
+        block: Option<SealedBlockWithSenders>,
+    ) -> eyre::Result<PathBuf> {
+        fs::create_dir_all(output_directory)?;
+
+        let execution = block
+            .as_ref()
+            .map(|block| self.write_block_diagnostics(blockchain_db, output_directory, block));
+        let report = serde_json::json!({
+            "message": message_path,
+            "blockNumber": block.as_ref().map(|block| block.number),
+            "blockHash": block.as_ref().map(|block| block.hash()),
+            "recordedResponse": recorded_response,
+            "response": response,
+            "executionError": execution.and_then(|result| result.err()).map(|err| err.to_string()),
+        });
+
+        let report_path = output_directory.join("divergence.json");
+        fs::write(&report_path, serde_json::to_vec_pretty(&report)?)?;
+        Ok(report_path)
+    }
+
+    /// Re-executes the block on top of its parent and writes its state diff and witness to the
+    /// output directory.
+    fn write_block_diagnostics<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
+        &self,
+        blockchain_db: &BlockchainProvider<N>,
+        output_directory: &Path,
+        block: &SealedBlockWithSenders,
+    ) -> eyre::Result<()> {
+        let parent_header = blockchain_db
+            .header(&block.parent_hash)?
+            .map(|header| SealedHeader::new(header, block.parent_hash))
+            .ok_or_else(|| eyre::eyre!("parent block not found: {}", block.parent_hash))?;
+
+        let state = blockchain_db.state_by_block_hash(block.parent_hash)?;
+        let executor = EthExecutorProvider::ethereum(blockchain_db.chain_spec())
+            .executor(StateProviderDatabase::new(state));
+        let output = executor.execute((&block.clone().unseal(), U256::MAX).into())?;
+
+        fs::write(
+            output_directory.join(format!("{}_{}.state_diff.json", block.number, block.hash())),
+            serde_json::to_vec(&output.state)?,
+        )?;
+
+        // The witness hook writes the witness of the re-executed block.
+        InvalidBlockWitnessHook::new(
+            blockchain_db.clone(),
+            EthEvmConfig::new(blockchain_db.chain_spec()),
+            output_directory.to_path_buf(),
+            None,
+        )
+        .on_invalid_block(&parent_header, block, &output, None);
+
+        Ok(())
+    }
+
     /// Execute `debug replay-engine` command
     pub async fn execute<N: CliNodeTypes<Engine = EthEngineTypes, ChainSpec = C::ChainSpec>>(
         self,
@@ -156,14 +251,19 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             let _ = tx.send(res);
         });
 
+        let output_directory =
+            self.output_dir.clone().unwrap_or_else(|| data_dir.data_dir().join("replay-engine"));
         let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
         for filepath in engine_api_store.engine_messages_iter()? {
             let contents =
                 fs::read(&filepath).wrap_err(format!("failed to read: {}", filepath.display()))?;
             let message = serde_json::from_slice(&contents)
                 .wrap_err(format!("failed to parse: {}", filepath.display()))?;
+            let recorded_response = engine_api_store
+                .read_response(&filepath)
+                .wrap_err(format!("failed to read response: {}", filepath.display()))?;
             debug!(target: "reth::cli", filepath = %filepath.display(), ?message, "Forwarding Engine API message");
-            match message {
+            let (response, block) = match message {
                 StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
                     let response = beacon_engine_handle
                         .fork_choice_updated(
@@ -171,15 +271,58 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
                             payload_attrs,
                             EngineApiMessageVersion::default(),
                         )
-                        .await?;
+                        .await
+                        .map_err(|error| match error {
+                            // the recorded response contains the error that was returned to the
+                            // consensus layer
+                            BeaconForkChoiceUpdateError::ForkchoiceUpdateError(error) => {
+                                error.to_string()
+                            }
+                            error => error.to_string(),
+                        });
                     debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    (
+                        StoredEngineApiResponse::forkchoice_updated(&response),
+                        blockchain_db.sealed_block_with_senders(
+                            state.head_block_hash.into(),
+                            TransactionVariant::WithHash,
+                        )?,
+                    )
                 }
                 StoredEngineApiMessage::NewPayload { payload, sidecar } => {
-                    let response = beacon_engine_handle.new_payload(payload, sidecar).await?;
+                    let block = try_into_sealed_block(payload.clone(), &sidecar)
+                        .ok()
+                        .and_then(|block| block.seal_with_senders());
+                    let response = beacon_engine_handle.new_payload(payload, sidecar).await;
                     debug!(target: "reth::cli", ?response, "Received for new payload");
+                    (StoredEngineApiResponse::new_payload(&response), block)
                 }
             };
 
+            if let Some(recorded_response) = recorded_response {
+                if !recorded_response.matches(&response) {
+                    let report_path = self.report_divergence(
+                        &blockchain_db,
+                        &output_directory,
+                        &filepath,
+                        &recorded_response,
+                        &response,
+                        block,
+                    )?;
+                    error!(
+                        target: "reth::cli",
+                        filepath = %filepath.display(),
+                        ?recorded_response,
+                        ?response,
+                        report_path = %report_path.display(),
+                        "Response diverges from the recorded response"
+                    );
+                    eyre::bail!("Replay diverged at {}", filepath.display())
+                }
+            } else {
+                warn!(target: "reth::cli", filepath = %filepath.display(), "No recorded response to compare against");
+            }
+
             // Pause before next message
             tokio::time::sleep(Duration::from_millis(self.interval)).await;
         }
@@ -6,6 +6,7 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 use clap::{Args, Parser};
 use reth::cli::Cli;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_exex::RemoteExEx;
 use reth_node_builder::{
     engine_tree_config::{
         TreeConfig, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD,
@@ -15,6 +16,7 @@ use reth_node_builder::{
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_provider::providers::BlockchainProvider2;
 use reth_tracing::tracing::warn;
+use std::path::PathBuf;
 use tracing::info;
 
 /// Parameters for configuring the engine
@@ -39,6 +41,11 @@ pub struct EngineArgs {
     /// Configure the target number of blocks to keep in memory.
     #[arg(long = "engine.memory-block-buffer-target", conflicts_with = "legacy", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
+
+    /// Enable state root task that computes the state root from a sparse trie while the block is
+    /// executed.
+    #[arg(long = "engine.state-root-task", conflicts_with = "legacy")]
+    pub state_root_task_enabled: bool,
 }
 
 impl Default for EngineArgs {
@@ -48,10 +55,36 @@ impl Default for EngineArgs {
             legacy: false,
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            state_root_task_enabled: false,
         }
     }
 }
 
+/// Parameters for configuring the built-in `ExEx`es
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Forward the notifications of the node to an `ExEx` running in a separate process, which
+    /// connects to the Unix socket at this path.
+    ///
+    /// Notifications are kept in a WAL in the data directory until the remote `ExEx` reports that
+    /// it processed them, and are replayed whenever it reconnects.
+    #[arg(long = "exex.remote-socket", value_name = "PATH")]
+    pub remote_socket: Option<PathBuf>,
+}
+
+/// Additional parameters of the reth binary
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+pub struct RethArgs {
+    /// Engine parameters
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
+    /// `ExEx` parameters
+    #[command(flatten)]
+    pub exex: ExExArgs,
+}
+
 fn main() {
     reth_cli_util::sigsegv_handler::install();
 
@@ -61,7 +94,8 @@ fn main() {
     }
 
     if let Err(err) =
-        Cli::<EthereumChainSpecParser, EngineArgs>::parse().run(|builder, engine_args| async move {
+        Cli::<EthereumChainSpecParser, RethArgs>::parse().run(|builder, args| async move {
+            let RethArgs { engine: engine_args, exex: exex_args } = args;
             if engine_args.experimental {
                 warn!(target: "reth::cli", "Experimental engine is default now, and the --engine.experimental flag is deprecated. To enable the legacy functionality, use --engine.legacy.");
             }
@@ -71,11 +105,19 @@ fn main() {
                 false => {
                     let engine_tree_config = TreeConfig::default()
                         .with_persistence_threshold(engine_args.persistence_threshold)
-                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target);
-                    let handle = builder
+                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target)
+                        .with_state_root_task(engine_args.state_root_task_enabled);
+                    let mut builder = builder
                         .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
                         .with_components(EthereumNode::components())
-                        .with_add_ons(EthereumAddOns::default())
+                        .with_add_ons(EthereumAddOns::default());
+                    if let Some(socket_path) = exex_args.remote_socket {
+                        builder = builder.install_exex("remote", |ctx| async move {
+                            let wal_directory = ctx.config.datadir().exex_remote_wal();
+                            Ok(RemoteExEx::from_context(ctx, socket_path, wal_directory)?.run())
+                        });
+                    }
+                    let handle = builder
                         .launch_with_fn(|builder| {
                             let launcher = EngineNodeLauncher::new(
                                 builder.task_executor().clone(),
@@ -89,7 +131,14 @@ fn main() {
                 }
                 true => {
                     info!(target: "reth::cli", "Running with legacy engine");
-                    let handle = builder.launch_node(EthereumNode::default()).await?;
+                    let mut builder = builder.node(EthereumNode::default());
+                    if let Some(socket_path) = exex_args.remote_socket {
+                        builder = builder.install_exex("remote", |ctx| async move {
+                            let wal_directory = ctx.config.datadir().exex_remote_wal();
+                            Ok(RemoteExEx::from_context(ctx, socket_path, wal_directory)?.run())
+                        });
+                    }
+                    let handle = builder.launch().await?;
                     handle.node_exit_future.await
                 }
             }
@@ -118,4 +167,18 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<RethArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, RethArgs::default());
+
+        let args = CommandParser::<RethArgs>::parse_from([
+            "reth",
+            "--exex.remote-socket",
+            "/tmp/exex.ipc",
+        ])
+        .args;
+        assert_eq!(args.exex.remote_socket, Some(PathBuf::from("/tmp/exex.ipc")));
+    }
 }
@@ -0,0 +1,241 @@
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use reth_fs_util::FsPathError;
+use reth_primitives::SealedBlock;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread::JoinHandle,
+};
+use tracing::warn;
+
+/// The default number of bad blocks that are kept around.
+pub const DEFAULT_BAD_BLOCKS_CAPACITY: usize = 128;
+
+/// A block that was rejected by the engine together with the reason it was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock {
+    /// The rejected block.
+    pub block: SealedBlock,
+    /// The validation error the block was rejected with.
+    pub error: String,
+}
+
+impl BadBlock {
+    /// Returns the hash of the rejected block.
+    pub const fn hash(&self) -> B256 {
+        self.block.hash()
+    }
+}
+
+/// A bounded ring buffer of recently rejected blocks.
+///
+/// Once the buffer is full, the oldest entry is evicted. If a path is configured, the buffer is
+/// written to disk by a background thread after every insert and restored from it on startup, so
+/// bad blocks survive node restarts.
+///
+/// This type is cheap to clone, all clones share the same buffer.
+#[derive(Debug, Clone)]
+pub struct BadBlocks {
+    inner: Arc<BadBlocksInner>,
+}
+
+impl BadBlocks {
+    /// Creates a new in-memory buffer that keeps at most `capacity` bad blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self::new_with_path(capacity, None, VecDeque::new())
+    }
+
+    /// Creates a new buffer that keeps at most `capacity` bad blocks and persists them to the
+    /// given file.
+    ///
+    /// If the file already exists, previously recorded bad blocks are loaded from it.
+    pub fn with_persistence(capacity: usize, path: PathBuf) -> Result<Self, FsPathError> {
+        let mut blocks: VecDeque<BadBlock> =
+            if path.exists() { reth_fs_util::read_json_file(&path)? } else { VecDeque::new() };
+        while blocks.len() > capacity {
+            blocks.pop_front();
+        }
+        Ok(Self::new_with_path(capacity, Some(path), blocks))
+    }
+
+    fn new_with_path(capacity: usize, path: Option<PathBuf>, blocks: VecDeque<BadBlock>) -> Self {
+        let writer = path
+            .clone()
+            .filter(|_| capacity > 0)
+            .map(|path| BadBlocksWriter::spawn(capacity, path, blocks.clone()));
+        Self {
+            inner: Arc::new(BadBlocksInner { capacity, path, blocks: RwLock::new(blocks), writer }),
+        }
+    }
+
+    /// Returns the maximum number of bad blocks that are kept.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Returns the file the bad blocks are persisted to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    /// Records a rejected block.
+    ///
+    /// Blocks that are already tracked are ignored.
+    pub fn insert(&self, block: SealedBlock, error: impl ToString) {
+        if self.inner.capacity == 0 {
+            return
+        }
+
+        let mut blocks = self.inner.blocks.write();
+        if blocks.iter().any(|bad| bad.hash() == block.hash()) {
+            return
+        }
+        if blocks.len() == self.inner.capacity {
+            blocks.pop_front();
+        }
+        let bad_block = BadBlock { block, error: error.to_string() };
+        if let Some(writer) = &self.inner.writer {
+            writer.send(bad_block.clone());
+        }
+        blocks.push_back(bad_block);
+    }
+
+    /// Returns the bad block with the given hash, if it is tracked.
+    pub fn get(&self, hash: &B256) -> Option<BadBlock> {
+        self.inner.blocks.read().iter().find(|bad| bad.hash() == *hash).cloned()
+    }
+
+    /// Returns all tracked bad blocks, most recent first.
+    pub fn blocks(&self) -> Vec<BadBlock> {
+        self.inner.blocks.read().iter().rev().cloned().collect()
+    }
+
+    /// Returns the number of tracked bad blocks.
+    pub fn len(&self) -> usize {
+        self.inner.blocks.read().len()
+    }
+
+    /// Returns `true` if no bad blocks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.inner.blocks.read().is_empty()
+    }
+}
+
+impl Default for BadBlocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_BAD_BLOCKS_CAPACITY)
+    }
+}
+
+#[derive(Debug)]
+struct BadBlocksInner {
+    /// Maximum number of tracked bad blocks.
+    capacity: usize,
+    /// Optional file the bad blocks are persisted to.
+    path: Option<PathBuf>,
+    /// The tracked bad blocks, oldest first.
+    blocks: RwLock<VecDeque<BadBlock>>,
+    /// Persists the bad blocks to [`Self::path`], if configured.
+    writer: Option<BadBlocksWriter>,
+}
+
+/// Handle to the thread that writes the bad blocks to disk, so that the engine doesn't block on
+/// file IO when it rejects a block.
+///
+/// The thread keeps its own copy of the buffer and rewrites the file with it whenever new bad
+/// blocks are recorded. Dropping the handle waits for the pending writes.
+#[derive(Debug)]
+struct BadBlocksWriter {
+    /// Sends newly recorded bad blocks to the thread.
+    tx: Option<mpsc::Sender<BadBlock>>,
+    /// Handle of the thread, joined on drop.
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BadBlocksWriter {
+    fn spawn(capacity: usize, path: PathBuf, mut blocks: VecDeque<BadBlock>) -> Self {
+        let (tx, rx) = mpsc::channel::<BadBlock>();
+        let handle = std::thread::Builder::new()
+            .name("Bad Blocks Writer".to_string())
+            .spawn(move || {
+                while let Ok(bad_block) = rx.recv() {
+                    // write once for all bad blocks that were recorded in the meantime
+                    for bad_block in std::iter::once(bad_block).chain(rx.try_iter()) {
+                        if blocks.len() == capacity {
+                            blocks.pop_front();
+                        }
+                        blocks.push_back(bad_block);
+                    }
+                    if let Err(err) = reth_fs_util::atomic_write_file(&path, |file| {
+                        serde_json::to_writer(file, &blocks)
+                    }) {
+                        warn!(target: "chain_state::bad_blocks", %err, "Failed to persist bad blocks");
+                    }
+                }
+            })
+            .unwrap();
+        Self { tx: Some(tx), handle: Some(handle) }
+    }
+
+    fn send(&self, bad_block: BadBlock) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(bad_block);
+        }
+    }
+}
+
+impl Drop for BadBlocksWriter {
+    fn drop(&mut self) {
+        // closing the channel stops the thread once it wrote the remaining bad blocks
+        self.tx.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::SealedHeader;
+
+    fn block(number: u64) -> SealedBlock {
+        let header = SealedHeader::seal(alloy_consensus::Header { number, ..Default::default() });
+        SealedBlock::new(header, Default::default())
+    }
+
+    #[test]
+    fn evicts_oldest() {
+        let bad_blocks = BadBlocks::new(2);
+        bad_blocks.insert(block(1), "one");
+        bad_blocks.insert(block(2), "two");
+        bad_blocks.insert(block(2), "two again");
+        assert_eq!(bad_blocks.len(), 2);
+
+        bad_blocks.insert(block(3), "three");
+        let numbers = bad_blocks.blocks().iter().map(|bad| bad.block.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![3, 2]);
+        assert!(bad_blocks.get(&block(1).hash()).is_none());
+        assert_eq!(bad_blocks.get(&block(2).hash()).unwrap().error, "two");
+    }
+
+    #[test]
+    fn persists_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad_blocks.json");
+
+        let bad_blocks = BadBlocks::with_persistence(2, path.clone()).unwrap();
+        bad_blocks.insert(block(1), "one");
+        bad_blocks.insert(block(2), "two");
+        bad_blocks.insert(block(3), "three");
+        let blocks = bad_blocks.blocks();
+
+        // dropping the buffer waits for the pending writes
+        drop(bad_blocks);
+        let restored = BadBlocks::with_persistence(1, path).unwrap();
+        assert_eq!(restored.blocks(), blocks[..1]);
+    }
+}
@@ -358,6 +358,27 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.in_memory_state.update_metrics();
     }
 
+    /// Removes all blocks above the given height from the in memory state.
+    ///
+    /// This is used to unwind the canonical chain to an ancestor of the current head. The pending
+    /// state is removed as well, because it builds on top of the removed blocks.
+    pub fn remove_blocks_above(&self, height: u64) {
+        {
+            // acquire locks, starting with the numbers lock
+            let mut numbers = self.inner.in_memory_state.numbers.write();
+            let mut blocks = self.inner.in_memory_state.blocks.write();
+
+            for (_, hash) in numbers.split_off(&(height + 1)) {
+                blocks.remove(&hash);
+            }
+
+            self.inner.in_memory_state.pending.send_modify(|p| {
+                p.take();
+            });
+        }
+        self.inner.in_memory_state.update_metrics();
+    }
+
     /// Returns in memory state corresponding the given hash.
     pub fn state_by_hash(&self, hash: B256) -> Option<Arc<BlockState<N>>> {
         self.inner.in_memory_state.state_by_hash(hash)
@@ -1233,6 +1254,28 @@ mod tests {
         assert_eq!(state.inner.in_memory_state.block_count(), 1);
     }
 
+    #[test]
+    fn test_in_memory_state_remove_blocks_above() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let blocks = test_block_builder.get_executed_blocks(0..4).collect::<Vec<_>>();
+        state.update_chain(NewCanonicalChain::Commit { new: blocks.clone() });
+        state.set_pending_block(
+            test_block_builder.get_executed_block_with_number(4, blocks[3].block().hash()),
+        );
+
+        state.remove_blocks_above(1);
+
+        assert_eq!(state.inner.in_memory_state.block_count(), 2);
+        assert_eq!(
+            state.head_state().unwrap().block_ref().block().hash(),
+            blocks[1].block().hash()
+        );
+        assert!(state.state_by_hash(blocks[2].block().hash()).is_none());
+        assert!(state.state_by_number(3).is_none());
+        assert!(state.pending_state().is_none());
+    }
+
     #[test]
     fn test_in_memory_state_set_pending_block() {
         let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
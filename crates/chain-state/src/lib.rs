@@ -21,6 +21,9 @@ pub use notifications::{
     ForkChoiceSubscriptions,
 };
 
+mod bad_blocks;
+pub use bad_blocks::{BadBlock, BadBlocks, DEFAULT_BAD_BLOCKS_CAPACITY};
+
 mod memory_overlay;
 pub use memory_overlay::{MemoryOverlayStateProvider, MemoryOverlayStateProviderRef};
 
@@ -3,11 +3,15 @@ use alloy_primitives::{hex, BlockHash};
 use clap::Parser;
 use reth_db::{
     static_file::{
-        ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+        AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask,
+        ReceiptMask, StorageChangeSetMask, TransactionMask,
     },
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
-use reth_db_api::table::{Decompress, DupSort, Table};
+use reth_db_api::{
+    models::{StoredAccountChangeSet, StoredStorageChangeSet},
+    table::{Decompress, DupSort, Table},
+};
 use reth_db_common::DbTool;
 use reth_node_builder::NodeTypesWithDB;
 use reth_provider::{providers::ProviderNodeTypes, StaticFileProviderFactory};
@@ -73,6 +77,12 @@ impl Command {
                         table_key::<tables::Receipts>(&key)?,
                         <ReceiptMask<<Receipts as Table>::Value>>::MASK,
                     ),
+                    StaticFileSegment::AccountChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, AccountChangeSetMask::MASK)
+                    }
+                    StaticFileSegment::StorageChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, StorageChangeSetMask::MASK)
+                    }
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -114,6 +124,16 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let changeset =
+                                        StoredAccountChangeSet::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let changeset =
+                                        StoredStorageChangeSet::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
                             }
                         }
                     }
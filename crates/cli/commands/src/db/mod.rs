@@ -11,6 +11,7 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod static_file;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -46,6 +47,8 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Static file maintenance commands
+    StaticFile(static_file::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -130,6 +133,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::StaticFile(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,30 @@
+use clap::{Parser, Subcommand};
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::ProviderFactory;
+
+mod recompress;
+
+/// The arguments for the `reth db static-file` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[clap(subcommand)]
+    subcommand: Subcommands,
+}
+
+impl Command {
+    /// Execute `db static-file` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        match self.subcommand {
+            Subcommands::Recompress(command) => command.execute(provider_factory),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Recompresses static files with per-segment zstd dictionaries
+    Recompress(recompress::Command),
+}
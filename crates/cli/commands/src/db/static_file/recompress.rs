@@ -0,0 +1,391 @@
+use ahash::RandomState;
+use clap::Parser;
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use reth_db::static_file::iter_static_files;
+use reth_fs_util as fs;
+use reth_nippy_jar::{
+    compression::{Compression, Compressors, Zstd},
+    NippyJar, NippyJarCursor, NippyJarWriter,
+};
+use reth_node_builder::{NodePrimitives, NodeTypesWithDB};
+use reth_provider::{
+    providers::{StaticFileProvider, STATIC_FILE_BACKUP_DIR},
+    ProviderFactory, StaticFileProviderFactory,
+};
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use std::{
+    hash::{BuildHasher, Hasher},
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Directory inside the static files directory where recompressed files are written to, before
+/// they replace the original ones.
+const STAGING_DIR: &str = "recompress-staging";
+
+/// The arguments for the `reth db static-file recompress` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Static file segments to recompress. Defaults to all segments.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// Maximum size in bytes of each trained column dictionary.
+    #[arg(long, default_value_t = 112_640)]
+    max_dict_size: usize,
+
+    /// Zstd compression level. A level of `0` uses zstd's default.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(i32).range(-7..=22))]
+    level: i32,
+
+    /// Maximum number of rows sampled across all static files of a segment to train its
+    /// dictionaries.
+    #[arg(long, default_value_t = 100_000)]
+    max_samples: usize,
+}
+
+impl Command {
+    /// Execute `db static-file recompress` command
+    ///
+    /// Only complete static files are recompressed, since the highest one of each segment is
+    /// still being appended to.
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        self.recompress(&provider_factory.static_file_provider())
+    }
+
+    fn recompress<N: NodePrimitives>(
+        &self,
+        static_file_provider: &StaticFileProvider<N>,
+    ) -> eyre::Result<()> {
+        let directory = static_file_provider.directory();
+        let staging_dir = directory.join(STAGING_DIR);
+        // An interrupted swap of a previous run was already rolled back when the static files
+        // were opened with read-write access.
+        let backup_dir = directory.join(STATIC_FILE_BACKUP_DIR);
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        let static_files = iter_static_files(directory)?;
+        let segments = if self.segments.is_empty() {
+            static_files.keys().copied().sorted().collect()
+        } else {
+            self.segments.clone()
+        };
+
+        let (mut total_before, mut total_after) = (0, 0);
+        for segment in segments {
+            let ranges = static_files.get(&segment).map(Vec::as_slice).unwrap_or_default();
+            let jars = ranges
+                .split_last()
+                .map(|(_, complete)| complete)
+                .unwrap_or_default()
+                .iter()
+                .map(|(block_range, _)| {
+                    let fixed_block_range =
+                        static_file_provider.find_fixed_range(block_range.start());
+                    NippyJar::<SegmentHeader>::load(
+                        &directory.join(segment.filename(&fixed_block_range)),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if jars.is_empty() {
+                println!("{segment}: no complete static files to recompress");
+                continue
+            }
+
+            info!(target: "reth::cli", %segment, files = jars.len(), "Training dictionaries");
+            let compressor = self.train_dictionaries(segment, &jars)?;
+
+            fs::create_dir_all(&staging_dir)?;
+            let (mut segment_before, mut segment_after) = (0, 0);
+            for jar in jars {
+                let (before, after) = recompress_jar(jar, &compressor, &staging_dir, &backup_dir)?;
+                segment_before += before;
+                segment_after += after;
+            }
+            fs::remove_dir_all(&staging_dir)?;
+
+            print_saved(segment.as_ref(), segment_before, segment_after);
+            total_before += segment_before;
+            total_after += segment_after;
+        }
+
+        print_saved("Total", total_before, total_after);
+
+        Ok(())
+    }
+
+    /// Trains one zstd dictionary per column from rows sampled evenly across all `jars`.
+    fn train_dictionaries(
+        &self,
+        segment: StaticFileSegment,
+        jars: &[NippyJar<SegmentHeader>],
+    ) -> eyre::Result<Compressors> {
+        let total_rows = jars.iter().map(|jar| jar.rows()).sum::<usize>();
+        let step = total_rows.div_ceil(self.max_samples.max(1)).max(1);
+
+        let mut samples = vec![Vec::new(); segment.columns()];
+        let mut first_row = 0;
+        for jar in jars {
+            let mut cursor = NippyJarCursor::new(jar)?;
+            let offset = (step - first_row % step) % step;
+            for row_number in (offset..jar.rows()).step_by(step) {
+                let Some(row) = cursor.row_by_number(row_number)? else { break };
+                for (column, value) in row.into_iter().enumerate() {
+                    samples[column].push(value.to_vec());
+                }
+            }
+            first_row += jar.rows();
+        }
+
+        let mut zstd =
+            Zstd::new(true, self.max_dict_size, segment.columns()).with_level(self.level);
+        zstd.prepare_compression(samples).map_err(|err| {
+            eyre::eyre!("failed to train {segment} dictionaries from {total_rows} rows: {err}")
+        })?;
+
+        Ok(Compressors::Zstd(zstd))
+    }
+}
+
+/// Rewrites `jar` with `compressor` and replaces the original files once the checksum of its
+/// decompressed content matches. The original is kept if the rewrite is not smaller.
+///
+/// Returns the total size of the jar files before and after.
+fn recompress_jar(
+    jar: NippyJar<SegmentHeader>,
+    compressor: &Compressors,
+    staging_dir: &Path,
+    backup_dir: &Path,
+) -> eyre::Result<(u64, u64)> {
+    let file_name = jar.data_path().file_name().expect("static file to have a file name");
+    let staged =
+        NippyJar::new(jar.columns(), &staging_dir.join(file_name), jar.user_header().clone())
+            .with_compressor(compressor.clone());
+
+    let mut writer = NippyJarWriter::new(staged)?;
+    let mut hasher = checksum_hasher();
+    let mut cursor = NippyJarCursor::new(&jar)?;
+    while let Some(row) = cursor.next_row()? {
+        for value in row {
+            hasher.write_usize(value.len());
+            hasher.write(value);
+            writer.append_column(Some(Ok(value)))?;
+        }
+    }
+    writer.commit()?;
+    drop(cursor);
+
+    // Reads back from disk, so the checksum covers the committed files
+    let staged = NippyJar::<SegmentHeader>::load(writer.into_jar().data_path())?;
+    eyre::ensure!(
+        staged.rows() == jar.rows(),
+        "row count mismatch for {}: {} != {}",
+        jar.data_path().display(),
+        staged.rows(),
+        jar.rows()
+    );
+    let checksum = checksum(&staged)?;
+    eyre::ensure!(
+        checksum == hasher.finish(),
+        "checksum mismatch for recompressed {}",
+        jar.data_path().display()
+    );
+
+    let (before, after) = (jar_size(&jar)?, jar_size(&staged)?);
+    if after >= before {
+        info!(
+            target: "reth::cli",
+            path = %jar.data_path().display(),
+            size = %human_bytes(before as f64),
+            "Recompressed static file is not smaller, keeping the original"
+        );
+        staged.delete()?;
+        return Ok((before, before))
+    }
+
+    info!(
+        target: "reth::cli",
+        path = %jar.data_path().display(),
+        before = %human_bytes(before as f64),
+        after = %human_bytes(after as f64),
+        "Recompressed static file"
+    );
+
+    swap_files(&jar, &staged, backup_dir)?;
+
+    Ok((before, after))
+}
+
+/// Replaces the files of `original` with the ones of `staged`.
+///
+/// Originals are moved to `backup_dir` first, so an interrupted swap is rolled back by
+/// [`restore_static_file_backup`](reth_provider::providers::restore_static_file_backup) the
+/// next time the static files are opened with read-write access, e.g. when the node starts.
+fn swap_files(
+    original: &NippyJar<SegmentHeader>,
+    staged: &NippyJar<SegmentHeader>,
+    backup_dir: &Path,
+) -> eyre::Result<()> {
+    let files = jar_files(original).into_iter().zip(jar_files(staged)).collect::<Vec<_>>();
+
+    fs::create_dir_all(backup_dir)?;
+    for (original, _) in &files {
+        fs::rename(original, backup_dir.join(original.file_name().expect("file name")))?;
+    }
+    for (original, staged) in &files {
+        fs::rename(staged, original)?;
+    }
+    fs::remove_dir_all(backup_dir)?;
+
+    Ok(())
+}
+
+/// Checksum of the decompressed content of all rows of `jar`.
+fn checksum(jar: &NippyJar<SegmentHeader>) -> eyre::Result<u64> {
+    let mut hasher = checksum_hasher();
+    let mut cursor = NippyJarCursor::new(jar)?;
+    while let Some(row) = cursor.next_row()? {
+        for value in row {
+            hasher.write_usize(value.len());
+            hasher.write(value);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+fn checksum_hasher() -> impl Hasher {
+    RandomState::with_seeds(1, 2, 3, 4).build_hasher()
+}
+
+/// Data, offsets and configuration files of `jar`.
+fn jar_files(jar: &NippyJar<SegmentHeader>) -> [PathBuf; 3] {
+    [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()]
+}
+
+/// Total size in bytes of the files of `jar`.
+fn jar_size(jar: &NippyJar<SegmentHeader>) -> eyre::Result<u64> {
+    jar_files(jar).iter().try_fold(0, |size, path| Ok(size + fs::metadata(path)?.len()))
+}
+
+fn print_saved(name: &str, before: u64, after: u64) {
+    let saved = before.saturating_sub(after);
+    let percentage = if before == 0 { 0.0 } else { saved as f64 / before as f64 * 100.0 };
+    println!(
+        "{name}: {} -> {} (saved {}, {percentage:.2}%)",
+        human_bytes(before as f64),
+        human_bytes(after as f64),
+        human_bytes(saved as f64),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, U256};
+    use reth_db::test_utils::create_test_static_files_dir;
+    use reth_primitives::Header;
+    use reth_provider::{HeaderProvider, StaticFileWriter};
+
+    #[test]
+    fn recompress_headers() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let static_file_provider = StaticFileProvider::<()>::read_write(&static_dir)
+            .unwrap()
+            .with_custom_blocks_per_file(500);
+
+        let tip = 1_100;
+        write_headers(&static_file_provider, tip);
+
+        let command =
+            Command::parse_from(["recompress", "--segments", "headers", "--max-dict-size", "1024"]);
+        command.recompress(&static_file_provider).unwrap();
+
+        // Complete static files are now compressed with dictionaries, while the highest one is
+        // left untouched.
+        for (start, use_dict) in [(0, true), (500, true), (1_000, false)] {
+            let fixed_block_range = static_file_provider.find_fixed_range(start);
+            let jar = NippyJar::<SegmentHeader>::load(
+                &static_dir.path().join(StaticFileSegment::Headers.filename(&fixed_block_range)),
+            )
+            .unwrap();
+            assert_eq!(
+                matches!(jar.compressor(), Some(Compressors::Zstd(zstd)) if zstd.use_dict),
+                use_dict
+            );
+        }
+        assert!(!static_dir.path().join(STAGING_DIR).exists());
+        assert!(!static_dir.path().join(STATIC_FILE_BACKUP_DIR).exists());
+
+        assert_headers(&static_file_provider, tip);
+    }
+
+    #[test]
+    fn interrupted_swap_is_rolled_back() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let static_file_provider = StaticFileProvider::<()>::read_write(&static_dir)
+            .unwrap()
+            .with_custom_blocks_per_file(500);
+        let tip = 1_100;
+        write_headers(&static_file_provider, tip);
+
+        // Interrupt the swap of the first static file after the originals were moved to the
+        // backup directory and its data file was replaced.
+        let fixed_block_range = static_file_provider.find_fixed_range(0);
+        let jar = NippyJar::<SegmentHeader>::load(
+            &static_dir.path().join(StaticFileSegment::Headers.filename(&fixed_block_range)),
+        )
+        .unwrap();
+        let backup_dir = static_dir.path().join(STATIC_FILE_BACKUP_DIR);
+        fs::create_dir_all(&backup_dir).unwrap();
+        for path in jar_files(&jar) {
+            std::fs::copy(&path, backup_dir.join(path.file_name().unwrap())).unwrap();
+        }
+        fs::write(jar.data_path(), [0xff; 64]).unwrap();
+        drop(static_file_provider);
+
+        // Opening the static files, e.g. on node startup, rolls back the swap
+        let static_file_provider = StaticFileProvider::<()>::read_write(&static_dir)
+            .unwrap()
+            .with_custom_blocks_per_file(500);
+        assert!(!backup_dir.exists());
+        assert_headers(&static_file_provider, tip);
+    }
+
+    fn header(number: u64) -> Header {
+        Header { number, gas_used: number * 21_000, ..Default::default() }
+    }
+
+    fn write_headers(static_file_provider: &StaticFileProvider<()>, tip: u64) {
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..=tip {
+            writer
+                .append_header(
+                    &header(number),
+                    U256::from(number),
+                    &B256::left_padding_from(&number.to_be_bytes()),
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+    }
+
+    fn assert_headers(static_file_provider: &StaticFileProvider<()>, tip: u64) {
+        for number in 0..=tip {
+            assert_eq!(
+                static_file_provider.header_by_number(number).unwrap(),
+                Some(header(number))
+            );
+            assert_eq!(
+                static_file_provider.header_td_by_number(number).unwrap(),
+                Some(U256::from(number))
+            );
+        }
+    }
+}
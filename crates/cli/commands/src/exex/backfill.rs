@@ -0,0 +1,222 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use futures::StreamExt;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_evm::execute::BlockExecutorProvider;
+use reth_exex::{BackfillJobFactory, ExExEvent, ExExNotification, RemoteExEx};
+use reth_provider::{providers::BlockchainProvider2, BlockNumReader};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth exex backfill` command
+///
+/// Executes a range of historical blocks from a read-only database and serves the resulting
+/// notifications to a remote ExEx over a Unix socket, see `reth_exex::RemoteExExClient`. The
+/// highest block the ExEx reports as finished is saved as a checkpoint in the output directory,
+/// and the next run of the command for the same ExEx continues after it.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The name of the ExEx, used to store its checkpoint.
+    #[arg(long)]
+    name: String,
+
+    /// The directory the checkpoint, the WAL and the socket of the ExEx are stored in, under
+    /// `<OUTPUT_DIR>/<NAME>`.
+    ///
+    /// The data directory is opened read-only, so nothing is written to it.
+    #[arg(long, value_name = "PATH")]
+    output_dir: PathBuf,
+
+    /// The first block to backfill.
+    ///
+    /// If the ExEx has a checkpoint above it, the backfill starts after the checkpoint instead.
+    #[arg(long, default_value_t = 1)]
+    from: BlockNumber,
+
+    /// The last block to backfill. Defaults to the latest block in the database.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The path of the Unix socket the ExEx connects to.
+    ///
+    /// Defaults to `<OUTPUT_DIR>/<NAME>/exex.ipc`.
+    #[arg(long, value_name = "PATH")]
+    socket: Option<PathBuf>,
+
+    /// The number of backfill jobs executing blocks in parallel.
+    #[arg(long, default_value_t = 4)]
+    jobs: usize,
+
+    /// The number of blocks executed by a backfill job at once.
+    #[arg(long, default_value_t = 100)]
+    batch_size: usize,
+
+    /// The maximum number of notifications sent to the ExEx that it hasn't finished yet.
+    #[arg(long, default_value_t = 16)]
+    max_pending: usize,
+}
+
+/// Progress of the backfill of an ExEx.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The highest block the ExEx has finished.
+    block: BlockNumber,
+}
+
+impl Checkpoint {
+    /// Reads the checkpoint from the given path, if it exists.
+    fn read(path: &Path) -> eyre::Result<Option<Self>> {
+        Ok(path.exists().then(|| reth_fs_util::read_json_file(path)).transpose()?)
+    }
+
+    /// Writes the checkpoint to the given path, atomically replacing the previous one so that a
+    /// crash never leaves a partially written checkpoint behind.
+    fn write(&self, path: &Path) -> eyre::Result<()> {
+        Ok(reth_fs_util::atomic_write_file(path, |file| serde_json::to_writer(file, self))?)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex backfill` command
+    pub async fn execute<N, E, F>(self, _ctx: CliContext, executor: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        E: BlockExecutorProvider,
+        F: FnOnce(Arc<C::ChainSpec>) -> E,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let backfill_dir = self.output_dir.join(&self.name);
+        reth_fs_util::create_dir_all(&backfill_dir)?;
+        let checkpoint_path = backfill_dir.join("checkpoint.json");
+
+        let checkpoint = Checkpoint::read(&checkpoint_path)?;
+        let from = checkpoint.as_ref().map_or(self.from, |checkpoint| {
+            info!(target: "reth::cli", exex = %self.name, block = checkpoint.block, "Found ExEx checkpoint");
+            self.from.max(checkpoint.block + 1)
+        });
+        let to = match self.to {
+            Some(to) => to,
+            None => provider_factory.best_block_number()?,
+        };
+        if from > to {
+            info!(target: "reth::cli", from, to, "Nothing to backfill");
+            return Ok(())
+        }
+
+        // Every run executes the blocks after the checkpoint again, so the notifications of a
+        // previous run must not be replayed.
+        let wal_dir = backfill_dir.join("wal");
+        if wal_dir.exists() {
+            reth_fs_util::remove_dir_all(&wal_dir)?;
+        }
+
+        let socket_path = self.socket.unwrap_or_else(|| backfill_dir.join("exex.ipc"));
+        let (notifications_tx, notifications_rx) = futures::channel::mpsc::unbounded();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+        let exex = tokio::spawn(
+            RemoteExEx::new(notifications_rx, events_tx, &socket_path, wal_dir)?.run(),
+        );
+
+        info!(target: "reth::cli", exex = %self.name, from, to, ?socket_path, "Backfilling ExEx");
+        let executor = executor(provider_factory.chain_spec());
+        let mut backfill =
+            BackfillJobFactory::new(executor, BlockchainProvider2::new(provider_factory)?)
+                .with_stream_parallelism(self.jobs)
+                .backfill(from..=to)
+                .into_stream()
+                .with_batch_size(self.batch_size);
+
+        // Tips of the notifications that the ExEx hasn't finished yet
+        let mut pending = VecDeque::new();
+        let mut executed = false;
+        while !executed || !pending.is_empty() {
+            tokio::select! {
+                chain = backfill.next(), if !executed && pending.len() < self.max_pending => {
+                    let Some(chain) = chain else {
+                        executed = true;
+                        continue
+                    };
+                    let chain = chain?;
+                    info!(target: "reth::cli", range = ?chain.range(), "Executed blocks");
+
+                    pending.push_back(chain.tip().number);
+                    notifications_tx
+                        .unbounded_send(Ok(ExExNotification::ChainCommitted { new: Arc::new(chain) }))?;
+                }
+                event = events_rx.recv() => {
+                    let Some(ExExEvent::FinishedHeight(height)) = event else {
+                        return exex.await?
+                    };
+
+                    while pending.front().is_some_and(|tip| *tip <= height.number) {
+                        pending.pop_front();
+                    }
+                    Checkpoint { block: height.number }.write(&checkpoint_path)?;
+                    info!(target: "reth::cli", block = height.number, "ExEx finished height");
+                }
+            }
+        }
+
+        // Closing the notification stream stops the remote ExEx.
+        drop(notifications_tx);
+        exex.await??;
+        info!(target: "reth::cli", exex = %self.name, from, to, "Backfill finished");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_backfill_args() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--name",
+            "indexer",
+            "--output-dir",
+            "/tmp/backfill",
+            "--from",
+            "100",
+        ])
+        .unwrap();
+        assert_eq!(cmd.name, "indexer");
+        assert_eq!(cmd.output_dir, PathBuf::from("/tmp/backfill"));
+        assert_eq!(cmd.from, 100);
+        assert_eq!(cmd.to, None);
+
+        // the output directory is required, the data directory is only read
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth", "--name", "indexer"])
+            .is_err());
+    }
+
+    #[test]
+    fn checkpoint_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("checkpoint.json");
+        assert_eq!(Checkpoint::read(&path).unwrap(), None);
+
+        Checkpoint { block: 10 }.write(&path).unwrap();
+        assert_eq!(Checkpoint::read(&path).unwrap(), Some(Checkpoint { block: 10 }));
+
+        // the previous checkpoint is replaced, and no temporary file is left behind
+        Checkpoint { block: 20 }.write(&path).unwrap();
+        assert_eq!(Checkpoint::read(&path).unwrap(), Some(Checkpoint { block: 20 }));
+        assert_eq!(reth_fs_util::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+}
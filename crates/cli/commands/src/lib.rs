@@ -12,6 +12,8 @@ pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+#[cfg(unix)]
+pub mod exex;
 pub mod import;
 pub mod init_cmd;
 pub mod init_state;
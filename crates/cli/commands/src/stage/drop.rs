@@ -34,15 +34,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         let tool = DbTool::new(provider_factory)?;
 
-        let static_file_segment = match self.stage {
-            StageEnum::Headers => Some(StaticFileSegment::Headers),
-            StageEnum::Bodies => Some(StaticFileSegment::Transactions),
-            StageEnum::Execution => Some(StaticFileSegment::Receipts),
-            _ => None,
+        let static_file_segments: &[StaticFileSegment] = match self.stage {
+            StageEnum::Headers => &[StaticFileSegment::Headers],
+            StageEnum::Bodies => &[StaticFileSegment::Transactions],
+            StageEnum::Execution => &[
+                StaticFileSegment::Receipts,
+                StaticFileSegment::AccountChangeSets,
+                StaticFileSegment::StorageChangeSets,
+            ],
+            _ => &[],
         };
 
         // Delete static file segment data before inserting the genesis header below
-        if let Some(static_file_segment) = static_file_segment {
+        for &static_file_segment in static_file_segments {
             let static_file_provider = tool.provider_factory.static_file_provider();
             let static_files = iter_static_files(static_file_provider.directory())?;
             if let Some(segment_static_files) = static_files.get(&static_file_segment) {
@@ -98,6 +102,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
                 reset_prune_checkpoint(tx, PruneSegment::Receipts)?;
                 reset_prune_checkpoint(tx, PruneSegment::ContractLogs)?;
+                reset_prune_checkpoint(tx, PruneSegment::AccountChangeSets)?;
+                reset_prune_checkpoint(tx, PruneSegment::StorageChangeSets)?;
                 reset_stage_checkpoint(tx, StageId::Execution)?;
 
                 let alloc = &self.env.chain.genesis().alloc;
@@ -148,6 +154,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 reset_stage_checkpoint(tx, StageId::TransactionLookup)?;
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressHistory>()?;
+                tx.clear::<tables::LogTopicHistory>()?;
+
+                // Without a checkpoint, the log index is no longer maintained.
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
+            StageEnum::TrieChangeSets => {
+                tx.clear::<tables::AccountsTrieChangeSets>()?;
+                tx.clear::<tables::StoragesTrieChangeSets>()?;
+
+                // Without a checkpoint, the trie changesets are no longer maintained.
+                tx.delete::<tables::StageCheckpoints>(StageId::TrieChangeSets.to_string(), None)?;
+                reset_prune_checkpoint(tx, PruneSegment::TrieChangeSets)?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
@@ -40,8 +40,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogsStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage, TrieChangeSetsStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageError, StageExt, UnwindInput,
     UnwindOutput,
@@ -313,6 +313,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => {
+                    (Box::new(IndexLogsStage::new(config.stages.index_logs)), None)
+                }
+                StageEnum::TrieChangeSets => (Box::new(TrieChangeSetsStage::new()), None),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -17,7 +17,10 @@ use reth_codecs::alloy::{
     withdrawal::Withdrawal,
 };
 use reth_db::{
-    models::{AccountBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    models::{
+        AccountBeforeTx, StorageBeforeTx, StoredAccountChangeSet, StoredBlockBodyIndices,
+        StoredBlockOmmers, StoredBlockWithdrawals, StoredStorageChangeSet,
+    },
     ClientVersion,
 };
 use reth_fs_util as fs;
@@ -110,6 +113,9 @@ compact_types!(
         StoredBlockOmmers,
         StoredBlockBodyIndices,
         StoredBlockWithdrawals,
+        StorageBeforeTx,
+        StoredAccountChangeSet,
+        StoredStorageChangeSet,
         // Manual implementations
         TransactionSignedNoHash,
         // Bytecode, // todo revm arbitrary
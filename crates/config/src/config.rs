@@ -120,6 +120,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Trie changesets stage configuration.
+    pub trie_changesets: TrieChangeSetsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -373,6 +377,31 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether the index of logs by address and topic is built and maintained.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
+/// Trie changesets stage configuration.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TrieChangeSetsConfig {
+    /// Whether the trie nodes are recorded before each block changes them, so that historical
+    /// proofs don't need to recalculate the trie.
+    pub enabled: bool,
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -410,6 +439,7 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     receipts_log_filter,
+                    trie_changesets,
                 },
         } = other;
 
@@ -424,6 +454,7 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.trie_changesets = self.segments.trie_changesets.or(trie_changesets);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -947,6 +978,7 @@ receipts = 'full'
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                trie_changesets: None,
             },
         };
 
@@ -962,6 +994,7 @@ receipts = 'full'
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                trie_changesets: Some(PruneMode::Distance(4000)),
             },
         };
 
@@ -977,6 +1010,7 @@ receipts = 'full'
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.segments.trie_changesets, Some(PruneMode::Distance(4000)));
     }
 
     #[test]
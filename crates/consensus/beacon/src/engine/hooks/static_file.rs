@@ -122,6 +122,10 @@ where
                         headers: Some(finalized_block_number),
                         receipts: Some(finalized_block_number),
                         transactions: Some(finalized_block_number),
+                        // Changesets are moved by the pipeline, once the history stages indexed
+                        // them.
+                        account_changesets: None,
+                        storage_changesets: None,
                     })?;
 
                 // Check if the moving data to static files has been requested.
@@ -28,8 +28,8 @@ use reth_payload_primitives::{PayloadAttributes, PayloadBuilderAttributes};
 use reth_payload_validator::ExecutionPayloadValidator;
 use reth_primitives::{Head, SealedBlock, SealedHeader};
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockIdReader, BlockReader, BlockSource, CanonChainTracker,
-    ChainSpecProvider, ProviderError, StageCheckpointReader,
+    providers::ProviderNodeTypes, BadBlocks, BlockIdReader, BlockReader, BlockSource,
+    CanonChainTracker, ChainSpecProvider, ProviderError, StageCheckpointReader,
 };
 use reth_stages_api::{ControlFlow, Pipeline, PipelineTarget, StageId};
 use reth_tasks::TaskSpawner;
@@ -222,6 +222,8 @@ where
     event_sender: EventSender<BeaconConsensusEngineEvent>,
     /// Consensus engine metrics.
     metrics: EngineMetrics,
+    /// Recently rejected blocks, exposed via `debug_getBadBlocks`.
+    bad_blocks: BadBlocks,
 }
 
 impl<N, BT, Client> BeaconConsensusEngine<N, BT, Client>
@@ -321,6 +323,7 @@ where
             hooks: EngineHooksController::new(hooks),
             event_sender,
             metrics: EngineMetrics::default(),
+            bad_blocks: BadBlocks::default(),
         };
 
         let maybe_pipeline_target = match target {
@@ -336,6 +339,12 @@ where
         Ok((this, handle))
     }
 
+    /// Sets the buffer that blocks rejected by the engine are recorded in.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -1667,6 +1676,7 @@ where
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
                         // keep track of the invalid header
+                        self.bad_blocks.insert(block.clone(), &error);
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
                             PayloadStatusEnum::Invalid { validation_error: error.to_string() },
@@ -1776,6 +1786,7 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.bad_blocks.insert(block.clone(), &err);
                             self.invalid_headers.insert(block.header);
                         }
                     }
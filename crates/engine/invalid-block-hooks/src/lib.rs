@@ -1,5 +1,11 @@
 //! Invalid block hook implementations.
 
+mod opcode;
+mod prestate;
+mod trace;
+mod utils;
 mod witness;
 
+pub use opcode::InvalidBlockOpcodeHook;
+pub use prestate::InvalidBlockPreStateHook;
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::GethDefaultTracingOptions;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives::{Receipt, SealedBlockWithSenders, SealedHeader};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+
+use crate::trace::{trace_invalid_block, BlockTracer};
+
+/// Traces the given block with the opcode-level struct logger and saves the traces to a file.
+#[derive(Debug)]
+pub struct InvalidBlockOpcodeHook<P, EvmConfig> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: EvmConfig,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case of a mismatch against the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, EvmConfig> InvalidBlockOpcodeHook<P, EvmConfig> {
+    /// Creates a new opcode hook.
+    pub const fn new(
+        provider: P,
+        evm_config: EvmConfig,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockHook for InvalidBlockOpcodeHook<P, EvmConfig>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader,
+        block: &SealedBlockWithSenders,
+        _output: &BlockExecutionOutput<Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        // Capture the full opcode-level struct log, including memory and return data.
+        let tracer = BlockTracer::Opcode(
+            GethDefaultTracingOptions::default()
+                .enable_memory()
+                .enable_return_data()
+                .with_disable_stack(false)
+                .with_disable_storage(false),
+        );
+        if let Err(err) = trace_invalid_block(
+            &self.provider,
+            &self.evm_config,
+            &self.output_directory,
+            self.healthy_node_client.as_ref(),
+            parent_header,
+            block,
+            &tracer,
+        ) {
+            warn!(target: "engine::invalid_block_hooks::opcode", %err, "Failed to invoke hook");
+        }
+    }
+}
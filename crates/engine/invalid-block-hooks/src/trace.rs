@@ -0,0 +1,326 @@
+use std::path::Path;
+
+use alloy_consensus::{Header, Transaction};
+use alloy_primitives::U256;
+use alloy_rpc_types_trace::geth::{
+    GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace, PreStateConfig, TraceResult,
+};
+use eyre::OptionExt;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm};
+use reth_primitives::{SealedBlockWithSenders, SealedHeader};
+use reth_provider::{ChainSpecProvider, StateProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
+    DatabaseCommit,
+};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+
+use crate::utils::{save_diff, save_file};
+
+/// The geth tracer that is used to trace the transactions of an invalid block.
+#[derive(Debug, Clone)]
+pub(crate) enum BlockTracer {
+    /// The prestate tracer with the given config.
+    PreState(PreStateConfig),
+    /// The default struct logger with the given config, producing opcode-level traces.
+    Opcode(GethDefaultTracingOptions),
+}
+
+impl BlockTracer {
+    /// Returns the name of the tracer that is used in the file names.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::PreState(_) => "prestate",
+            Self::Opcode(_) => "opcode",
+        }
+    }
+
+    /// Returns the `debug_traceBlock*` options that produce the same traces.
+    fn tracing_options(&self) -> GethDebugTracingOptions {
+        match self {
+            Self::PreState(config) => GethDebugTracingOptions::prestate_tracer(*config),
+            Self::Opcode(config) => {
+                GethDebugTracingOptions { config: *config, ..Default::default() }
+            }
+        }
+    }
+
+    fn inspector_config(&self) -> TracingInspectorConfig {
+        match self {
+            Self::PreState(config) => TracingInspectorConfig::from_geth_prestate_config(config),
+            Self::Opcode(config) => TracingInspectorConfig::from_geth_config(config),
+        }
+    }
+}
+
+/// Re-executes the transactions of the block on top of its parent state, traces each of them with
+/// the given tracer and saves the traces to the output directory.
+///
+/// If the healthy node client is provided, the traces are compared against the ones returned by
+/// the healthy node and the diff is saved in case of a mismatch.
+pub(crate) fn trace_invalid_block<P, EvmConfig>(
+    provider: &P,
+    evm_config: &EvmConfig,
+    output_directory: &Path,
+    healthy_node_client: Option<&jsonrpsee::http_client::HttpClient>,
+    parent_header: &SealedHeader,
+    block: &SealedBlockWithSenders,
+    tracer: &BlockTracer,
+) -> eyre::Result<()>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    let traces = trace_block(provider, evm_config, parent_header, block, tracer)?;
+
+    let re_executed_path = save_file(
+        output_directory,
+        format!("{}_{}.{}.re_executed.json", block.number, block.hash(), tracer.name()),
+        &traces,
+    )?;
+
+    if let Some(healthy_node_client) = healthy_node_client {
+        // Compare the traces against the healthy node.
+        let healthy_node_traces = futures::executor::block_on(async move {
+            DebugApiClient::debug_trace_block_by_number(
+                healthy_node_client,
+                block.number.into(),
+                Some(tracer.tracing_options()),
+            )
+            .await
+        })?;
+
+        let healthy_path = save_file(
+            output_directory,
+            format!("{}_{}.{}.healthy.json", block.number, block.hash(), tracer.name()),
+            &healthy_node_traces,
+        )?;
+
+        // If the traces are different, write the diff to the output directory.
+        if traces != healthy_node_traces {
+            let filename = format!("{}_{}.{}.diff", block.number, block.hash(), tracer.name());
+            let diff_path = save_diff(output_directory, filename, &traces, &healthy_node_traces)?;
+            warn!(
+                target: "engine::invalid_block_hooks::trace",
+                tracer = tracer.name(),
+                diff_path = %diff_path.display(),
+                re_executed_path = %re_executed_path.display(),
+                healthy_path = %healthy_path.display(),
+                "Trace mismatch against healthy node"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-executes the transactions of the block on top of its parent state and returns the trace of
+/// each transaction.
+fn trace_block<P, EvmConfig>(
+    provider: &P,
+    evm_config: &EvmConfig,
+    parent_header: &SealedHeader,
+    block: &SealedBlockWithSenders,
+    tracer: &BlockTracer,
+) -> eyre::Result<Vec<TraceResult>>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    // Setup database.
+    let mut db = CacheDB::new(StateProviderDatabase::new(
+        provider.state_by_block_hash(parent_header.hash())?,
+    ));
+
+    // Setup environment for the execution.
+    let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+    let mut block_env = BlockEnv::default();
+    evm_config.fill_cfg_and_block_env(&mut cfg, &mut block_env, block.header(), U256::MAX);
+
+    // Apply pre-block system contract calls.
+    let mut evm = evm_config.evm_with_env(
+        &mut db,
+        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default()),
+    );
+    SystemCaller::new(evm_config.clone(), provider.chain_spec())
+        .apply_pre_execution_changes(&block.clone().unseal(), &mut evm)?;
+    drop(evm);
+
+    let mut traces = Vec::with_capacity(block.body.transactions.len());
+    for tx in block.transactions() {
+        let signer = tx.recover_signer().ok_or_eyre("failed to recover sender")?;
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            evm_config.tx_env(tx, signer),
+        );
+
+        let mut inspector = TracingInspector::new(tracer.inspector_config());
+        let mut evm = evm_config.evm_with_env_and_inspector(&mut db, env, &mut inspector);
+        let result = evm.transact()?;
+        drop(evm);
+
+        inspector.set_transaction_gas_limit(tx.gas_limit());
+        let trace: GethTrace = match tracer {
+            BlockTracer::PreState(config) => {
+                inspector.geth_builder().geth_prestate_traces(&result, config, &db)?.into()
+            }
+            BlockTracer::Opcode(config) => {
+                let gas_used = result.result.gas_used();
+                let return_value = result.result.output().cloned().unwrap_or_default();
+                inspector.geth_builder().geth_traces(gas_used, return_value, *config).into()
+            }
+        };
+        traces.push(TraceResult::Success { result: trace, tx_hash: Some(tx.hash()) });
+
+        db.commit(result.state);
+    }
+
+    Ok(traces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InvalidBlockOpcodeHook, InvalidBlockPreStateHook};
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{address, bytes, Address, TxKind, B256};
+    use alloy_rpc_types_trace::geth::PreStateFrame;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_engine_primitives::InvalidBlockHook;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        sign_message, BlockBody, Receipt, SealedBlock, Transaction, TransactionSigned,
+    };
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        BlockExecutionOutput,
+    };
+    use std::sync::Arc;
+
+    /// A contract that stores `1` in slot `0`.
+    const CONTRACT: Address = address!("00000000000000000000000000000000000000aa");
+
+    /// Returns a provider with the parent state and a block with a single call to [`CONTRACT`].
+    fn setup() -> (MockEthProvider, SealedHeader, SealedBlockWithSenders) {
+        let provider = MockEthProvider {
+            chain_spec: Arc::new(ChainSpecBuilder::mainnet().london_activated().build()),
+            ..Default::default()
+        };
+
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            gas_limit: 100_000,
+            max_fee_per_gas: 10_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(CONTRACT),
+            ..Default::default()
+        });
+        let signature =
+            sign_message(B256::with_last_byte(1), transaction.signature_hash()).unwrap();
+        let transaction = TransactionSigned::new_unhashed(transaction, signature);
+        let sender = transaction.recover_signer().unwrap();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+        provider.add_account(
+            CONTRACT,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(bytes!("600160005500")),
+        );
+
+        let parent = SealedHeader::seal(Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        });
+        let header = SealedHeader::seal(Header {
+            parent_hash: parent.hash(),
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        });
+        let block = SealedBlock::new(
+            header,
+            BlockBody { transactions: vec![transaction], ..Default::default() },
+        );
+
+        (provider, parent, SealedBlockWithSenders::new(block, vec![sender]).unwrap())
+    }
+
+    fn output() -> BlockExecutionOutput<Receipt> {
+        BlockExecutionOutput {
+            state: Default::default(),
+            receipts: Vec::new(),
+            requests: Default::default(),
+            gas_used: 0,
+        }
+    }
+
+    /// Reads the traces the hook saved for the block.
+    fn read_traces(
+        output_directory: &Path,
+        block: &SealedBlockWithSenders,
+        tracer: &str,
+    ) -> Vec<TraceResult> {
+        let filename = format!("{}_{}.{tracer}.re_executed.json", block.number, block.hash());
+        serde_json::from_slice(&std::fs::read(output_directory.join(filename)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn prestate_hook_saves_traces() {
+        let output_directory = tempfile::tempdir().unwrap();
+        let (provider, parent, block) = setup();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        InvalidBlockPreStateHook::new(
+            provider,
+            evm_config,
+            output_directory.path().to_path_buf(),
+            None,
+        )
+        .on_invalid_block(&parent, &block, &output(), None);
+
+        let traces = read_traces(output_directory.path(), &block, "prestate");
+        let [TraceResult::Success {
+            result: GethTrace::PreStateTracer(PreStateFrame::Diff(diff)),
+            tx_hash,
+        }] = traces.as_slice()
+        else {
+            panic!("expected a single prestate diff, got {traces:?}")
+        };
+        assert_eq!(*tx_hash, Some(block.body.transactions[0].hash()));
+        assert!(diff.pre[&CONTRACT].storage.is_empty());
+        assert_eq!(diff.post[&CONTRACT].storage[&B256::ZERO], B256::with_last_byte(1));
+    }
+
+    #[test]
+    fn opcode_hook_saves_traces() {
+        let output_directory = tempfile::tempdir().unwrap();
+        let (provider, parent, block) = setup();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        InvalidBlockOpcodeHook::new(
+            provider,
+            evm_config,
+            output_directory.path().to_path_buf(),
+            None,
+        )
+        .on_invalid_block(&parent, &block, &output(), None);
+
+        let traces = read_traces(output_directory.path(), &block, "opcode");
+        let [TraceResult::Success { result: GethTrace::Default(frame), tx_hash }] =
+            traces.as_slice()
+        else {
+            panic!("expected a single struct log trace, got {traces:?}")
+        };
+        assert_eq!(*tx_hash, Some(block.body.transactions[0].hash()));
+        assert!(!frame.failed);
+        let ops = frame.struct_logs.iter().map(|log| log.op.as_str()).collect::<Vec<_>>();
+        assert_eq!(ops, ["PUSH1", "PUSH1", "SSTORE", "STOP"]);
+        let sstore = &frame.struct_logs[2];
+        assert_eq!(sstore.stack.as_ref().unwrap().len(), 2);
+        assert_eq!(sstore.storage.as_ref().unwrap()[&B256::ZERO], B256::with_last_byte(1));
+    }
+}
@@ -1,10 +1,9 @@
-use std::{collections::HashMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{collections::HashMap, fmt::Debug, path::PathBuf};
 
 use alloy_consensus::Header;
 use alloy_primitives::{keccak256, B256, U256};
 use alloy_rpc_types_debug::ExecutionWitness;
 use eyre::OptionExt;
-use pretty_assertions::Comparison;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_engine_primitives::InvalidBlockHook;
 use reth_evm::{
@@ -21,7 +20,8 @@ use reth_revm::{
 use reth_rpc_api::DebugApiClient;
 use reth_tracing::tracing::warn;
 use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
-use serde::Serialize;
+
+use crate::utils::{save_diff, save_file};
 
 /// Generates a witness for the given block and saves it to a file.
 #[derive(Debug)]
@@ -281,18 +281,12 @@ where
         original: &T,
         new: &T,
     ) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        let diff = Comparison::new(original, new);
-        File::create(&path)?.write_all(diff.to_string().as_bytes())?;
-
-        Ok(path)
+        save_diff(&self.output_directory, filename, original, new)
     }
 
-    fn save_file<T: Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
-
-        Ok(path)
+    /// Saves the value into a file with the given name in the output directory.
+    fn save_file<T: serde::Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
+        save_file(&self.output_directory, filename, value)
     }
 }
 
@@ -3,7 +3,11 @@
 use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadSidecar, ForkchoiceState};
 use eyre::OptionExt;
-use futures_util::{stream::Fuse, StreamExt};
+use futures_util::{
+    stream::{self, Fuse, Select},
+    StreamExt,
+};
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthereumHardforks;
 use reth_engine_primitives::{BeaconEngineMessage, EngineApiMessageVersion, EngineTypes};
 use reth_payload_builder::PayloadBuilderHandle;
@@ -19,7 +23,10 @@ use std::{
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -29,17 +36,20 @@ use tracing::error;
 #[derive(Debug)]
 pub enum MiningMode {
     /// In this mode a block is built as soon as
-    /// a valid transaction reaches the pool.
-    Instant(Fuse<ReceiverStream<TxHash>>),
+    /// a valid transaction reaches the pool or the private transaction pool.
+    Instant(Fuse<Select<ReceiverStream<TxHash>, ReceiverStream<TxHash>>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request, see [`LocalMinerHandle::mine`].
+    Manual,
 }
 
 impl MiningMode {
     /// Constructor for a [`MiningMode::Instant`]
     pub fn instant<Pool: TransactionPool>(pool: Pool) -> Self {
-        let rx = pool.pending_transactions_listener();
-        Self::Instant(ReceiverStream::new(rx).fuse())
+        let pending = ReceiverStream::new(pool.pending_transactions_listener());
+        let private = ReceiverStream::new(pool.private_pool().new_transactions_listener());
+        Self::Instant(stream::select(pending, private).fuse())
     }
 
     /// Constructor for a [`MiningMode::Interval`]
@@ -47,6 +57,11 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Returns `true` if this is [`MiningMode::Instant`].
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant(_))
+    }
 }
 
 impl Future for MiningMode {
@@ -68,10 +83,104 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// Commands that can be sent to the [`LocalMiner`] through a [`LocalMinerHandle`].
+#[derive(Debug)]
+enum MinerCommand {
+    /// Mines the given number of blocks.
+    Mine { blocks: u64, interval: Option<u64>, tx: oneshot::Sender<eyre::Result<Vec<B256>>> },
+    /// Replaces the mining mode.
+    SetMode(MiningMode),
+    /// Returns whether the miner is in [`MiningMode::Instant`].
+    IsAutomine(oneshot::Sender<bool>),
+    /// Sets the timestamp of the next block.
+    SetNextBlockTimestamp { timestamp: u64, tx: oneshot::Sender<eyre::Result<()>> },
+    /// Adds the given number of seconds to the clock and returns the total offset.
+    IncreaseTime { seconds: u64, tx: oneshot::Sender<i64> },
+    /// Sets the clock to the given timestamp and returns the total offset.
+    SetTime { timestamp: u64, tx: oneshot::Sender<i64> },
+    /// Sets a fixed interval between block timestamps and returns whether one was set before.
+    SetBlockTimestampInterval { interval: Option<u64>, tx: oneshot::Sender<bool> },
+    /// Unwinds the chain to the given canonical block.
+    RevertTo { hash: B256, tx: oneshot::Sender<eyre::Result<()>> },
+}
+
+/// A handle to control a running [`LocalMiner`].
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: UnboundedSender<MinerCommand>,
+}
+
+impl LocalMinerHandle {
+    /// Mines the given number of blocks, regardless of the mining mode, and returns their hashes.
+    ///
+    /// If an interval is given, the timestamps of consecutive blocks are `interval` seconds apart.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        self.request(|tx| MinerCommand::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Replaces the mining mode of the miner.
+    pub fn set_mining_mode(&self, mode: MiningMode) -> eyre::Result<()> {
+        self.to_miner.send(MinerCommand::SetMode(mode)).map_err(|_| eyre::eyre!("miner stopped"))
+    }
+
+    /// Returns `true` if a block is built as soon as a transaction reaches the pool.
+    pub async fn is_automine(&self) -> eyre::Result<bool> {
+        self.request(MinerCommand::IsAutomine).await
+    }
+
+    /// Sets the timestamp of the next block.
+    ///
+    /// The timestamp must be after the timestamp of the latest block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> eyre::Result<()> {
+        self.request(|tx| MinerCommand::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Moves the clock that is used for block timestamps forward by the given number of seconds.
+    ///
+    /// Returns the total offset of the clock in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> eyre::Result<i64> {
+        self.request(|tx| MinerCommand::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Sets the clock that is used for block timestamps to the given timestamp.
+    ///
+    /// Returns the total offset of the clock in seconds.
+    pub async fn set_time(&self, timestamp: u64) -> eyre::Result<i64> {
+        self.request(|tx| MinerCommand::SetTime { timestamp, tx }).await
+    }
+
+    /// Sets a fixed number of seconds between block timestamps, or removes it if `None`.
+    ///
+    /// Returns `true` if an interval was set before.
+    pub async fn set_block_timestamp_interval(&self, interval: Option<u64>) -> eyre::Result<bool> {
+        self.request(|tx| MinerCommand::SetBlockTimestampInterval { interval, tx }).await
+    }
+
+    /// Unwinds the canonical chain to the given ancestor of the current head.
+    ///
+    /// This requires the engine tree to be configured to unwind the canonical chain on forkchoice
+    /// updates to an ancestor of the head.
+    pub async fn revert_to(&self, hash: B256) -> eyre::Result<()> {
+        self.request(|tx| MinerCommand::RevertTo { hash, tx }).await?
+    }
+
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> MinerCommand,
+    ) -> eyre::Result<T> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(command(tx)).map_err(|_| eyre::eyre!("miner stopped"))?;
+        Ok(rx.await?)
+    }
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
@@ -85,10 +194,21 @@ pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
     mode: MiningMode,
     /// The payload builder for the engine
     payload_builder: PayloadBuilderHandle<EngineT>,
+    /// Sender for the executed blocks of built payloads, so that the engine doesn't need to
+    /// execute them again.
+    executed_blocks: UnboundedSender<ExecutedBlock>,
+    /// Receiver for commands from [`LocalMinerHandle`]s.
+    commands: UnboundedReceiver<MinerCommand>,
     /// Timestamp for the next block.
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// The timestamp of the next block, if set explicitly.
+    next_timestamp: Option<u64>,
+    /// Offset of the clock that is used for block timestamps, in seconds.
+    time_offset: i64,
+    /// Fixed number of seconds between block timestamps.
+    block_timestamp_interval: Option<u64>,
 }
 
 impl<EngineT, Provider, B> LocalMiner<EngineT, Provider, B>
@@ -97,16 +217,18 @@ where
     Provider: BlockReader + ChainSpecProvider<ChainSpec: EthereumHardforks> + 'static,
     B: PayloadAttributesBuilder<<EngineT as PayloadTypes>::PayloadAttributes>,
 {
-    /// Spawns a new [`LocalMiner`] with the given parameters.
+    /// Spawns a new [`LocalMiner`] with the given parameters and returns a handle to it.
     pub fn spawn_new(
         provider: Provider,
         payload_attributes_builder: B,
         to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
+        executed_blocks: UnboundedSender<ExecutedBlock>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<EngineT>,
-    ) {
+    ) -> LocalMinerHandle {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+        let (to_miner, commands) = mpsc::unbounded_channel();
 
         let miner = Self {
             provider,
@@ -114,12 +236,19 @@ where
             to_engine,
             mode,
             payload_builder,
+            executed_blocks,
+            commands,
             last_timestamp: latest_header.timestamp,
             last_block_hashes: vec![latest_header.hash()],
+            next_timestamp: None,
+            time_offset: 0,
+            block_timestamp_interval: None,
         };
 
         // Spawn the miner
         tokio::spawn(miner.run());
+
+        LocalMinerHandle { to_miner }
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -139,10 +268,91 @@ where
                         error!(target: "engine::local", "Error updating fork choice: {:?}", e);
                     }
                 }
+                // handle requests from the miner handles
+                Some(command) = self.commands.recv() => self.on_command(command).await,
             }
         }
     }
 
+    /// Handles a command sent through a [`LocalMinerHandle`].
+    async fn on_command(&mut self, command: MinerCommand) {
+        match command {
+            MinerCommand::Mine { blocks, interval, tx } => {
+                let _ = tx.send(self.mine(blocks, interval).await);
+            }
+            MinerCommand::SetMode(mode) => self.mode = mode,
+            MinerCommand::IsAutomine(tx) => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            MinerCommand::SetNextBlockTimestamp { timestamp, tx } => {
+                let res = if timestamp > self.last_timestamp {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                } else {
+                    Err(eyre::eyre!(
+                        "timestamp {timestamp} is not after the latest block timestamp {}",
+                        self.last_timestamp
+                    ))
+                };
+                let _ = tx.send(res);
+            }
+            MinerCommand::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add_unsigned(seconds);
+                let _ = tx.send(self.time_offset);
+            }
+            MinerCommand::SetTime { timestamp, tx } => {
+                self.time_offset = (timestamp as i64).saturating_sub(unix_now() as i64);
+                let _ = tx.send(self.time_offset);
+            }
+            MinerCommand::SetBlockTimestampInterval { interval, tx } => {
+                let previous =
+                    std::mem::replace(&mut self.block_timestamp_interval, interval).is_some();
+                let _ = tx.send(previous);
+            }
+            MinerCommand::RevertTo { hash, tx } => {
+                let _ = tx.send(self.revert_to(hash).await);
+            }
+        }
+    }
+
+    /// Makes the given ancestor of the current head the new head of the chain.
+    async fn revert_to(&mut self, hash: B256) -> eyre::Result<()> {
+        let header = self.provider.sealed_header_by_hash(hash)?.ok_or_eyre("unknown block")?;
+
+        // forget about all blocks above the new head
+        match self.last_block_hashes.iter().position(|block_hash| *block_hash == hash) {
+            Some(idx) => self.last_block_hashes.truncate(idx + 1),
+            None => self.last_block_hashes = vec![hash],
+        }
+        self.last_timestamp = header.timestamp;
+        self.next_timestamp = None;
+
+        self.update_forkchoice_state().await
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    async fn mine(&mut self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        let mut hashes = Vec::new();
+        for i in 0..blocks {
+            if let Some(interval) = interval.filter(|_| i > 0) {
+                self.next_timestamp = Some(self.last_timestamp + interval);
+            }
+            hashes.push(self.advance().await?);
+        }
+        Ok(hashes)
+    }
+
+    /// Returns the timestamp for the next block.
+    fn next_block_timestamp(&self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp {
+            return timestamp
+        }
+        if let Some(interval) = self.block_timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        std::cmp::max(self.last_timestamp + 1, unix_now().saturating_add_signed(self.time_offset))
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -178,14 +388,10 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let timestamp = self.next_block_timestamp();
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
@@ -210,6 +416,11 @@ where
 
         let block = payload.block();
 
+        // hand the executed block to the engine, so that it doesn't need to execute it again
+        if let Some(executed_block) = payload.executed_block() {
+            self.executed_blocks.send(executed_block)?;
+        }
+
         let cancun_fields = self
             .provider
             .chain_spec()
@@ -236,6 +447,7 @@ where
         }
 
         self.last_timestamp = timestamp;
+        self.next_timestamp = None;
         self.last_block_hashes.push(block.hash());
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
@@ -243,6 +455,17 @@ where
                 self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
         }
 
-        Ok(())
+        // make the new block canonical right away
+        self.update_forkchoice_state().await?;
+
+        Ok(block.hash())
     }
 }
+
+/// Returns the current unix timestamp in seconds.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
@@ -14,9 +14,10 @@ use std::{
     task::{Context, Poll},
 };
 
-use crate::miner::{LocalMiner, MiningMode};
+use crate::miner::{LocalMiner, LocalMinerHandle, MiningMode};
 use futures_util::{Stream, StreamExt};
 use reth_beacon_consensus::{BeaconConsensusEngineEvent, EngineNodeTypes};
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthChainSpec;
 use reth_consensus::Consensus;
 use reth_engine_primitives::BeaconEngineMessage;
@@ -34,10 +35,12 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributesBuilder, PayloadTypes};
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_provider::{providers::BlockchainProvider2, ChainSpecProvider, ProviderFactory};
+use reth_provider::{
+    providers::BlockchainProvider2, BadBlocks, ChainSpecProvider, ProviderFactory,
+};
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::MetricEventsSender;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tracing::error;
 
 /// Provides a local dev service engine that can be used to drive the
@@ -55,6 +58,10 @@ where
     handler: EngineApiRequestHandler<EngineApiRequest<N::Engine>>,
     /// Receiver for incoming requests (from the engine API endpoint) that need to be processed.
     incoming_requests: EngineMessageStream<N::Engine>,
+    /// Receiver for the executed blocks of payloads built by the [`LocalMiner`].
+    executed_blocks: UnboundedReceiver<ExecutedBlock>,
+    /// Handle to the spawned [`LocalMiner`].
+    miner: LocalMinerHandle,
 }
 
 impl<N> LocalEngineService<N>
@@ -72,6 +79,7 @@ where
         payload_builder: PayloadBuilderHandle<N::Engine>,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook>,
+        bad_blocks: BadBlocks,
         sync_metrics_tx: MetricEventsSender,
         to_engine: UnboundedSender<BeaconEngineMessage<N::Engine>>,
         from_engine: EngineMessageStream<N::Engine>,
@@ -101,20 +109,28 @@ where
             canonical_in_memory_state,
             tree_config,
             invalid_block_hook,
+            bad_blocks,
             engine_kind,
         );
 
         let handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
 
-        LocalMiner::spawn_new(
+        let (executed_blocks_tx, executed_blocks) = mpsc::unbounded_channel();
+        let miner = LocalMiner::spawn_new(
             blockchain_db,
             payload_attributes_builder,
             to_engine,
+            executed_blocks_tx,
             mode,
             payload_builder,
         );
 
-        Self { handler, incoming_requests: from_engine }
+        Self { handler, incoming_requests: from_engine, executed_blocks, miner }
+    }
+
+    /// Returns a handle to the [`LocalMiner`] that drives the chain.
+    pub const fn miner(&self) -> &LocalMinerHandle {
+        &self.miner
     }
 }
 
@@ -144,6 +160,13 @@ where
             }
         }
 
+        // insert the blocks built by the miner before their payloads arrive, so that they don't
+        // need to be executed again
+        while let Poll::Ready(Some(block)) = this.executed_blocks.poll_recv(cx) {
+            this.handler
+                .on_event(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(block)));
+        }
+
         // forward incoming requests to the handler
         while let Poll::Ready(Some(req)) = this.incoming_requests.poll_next_unpin(cx) {
             this.handler.on_event(FromEngine::Request(req.into()));
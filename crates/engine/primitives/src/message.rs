@@ -85,6 +85,12 @@ impl OnForkChoiceUpdated {
         }
     }
 
+    /// Creates a new instance of `OnForkChoiceUpdated` with the given status of the forkchoice
+    /// update that resolves to the given result.
+    pub fn ready(forkchoice_status: ForkchoiceStatus, result: ForkChoiceUpdateResult) -> Self {
+        Self { forkchoice_status, fut: Either::Left(futures::future::ready(result)) }
+    }
+
     /// If the forkchoice update was successful and no payload attributes were provided, this method
     pub const fn updated_with_pending_payload_id(
         payload_status: PayloadStatus,
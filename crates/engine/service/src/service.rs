@@ -20,7 +20,7 @@ use reth_network_p2p::EthBlockClient;
 use reth_node_types::NodeTypesWithEngine;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_provider::{providers::BlockchainProvider2, ProviderFactory};
+use reth_provider::{providers::BlockchainProvider2, BadBlocks, ProviderFactory};
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::{MetricEventsSender, Pipeline};
 use reth_tasks::TaskSpawner;
@@ -79,6 +79,7 @@ where
         payload_builder: PayloadBuilderHandle<N::Engine>,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook>,
+        bad_blocks: BadBlocks,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
         let engine_kind =
@@ -102,6 +103,7 @@ where
             canonical_in_memory_state,
             tree_config,
             invalid_block_hook,
+            bad_blocks,
             engine_kind,
         );
 
@@ -206,6 +208,7 @@ mod tests {
             PayloadBuilderHandle::new(tx),
             TreeConfig::default(),
             Box::new(NoopInvalidBlockHook::default()),
+            BadBlocks::default(),
             sync_metrics_tx,
         );
     }
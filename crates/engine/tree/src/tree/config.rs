@@ -32,6 +32,14 @@ pub struct TreeConfig {
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
     /// a batch of downloaded blocks.
     max_execute_block_batch_size: usize,
+    /// Whether a forkchoice update to an ancestor of the canonical head unwinds the canonical
+    /// chain to that block.
+    ///
+    /// This is only meant for dev chains, where it is used to revert to state snapshots.
+    unwind_canonical_header: bool,
+    /// Whether to calculate the state root with the streaming state root task, which fetches
+    /// proofs while the block is executed and updates a sparse trie.
+    use_state_root_task: bool,
 }
 
 impl Default for TreeConfig {
@@ -42,6 +50,8 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            unwind_canonical_header: false,
+            use_state_root_task: false,
         }
     }
 }
@@ -61,6 +71,8 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            unwind_canonical_header: false,
+            use_state_root_task: false,
         }
     }
 
@@ -89,6 +101,17 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Returns whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain.
+    pub const fn unwind_canonical_header(&self) -> bool {
+        self.unwind_canonical_header
+    }
+
+    /// Returns whether the state root is calculated with the streaming state root task.
+    pub const fn use_state_root_task(&self) -> bool {
+        self.use_state_root_task
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -127,4 +150,17 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain.
+    pub const fn with_unwind_canonical_header(mut self, unwind_canonical_header: bool) -> Self {
+        self.unwind_canonical_header = unwind_canonical_header;
+        self
+    }
+
+    /// Setter for whether the state root is calculated with the streaming state root task.
+    pub const fn with_state_root_task(mut self, use_state_root_task: bool) -> Self {
+        self.use_state_root_task = use_state_root_task;
+        self
+    }
 }
@@ -56,6 +56,16 @@ pub(crate) struct BlockValidationMetrics {
     pub(crate) state_root_histogram: Histogram,
     /// Latest state root duration
     pub(crate) state_root_duration: Gauge,
+    /// Number of blocks whose state root was computed by the state root task
+    pub(crate) state_root_task_blocks: Counter,
+    /// Number of blocks whose state root was computed in parallel
+    pub(crate) state_root_parallel_blocks: Counter,
+    /// Number of blocks whose state root was computed serially
+    pub(crate) state_root_serial_blocks: Counter,
+    /// Number of blocks whose state root was computed while blocks were being persisted
+    pub(crate) state_root_during_persistence_blocks: Counter,
+    /// Number of blocks whose state root was computed serially while blocks were being persisted
+    pub(crate) state_root_serial_during_persistence_blocks: Counter,
 }
 
 impl BlockValidationMetrics {
@@ -66,4 +76,34 @@ impl BlockValidationMetrics {
         self.state_root_duration.set(elapsed_as_secs);
         self.state_root_histogram.record(elapsed_as_secs);
     }
+
+    /// Records the strategy that was used to compute the state root of a block.
+    pub(crate) fn record_state_root_strategy(
+        &self,
+        strategy: StateRootStrategy,
+        persistence_in_progress: bool,
+    ) {
+        match strategy {
+            StateRootStrategy::StateRootTask => self.state_root_task_blocks.increment(1),
+            StateRootStrategy::Parallel => self.state_root_parallel_blocks.increment(1),
+            StateRootStrategy::Serial => self.state_root_serial_blocks.increment(1),
+        }
+        if persistence_in_progress {
+            self.state_root_during_persistence_blocks.increment(1);
+            if strategy == StateRootStrategy::Serial {
+                self.state_root_serial_during_persistence_blocks.increment(1);
+            }
+        }
+    }
+}
+
+/// The strategy that was used to compute the state root of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StateRootStrategy {
+    /// The state root was computed by the state root task while the block was executed.
+    StateRootTask,
+    /// The state root was computed in parallel after the block was executed.
+    Parallel,
+    /// The state root was computed on a single thread after the block was executed.
+    Serial,
 }
@@ -22,7 +22,7 @@ use reth_blockchain_tree::{
     BlockBuffer, BlockStatus2, InsertPayloadOk2,
 };
 use reth_chain_state::{
-    CanonicalInMemoryState, ExecutedBlock, MemoryOverlayStateProvider, NewCanonicalChain,
+    BadBlocks, CanonicalInMemoryState, ExecutedBlock, MemoryOverlayStateProvider, NewCanonicalChain,
 };
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::{Consensus, PostExecutionInput};
@@ -31,7 +31,7 @@ use reth_engine_primitives::{
     ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
-use reth_evm::execute::BlockExecutorProvider;
+use reth_evm::{execute::BlockExecutorProvider, system_calls::OnStateHook};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_builder_primitives::PayloadBuilder;
 use reth_payload_primitives::{PayloadAttributes, PayloadBuilderAttributes};
@@ -70,7 +70,7 @@ mod metrics;
 mod persistence_state;
 use crate::{
     engine::{EngineApiKind, EngineApiRequest},
-    tree::metrics::EngineApiMetrics,
+    tree::metrics::{EngineApiMetrics, StateRootStrategy},
 };
 pub use config::TreeConfig;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
@@ -78,6 +78,7 @@ pub use persistence_state::PersistenceState;
 pub use reth_engine_primitives::InvalidBlockHook;
 
 mod root;
+use root::{StateRootConfig, StateRootHandle, StateRootTask};
 
 /// Keeps track of the state of the tree.
 ///
@@ -506,6 +507,8 @@ pub struct EngineApiTreeHandler<P, E, T: EngineTypes, Spec> {
     metrics: EngineApiMetrics,
     /// An invalid block hook.
     invalid_block_hook: Box<dyn InvalidBlockHook>,
+    /// Recently rejected blocks, exposed via `debug_getBadBlocks`.
+    bad_blocks: BadBlocks,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
 }
@@ -529,6 +532,7 @@ impl<P: Debug, E: Debug, T: EngineTypes + Debug, Spec: Debug> std::fmt::Debug
             .field("config", &self.config)
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
+            .field("bad_blocks", &self.bad_blocks)
             .field("engine_kind", &self.engine_kind)
             .finish()
     }
@@ -577,6 +581,7 @@ where
             metrics: Default::default(),
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            bad_blocks: BadBlocks::default(),
             engine_kind,
         }
     }
@@ -586,6 +591,11 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the buffer rejected blocks are recorded in.
+    fn set_bad_blocks(&mut self, bad_blocks: BadBlocks) {
+        self.bad_blocks = bad_blocks;
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -602,6 +612,7 @@ where
         canonical_in_memory_state: CanonicalInMemoryState,
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook>,
+        bad_blocks: BadBlocks,
         kind: EngineApiKind,
     ) -> (Sender<FromEngine<EngineApiRequest<T>>>, UnboundedReceiver<EngineApiEvent>) {
         let best_block_number = provider.best_block_number().unwrap_or(0);
@@ -635,6 +646,7 @@ where
             kind,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_bad_blocks(bad_blocks);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
@@ -1031,6 +1043,31 @@ where
             return Ok(valid_outcome(state.head_block_hash))
         }
 
+        // if enabled, a forkchoice update to an ancestor of the canonical head unwinds the
+        // canonical chain to it, e.g. to revert to a snapshot on dev chains
+        if self.config.unwind_canonical_header() {
+            if let Some(canonical_header) = self
+                .find_canonical_header(state.head_block_hash)?
+                .filter(|header| header.number < self.state.tree_state.canonical_block_number())
+            {
+                self.unwind_canonical_head(canonical_header.clone());
+
+                // update the safe and finalized blocks and ensure their values are valid
+                if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+                    // safe or finalized hashes are invalid
+                    return Ok(TreeOutcome::new(outcome))
+                }
+
+                if let Some(attr) = attrs {
+                    let updated =
+                        self.process_payload_attributes(attr, &canonical_header, state, version);
+                    return Ok(TreeOutcome::new(updated))
+                }
+
+                return Ok(valid_outcome(state.head_block_hash))
+            }
+        }
+
         // 2. ensure we can apply a new chain update for the head block
         if let Some(chain_update) = self.on_new_head(state.head_block_hash)? {
             let tip = chain_update.tip().header.clone();
@@ -1994,6 +2031,38 @@ where
         ));
     }
 
+    /// Unwinds the canonical chain to the given ancestor of the current canonical head.
+    ///
+    /// The blocks above the new head are removed from the in-memory canonical state, and from disk
+    /// if they were already persisted. They are kept in the tree state like any other sidechain
+    /// block.
+    fn unwind_canonical_head(&mut self, new_head: SealedHeader) {
+        let start = Instant::now();
+        let current_head = *self.state.tree_state.canonical_head();
+        debug!(target: "engine::tree", from=?current_head, to=?new_head.num_hash(), "unwinding canonical chain");
+
+        // schedule a remove_above call if blocks above the new head are on disk
+        if new_head.number < self.persistence_state.last_persisted_block.number {
+            self.persistence_state.schedule_removal(new_head.number);
+        }
+
+        // update the tracked canonical head
+        self.state.tree_state.set_canonical_head(new_head.num_hash());
+
+        // update the tracked in-memory state, this also drops the pending block
+        self.canonical_in_memory_state.remove_blocks_above(new_head.number);
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+
+        self.update_reorg_metrics((current_head.number - new_head.number) as usize);
+        self.metrics.tree.canonical_chain_height.set(new_head.number as f64);
+
+        // emit event
+        self.emit_event(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(new_head),
+            start.elapsed(),
+        ));
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
@@ -2120,6 +2189,14 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<InsertPayloadOk2, InsertBlockErrorTwo> {
+        // blocks that were already inserted, like the executed blocks of the local miner, don't
+        // need to be recovered again
+        match self.block_by_hash(block.hash()) {
+            Ok(Some(_)) => return Ok(InsertPayloadOk2::AlreadySeen(BlockStatus2::Valid)),
+            Ok(None) => {}
+            Err(err) => return Err(InsertBlockErrorTwo::new(block, err.into())),
+        }
+
         match block.try_seal_with_senders() {
             Ok(block) => self.insert_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
@@ -2190,13 +2267,29 @@ where
 
         let exec_time = Instant::now();
 
-        // TODO: create StateRootTask with the receiving end of a channel and
-        // pass the sending end of the channel to the state hook.
-        let noop_state_hook = |_result_and_state: &ResultAndState| {};
+        // The state root task reads from the database while the block is executed. If blocks are
+        // being persisted, it overlays all in-memory blocks on top of the database, see
+        // `state_root_view_and_input`.
+        let persistence_in_progress = self.persistence_state.in_progress();
+        let state_root_handle = if self.config.use_state_root_task() {
+            match self.spawn_state_root_task(block.parent_hash, persistence_in_progress) {
+                Ok(handle) => Some(handle),
+                Err(error) => {
+                    debug!(target: "engine::tree", %error, "Failed to spawn state root task, falling back");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let state_hook: Box<dyn OnStateHook> = match &state_root_handle {
+            Some(handle) => Box::new(handle.state_hook()),
+            None => Box::new(|_result_and_state: &ResultAndState| {}),
+        };
         let output = self.metrics.executor.execute_metered(
             executor,
             (&block, U256::MAX).into(),
-            Box::new(noop_state_hook),
+            state_hook,
         )?;
 
         trace!(target: "engine::tree", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
@@ -2215,25 +2308,45 @@ where
             return Err(err.into())
         }
 
-        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+        let hashed_state = Arc::new(HashedPostState::from_bundle_state(&output.state.state));
 
         trace!(target: "engine::tree", block=?sealed_block.num_hash(), "Calculating block state root");
         let root_time = Instant::now();
         let mut state_root_result = None;
+        let mut state_root_strategy = StateRootStrategy::Serial;
+
+        // The state root task already fetched the proofs during execution, so only the final
+        // state needs to be applied. If it fails or its root doesn't match, we fall back to the
+        // regular computation, which determines whether the block is actually invalid.
+        if let Some(handle) = state_root_handle {
+            match handle.finish(hashed_state.clone()) {
+                Ok((state_root, trie_output)) if state_root == block.state_root => {
+                    state_root_result = Some((state_root, trie_output));
+                    state_root_strategy = StateRootStrategy::StateRootTask;
+                }
+                Ok((state_root, _)) => {
+                    debug!(target: "engine::tree", block=?sealed_block.num_hash(), ?state_root, "State root task returned a different state root, falling back");
+                }
+                Err(error) => {
+                    debug!(target: "engine::tree", block=?sealed_block.num_hash(), %error, "State root task failed, falling back");
+                }
+            }
+        }
 
-        // TODO: switch to calculate state root using `StateRootTask`.
-
-        // We attempt to compute state root in parallel if we are currently not persisting anything
-        // to database. This is safe, because the database state cannot change until we
-        // finish parallel computation. It is important that nothing is being persisted as
-        // we are computing in parallel, because we initialize a different database transaction
-        // per thread and it might end up with a different view of the database.
-        let persistence_in_progress = self.persistence_state.in_progress();
-        if !persistence_in_progress {
-            state_root_result = match self
-                .compute_state_root_parallel(block.parent_hash, &hashed_state)
-            {
-                Ok((state_root, trie_output)) => Some((state_root, trie_output)),
+        // We attempt to compute state root in parallel. Every thread initializes a different
+        // database transaction, so the consistent view makes sure that all of them observe a
+        // database state that is compatible with the in-memory state we overlay. If the database
+        // changes in an incompatible way, we fall back to the serial computation.
+        if state_root_result.is_none() {
+            state_root_result = match self.compute_state_root_parallel(
+                block.parent_hash,
+                &hashed_state,
+                persistence_in_progress,
+            ) {
+                Ok((state_root, trie_output)) => {
+                    state_root_strategy = StateRootStrategy::Parallel;
+                    Some((state_root, trie_output))
+                }
                 Err(ParallelStateRootError::Provider(ProviderError::ConsistentView(error))) => {
                     debug!(target: "engine", %error, "Parallel state root computation failed consistency check, falling back");
                     None
@@ -2246,7 +2359,7 @@ where
             result
         } else {
             debug!(target: "engine::tree", block=?sealed_block.num_hash(), persistence_in_progress, "Failed to compute state root in parallel");
-            state_provider.state_root_with_updates(hashed_state.clone())?
+            state_provider.state_root_with_updates((*hashed_state).clone())?
         };
 
         if state_root != block.state_root {
@@ -2265,13 +2378,16 @@ where
 
         let root_elapsed = root_time.elapsed();
         self.metrics.block_validation.record_state_root(&trie_output, root_elapsed.as_secs_f64());
-        debug!(target: "engine::tree", ?root_elapsed, block=?sealed_block.num_hash(), "Calculated state root");
+        self.metrics
+            .block_validation
+            .record_state_root_strategy(state_root_strategy, persistence_in_progress);
+        debug!(target: "engine::tree", ?root_elapsed, ?state_root_strategy, persistence_in_progress, block=?sealed_block.num_hash(), "Calculated state root");
 
         let executed = ExecutedBlock {
             block: sealed_block.clone(),
             senders: Arc::new(block.senders),
             execution_output: Arc::new(ExecutionOutcome::from((output, block_number))),
-            hashed_state: Arc::new(hashed_state),
+            hashed_state,
             trie: Arc::new(trie_output),
         };
 
@@ -2309,13 +2425,64 @@ where
         &self,
         parent_hash: B256,
         hashed_state: &HashedPostState,
+        persistence_in_progress: bool,
     ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
-        // TODO: when we switch to calculate state root using `StateRootTask` this
-        // method can be still useful to calculate the required `TrieInput` to
-        // create the task.
-        let consistent_view = ConsistentDbView::new_with_latest_tip(self.provider.clone())?;
+        let (consistent_view, mut input) =
+            self.state_root_view_and_input(parent_hash, persistence_in_progress)?;
+
+        // Extend with block we are validating root for.
+        input.append_ref(hashed_state);
+
+        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+    }
+
+    /// Spawns a [`StateRootTask`] that calculates the state root of a block on top of the given
+    /// parent while the block is executed.
+    fn spawn_state_root_task(
+        &self,
+        parent_hash: B256,
+        persistence_in_progress: bool,
+    ) -> Result<StateRootHandle, ParallelStateRootError> {
+        let (consistent_view, input) =
+            self.state_root_view_and_input(parent_hash, persistence_in_progress)?;
+        Ok(StateRootTask::spawn(StateRootConfig::new(consistent_view, input)))
+    }
+
+    /// Returns the consistent database view and the [`TrieInput`] of the state of the given parent
+    /// block: the reverts of the persisted state back to the parent, or the in-memory blocks on
+    /// top of the persisted state.
+    ///
+    /// If blocks are being persisted, the persisted state can advance while the state root is
+    /// computed. If the parent is in memory, all of its in-memory ancestors are overlaid on top of
+    /// the database, so the persisted state can be at any of them, see
+    /// [`ConsistentDbView::new_with_overlay`].
+    fn state_root_view_and_input(
+        &self,
+        parent_hash: B256,
+        persistence_in_progress: bool,
+    ) -> Result<(ConsistentDbView<P>, TrieInput), ParallelStateRootError> {
         let mut input = TrieInput::default();
 
+        if persistence_in_progress {
+            if let Some((historical, blocks)) = self.state.tree_state.blocks_by_hash(parent_hash) {
+                debug!(target: "engine::tree", %parent_hash, %historical, "Calculating state root in parallel, parent found in memory, persistence in progress");
+                // The blocks are ordered from the parent to the oldest one
+                let anchor_number =
+                    blocks.last().map_or(0, |block| block.block.number.saturating_sub(1));
+                let consistent_view = ConsistentDbView::new_with_overlay(
+                    self.provider.clone(),
+                    BlockNumHash::new(anchor_number, historical),
+                    blocks.iter().map(|block| block.block.num_hash()),
+                );
+
+                for block in blocks.iter().rev() {
+                    input.append_cached_ref(block.trie_updates(), block.hashed_state())
+                }
+                return Ok((consistent_view, input))
+            }
+        }
+
+        let consistent_view = ConsistentDbView::new_with_latest_tip(self.provider.clone())?;
         if let Some((historical, blocks)) = self.state.tree_state.blocks_by_hash(parent_hash) {
             debug!(target: "engine::tree", %parent_hash, %historical, "Calculating state root in parallel, parent found in memory");
             // Retrieve revert state for historical block.
@@ -2333,10 +2500,7 @@ where
             input.append(revert_state);
         }
 
-        // Extend with block we are validating root for.
-        input.append_ref(hashed_state);
-
-        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+        Ok((consistent_view, input))
     }
 
     /// Handles an error that occurred while inserting a block.
@@ -2365,8 +2529,9 @@ where
             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
         };
 
-        // keep track of the invalid header
-        self.state.invalid_headers.insert(block.header);
+        // keep track of the invalid header and the full block for debugging
+        self.state.invalid_headers.insert(block.header.clone());
+        self.bad_blocks.insert(block, &validation_err);
         Ok(PayloadStatus::new(
             PayloadStatusEnum::Invalid { validation_error: validation_err.to_string() },
             latest_valid_hash,
@@ -3668,6 +3833,31 @@ mod tests {
         test_harness.check_canon_head(fork_chain_last_hash);
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_fcu_unwind_to_canonical_ancestor() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+        test_harness.tree.config = TreeConfig::default().with_unwind_canonical_header(true);
+
+        let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(main_chain.clone());
+        test_harness.tree.persistence_state.last_persisted_block = main_chain[3].block().num_hash();
+
+        let new_head = main_chain[2].block().hash();
+        test_harness.send_fcu(new_head, ForkchoiceStatus::Valid).await;
+
+        test_harness.check_canon_commit(new_head).await;
+        test_harness.check_fcu(new_head, ForkchoiceStatus::Valid).await;
+        test_harness.check_canon_head(new_head);
+
+        // the unwound blocks are no longer canonical and are removed from disk
+        let in_memory_state = &test_harness.tree.canonical_in_memory_state;
+        assert_eq!(in_memory_state.get_canonical_head().hash(), new_head);
+        assert!(in_memory_state.header_by_hash(main_chain[3].block().hash()).is_none());
+        assert!(in_memory_state.pending_state().is_none());
+        assert_eq!(test_harness.tree.persistence_state.remove_above_state, vec![2]);
+    }
+
     #[tokio::test]
     async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
         reth_tracing::init_test_tracing();
@@ -1,36 +1,109 @@
 //! State root task related functionality.
 
-use reth_provider::providers::ConsistentDbView;
-use reth_trie::{updates::TrieUpdates, TrieInput};
-use reth_trie_parallel::root::ParallelStateRootError;
-use revm_primitives::{EvmState, B256};
-use std::sync::{
-    mpsc::{self, Receiver, RecvError},
-    Arc,
+use alloy_primitives::{
+    keccak256,
+    map::{HashMap, HashSet},
 };
-use tracing::debug;
+use reth_evm::system_calls::OnStateHook;
+use reth_provider::{providers::ConsistentDbView, BlockReader, DatabaseProviderFactory};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut,
+    updates::{TrieUpdates, TrieUpdatesSorted},
+    HashedPostState, HashedPostStateSorted, MultiProof, Nibbles, TrieInput,
+};
+use reth_trie_parallel::{proof::ParallelProof, root::ParallelStateRootError};
+use reth_trie_sparse::{SparseStateTrie, SparseStateTrieError, SparseTrieError};
+use revm_primitives::{EvmState, ResultAndState, B256};
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::Instant,
+};
+use tracing::{debug, trace};
+
+/// Maximum number of multiproofs that are calculated concurrently.
+///
+/// Targets of state updates that arrive while all slots are busy are batched into the next
+/// multiproof.
+const MAX_CONCURRENT_MULTIPROOFS: usize = 4;
 
 /// Result of the state root calculation
-pub(crate) type StateRootResult = Result<(B256, TrieUpdates), ParallelStateRootError>;
+pub(crate) type StateRootResult = Result<(B256, TrieUpdates), StateRootTaskError>;
+
+/// Accounts and their storage slots that a multiproof is generated for.
+type ProofTargets = HashMap<B256, HashSet<B256>>;
+
+/// Errors that can occur in the [`StateRootTask`].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum StateRootTaskError {
+    /// Failed to calculate a multiproof.
+    #[error(transparent)]
+    Proof(#[from] ParallelStateRootError),
+    /// Failed to update the sparse trie.
+    #[error(transparent)]
+    SparseTrie(#[from] SparseStateTrieError),
+    /// The handle was dropped before the final state was sent, or the task exited without
+    /// sending a result.
+    #[error("state root task was cancelled")]
+    Cancelled,
+}
+
+impl From<SparseTrieError> for StateRootTaskError {
+    fn from(error: SparseTrieError) -> Self {
+        Self::SparseTrie(error.into())
+    }
+}
+
+/// Messages handled by the [`StateRootTask`].
+#[derive(Debug)]
+pub(crate) enum StateRootMessage {
+    /// State changes of a single transaction or system call.
+    StateUpdate(EvmState),
+    /// The hashed state of the whole block, sent once execution finished.
+    FinalState(Arc<HashedPostState>),
+    /// A multiproof was calculated for the given targets.
+    ProofCalculated {
+        /// Targets of the multiproof.
+        targets: ProofTargets,
+        /// The calculated multiproof.
+        result: Result<MultiProof, ParallelStateRootError>,
+    },
+    /// The handle was dropped, the result is no longer needed.
+    Cancel,
+}
 
 /// Handle to a spawned state root task.
 #[derive(Debug)]
-#[allow(dead_code)]
 pub(crate) struct StateRootHandle {
+    /// Channel for sending messages to the task.
+    tx: Sender<StateRootMessage>,
     /// Channel for receiving the final result.
-    rx: mpsc::Receiver<StateRootResult>,
+    rx: Receiver<StateRootResult>,
 }
 
-#[allow(dead_code)]
 impl StateRootHandle {
-    /// Creates a new handle from a receiver.
-    pub(crate) const fn new(rx: mpsc::Receiver<StateRootResult>) -> Self {
-        Self { rx }
+    /// Returns a state hook that streams the state changes of executed transactions to the task.
+    pub(crate) fn state_hook(&self) -> impl OnStateHook {
+        let tx = self.tx.clone();
+        move |result_and_state: &ResultAndState| {
+            let _ = tx.send(StateRootMessage::StateUpdate(result_and_state.state.clone()));
+        }
     }
 
-    /// Waits for the state root calculation to complete.
-    pub(crate) fn wait_for_result(self) -> StateRootResult {
-        self.rx.recv().expect("state root task was dropped without sending result")
+    /// Sends the hashed state of the whole block and waits for the state root calculation to
+    /// complete.
+    pub(crate) fn finish(self, state: Arc<HashedPostState>) -> StateRootResult {
+        let _ = self.tx.send(StateRootMessage::FinalState(state));
+        self.rx.recv().unwrap_or(Err(StateRootTaskError::Cancelled))
+    }
+}
+
+impl Drop for StateRootHandle {
+    fn drop(&mut self) {
+        // the task ignores this once it received the final state
+        let _ = self.tx.send(StateRootMessage::Cancel);
     }
 }
 
@@ -39,153 +112,379 @@ impl StateRootHandle {
 pub(crate) struct StateRootConfig<Factory> {
     /// View over the state in the database.
     pub consistent_view: ConsistentDbView<Factory>,
-    /// Latest trie input.
-    pub input: Arc<TrieInput>,
+    /// The sorted collection of cached in-memory intermediate trie nodes.
+    pub nodes_sorted: Arc<TrieUpdatesSorted>,
+    /// The sorted in-memory overlay hashed state.
+    pub state_sorted: Arc<HashedPostStateSorted>,
+    /// The collection of prefix sets of the in-memory overlay.
+    pub prefix_sets: Arc<TriePrefixSetsMut>,
 }
 
-/// Wrapper for std channel receiver to maintain compatibility with `UnboundedReceiverStream`
-#[allow(dead_code)]
-pub(crate) struct StdReceiverStream {
-    rx: Receiver<EvmState>,
-}
-
-#[allow(dead_code)]
-impl StdReceiverStream {
-    pub(crate) const fn new(rx: Receiver<EvmState>) -> Self {
-        Self { rx }
-    }
-
-    pub(crate) fn recv(&self) -> Result<EvmState, RecvError> {
-        self.rx.recv()
+impl<Factory> StateRootConfig<Factory> {
+    /// Creates the configuration from the trie input of the parent state.
+    pub(crate) fn new(consistent_view: ConsistentDbView<Factory>, input: TrieInput) -> Self {
+        Self {
+            consistent_view,
+            nodes_sorted: Arc::new(input.nodes.into_sorted()),
+            state_sorted: Arc::new(input.state.into_sorted()),
+            prefix_sets: Arc::new(input.prefix_sets),
+        }
     }
 }
 
 /// Standalone task that receives a transaction state stream and updates relevant
 /// data structures to calculate state root.
 ///
-/// It is responsible of  initializing a blinded sparse trie and subscribe to
-/// transaction state stream. As it receives transaction execution results, it
-/// fetches the proofs for relevant accounts from the database and reveal them
-/// to the tree.
-/// Then it updates relevant leaves according to the result of the transaction.
-#[allow(dead_code)]
+/// While the block is executed, the task receives the state changes of every transaction and
+/// fetches multiproofs for the touched accounts and storage slots from the database in the
+/// background. The proofs are revealed in a [`SparseStateTrie`] as soon as they arrive.
+///
+/// Once execution finished, the task receives the hashed state of the whole block, fetches the
+/// proofs for any remaining targets (e.g. withdrawals) and applies the state to the sparse trie.
+/// Proofs are always revealed against the unmodified parent state, so nodes that are shared
+/// between proofs of different transactions never conflict with already applied changes.
+#[derive(Debug)]
 pub(crate) struct StateRootTask<Factory> {
-    /// Incoming state updates.
-    state_stream: StdReceiverStream,
+    /// Incoming state updates and calculated proofs.
+    rx: Receiver<StateRootMessage>,
+    /// Sender for proof results, cloned into every proof calculation.
+    tx: Sender<StateRootMessage>,
     /// Task configuration.
     config: StateRootConfig<Factory>,
+    /// Targets that were already fetched or are currently being fetched.
+    fetched_targets: ProofTargets,
+    /// Targets that are waiting for a free proof calculation slot.
+    pending_targets: ProofTargets,
+    /// Number of multiproofs that are currently being calculated.
+    proofs_in_flight: usize,
+    /// The sparse trie the proofs are revealed in.
+    trie: SparseStateTrie,
 }
 
-#[allow(dead_code)]
 impl<Factory> StateRootTask<Factory>
 where
-    Factory: Send + 'static,
+    Factory: DatabaseProviderFactory<Provider: BlockReader> + Clone + Send + Sync + 'static,
 {
-    /// Creates a new `StateRootTask`.
-    pub(crate) const fn new(
-        config: StateRootConfig<Factory>,
-        state_stream: StdReceiverStream,
-    ) -> Self {
-        Self { config, state_stream }
-    }
+    /// Spawns the state root task and returns a handle to stream state updates to it and await
+    /// its result.
+    pub(crate) fn spawn(config: StateRootConfig<Factory>) -> StateRootHandle {
+        let (tx, rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::sync_channel(1);
 
-    /// Spawns the state root task and returns a handle to await its result.
-    pub(crate) fn spawn(self) -> StateRootHandle {
-        let (tx, rx) = mpsc::sync_channel(1);
+        let task = Self {
+            rx,
+            tx: tx.clone(),
+            config,
+            fetched_targets: Default::default(),
+            pending_targets: Default::default(),
+            proofs_in_flight: 0,
+            trie: SparseStateTrie::default().with_updates(true),
+        };
         std::thread::Builder::new()
             .name("State Root Task".to_string())
             .spawn(move || {
-                debug!(target: "engine::tree", "Starting state root task");
-                let result = self.run();
-                let _ = tx.send(result);
+                debug!(target: "engine::root", "Starting state root task");
+                let result = task.run();
+                let _ = result_tx.send(result);
             })
             .expect("failed to spawn state root thread");
 
-        StateRootHandle::new(rx)
+        StateRootHandle { tx, rx: result_rx }
     }
 
-    /// Handles state updates.
-    fn on_state_update(
-        _view: &reth_provider::providers::ConsistentDbView<impl Send + 'static>,
-        _input: &std::sync::Arc<reth_trie::TrieInput>,
-        _state: EvmState,
-    ) {
-        // Default implementation of state update handling
-        // TODO: calculate hashed state update and dispatch proof gathering for it.
+    fn run(mut self) -> StateRootResult {
+        let mut final_state: Option<Arc<HashedPostState>> = None;
+        let mut updates = 0;
+        let mut proofs = 0;
+
+        loop {
+            if self.proofs_in_flight == 0 && self.pending_targets.is_empty() {
+                if let Some(state) = final_state.take() {
+                    let started = Instant::now();
+                    let result = self.apply_state(&state);
+                    debug!(target: "engine::root", updates, proofs, elapsed = ?started.elapsed(), "Calculated state root");
+                    return result
+                }
+            }
+
+            match self.rx.recv().expect("task holds a sender") {
+                StateRootMessage::StateUpdate(state) => {
+                    updates += 1;
+                    self.add_targets(evm_state_targets(&state));
+                }
+                StateRootMessage::FinalState(state) => {
+                    trace!(target: "engine::root", updates, "Received final state");
+                    self.add_targets(hashed_state_targets(&state));
+                    final_state = Some(state);
+                }
+                StateRootMessage::ProofCalculated { targets, result } => {
+                    proofs += 1;
+                    self.proofs_in_flight -= 1;
+                    self.trie.reveal_multiproof(targets, result?)?;
+                }
+                StateRootMessage::Cancel => {
+                    if final_state.is_none() {
+                        return Err(StateRootTaskError::Cancelled)
+                    }
+                }
+            }
+
+            self.dispatch_proofs();
+        }
     }
-}
 
-#[allow(dead_code)]
-impl<Factory> StateRootTask<Factory>
-where
-    Factory: Send + 'static,
-{
-    fn run(self) -> StateRootResult {
-        while let Ok(state) = self.state_stream.recv() {
-            Self::on_state_update(&self.config.consistent_view, &self.config.input, state);
+    /// Adds the targets that were not fetched yet to the pending targets.
+    fn add_targets(&mut self, targets: ProofTargets) {
+        for (address, slots) in targets {
+            let fetched = self.fetched_targets.get(&address);
+            let new_slots = slots
+                .into_iter()
+                .filter(|slot| !fetched.is_some_and(|fetched| fetched.contains(slot)))
+                .collect::<HashSet<_>>();
+            if fetched.is_some() && new_slots.is_empty() {
+                continue
+            }
+            self.fetched_targets.entry(address).or_default().extend(new_slots.iter().copied());
+            self.pending_targets.entry(address).or_default().extend(new_slots);
         }
+    }
 
-        // TODO:
-        //    * keep track of proof calculation
-        //    * keep track of intermediate root computation
-        //    * return final state root result
-        Ok((B256::default(), TrieUpdates::default()))
+    /// Spawns a multiproof calculation for the pending targets if a slot is free.
+    fn dispatch_proofs(&mut self) {
+        if self.pending_targets.is_empty() || self.proofs_in_flight >= MAX_CONCURRENT_MULTIPROOFS {
+            return
+        }
+
+        let targets = std::mem::take(&mut self.pending_targets);
+        let proof = ParallelProof::new(
+            self.config.consistent_view.clone(),
+            self.config.nodes_sorted.clone(),
+            self.config.state_sorted.clone(),
+            self.config.prefix_sets.clone(),
+        )
+        .with_branch_node_masks(true);
+        let tx = self.tx.clone();
+        self.proofs_in_flight += 1;
+
+        // Storage proofs are calculated on the rayon pool, so the account proof must not block a
+        // rayon thread.
+        std::thread::Builder::new()
+            .name("State Root Proof".to_string())
+            .spawn(move || {
+                let result = proof.multiproof(targets.clone());
+                let _ = tx.send(StateRootMessage::ProofCalculated { targets, result });
+            })
+            .expect("failed to spawn state root proof thread");
+    }
+
+    /// Applies the hashed state of the block to the revealed sparse trie and calculates the root.
+    fn apply_state(mut self, state: &HashedPostState) -> StateRootResult {
+        for (address, storage) in &state.storages {
+            if storage.wiped {
+                self.trie.wipe_storage(*address)?;
+            }
+            for (slot, value) in &storage.storage {
+                let slot = Nibbles::unpack(slot);
+                if value.is_zero() {
+                    self.trie.remove_storage_leaf(*address, &slot)?;
+                } else {
+                    self.trie.update_storage_leaf(
+                        *address,
+                        slot,
+                        alloy_rlp::encode_fixed_size(value).to_vec(),
+                    )?;
+                }
+            }
+        }
+
+        for (address, account) in &state.accounts {
+            match account {
+                Some(account) => self.trie.update_account(*address, *account)?,
+                None => self.trie.remove_account(*address)?,
+            }
+        }
+
+        let root = self.trie.root().ok_or(SparseTrieError::Blind)?;
+        let trie_updates = self.trie.take_trie_updates().unwrap_or_default();
+        Ok((root, trie_updates))
+    }
+}
+
+/// Returns the hashed accounts and storage slots changed by a transaction.
+fn evm_state_targets(state: &EvmState) -> ProofTargets {
+    state
+        .iter()
+        .filter(|(_, account)| account.is_touched())
+        .map(|(address, account)| {
+            let slots = account
+                .changed_storage_slots()
+                .map(|(slot, _)| keccak256(B256::from(*slot)))
+                .collect();
+            (keccak256(address), slots)
+        })
+        .collect()
+}
+
+/// Returns the accounts and storage slots of the hashed state.
+fn hashed_state_targets(state: &HashedPostState) -> ProofTargets {
+    let mut targets = ProofTargets::default();
+    for address in state.accounts.keys() {
+        targets.entry(*address).or_default();
     }
+    for (address, storage) in &state.storages {
+        targets.entry(*address).or_default().extend(storage.storage.keys().copied());
+    }
+    targets
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_provider::{providers::ConsistentDbView, test_utils::MockEthProvider};
+    use reth_primitives::Account;
+    use reth_provider::{
+        providers::ConsistentDbView, test_utils::create_test_provider_factory, HashingWriter,
+        StateRootProvider,
+    };
     use reth_trie::TrieInput;
     use revm_primitives::{
-        Account, AccountInfo, AccountStatus, Address, EvmState, EvmStorage, EvmStorageSlot,
-        HashMap, B256, U256,
+        Account as RevmAccount, AccountInfo, AccountStatus, Address, EvmStorage, EvmStorageSlot,
+        ExecutionResult, Output, SuccessReason, U256,
     };
-    use std::sync::Arc;
-
-    fn create_mock_config() -> StateRootConfig<MockEthProvider> {
-        let factory = MockEthProvider::default();
-        let view = ConsistentDbView::new(factory, None);
-        let input = Arc::new(TrieInput::default());
-        StateRootConfig { consistent_view: view, input }
-    }
-
-    fn create_mock_state() -> revm_primitives::EvmState {
-        let mut state_changes: EvmState = HashMap::default();
-        let storage = EvmStorage::from_iter([(U256::from(1), EvmStorageSlot::new(U256::from(2)))]);
-        let account = Account {
-            info: AccountInfo {
-                balance: U256::from(100),
-                nonce: 10,
-                code_hash: B256::random(),
-                code: Default::default(),
+
+    fn result_and_state(state: EvmState) -> ResultAndState {
+        ResultAndState {
+            result: ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: 0,
+                gas_refunded: 0,
+                logs: Vec::new(),
+                output: Output::Call(Default::default()),
             },
-            storage,
-            status: AccountStatus::Loaded,
-        };
+            state,
+        }
+    }
 
-        let address = Address::random();
-        state_changes.insert(address, account);
+    /// Creates a state update that changes the balance of the accounts and sets the given slots.
+    fn create_state_update(
+        accounts: &[Address],
+        round: u64,
+        slots: impl Fn(&Address) -> Vec<(U256, U256)>,
+    ) -> EvmState {
+        accounts
+            .iter()
+            .map(|address| {
+                let storage = slots(address)
+                    .into_iter()
+                    .map(|(slot, value)| (slot, EvmStorageSlot::new_changed(U256::ZERO, value)))
+                    .collect::<EvmStorage>();
+                let account = RevmAccount {
+                    info: AccountInfo {
+                        balance: U256::from(round * 1_000 + 1),
+                        nonce: round,
+                        ..Default::default()
+                    },
+                    storage,
+                    status: AccountStatus::Touched,
+                };
+                (*address, account)
+            })
+            .collect()
+    }
 
-        state_changes
+    /// Folds the state updates into the hashed state of the block.
+    fn hashed_state(updates: &[EvmState]) -> HashedPostState {
+        let mut hashed_state = HashedPostState::default();
+        for update in updates {
+            for (address, account) in update {
+                let hashed_address = keccak256(address);
+                hashed_state
+                    .accounts
+                    .insert(hashed_address, Some(Account::from(account.info.clone())));
+                let storage = hashed_state.storages.entry(hashed_address).or_default();
+                for (slot, value) in account.changed_storage_slots() {
+                    storage.storage.insert(keccak256(B256::from(*slot)), value.present_value);
+                }
+            }
+        }
+        hashed_state
     }
 
     #[test]
     fn test_state_root_task() {
-        let config = create_mock_config();
-        let (tx, rx) = std::sync::mpsc::channel();
-        let stream = StdReceiverStream::new(rx);
+        let factory = create_test_provider_factory();
 
-        let task = StateRootTask::new(config, stream);
-        let handle = task.spawn();
+        let existing = (0..20u8).map(|i| Address::with_last_byte(i + 1)).collect::<Vec<_>>();
+        let created = (0..5u8).map(|i| Address::with_last_byte(i + 101)).collect::<Vec<_>>();
 
-        for _ in 0..10 {
-            tx.send(create_mock_state()).expect("failed to send state");
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_account_for_hashing(existing.iter().map(|address| {
+                (*address, Some(Account { balance: U256::from(7), ..Default::default() }))
+            }))
+            .unwrap();
+        provider_rw
+            .insert_storage_for_hashing(existing.iter().step_by(2).map(|address| {
+                let storage = (1..10u64)
+                    .map(|slot| reth_primitives::StorageEntry {
+                        key: B256::from(U256::from(slot)),
+                        value: U256::from(slot * 3),
+                    })
+                    .collect::<Vec<_>>();
+                (*address, storage)
+            }))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let updates = (1..=10u64)
+            .map(|round| {
+                let accounts = if round % 3 == 0 { &created } else { &existing };
+                create_state_update(accounts, round, |address| {
+                    let base = u64::from(address.0[19]) + round;
+                    vec![
+                        (U256::from(base % 12 + 1), U256::from(round)),
+                        (U256::from(base % 5 + 20), U256::from(round + 1)),
+                    ]
+                })
+            })
+            .collect::<Vec<_>>();
+        let hashed_state = hashed_state(&updates);
+
+        let config = StateRootConfig::new(
+            ConsistentDbView::new(factory.clone(), None),
+            TrieInput::default(),
+        );
+        let handle = StateRootTask::spawn(config);
+        let mut state_hook = handle.state_hook();
+        for update in updates {
+            state_hook.on_state(&result_and_state(update));
         }
-        drop(tx);
+        drop(state_hook);
+
+        let (root, trie_updates) = handle.finish(Arc::new(hashed_state.clone())).unwrap();
+
+        let (expected_root, expected_updates) =
+            factory.latest().unwrap().state_root_with_updates(hashed_state).unwrap();
+        assert_eq!(root, expected_root);
+        assert_eq!(trie_updates.account_nodes_ref(), expected_updates.account_nodes_ref());
+        // The sparse trie doesn't report storage tries that were touched without any node changes
+        let expected_storage_tries = expected_updates
+            .storage_tries_ref()
+            .iter()
+            .filter(|(_, updates)| !updates.is_empty())
+            .map(|(address, updates)| (*address, updates.clone()))
+            .collect::<std::collections::HashMap<_, _>>();
+        assert_eq!(trie_updates.storage_tries_ref(), &expected_storage_tries);
+    }
+
+    #[test]
+    fn test_state_root_task_exited_without_result() {
+        let (tx, _rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::sync_channel(1);
+        // the task thread panicked and dropped its result sender
+        drop(result_tx);
 
-        let result = handle.wait_for_result();
-        assert!(result.is_ok(), "sync block execution failed");
+        let handle = StateRootHandle { tx, rx: result_rx };
+        let result = handle.finish(Arc::new(HashedPostState::default()));
+        assert!(matches!(result, Err(StateRootTaskError::Cancelled)));
     }
 }
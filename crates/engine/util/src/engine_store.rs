@@ -1,17 +1,22 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState};
-use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, EngineTypes};
+use alloy_rpc_types_engine::{
+    ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
+};
+use futures::{Future, Stream, StreamExt};
+use reth_engine_primitives::{BeaconEngineMessage, EngineTypes, OnForkChoiceUpdated};
+use reth_errors::RethResult;
 use reth_fs_util as fs;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    fmt::Display,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
 /// A message from the engine API that has been stored to disk.
@@ -35,8 +40,53 @@ pub enum StoredEngineApiMessage<Attributes> {
     },
 }
 
+/// The response to an engine API message that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The payload status returned by an `engine_newPayload` method call.
+    NewPayload(PayloadStatus),
+    /// The result returned by an `engine_forkchoiceUpdated` method call.
+    ForkchoiceUpdated(ForkchoiceUpdated),
+    /// The error returned by the method call.
+    Error(String),
+}
+
+impl StoredEngineApiResponse {
+    /// Creates the stored response from the result of an `engine_newPayload` method call.
+    pub fn new_payload<E: Display>(result: &Result<PayloadStatus, E>) -> Self {
+        match result {
+            Ok(status) => Self::NewPayload(status.clone()),
+            Err(error) => Self::Error(error.to_string()),
+        }
+    }
+
+    /// Creates the stored response from the result of an `engine_forkchoiceUpdated` method call.
+    pub fn forkchoice_updated<E: Display>(result: &Result<ForkchoiceUpdated, E>) -> Self {
+        match result {
+            Ok(updated) => Self::ForkchoiceUpdated(updated.clone()),
+            Err(error) => Self::Error(error.to_string()),
+        }
+    }
+
+    /// Returns `true` if the responses are equivalent.
+    ///
+    /// Payload IDs depend on the version of the method call, so only their presence is compared.
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ForkchoiceUpdated(this), Self::ForkchoiceUpdated(other)) => {
+                this.payload_status == other.payload_status &&
+                    this.payload_id.is_some() == other.payload_id.is_some()
+            }
+            _ => self == other,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+///
+/// The response to a message is stored next to it, see [`EngineMessageStore::response_path`].
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -50,13 +100,21 @@ impl EngineMessageStore {
         Self { path }
     }
 
+    /// Returns the path of the file that stores the response to the message stored at the given
+    /// path.
+    pub fn response_path(message_path: &Path) -> PathBuf {
+        message_path.with_extension("response.json")
+    }
+
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message, if the message has been stored.
     pub fn on_message<Engine>(
         &self,
         msg: &BeaconEngineMessage<Engine>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<Option<PathBuf>>
     where
         Engine: EngineTypes,
     {
@@ -69,19 +127,25 @@ impl EngineMessageStore {
                 tx: _tx,
                 version: _version,
             } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                Ok(Some(path))
             }
             BeaconEngineMessage::NewPayload { payload, sidecar, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(
                         &StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
                             payload: payload.clone(),
@@ -89,13 +153,35 @@ impl EngineMessageStore {
                         },
                     )?,
                 )?;
+                Ok(Some(path))
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
-        };
+            BeaconEngineMessage::TransitionConfigurationExchanged => Ok(None),
+        }
+    }
+
+    /// Stores the response to the message stored at the given path.
+    pub fn on_response(
+        &self,
+        message_path: &Path,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        fs::write(Self::response_path(message_path), serde_json::to_vec(response)?)?;
         Ok(())
     }
 
+    /// Reads the response to the message stored at the given path, if it has been stored.
+    pub fn read_response(
+        &self,
+        message_path: &Path,
+    ) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
@@ -103,6 +189,10 @@ impl EngineMessageStore {
             let entry = entry?;
             let filename = entry.file_name();
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
+                if filename.ends_with(".response.json") {
+                    // responses are read together with their messages
+                    continue
+                }
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
                     tracing::debug!(target: "engine::store", timestamp, filename, "Queued engine API message");
@@ -117,8 +207,14 @@ impl EngineMessageStore {
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
+/// A future that stores the response to an Engine API message and forwards it.
+type PendingResponse = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A wrapper stream that stores Engine API messages and their responses in
 /// the specified directory.
+///
+/// Responses are stored and forwarded by a spawned task, so that they reach the consensus layer
+/// as soon as the engine sends them, independent of when the stream is polled next.
 #[derive(Debug)]
 #[pin_project::pin_project]
 pub struct EngineStoreStream<S> {
@@ -145,12 +241,248 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
+        let Some(msg) = ready!(this.stream.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        let msg = match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(Some(path)) => {
+                let (msg, response) = intercept_response(msg, this.store.clone(), path);
+                if let Some(response) = response {
+                    tokio::spawn(response);
+                }
+                msg
+            }
+            Ok(None) => msg,
+            Err(error) => {
                 error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+                msg
             }
+        };
+        Poll::Ready(Some(msg))
+    }
+}
+
+/// Replaces the response sender of the message, so that the response is stored next to the
+/// message at the given path before it's forwarded to the original sender.
+///
+/// Returns the message and the future that stores and forwards the response.
+fn intercept_response<Engine: EngineTypes>(
+    msg: BeaconEngineMessage<Engine>,
+    store: EngineMessageStore,
+    path: PathBuf,
+) -> (BeaconEngineMessage<Engine>, Option<PendingResponse>) {
+    let on_response = move |response: &StoredEngineApiResponse| {
+        if let Err(error) = store.on_response(&path, response) {
+            error!(target: "engine::stream::store", path = %path.display(), %error, "Error storing Engine API response");
+        }
+    };
+
+    match msg {
+        BeaconEngineMessage::NewPayload { payload, sidecar, tx } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let response = async move {
+                let Ok(result) = response_rx.await else { return };
+                on_response(&StoredEngineApiResponse::new_payload(&result));
+                let _ = tx.send(result);
+            };
+            (
+                BeaconEngineMessage::NewPayload { payload, sidecar, tx: response_tx },
+                Some(Box::pin(response)),
+            )
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+            let (response_tx, response_rx) = oneshot::channel::<RethResult<OnForkChoiceUpdated>>();
+            let response = async move {
+                let Ok(result) = response_rx.await else { return };
+                let result = match result {
+                    Ok(on_updated) => {
+                        // The result is only known once the payload job has been initiated.
+                        let forkchoice_status = on_updated.forkchoice_status();
+                        let result = on_updated.await;
+                        on_response(&StoredEngineApiResponse::forkchoice_updated(&result));
+                        Ok(OnForkChoiceUpdated::ready(forkchoice_status, result))
+                    }
+                    Err(error) => {
+                        on_response(&StoredEngineApiResponse::Error(error.to_string()));
+                        Err(error)
+                    }
+                };
+                let _ = tx.send(result);
+            };
+            (
+                BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    version,
+                    tx: response_tx,
+                },
+                Some(Box::pin(response)),
+            )
         }
-        Poll::Ready(next)
+        msg @ BeaconEngineMessage::TransitionConfigurationExchanged => (msg, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, B64};
+    use alloy_rpc_types_engine::{ForkchoiceUpdateError, PayloadId, PayloadStatusEnum};
+    use futures::channel::mpsc;
+    use reth_engine_primitives::{
+        BeaconOnNewPayloadError, EngineApiMessageVersion, ForkchoiceStatus,
+    };
+    use reth_errors::RethError;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_primitives::SealedBlock;
+    use reth_rpc_types_compat::engine::payload::block_to_payload;
+
+    type Message = BeaconEngineMessage<EthEngineTypes>;
+
+    /// Sends the message through a store stream and returns the message received by the engine
+    /// and the path it was stored at.
+    async fn store_message(store: &EngineMessageStore, msg: Message) -> (Message, PathBuf) {
+        let (tx, rx) = mpsc::unbounded();
+        let mut stream = EngineStoreStream::new(rx, store.path.clone());
+        tx.unbounded_send(msg).unwrap();
+        let msg = stream.next().await.unwrap();
+        let path = store.engine_messages_iter().unwrap().last().unwrap();
+        (msg, path)
+    }
+
+    fn new_payload(tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>) -> Message {
+        BeaconEngineMessage::NewPayload {
+            payload: block_to_payload(SealedBlock::default()),
+            sidecar: ExecutionPayloadSidecar::none(),
+            tx,
+        }
+    }
+
+    fn forkchoice_updated(tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>) -> Message {
+        BeaconEngineMessage::ForkchoiceUpdated {
+            state: ForkchoiceState::default(),
+            payload_attrs: None,
+            version: EngineApiMessageVersion::V3,
+            tx,
+        }
+    }
+
+    #[tokio::test]
+    async fn stores_and_forwards_new_payload_response() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(temp_dir.path().to_path_buf());
+
+        let (tx, rx) = oneshot::channel();
+        let (msg, path) = store_message(&store, new_payload(tx)).await;
+        let BeaconEngineMessage::NewPayload { tx: engine_tx, .. } = msg else { unreachable!() };
+
+        let status = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::random()));
+        engine_tx.send(Ok(status.clone())).unwrap();
+        assert_eq!(rx.await.unwrap().unwrap(), status);
+        assert_eq!(
+            store.read_response(&path).unwrap(),
+            Some(StoredEngineApiResponse::NewPayload(status))
+        );
+        // responses are not replayed as messages
+        assert_eq!(store.engine_messages_iter().unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn stores_and_forwards_forkchoice_updated_response() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(temp_dir.path().to_path_buf());
+
+        let (tx, rx) = oneshot::channel();
+        let (msg, path) = store_message(&store, forkchoice_updated(tx)).await;
+        let BeaconEngineMessage::ForkchoiceUpdated { tx: engine_tx, .. } = msg else {
+            unreachable!()
+        };
+
+        let status = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::random()));
+        engine_tx.send(Ok(OnForkChoiceUpdated::valid(status.clone()))).unwrap();
+        let on_updated = rx.await.unwrap().unwrap();
+        assert_eq!(on_updated.forkchoice_status(), ForkchoiceStatus::Valid);
+        assert_eq!(on_updated.await.unwrap(), ForkchoiceUpdated::new(status.clone()));
+        assert_eq!(
+            store.read_response(&path).unwrap(),
+            Some(StoredEngineApiResponse::ForkchoiceUpdated(ForkchoiceUpdated::new(status)))
+        );
+    }
+
+    #[tokio::test]
+    async fn stores_and_forwards_forkchoice_updated_errors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(temp_dir.path().to_path_buf());
+
+        // the forkchoice update failed
+        let (tx, rx) = oneshot::channel();
+        let (msg, path) = store_message(&store, forkchoice_updated(tx)).await;
+        let BeaconEngineMessage::ForkchoiceUpdated { tx: engine_tx, .. } = msg else {
+            unreachable!()
+        };
+        engine_tx.send(Ok(OnForkChoiceUpdated::invalid_state())).unwrap();
+        let on_updated = rx.await.unwrap().unwrap();
+        assert_eq!(on_updated.forkchoice_status(), ForkchoiceStatus::Invalid);
+        assert_eq!(on_updated.await.unwrap_err(), ForkchoiceUpdateError::InvalidState);
+        assert_eq!(
+            store.read_response(&path).unwrap(),
+            Some(StoredEngineApiResponse::Error(ForkchoiceUpdateError::InvalidState.to_string()))
+        );
+
+        // the engine failed to handle the message
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let (tx, rx) = oneshot::channel();
+        let (msg, path) = store_message(&store, forkchoice_updated(tx)).await;
+        let BeaconEngineMessage::ForkchoiceUpdated { tx: engine_tx, .. } = msg else {
+            unreachable!()
+        };
+        engine_tx.send(Err(RethError::msg("engine failed"))).unwrap();
+        assert_eq!(rx.await.unwrap().unwrap_err().to_string(), "engine failed");
+        assert_eq!(
+            store.read_response(&path).unwrap(),
+            Some(StoredEngineApiResponse::Error("engine failed".to_string()))
+        );
+    }
+
+    #[test]
+    fn forkchoice_updated_matches_ignores_payload_id_value() {
+        let status = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::random()));
+        let updated = |payload_id: Option<u64>| {
+            let updated = ForkchoiceUpdated::new(status.clone());
+            let updated = match payload_id {
+                Some(id) => updated.with_payload_id(PayloadId(B64::from(id))),
+                None => updated,
+            };
+            StoredEngineApiResponse::ForkchoiceUpdated(updated)
+        };
+
+        // only the presence of the payload ID is compared
+        assert!(updated(Some(1)).matches(&updated(Some(2))));
+        assert!(updated(None).matches(&updated(None)));
+        assert!(!updated(Some(1)).matches(&updated(None)));
+        assert!(!updated(None).matches(&updated(Some(1))));
+
+        // the payload status is compared exactly
+        let syncing = StoredEngineApiResponse::ForkchoiceUpdated(ForkchoiceUpdated::from_status(
+            PayloadStatusEnum::Syncing,
+        ));
+        assert!(!updated(None).matches(&syncing));
+        assert!(!updated(None).matches(&StoredEngineApiResponse::Error("error".to_string())));
+    }
+
+    #[test]
+    fn new_payload_matches_exactly() {
+        let valid = StoredEngineApiResponse::NewPayload(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            Some(B256::with_last_byte(1)),
+        ));
+        let other_hash = StoredEngineApiResponse::NewPayload(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            Some(B256::with_last_byte(2)),
+        ));
+        assert!(valid.matches(&valid.clone()));
+        assert!(!valid.matches(&other_hash));
+        assert!(StoredEngineApiResponse::Error("a".to_string())
+            .matches(&StoredEngineApiResponse::Error("a".to_string())));
+        assert!(!StoredEngineApiResponse::Error("a".to_string())
+            .matches(&StoredEngineApiResponse::Error("b".to_string())));
     }
 }
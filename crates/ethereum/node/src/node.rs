@@ -177,6 +177,7 @@ where
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
+            .with_admission_policies(pool_config.admission_policies.clone())
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
             .build_with_tasks(
                 ctx.provider().clone(),
@@ -194,19 +195,41 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
-
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
-                "local transactions backup task",
-                |shutdown| {
-                    reth_transaction_pool::maintain::backup_local_transactions_task(
-                        shutdown,
-                        pool.clone(),
-                        transactions_backup_config,
-                    )
-                },
-            );
+            let txpool_args = &ctx.config().txpool;
+            if txpool_args.persist {
+                let mut snapshot_config =
+                    reth_transaction_pool::maintain::TransactionPoolSnapshotConfig::new(
+                        data_dir.txpool_snapshot(),
+                    );
+                if let Some(interval) = txpool_args.persist_interval {
+                    snapshot_config = snapshot_config.with_interval(interval);
+                }
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool snapshot task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::snapshot_transaction_pool_task(
+                            shutdown,
+                            pool.clone(),
+                            snapshot_config,
+                        )
+                    },
+                );
+            } else {
+                let transactions_backup_config =
+                    reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions backup task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::backup_local_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            transactions_backup_config,
+                        )
+                    },
+                );
+            }
 
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
@@ -1,10 +1,18 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_primitives::{address, b256, hex, Bytes, B256, U256};
+use alloy_provider::{
+    network::{Ethereum, EthereumWallet, Network, TransactionBuilder},
+    Provider, ProviderBuilder,
+};
 use futures::StreamExt;
-use reth::{args::DevArgs, rpc::api::eth::helpers::EthTransactions};
+use reth::{
+    args::{DevArgs, RpcServerArgs},
+    rpc::{api::eth::helpers::EthTransactions, builder::RpcModuleSelection},
+};
 use reth_chainspec::ChainSpec;
+use reth_e2e_test_utils::wallet::Wallet;
 use reth_node_api::FullNodeComponents;
 use reth_node_builder::{
     rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
@@ -42,6 +50,288 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_set_balance_on_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let mut rpc = RpcServerArgs::default().with_unused_ports().with_http();
+    rpc.http_api = Some(RpcModuleSelection::All);
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_rpc(rpc)
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let provider =
+        ProviderBuilder::new().on_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+    let account = address!("00000000000000000000000000000000000000aa");
+    let balance = U256::from(1_000_000_000u64);
+
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, balance)).await?;
+    assert_eq!(provider.get_block_number().await?, 1);
+    assert_eq!(provider.get_balance(account).await?, balance);
+
+    provider.raw_request::<_, ()>("hardhat_mine".into(), (U256::from(2),)).await?;
+    assert_eq!(provider.get_block_number().await?, 3);
+    assert_eq!(provider.get_balance(account).await?, balance);
+
+    let snapshot: U256 = provider.raw_request("evm_snapshot".into(), ()).await?;
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::ZERO)).await?;
+    assert_eq!(provider.get_block_number().await?, 4);
+    assert_eq!(provider.get_balance(account).await?, U256::ZERO);
+
+    assert!(provider.raw_request::<_, bool>("evm_revert".into(), (snapshot,)).await?);
+    assert_eq!(provider.get_block_number().await?, 3);
+    assert_eq!(provider.get_balance(account).await?, balance);
+    assert!(!provider.raw_request::<_, bool>("evm_revert".into(), (snapshot,)).await?);
+
+    // the chain can be extended after a revert
+    let block: serde_json::Value = provider.raw_request("evm_mine".into(), ()).await?;
+    assert_eq!(block["number"], "0x4");
+    assert_eq!(provider.get_block_number().await?, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_impersonate_and_dump_state_on_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let mut rpc = RpcServerArgs::default().with_unused_ports().with_http();
+    rpc.http_api = Some(RpcModuleSelection::All);
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_rpc(rpc)
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let provider =
+        ProviderBuilder::new().on_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+    let account = address!("00000000000000000000000000000000000000bb");
+    let coinbase = address!("00000000000000000000000000000000000000cc");
+
+    provider
+        .raw_request::<_, ()>(
+            "anvil_setBalance".into(),
+            (account, U256::from(10).pow(U256::from(18))),
+        )
+        .await?;
+    provider.raw_request::<_, ()>("anvil_setCoinbase".into(), (coinbase,)).await?;
+    provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (account,)).await?;
+
+    let tx = <Ethereum as Network>::TransactionRequest::default()
+        .with_from(account)
+        .with_to(coinbase)
+        .with_value(U256::from(1))
+        .with_max_fee_per_gas(10_000_000_000)
+        .with_max_priority_fee_per_gas(1_000_000_000);
+    let receipt = provider.send_transaction(tx.clone()).await?.get_receipt().await?;
+    assert!(receipt.status());
+    assert_eq!(receipt.from, account);
+    let transaction = provider.get_transaction_by_hash(receipt.transaction_hash).await?.unwrap();
+    assert_eq!(transaction.from, account);
+
+    let block: serde_json::Value = provider.raw_request("evm_mine".into(), ()).await?;
+    assert_eq!(block["miner"], serde_json::to_value(coinbase)?);
+
+    provider.raw_request::<_, ()>("anvil_stopImpersonatingAccount".into(), (account,)).await?;
+    assert!(provider.send_transaction(tx).await.is_err());
+
+    // the dumped state can be loaded again
+    let balance = provider.get_balance(account).await?;
+    let state: Bytes = provider.raw_request("anvil_dumpState".into(), ()).await?;
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::ZERO)).await?;
+    assert_eq!(provider.get_balance(account).await?, U256::ZERO);
+    assert!(provider.raw_request::<_, bool>("anvil_loadState".into(), (state,)).await?);
+    assert_eq!(provider.get_balance(account).await?, balance);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn revert_keeps_valid_pool_transactions_on_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let mut rpc = RpcServerArgs::default().with_unused_ports().with_http();
+    rpc.http_api = Some(RpcModuleSelection::All);
+    // only mine blocks on request, so that transactions stay in the pool
+    let node_config =
+        NodeConfig::test().with_chain(custom_chain()).with_rpc(rpc).with_dev(DevArgs {
+            dev: true,
+            block_time: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let provider =
+        ProviderBuilder::new().on_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+    let reverted = address!("00000000000000000000000000000000000000dd");
+    let unaffected = address!("00000000000000000000000000000000000000ee");
+    for account in [reverted, unaffected] {
+        provider
+            .raw_request::<_, ()>(
+                "anvil_setBalance".into(),
+                (account, U256::from(10).pow(U256::from(18))),
+            )
+            .await?;
+        provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (account,)).await?;
+    }
+    let transfer = |from| {
+        <Ethereum as Network>::TransactionRequest::default()
+            .with_from(from)
+            .with_to(unaffected)
+            .with_value(U256::from(1))
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(10_000_000_000)
+            .with_max_priority_fee_per_gas(1_000_000_000)
+    };
+
+    let snapshot: U256 = provider.raw_request("evm_snapshot".into(), ()).await?;
+    let mined = *provider.send_transaction(transfer(reverted)).await?.tx_hash();
+    let block: serde_json::Value = provider.raw_request("evm_mine".into(), ()).await?;
+    assert_eq!(block["transactions"][0]["hash"], serde_json::to_value(mined)?);
+
+    let gapped = *provider.send_transaction(transfer(reverted).with_nonce(1)).await?.tx_hash();
+    let valid = *provider.send_transaction(transfer(unaffected)).await?.tx_hash();
+
+    // the reverted nonce leaves a gap in front of the first transaction, the other one is kept
+    assert!(provider.raw_request::<_, bool>("evm_revert".into(), (snapshot,)).await?);
+    assert_eq!(provider.get_transaction_count(reverted).await?, 0);
+    assert!(provider.get_transaction_by_hash(gapped).await?.is_none());
+    assert!(provider.get_transaction_by_hash(valid).await?.is_some());
+
+    let block: serde_json::Value = provider.raw_request("evm_mine".into(), ()).await?;
+    assert_eq!(block["transactions"][0]["hash"], serde_json::to_value(valid)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_search_transactions_on_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let mut rpc = RpcServerArgs::default().with_unused_ports().with_http();
+    rpc.http_api = Some(RpcModuleSelection::All);
+    // only mine blocks on request, so that several transactions end up in one block
+    let node_config =
+        NodeConfig::test().with_chain(custom_chain()).with_rpc(rpc).with_dev(DevArgs {
+            dev: true,
+            block_time: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let signer = Wallet::default().with_chain_id(2600).gen().swap_remove(0);
+    let sender = signer.address();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(signer))
+        .on_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+    provider
+        .raw_request::<_, ()>(
+            "anvil_setBalance".into(),
+            (sender, U256::from(10).pow(U256::from(18))),
+        )
+        .await?;
+
+    let transfer = <Ethereum as Network>::TransactionRequest::default()
+        .with_to(address!("00000000000000000000000000000000000000ff"))
+        .with_value(U256::from(1))
+        .with_gas_limit(21_000)
+        .with_max_fee_per_gas(10_000_000_000)
+        .with_max_priority_fee_per_gas(1_000_000_000);
+    let mut hashes = Vec::new();
+    for block_txs in [2, 1] {
+        for _ in 0..block_txs {
+            hashes.push(*provider.send_transaction(transfer.clone()).await?.tx_hash());
+        }
+        let _: serde_json::Value = provider.raw_request("evm_mine".into(), ()).await?;
+    }
+
+    // both searches return the newest transaction first, also within a block
+    hashes.reverse();
+    for method in ["ots_searchTransactionsBefore", "ots_searchTransactionsAfter"] {
+        let result: serde_json::Value =
+            provider.raw_request(method.into(), (sender, 0, 10)).await?;
+        let found = result["txs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tx| serde_json::from_value(tx["hash"].clone()))
+            .collect::<Result<Vec<B256>, _>>()?;
+        assert_eq!(found, hashes, "{method}");
+        let receipts = result["receipts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|receipt| serde_json::from_value(receipt["transactionHash"].clone()))
+            .collect::<Result<Vec<B256>, _>>()?;
+        assert_eq!(receipts, hashes, "{method}");
+    }
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
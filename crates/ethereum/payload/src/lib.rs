@@ -13,8 +13,8 @@ use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
 use alloy_eips::{eip4844::MAX_DATA_GAS_PER_BLOCK, eip7685::Requests, merge::BEACON_NONCE};
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
-    commit_withdrawals, is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder,
-    PayloadConfig,
+    commit_state_overrides, commit_withdrawals, is_better_payload, BuildArguments, BuildOutcome,
+    PayloadBuilder, PayloadConfig,
 };
 use reth_chain_state::ExecutedBlock;
 use reth_chainspec::ChainSpec;
@@ -32,8 +32,8 @@ use reth_primitives::{
 use reth_provider::{ChainSpecProvider, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
 use reth_transaction_pool::{
-    noop::NoopTransactionPool, BestTransactions, BestTransactionsAttributes, TransactionPool,
-    ValidPoolTransaction,
+    noop::NoopTransactionPool, BestTransactions, BestTransactionsAttributes, BlockOverrides,
+    PoolBundle, TransactionPool, ValidPoolTransaction,
 };
 use reth_trie::HashedPostState;
 use revm::{
@@ -148,7 +148,7 @@ pub fn default_ethereum_payload<EvmConfig, Pool, Client, F>(
     evm_config: EvmConfig,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     initialized_cfg: CfgEnvWithHandlerCfg,
-    initialized_block_env: BlockEnv,
+    mut initialized_block_env: BlockEnv,
     best_txs: F,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
@@ -157,6 +157,11 @@ where
     Pool: TransactionPool,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
 {
+    let bundles = args.bundles();
+    let private_txs = args.private_transactions();
+    let impersonated_txs = args.impersonated_transactions();
+    let state_overrides = args.state_overrides();
+    let block_overrides = args.block_overrides();
     let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
 
     let chain_spec = client.chain_spec();
@@ -167,6 +172,23 @@ where
     let PayloadConfig { parent_header, extra_data, attributes } = config;
 
     debug!(target: "payload_builder", id=%attributes.id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
+
+    // apply the block overrides of dev chains
+    let BlockOverrides { coinbase, gas_limit, base_fee, prev_randao } = block_overrides;
+    if let Some(coinbase) = coinbase {
+        initialized_block_env.coinbase = coinbase;
+    }
+    if let Some(gas_limit) = gas_limit {
+        initialized_block_env.gas_limit = U256::from(gas_limit);
+    }
+    if let Some(base_fee) = base_fee {
+        initialized_block_env.basefee = U256::from(base_fee);
+    }
+    if let Some(prev_randao) = prev_randao {
+        initialized_block_env.prevrandao = Some(prev_randao);
+    }
+    let mix_hash = prev_randao.unwrap_or(attributes.prev_randao);
+
     let mut cumulative_gas_used = 0;
     let mut sum_blob_gas_used = 0;
     let block_gas_limit: u64 = initialized_block_env.gas_limit.to::<u64>();
@@ -214,6 +236,10 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    // apply the account overrides of dev chains before any transaction is executed
+    commit_state_overrides(&mut db, state_overrides)
+        .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
     let env = EnvWithHandlerCfg::new_with_cfg_env(
         initialized_cfg.clone(),
         initialized_block_env.clone(),
@@ -222,6 +248,84 @@ where
     let mut evm = evm_config.evm_with_env(&mut db, env);
 
     let mut receipts = Vec::new();
+
+    // include the bundles that target this block at the top of the block, followed by the private
+    // transactions, which are included individually and are skipped if they revert, and the
+    // transactions of impersonated accounts of dev chains, which are included even if they revert
+    let private_bundles = private_txs.into_iter().map(|tx| PoolBundle::new(vec![tx], block_number));
+    let impersonated_bundles = impersonated_txs.into_iter().map(|tx| {
+        let mut bundle = PoolBundle::new(vec![tx], block_number);
+        bundle.reverting_tx_hashes = bundle.transactions.iter().map(|tx| tx.hash()).collect();
+        bundle
+    });
+    'bundles: for bundle in bundles.into_iter().chain(private_bundles).chain(impersonated_bundles) {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        // ensure we still have capacity for the entire bundle
+        if cumulative_gas_used + bundle.gas_limit() > block_gas_limit {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle that exceeds the block gas limit");
+            continue
+        }
+
+        // bundles are included atomically, so we need to be able to roll back the state changes
+        // of the already executed bundle transactions
+        let cache = evm.db().cache.clone();
+        let transition_state = evm.db().transition_state.clone();
+
+        let mut bundle_gas_used = 0;
+        let mut bundle_fees = U256::ZERO;
+        let mut bundle_receipts = Vec::with_capacity(bundle.transactions.len());
+        for tx in &bundle.transactions {
+            *evm.tx_mut() = evm_config.tx_env(tx.as_signed(), tx.signer());
+
+            let failure = match evm.transact() {
+                Ok(ResultAndState { result, state }) => {
+                    if result.is_success() || bundle.can_revert(&tx.hash()) {
+                        evm.db_mut().commit(state);
+
+                        let gas_used = result.gas_used();
+                        bundle_gas_used += gas_used;
+
+                        #[allow(clippy::needless_update)] // side-effect of optimism fields
+                        bundle_receipts.push(Some(Receipt {
+                            tx_type: tx.tx_type(),
+                            success: result.is_success(),
+                            cumulative_gas_used: cumulative_gas_used + bundle_gas_used,
+                            logs: result.into_logs().into_iter().map(Into::into).collect(),
+                            ..Default::default()
+                        }));
+
+                        let miner_fee = tx
+                            .effective_tip_per_gas(Some(base_fee))
+                            .expect("fee is always valid; execution succeeded");
+                        bundle_fees += U256::from(miner_fee) * U256::from(gas_used);
+                        continue
+                    }
+                    "transaction reverted".to_string()
+                }
+                Err(EVMError::Transaction(err)) => err.to_string(),
+                Err(err) => {
+                    // this is an error that we should treat as fatal for this attempt
+                    return Err(PayloadBuilderError::EvmExecutionError(err))
+                }
+            };
+
+            trace!(target: "payload_builder", %failure, bundle=?bundle.hash(), tx=?tx.hash(), "skipping bundle");
+            evm.db_mut().cache = cache;
+            evm.db_mut().transition_state = transition_state;
+            continue 'bundles
+        }
+
+        cumulative_gas_used += bundle_gas_used;
+        total_fees += bundle_fees;
+        receipts.extend(bundle_receipts);
+        executed_senders.extend(bundle.transactions.iter().map(|tx| tx.signer()));
+        executed_txs.extend(bundle.transactions.into_iter().map(|tx| tx.into_signed()));
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -426,7 +530,7 @@ where
         withdrawals_root,
         logs_bloom,
         timestamp: attributes.timestamp,
-        mix_hash: attributes.prev_randao,
+        mix_hash,
         nonce: BEACON_NONCE.into(),
         base_fee_per_gas: Some(base_fee),
         number: parent_header.number + 1,
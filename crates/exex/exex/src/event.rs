@@ -1,7 +1,7 @@
 use alloy_eips::BlockNumHash;
 
 /// Events emitted by an `ExEx`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ExExEvent {
     /// Highest block processed by the `ExEx`.
     ///
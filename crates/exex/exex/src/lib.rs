@@ -52,6 +52,14 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+mod options;
+pub use options::*;
+
+#[cfg(unix)]
+mod remote;
+#[cfg(unix)]
+pub use remote::*;
+
 mod wal;
 pub use wal::*;
 
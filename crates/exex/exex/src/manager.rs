@@ -1,5 +1,6 @@
 use crate::{
-    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight, WalHandle,
+    notifications::ExExShared, wal::Wal, ExExEvent, ExExLagPolicy, ExExNotification,
+    ExExNotifications, FinishedExExHeight, WalHandle,
 };
 use alloy_eips::BlockNumHash;
 use futures::StreamExt;
@@ -61,6 +62,13 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The number of notifications buffered for an `ExEx` that it hasn't received yet.
+    lag: Gauge,
+    /// The total number of times the notifications buffered for an `ExEx` were dropped, according
+    /// to [`ExExLagPolicy::Resync`].
+    resyncs_total: Counter,
+    /// Whether an `ExEx` was disconnected, according to [`ExExLagPolicy::Disconnect`].
+    disconnected: Gauge,
 }
 
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -84,6 +92,10 @@ pub struct ExExHandle {
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumHash>,
+    /// State shared with the [`ExExNotifications`] of the `ExEx`.
+    shared: Arc<ExExShared>,
+    /// Whether the `ExEx` was disconnected according to [`ExExLagPolicy::Disconnect`].
+    disconnected: bool,
 }
 
 impl ExExHandle {
@@ -100,8 +112,10 @@ impl ExExHandle {
     ) -> (Self, UnboundedSender<ExExEvent>, ExExNotifications<P, E>) {
         let (notification_tx, notification_rx) = mpsc::channel(1);
         let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let shared = Arc::new(ExExShared::default());
         let notifications =
-            ExExNotifications::new(node_head, provider, executor, notification_rx, wal_handle);
+            ExExNotifications::new(node_head, provider, executor, notification_rx, wal_handle)
+                .with_shared(shared.clone());
 
         (
             Self {
@@ -111,6 +125,8 @@ impl ExExHandle {
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                shared,
+                disconnected: false,
             },
             event_tx,
             notifications,
@@ -180,6 +196,43 @@ impl ExExHandle {
             Err(err) => Poll::Ready(Err(err)),
         }
     }
+
+    /// Applies the lag policy of the `ExEx`, given the ID of the next notification the manager
+    /// will buffer.
+    ///
+    /// Returns `true` if the notifications buffered for the `ExEx` were skipped.
+    fn apply_lag_policy(&mut self, next_id: usize) -> bool {
+        if self.disconnected {
+            self.next_notification_id = next_id;
+            return true
+        }
+
+        let lag = next_id - self.next_notification_id;
+        self.metrics.lag.set(lag as f64);
+
+        match *self.shared.lag_policy.lock() {
+            ExExLagPolicy::Resync { max_lag } if lag > max_lag => {
+                warn!(target: "exex::manager", exex_id = %self.id, %lag, "ExEx is lagging behind, dropping its notifications to resync");
+                self.shared.resync.store(true, Ordering::Relaxed);
+                self.metrics.resyncs_total.increment(1);
+            }
+            ExExLagPolicy::Disconnect { max_lag } if lag > max_lag => {
+                warn!(target: "exex::manager", exex_id = %self.id, %lag, "ExEx is lagging behind, disconnecting it");
+                self.disconnected = true;
+                self.metrics.disconnected.set(1.0);
+                // Dropping the sender closes the channel, so that the notifications stream of the
+                // ExEx ends instead of waiting for notifications that will never arrive.
+                self.sender.close();
+            }
+            ExExLagPolicy::BlockPruning |
+            ExExLagPolicy::Resync { .. } |
+            ExExLagPolicy::Disconnect { .. } => return false,
+        }
+
+        self.next_notification_id = next_id;
+        self.metrics.lag.set(0.0);
+        true
+    }
 }
 
 /// Metrics for the `ExEx` manager.
@@ -355,6 +408,8 @@ where
         let exex_finished_heights = self
             .exex_handles
             .iter()
+            // Disconnected ExExes don't hold back the WAL
+            .filter(|exex_handle| !exex_handle.disconnected)
             // Get ID and finished height for each ExEx
             .map(|exex_handle| (&exex_handle.id, exex_handle.finished_height))
             // Deduplicate all hashes
@@ -492,7 +547,9 @@ where
                 .next_notification_id
                 .checked_sub(this.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = this.buffer.get(notification_index) {
+            if exex.apply_lag_policy(this.next_id) {
+                // The buffered notifications were skipped for the ExEx
+            } else if let Some(notification) = this.buffer.get(notification_index) {
                 if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
                     // The channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
@@ -510,11 +567,14 @@ where
         // Update capacity
         this.update_capacity();
 
-        // Update watch channel block number
-        let finished_height = this.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
+        // Update watch channel block number, disconnected ExExes don't hold back pruning
+        let mut connected_exexs = this.exex_handles.iter().filter(|exex| !exex.disconnected);
+        let finished_height = connected_exexs.clone().try_fold(u64::MAX, |curr, exex| {
             exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
         });
-        if let Ok(finished_height) = finished_height {
+        if connected_exexs.next().is_none() {
+            let _ = this.finished_height.send(FinishedExExHeight::NoExExs);
+        } else if let Ok(finished_height) = finished_height {
             let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
         }
 
@@ -656,8 +716,10 @@ mod tests {
     use reth_evm_ethereum::execute::EthExecutorProvider;
     use reth_primitives::SealedBlockWithSenders;
     use reth_provider::{
-        providers::BlockchainProvider2, test_utils::create_test_provider_factory, BlockReader,
-        BlockWriter, Chain, DatabaseProviderFactory, StorageLocation, TransactionVariant,
+        providers::BlockchainProvider2,
+        test_utils::{create_test_provider_factory, MockEthProvider},
+        BlockReader, BlockWriter, Chain, DatabaseProviderFactory, StorageLocation,
+        TransactionVariant,
     };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
@@ -1084,6 +1146,84 @@ mod tests {
         assert_eq!(exex_handle.next_notification_id, 23);
     }
 
+    #[tokio::test]
+    async fn test_lag_policy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (mut exex_handle, _, notifications) =
+            ExExHandle::new("test_exex".to_string(), Head::default(), (), (), wal.handle());
+
+        // By default, the manager waits for the ExEx
+        assert!(!exex_handle.apply_lag_policy(100));
+        assert_eq!(exex_handle.next_notification_id, 0);
+
+        // Notifications are dropped once the ExEx lags behind too much, and the stream resyncs
+        notifications.set_lag_policy(ExExLagPolicy::Resync { max_lag: 2 });
+        assert!(!exex_handle.apply_lag_policy(2));
+        assert!(exex_handle.apply_lag_policy(3));
+        assert_eq!(exex_handle.next_notification_id, 3);
+        assert!(exex_handle.shared.resync.load(Ordering::Relaxed));
+        assert!(!exex_handle.disconnected);
+
+        // The ExEx doesn't receive notifications anymore once it's disconnected
+        notifications.set_lag_policy(ExExLagPolicy::Disconnect { max_lag: 2 });
+        assert!(!exex_handle.apply_lag_policy(5));
+        assert!(exex_handle.apply_lag_policy(6));
+        assert!(exex_handle.disconnected);
+        assert!(exex_handle.apply_lag_policy(7));
+        assert_eq!(exex_handle.next_notification_id, 7);
+    }
+
+    #[tokio::test]
+    async fn test_lag_policy_disconnect_ends_notifications() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider2::new(provider_factory).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (mut exex_handle, _, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Head::default(),
+            provider,
+            EthExecutorProvider::mainnet(),
+            wal.handle(),
+        );
+        notifications.set_lag_policy(ExExLagPolicy::Disconnect { max_lag: 0 });
+
+        assert!(exex_handle.apply_lag_policy(1));
+        assert!(exex_handle.disconnected);
+
+        // The stream ends instead of waiting for notifications forever
+        assert!(notifications.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_resync_is_retried() {
+        // without any blocks, the latest header of the node can't be looked up
+        let provider = MockEthProvider::default();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (mut exex_handle, _, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Head::default(),
+            provider,
+            EthExecutorProvider::mainnet(),
+            wal.handle(),
+        );
+        notifications.set_lag_policy(ExExLagPolicy::Resync { max_lag: 0 });
+        assert!(exex_handle.apply_lag_policy(1));
+
+        // The stream stays usable and resyncs again on the next poll
+        assert!(notifications.next().await.unwrap().is_err());
+        assert!(exex_handle.shared.resync.load(Ordering::Relaxed));
+        assert!(notifications.next().await.unwrap().is_err());
+    }
+
     #[tokio::test]
     async fn test_notification_if_finished_height_gt_chain_tip() {
         let provider_factory = create_test_provider_factory();
@@ -1,14 +1,21 @@
-use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
+use crate::{
+    BackfillJobFactory, ExExLagPolicy, ExExNotification, ExExNotificationFilter, StreamBackfillJob,
+    WalHandle,
+};
 use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
 use reth_chainspec::Head;
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex_types::ExExHead;
-use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+use reth_provider::{BlockReader, Chain, HeaderProvider, ProviderError, StateProviderFactory};
 use reth_tracing::tracing::debug;
 use std::{
     fmt::Debug,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
 };
 use tokio::sync::mpsc::Receiver;
@@ -19,6 +26,26 @@ use tokio::sync::mpsc::Receiver;
 #[derive(Debug)]
 pub struct ExExNotifications<P, E> {
     inner: ExExNotificationsInner<P, E>,
+    /// Filter applied to all notifications.
+    filter: ExExNotificationFilter,
+    /// State shared with the [`ExExHandle`](crate::ExExHandle) of the manager, if the stream
+    /// was created by one.
+    shared: Option<Arc<ExExShared>>,
+    /// The head of the `ExEx` according to the notifications it received.
+    exex_head: Option<ExExHead>,
+    /// Whether the stream has resynced after the manager dropped notifications for the `ExEx`.
+    resynced: bool,
+}
+
+/// State shared between the [`ExExNotifications`] of an `ExEx` and its
+/// [`ExExHandle`](crate::ExExHandle) in the manager.
+#[derive(Debug, Default)]
+pub(crate) struct ExExShared {
+    /// The policy applied by the manager when the `ExEx` falls behind.
+    pub(crate) lag_policy: Mutex<ExExLagPolicy>,
+    /// Set by the manager when it dropped notifications for the `ExEx`, according to
+    /// [`ExExLagPolicy::Resync`].
+    pub(crate) resync: AtomicBool,
 }
 
 /// A trait, that represents a stream of [`ExExNotification`]s. The stream will emit notifications
@@ -84,8 +111,139 @@ impl<P, E> ExExNotifications<P, E> {
                 notifications,
                 wal_handle,
             )),
+            filter: ExExNotificationFilter::new(),
+            shared: None,
+            exex_head: None,
+            resynced: false,
+        }
+    }
+
+    /// Sets the filter applied to all notifications of the stream.
+    pub fn set_filter(&mut self, filter: ExExNotificationFilter) {
+        self.filter = filter;
+    }
+
+    /// Returns the stream with the given filter applied to all notifications.
+    pub fn with_filter(mut self, filter: ExExNotificationFilter) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    /// Sets the policy applied by the [`ExExManager`](crate::ExExManager) when the `ExEx` falls
+    /// behind.
+    ///
+    /// Has no effect if the stream wasn't created by an [`ExExHandle`](crate::ExExHandle).
+    pub fn set_lag_policy(&self, lag_policy: ExExLagPolicy) {
+        if let Some(shared) = &self.shared {
+            *shared.lag_policy.lock() = lag_policy;
         }
     }
+
+    /// Returns the stream with the given policy applied by the
+    /// [`ExExManager`](crate::ExExManager) when the `ExEx` falls behind.
+    pub fn with_lag_policy(self, lag_policy: ExExLagPolicy) -> Self {
+        self.set_lag_policy(lag_policy);
+        self
+    }
+
+    /// Sets the state shared with the [`ExExHandle`](crate::ExExHandle) of the manager.
+    pub(crate) fn with_shared(mut self, shared: Arc<ExExShared>) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    /// Updates the head of the `ExEx` with the notification it receives.
+    fn update_exex_head(&mut self, notification: &ExExNotification) {
+        if let Some(committed_chain) = notification.committed_chain() {
+            if let Some((_, tip)) = committed_chain.blocks().last_key_value() {
+                self.exex_head = Some(ExExHead { block: tip.num_hash() });
+            }
+        } else if let Some(reverted_chain) = notification.reverted_chain() {
+            if let Some((_, first_block)) = reverted_chain.blocks().first_key_value() {
+                self.exex_head = first_block
+                    .number
+                    .checked_sub(1)
+                    .map(|number| ExExHead { block: (number, first_block.parent_hash).into() });
+            }
+        }
+    }
+}
+
+impl<P, E> ExExNotifications<P, E>
+where
+    P: BlockReader + HeaderProvider + StateProviderFactory + Clone + Unpin + 'static,
+    E: BlockExecutorProvider + Clone + Unpin + 'static,
+{
+    /// Resyncs the stream after the manager dropped notifications for the `ExEx`.
+    ///
+    /// The notifications that are still in the channel are discarded, and the stream continues
+    /// from the head of the `ExEx` with the WAL and the latest block of the node, see
+    /// [`ExExNotificationsWithHead`].
+    fn resync(&mut self) -> eyre::Result<()> {
+        let (provider, notifications) = match &mut self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => {
+                (&notifications.provider, &mut notifications.notifications)
+            }
+            ExExNotificationsInner::WithHead(notifications) => {
+                (&notifications.provider, &mut notifications.notifications)
+            }
+            ExExNotificationsInner::Invalid => unreachable!(),
+        };
+
+        // Discard the notifications before looking up the latest block, so that the blocks of
+        // the discarded notifications are included in the backfill. The stream is only replaced
+        // once the lookup succeeded, so it stays usable if it fails.
+        while notifications.try_recv().is_ok() {}
+        let number = provider.best_block_number()?;
+        let header =
+            provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+
+        let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
+        let (node_head, provider, executor, notifications, wal_handle, exex_head) = match current {
+            ExExNotificationsInner::WithoutHead(notifications) => (
+                notifications.node_head,
+                notifications.provider,
+                notifications.executor,
+                notifications.notifications,
+                notifications.wal_handle,
+                None,
+            ),
+            ExExNotificationsInner::WithHead(notifications) => (
+                notifications.node_head,
+                notifications.provider,
+                notifications.executor,
+                notifications.notifications,
+                notifications.wal_handle,
+                Some(notifications.exex_head),
+            ),
+            ExExNotificationsInner::Invalid => unreachable!(),
+        };
+        let latest_head = Head {
+            number,
+            hash: header.hash(),
+            difficulty: header.difficulty,
+            timestamp: header.timestamp,
+            ..node_head
+        };
+
+        let exex_head = self
+            .exex_head
+            .or(exex_head)
+            .unwrap_or_else(|| ExExHead { block: (node_head.number, node_head.hash).into() });
+        debug!(target: "exex::notifications", ?exex_head, ?latest_head, "Resyncing notifications");
+
+        self.inner = ExExNotificationsInner::WithHead(ExExNotificationsWithHead::new(
+            latest_head,
+            provider,
+            executor,
+            notifications,
+            wal_handle,
+            exex_head,
+        ));
+        self.resynced = true;
+
+        Ok(())
+    }
 }
 
 impl<P, E> ExExNotificationsStream for ExExNotifications<P, E>
@@ -148,12 +306,49 @@ where
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        match &mut self.get_mut().inner {
-            ExExNotificationsInner::WithoutHead(notifications) => {
-                notifications.poll_next_unpin(cx).map(|result| result.map(Ok))
+        let this = self.get_mut();
+
+        if let Some(shared) = this.shared.clone() {
+            if shared.resync.swap(false, Ordering::Relaxed) {
+                if let Err(err) = this.resync() {
+                    // retry on the next poll, the discarded notifications are still missing
+                    shared.resync.store(true, Ordering::Relaxed);
+                    return Poll::Ready(Some(Err(err)))
+                }
             }
-            ExExNotificationsInner::WithHead(notifications) => notifications.poll_next_unpin(cx),
-            ExExNotificationsInner::Invalid => unreachable!(),
+        }
+
+        loop {
+            let notification = match &mut this.inner {
+                ExExNotificationsInner::WithoutHead(notifications) => {
+                    ready!(notifications.poll_next_unpin(cx)).map(Ok)
+                }
+                ExExNotificationsInner::WithHead(notifications) => {
+                    ready!(notifications.poll_next_unpin(cx))
+                }
+                ExExNotificationsInner::Invalid => unreachable!(),
+            };
+            let Some(notification) = notification.transpose()? else { return Poll::Ready(None) };
+
+            // After a resync, the committed notifications that the manager sent before it saw
+            // the resync may already be covered by the backfill.
+            if this.resynced {
+                if let (ExExNotification::ChainCommitted { new }, Some(exex_head)) =
+                    (&notification, this.exex_head)
+                {
+                    if new
+                        .blocks()
+                        .last_key_value()
+                        .is_some_and(|(tip, _)| *tip <= exex_head.block.number)
+                    {
+                        debug!(target: "exex::notifications", range = ?new.range(), ?exex_head, "Skipping notification after resync");
+                        continue
+                    }
+                }
+            }
+
+            this.update_exex_head(&notification);
+            return Poll::Ready(Some(Ok(this.filter.apply(notification))))
         }
     }
 }
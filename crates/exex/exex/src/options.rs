@@ -0,0 +1,206 @@
+use crate::ExExNotification;
+use alloy_primitives::Address;
+use reth_primitives::{Receipts, SealedBlock, SealedBlockWithSenders};
+use reth_provider::{Chain, ExecutionOutcome};
+use std::{collections::HashSet, sync::Arc};
+
+/// Filter applied to the [`ExExNotification`]s received by an `ExEx`, set with
+/// [`ExExNotifications::set_filter`](crate::ExExNotifications::set_filter).
+///
+/// The blocks of a filtered notification are still the same, so that the `ExEx` can keep track of
+/// the chain, but the data it doesn't need is stripped from them.
+///
+/// By default, notifications are not filtered.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExExNotificationFilter {
+    /// Whether to strip the block bodies.
+    headers_only: bool,
+    /// Whether to strip the execution outcome and the trie updates.
+    skip_execution_outcome: bool,
+    /// The addresses for which receipts with logs are kept.
+    receipt_addresses: Option<HashSet<Address>>,
+}
+
+impl ExExNotificationFilter {
+    /// Creates a filter that passes notifications unchanged.
+    pub const fn new() -> Self {
+        Self { headers_only: false, skip_execution_outcome: false, receipt_addresses: None }
+    }
+
+    /// Only keep the block headers, stripping the block bodies, the execution outcome and the trie
+    /// updates.
+    pub const fn headers_only(mut self) -> Self {
+        self.headers_only = true;
+        self.skip_execution_outcome = true;
+        self
+    }
+
+    /// Strip the execution outcome and the trie updates, keeping the blocks.
+    pub const fn skip_execution_outcome(mut self) -> Self {
+        self.skip_execution_outcome = true;
+        self
+    }
+
+    /// Only keep the receipts that contain logs emitted by one of the given addresses. Other
+    /// receipts are replaced with `None`, as if they were pruned.
+    pub fn receipts_for_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.receipt_addresses = Some(addresses.into_iter().collect());
+        self
+    }
+
+    /// Returns `true` if the filter passes notifications unchanged.
+    pub const fn is_empty(&self) -> bool {
+        !self.headers_only && !self.skip_execution_outcome && self.receipt_addresses.is_none()
+    }
+
+    /// Applies the filter to the notification.
+    pub fn apply(&self, notification: ExExNotification) -> ExExNotification {
+        if self.is_empty() {
+            return notification
+        }
+
+        match notification {
+            ExExNotification::ChainCommitted { new } => {
+                ExExNotification::ChainCommitted { new: self.apply_chain(&new) }
+            }
+            ExExNotification::ChainReorged { old, new } => ExExNotification::ChainReorged {
+                old: self.apply_chain(&old),
+                new: self.apply_chain(&new),
+            },
+            ExExNotification::ChainReverted { old } => {
+                ExExNotification::ChainReverted { old: self.apply_chain(&old) }
+            }
+        }
+    }
+
+    /// Applies the filter to the chain.
+    fn apply_chain(&self, chain: &Chain) -> Arc<Chain> {
+        let blocks = chain.blocks_iter().map(|block| {
+            if self.headers_only {
+                SealedBlockWithSenders {
+                    block: SealedBlock { header: block.header.clone(), body: Default::default() },
+                    senders: Vec::new(),
+                }
+            } else {
+                block.clone()
+            }
+        });
+
+        let outcome = chain.execution_outcome();
+        if self.skip_execution_outcome {
+            // Keep an empty list of receipts for each block, so that the outcome still covers
+            // the blocks of the chain.
+            let receipts = Receipts { receipt_vec: vec![Vec::new(); chain.len()] };
+            let outcome = ExecutionOutcome {
+                receipts,
+                first_block: outcome.first_block,
+                ..Default::default()
+            };
+            return Arc::new(Chain::new(blocks, outcome, None))
+        }
+
+        let mut outcome = outcome.clone();
+        if let Some(addresses) = &self.receipt_addresses {
+            for receipt in outcome.receipts.iter_mut().flatten() {
+                if receipt.as_ref().is_some_and(|receipt| {
+                    !receipt.logs.iter().any(|log| addresses.contains(&log.address))
+                }) {
+                    *receipt = None;
+                }
+            }
+        }
+        Arc::new(Chain::new(blocks, outcome, chain.trie_updates().cloned()))
+    }
+}
+
+/// Policy applied by the [`ExExManager`](crate::ExExManager) when an `ExEx` falls behind, set with
+/// [`ExExNotifications::set_lag_policy`](crate::ExExNotifications::set_lag_policy).
+///
+/// The lag of an `ExEx` is the number of notifications that the manager has buffered for it, but
+/// that it hasn't received yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExLagPolicy {
+    /// Wait for the `ExEx` to catch up. Once the buffer of the manager is full, no new
+    /// notifications are accepted, and pruning is held back at the finished height of the `ExEx`.
+    #[default]
+    BlockPruning,
+    /// Once the `ExEx` is more than `max_lag` notifications behind, drop the notifications
+    /// buffered for it. Its notification stream then resyncs from the WAL and the database,
+    /// starting at the last block it received.
+    Resync {
+        /// The maximum number of notifications the `ExEx` can be behind.
+        max_lag: usize,
+    },
+    /// Once the `ExEx` is more than `max_lag` notifications behind, disconnect it. Its
+    /// [`ExExNotifications`](crate::ExExNotifications) stream ends after the notifications that
+    /// were already sent, and it doesn't hold back pruning anymore.
+    Disconnect {
+        /// The maximum number of notifications the `ExEx` can be behind.
+        max_lag: usize,
+    },
+}
+
+impl ExExLagPolicy {
+    /// Returns the maximum number of notifications the `ExEx` can be behind, if the policy doesn't
+    /// wait for it.
+    pub const fn max_lag(&self) -> Option<usize> {
+        match self {
+            Self::BlockPruning => None,
+            Self::Resync { max_lag } | Self::Disconnect { max_lag } => Some(*max_lag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Log;
+    use reth_primitives::Receipt;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+
+    #[test]
+    fn filters_notifications() {
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(2), ..Default::default() })
+                .seal_with_senders::<reth_primitives::Block>()
+                .unwrap();
+        let address = Address::with_last_byte(1);
+        let receipt = |address| Receipt {
+            logs: vec![Log::new_unchecked(address, Vec::new(), Default::default())],
+            ..Default::default()
+        };
+        let outcome = ExecutionOutcome {
+            receipts: Receipts {
+                receipt_vec: vec![vec![Some(receipt(address)), Some(receipt(Address::ZERO))]],
+            },
+            first_block: 1,
+            ..Default::default()
+        };
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new([block.clone()], outcome, None)),
+        };
+
+        let filter = ExExNotificationFilter::default();
+        assert!(filter.is_empty());
+        assert_eq!(filter.apply(notification.clone()), notification);
+
+        let filtered = ExExNotificationFilter::default()
+            .receipts_for_addresses([address])
+            .apply(notification.clone());
+        let chain = filtered.committed_chain().unwrap();
+        assert_eq!(chain.blocks_iter().next(), Some(&block));
+        assert_eq!(
+            chain.execution_outcome().receipts.receipt_vec,
+            vec![vec![Some(receipt(address)), None]]
+        );
+
+        let filtered = ExExNotificationFilter::default().headers_only().apply(notification);
+        let chain = filtered.committed_chain().unwrap();
+        let filtered_block = chain.blocks_iter().next().unwrap();
+        assert_eq!(filtered_block.header, block.header);
+        assert!(filtered_block.body.transactions.is_empty());
+        assert_eq!(chain.execution_outcome().receipts.receipt_vec, vec![Vec::new()]);
+        assert_eq!(chain.execution_outcome().first_block, 1);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{ExExContext, ExExEvent, ExExNotification, ExExNotifications, Wal};
+use bytes::Bytes;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
+};
+use reth_exex_types::serde_bincode_compat;
+use reth_node_api::FullNodeComponents;
+use reth_tracing::tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::path::Path;
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// A connection between the node and a remote `ExEx`. Messages are bincode-encoded and prefixed
+/// with their length.
+type Connection = Framed<UnixStream, LengthDelimitedCodec>;
+
+/// A message sent by the node to a remote `ExEx`.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RemoteExExMessage {
+    /// A notification about the canonical chain.
+    Notification(#[serde_as(as = "serde_bincode_compat::ExExNotification")] ExExNotification),
+}
+
+/// An `ExEx` that forwards the notifications of the node to an `ExEx` running in a separate
+/// process, connected over a Unix socket. See [`RemoteExExClient`] for the other side of the
+/// connection.
+///
+/// Notifications are committed to a dedicated [`Wal`] before they're forwarded, and are only
+/// removed from it once the remote `ExEx` reports with [`ExExEvent::FinishedHeight`] that it has
+/// processed them. The event is then passed on to the node. On every (re)connect, all
+/// notifications that are still in the WAL are replayed, so the remote `ExEx` receives every
+/// notification at least once and needs to skip the ones it has already processed.
+///
+/// Only one remote `ExEx` can be connected at a time, a new connection replaces the previous one.
+/// While no remote `ExEx` is connected, notifications accumulate in the WAL and the node doesn't
+/// prune the blocks that haven't been processed yet.
+#[derive(Debug)]
+pub struct RemoteExEx<N> {
+    /// Notifications of the node.
+    notifications: N,
+    /// Channel used to send [`ExExEvent`]s to the node.
+    events: UnboundedSender<ExExEvent>,
+    /// Listener for connections of the remote `ExEx`.
+    listener: UnixListener,
+    /// WAL of the notifications that haven't been processed by the remote `ExEx` yet.
+    wal: Wal,
+}
+
+impl<N> RemoteExEx<N> {
+    /// Creates a new [`RemoteExEx`] listening on the given socket path and storing its WAL in the
+    /// given directory.
+    ///
+    /// A stale socket file left by a previous run is removed.
+    pub fn new(
+        notifications: N,
+        events: UnboundedSender<ExExEvent>,
+        socket_path: impl AsRef<Path>,
+        wal_directory: impl AsRef<Path>,
+    ) -> eyre::Result<Self> {
+        let socket_path = socket_path.as_ref();
+        if socket_path.exists() {
+            reth_fs_util::remove_file(socket_path)?;
+        }
+        let listener = UnixListener::bind(socket_path)?;
+        info!(target: "exex::remote", ?socket_path, "Listening for the remote ExEx");
+
+        Ok(Self { notifications, events, listener, wal: Wal::new(wal_directory)? })
+    }
+}
+
+impl<P, E> RemoteExEx<ExExNotifications<P, E>> {
+    /// Creates a new [`RemoteExEx`] from the context of an installed `ExEx`.
+    ///
+    /// See [`RemoteExEx::new`] for more details.
+    pub fn from_context<Node: FullNodeComponents<Provider = P, Executor = E>>(
+        ctx: ExExContext<Node>,
+        socket_path: impl AsRef<Path>,
+        wal_directory: impl AsRef<Path>,
+    ) -> eyre::Result<Self> {
+        Self::new(ctx.notifications, ctx.events, socket_path, wal_directory)
+    }
+}
+
+impl<N> RemoteExEx<N>
+where
+    N: Stream<Item = eyre::Result<ExExNotification>> + Unpin,
+{
+    /// Runs the remote `ExEx` until the notification stream of the node is closed.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let mut connection = None;
+
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, _) = accepted?;
+                    info!(target: "exex::remote", "Remote ExEx connected");
+
+                    match self.connect(stream) {
+                        Ok(new_connection) => connection = Some(new_connection),
+                        Err(err) => {
+                            warn!(target: "exex::remote", %err, "Failed to read notifications to replay to the remote ExEx")
+                        }
+                    }
+                }
+                notification = self.notifications.next() => {
+                    let Some(notification) = notification else { return Ok(()) };
+                    let notification = notification?;
+                    self.wal.commit(&notification)?;
+
+                    if let Some(conn) = &connection {
+                        if conn.notifications.send(notification).is_err() {
+                            // the writer task logged the error already
+                            connection = None;
+                        }
+                    }
+                }
+                event = next_event(&mut connection) => match event {
+                    Some(Ok(event)) => {
+                        if let Err(err) = self.on_event(event) {
+                            warn!(target: "exex::remote", %err, ?event, "Failed to handle event of the remote ExEx, disconnecting it");
+                            connection = None;
+                            continue
+                        }
+                        self.events.send(event)?;
+                    }
+                    Some(Err(err)) => {
+                        warn!(target: "exex::remote", %err, "Invalid message from the remote ExEx");
+                        connection = None;
+                    }
+                    None => {
+                        info!(target: "exex::remote", "Remote ExEx disconnected");
+                        connection = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets up a newly connected remote `ExEx`.
+    ///
+    /// The notifications are written to the connection by a separate task, which first replays
+    /// all notifications in the WAL and then forwards the new ones in order, so that neither a
+    /// long replay nor a slow remote `ExEx` blocks the node notifications and the events.
+    fn connect(&self, stream: UnixStream) -> eyre::Result<RemoteConnection> {
+        let replay = self.wal.iter_notifications()?.collect::<eyre::Result<Vec<_>>>()?;
+        let (sink, events) = Framed::new(stream, LengthDelimitedCodec::new()).split();
+        let (notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        tokio::spawn(write_notifications(sink, replay, notifications_rx));
+
+        Ok(RemoteConnection { notifications: notifications_tx, events })
+    }
+
+    /// Handles an event of the remote `ExEx`, before it's passed on to the node.
+    fn on_event(&self, event: ExExEvent) -> eyre::Result<()> {
+        debug!(target: "exex::remote", ?event, "Received event from the remote ExEx");
+        match event {
+            ExExEvent::FinishedHeight(height) => self.wal.finalize(height),
+        }
+    }
+}
+
+/// A connected remote `ExEx`.
+#[derive(Debug)]
+struct RemoteConnection {
+    /// Channel to the task writing notifications to the connection.
+    notifications: UnboundedSender<ExExNotification>,
+    /// Events sent by the remote `ExEx`.
+    events: SplitStream<Connection>,
+}
+
+/// Replays the notifications of the WAL and then writes the new notifications to the connection,
+/// until the connection fails or the [`RemoteConnection`] is dropped.
+async fn write_notifications(
+    mut sink: SplitSink<Connection, Bytes>,
+    replay: Vec<ExExNotification>,
+    mut notifications: UnboundedReceiver<ExExNotification>,
+) {
+    debug!(target: "exex::remote", count = replay.len(), "Replaying notifications");
+    for notification in replay {
+        if let Err(err) = send_notification(&mut sink, notification).await {
+            warn!(target: "exex::remote", %err, "Failed to replay notifications to the remote ExEx");
+            return
+        }
+    }
+
+    while let Some(notification) = notifications.recv().await {
+        if let Err(err) = send_notification(&mut sink, notification).await {
+            warn!(target: "exex::remote", %err, "Failed to send notification to the remote ExEx");
+            return
+        }
+    }
+}
+
+/// Sends the notification over the connection.
+async fn send_notification(
+    sink: &mut SplitSink<Connection, Bytes>,
+    notification: ExExNotification,
+) -> eyre::Result<()> {
+    let message = bincode::serialize(&RemoteExExMessage::Notification(notification))?;
+    sink.send(Bytes::from(message)).await?;
+    Ok(())
+}
+
+/// Returns the next event of the remote `ExEx`, or `None` if it disconnected. Pending forever if
+/// there's no connection.
+async fn next_event(connection: &mut Option<RemoteConnection>) -> Option<eyre::Result<ExExEvent>> {
+    let Some(connection) = connection else { return std::future::pending().await };
+    let frame = connection.events.next().await?;
+    Some(frame.map_err(Into::into).and_then(|frame| Ok(bincode::deserialize(&frame)?)))
+}
+
+/// Client used by an `ExEx` running in a separate process to receive notifications from a
+/// [`RemoteExEx`] installed in the node.
+#[derive(Debug)]
+pub struct RemoteExExClient {
+    connection: Connection,
+}
+
+impl RemoteExExClient {
+    /// Connects to the [`RemoteExEx`] listening on the given socket path.
+    pub async fn connect(socket_path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let stream = UnixStream::connect(socket_path).await?;
+        Ok(Self { connection: Framed::new(stream, LengthDelimitedCodec::new()) })
+    }
+
+    /// Returns the next notification, or `None` if the node closed the connection.
+    ///
+    /// Right after connecting, all notifications that haven't been reported as processed with
+    /// [`ExExEvent::FinishedHeight`] are replayed.
+    pub async fn next_notification(&mut self) -> eyre::Result<Option<ExExNotification>> {
+        let Some(frame) = self.connection.next().await else { return Ok(None) };
+        let RemoteExExMessage::Notification(notification) = bincode::deserialize(&frame?)?;
+        Ok(Some(notification))
+    }
+
+    /// Sends the event to the node.
+    pub async fn send_event(&mut self, event: ExExEvent) -> eyre::Result<()> {
+        self.connection.send(Bytes::from(bincode::serialize(&event)?)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use eyre::OptionExt;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn forwards_and_replays_notifications() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let notifications = random_block_range(&mut rng, 0..=2, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| {
+                let block = block
+                    .seal_with_senders::<reth_primitives::Block>()
+                    .ok_or_eyre("failed to recover senders")?;
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let block = |idx: usize| {
+            let chain = notifications[idx].committed_chain().unwrap();
+            BlockNumHash::new(chain.tip().number, chain.tip().hash())
+        };
+
+        let temp_dir = tempfile::tempdir()?;
+        let socket_path = temp_dir.path().join("exex.ipc");
+        let (notifications_tx, notifications_rx) = futures::channel::mpsc::unbounded();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+        let exex = RemoteExEx::new(
+            notifications_rx,
+            events_tx,
+            &socket_path,
+            temp_dir.path().join("wal"),
+        )?;
+        let exex = tokio::spawn(exex.run());
+
+        // notifications are forwarded and events are passed on to the node
+        let mut client = RemoteExExClient::connect(&socket_path).await?;
+        notifications_tx.unbounded_send(Ok(notifications[0].clone()))?;
+        notifications_tx.unbounded_send(Ok(notifications[1].clone()))?;
+        assert_eq!(client.next_notification().await?, Some(notifications[0].clone()));
+        assert_eq!(client.next_notification().await?, Some(notifications[1].clone()));
+        client.send_event(ExExEvent::FinishedHeight(block(0))).await?;
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(block(0))));
+        drop(client);
+
+        // unprocessed notifications are replayed on reconnect, including the ones received while
+        // disconnected
+        notifications_tx.unbounded_send(Ok(notifications[2].clone()))?;
+        let mut client = RemoteExExClient::connect(&socket_path).await?;
+        assert_eq!(client.next_notification().await?, Some(notifications[1].clone()));
+        assert_eq!(client.next_notification().await?, Some(notifications[2].clone()));
+
+        // the remote ExEx stops with the notification stream of the node
+        drop(notifications_tx);
+        exex.await??;
+        assert_eq!(client.next_notification().await?, None);
+
+        Ok(())
+    }
+}
@@ -35,6 +35,9 @@ pub use state::*;
 pub mod receipts;
 pub use receipts::*;
 
+pub mod snap;
+pub use snap::*;
+
 pub mod disconnect_reason;
 pub use disconnect_reason::*;
 
@@ -0,0 +1,438 @@
+//! Types for the `snap/1` protocol: <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! Unlike `eth/66` requests, every `snap` message carries its request id as the first field of
+//! the message itself.
+
+use alloy_consensus::{constants::KECCAK_EMPTY, EMPTY_ROOT_HASH};
+use alloy_primitives::{
+    bytes::{Buf, BufMut},
+    Bytes, B256, U256,
+};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_codecs_derive::add_arbitrary_tests;
+
+/// Requests an unknown number of accounts from a given account trie, starting at the specified
+/// account hash and capped by the maximum allowed response size in bytes.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetAccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The account hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// The account hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// A single account of an [`AccountRange`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account in the [`SlimAccount`] format.
+    pub body: SlimAccount,
+}
+
+/// The response to [`GetAccountRange`].
+///
+/// The accounts are consecutive and ordered by their hash. The proof contains the merkle proofs
+/// of the starting hash and of the last returned account, so the range can be verified against
+/// the requested state root.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The consecutive accounts of the requested range.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes that prove the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account as it is encoded in `snap` messages.
+///
+/// This is the account as it is stored in the state trie, except that the storage root and the
+/// code hash are empty if the account has no storage or no code.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct SlimAccount {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The storage root, empty if it is the root of the empty trie.
+    pub storage_root: Bytes,
+    /// The code hash, empty if it is the hash of the empty code.
+    pub code_hash: Bytes,
+}
+
+impl SlimAccount {
+    /// Creates the slim representation of the account.
+    pub fn new(nonce: u64, balance: U256, storage_root: B256, code_hash: B256) -> Self {
+        let slim = |hash: B256, empty: B256| {
+            if hash == empty {
+                Bytes::new()
+            } else {
+                Bytes::copy_from_slice(hash.as_slice())
+            }
+        };
+        Self {
+            nonce,
+            balance,
+            storage_root: slim(storage_root, EMPTY_ROOT_HASH),
+            code_hash: slim(code_hash, KECCAK_EMPTY),
+        }
+    }
+
+    /// Returns the storage root of the account.
+    ///
+    /// Returns `None` if the storage root is neither empty nor 32 bytes long.
+    pub fn storage_root(&self) -> Option<B256> {
+        Self::full(&self.storage_root, EMPTY_ROOT_HASH)
+    }
+
+    /// Returns the code hash of the account.
+    ///
+    /// Returns `None` if the code hash is neither empty nor 32 bytes long.
+    pub fn code_hash(&self) -> Option<B256> {
+        Self::full(&self.code_hash, KECCAK_EMPTY)
+    }
+
+    fn full(hash: &Bytes, empty: B256) -> Option<B256> {
+        match hash.len() {
+            0 => Some(empty),
+            32 => Some(B256::from_slice(hash)),
+            _ => None,
+        }
+    }
+}
+
+/// Requests the storage slots of multiple accounts' storage tries.
+///
+/// The starting and limit hashes only apply to the first account. If they are empty, the entire
+/// storage of the account is requested.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetStorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The account hashes of the storage tries to serve.
+    pub account_hashes: Vec<B256>,
+    /// The storage slot hash of the first slot to retrieve.
+    pub starting_hash: Bytes,
+    /// The storage slot hash after which to stop serving.
+    pub limit_hash: Bytes,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// A single storage slot of a [`StorageRanges`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageData {
+    /// The hash of the storage slot key.
+    pub hash: B256,
+    /// The RLP encoded value of the storage slot.
+    pub data: Bytes,
+}
+
+/// The response to [`GetStorageRanges`].
+///
+/// Contains the consecutive slots of each served account. Only the slots of the last account may
+/// be incomplete, in which case the proof contains the merkle proofs of its starting hash and of
+/// its last returned slot.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The storage slots of each served account.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes that prove the last range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests contract bytecodes by their hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The code hashes to retrieve the code for.
+    pub hashes: Vec<B256>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`].
+///
+/// The codes are in request order, unknown codes are skipped.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct ByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The requested bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// A set of trie node paths of [`GetTrieNodes`].
+///
+/// If the set contains a single path, it is the compact encoded path of a node in the account
+/// trie. Otherwise the first element is the hash of an account and the remaining elements are
+/// compact encoded paths of nodes in the storage trie of the account.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct TriePathSet(pub Vec<Bytes>);
+
+impl Encodable for TriePathSet {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.0.encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+}
+
+impl Decodable for TriePathSet {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self(Vec::decode(buf)?))
+    }
+}
+
+/// Requests trie nodes of the account trie or of storage tries by their path.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetTrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The paths of the requested trie nodes.
+    pub paths: Vec<TriePathSet>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`].
+///
+/// The nodes are in request order, the response stops at the first unknown node.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The RLP encoded trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents message IDs for `snap` protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessageId {
+    /// Requests a range of accounts.
+    GetAccountRange = 0x00,
+    /// Represents a range of accounts.
+    AccountRange = 0x01,
+    /// Requests ranges of storage slots.
+    GetStorageRanges = 0x02,
+    /// Represents ranges of storage slots.
+    StorageRanges = 0x03,
+    /// Requests contract bytecodes.
+    GetByteCodes = 0x04,
+    /// Represents contract bytecodes.
+    ByteCodes = 0x05,
+    /// Requests trie nodes.
+    GetTrieNodes = 0x06,
+    /// Represents trie nodes.
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// Returns the max value.
+    pub const fn max() -> u8 {
+        Self::TrieNodes as u8
+    }
+}
+
+impl Encodable for SnapMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for SnapMessageId {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let id = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// Represents a message in the `snap` protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessage {
+    /// Represents a `GetAccountRange` request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an `AccountRange` response.
+    AccountRange(AccountRange),
+    /// Represents a `GetStorageRanges` request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a `StorageRanges` response.
+    StorageRanges(StorageRanges),
+    /// Represents a `GetByteCodes` request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a `ByteCodes` response.
+    ByteCodes(ByteCodes),
+    /// Represents a `GetTrieNodes` request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a `TrieNodes` response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Decodes a message, prefixed with its message ID.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let message = match SnapMessageId::decode(buf)? {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        };
+        Ok(message)
+    }
+
+    /// Encodes the message, prefixed with its message ID.
+    pub fn encode_message(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(out),
+            Self::AccountRange(msg) => msg.encode(out),
+            Self::GetStorageRanges(msg) => msg.encode(out),
+            Self::StorageRanges(msg) => msg.encode(out),
+            Self::GetByteCodes(msg) => msg.encode(out),
+            Self::ByteCodes(msg) => msg.encode(out),
+            Self::GetTrieNodes(msg) => msg.encode(out),
+            Self::TrieNodes(msg) => msg.encode(out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    #[test]
+    fn slim_account_roundtrip() {
+        let empty = SlimAccount::new(1, U256::from(2), EMPTY_ROOT_HASH, KECCAK_EMPTY);
+        assert!(empty.storage_root.is_empty());
+        assert!(empty.code_hash.is_empty());
+        assert_eq!(empty.storage_root(), Some(EMPTY_ROOT_HASH));
+        assert_eq!(empty.code_hash(), Some(KECCAK_EMPTY));
+
+        let root = b256!("29bc1e23bb1f7c7e8f8a8b6b7d41a87c4fbe0d3e6e9b5dd6c4f13b35e6ea0d43");
+        let account = SlimAccount::new(1, U256::from(2), root, root);
+        assert_eq!(account.storage_root(), Some(root));
+        assert_eq!(account.code_hash(), Some(root));
+    }
+
+    #[test]
+    fn message_roundtrip() {
+        let message = SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: 7,
+            root_hash: B256::with_last_byte(1),
+            paths: vec![
+                TriePathSet(vec![hex!("00").into()]),
+                TriePathSet(vec![B256::with_last_byte(2).into(), hex!("1f").into()]),
+            ],
+            response_bytes: 512 * 1024,
+        });
+
+        let mut buf = Vec::new();
+        message.encode_message(&mut buf);
+        assert_eq!(buf[0], SnapMessageId::GetTrieNodes as u8);
+
+        let decoded = SnapMessage::decode_message(&mut &buf[..]).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.request_id(), 7);
+    }
+}
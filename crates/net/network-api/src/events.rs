@@ -4,7 +4,7 @@ use std::{fmt, net::SocketAddr, sync::Arc};
 
 use reth_eth_wire_types::{
     message::RequestPair, BlockBodies, BlockHeaders, Capabilities, DisconnectReason, EthMessage,
-    EthNetworkPrimitives, EthVersion, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    EthMessageID, EthNetworkPrimitives, EthVersion, GetBlockBodies, GetBlockHeaders, GetNodeData,
     GetPooledTransactions, GetReceipts, NetworkPrimitives, NodeData, PooledTransactions, Receipts,
     Status,
 };
@@ -25,6 +25,11 @@ pub trait NetworkEventListenerProvider: Send + Sync {
     ///
     /// This stream yields [`DiscoveryEvent`]s for each peer that is discovered.
     fn discovery_listener(&self) -> UnboundedReceiverStream<DiscoveryEvent>;
+    /// Returns a new [`PeerMessageEvent`] stream.
+    ///
+    /// This stream yields a [`PeerMessageEvent`] for each `eth` message that is exchanged with a
+    /// peer.
+    fn peer_message_listener(&self) -> EventStream<PeerMessageEvent>;
 }
 
 /// (Non-exhaustive) Events emitted by the network that are of interest for subscribers.
@@ -92,6 +97,30 @@ impl<R> Clone for NetworkEvent<R> {
     }
 }
 
+/// Whether a [`PeerMessageEvent`] was sent or received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerMessageDirection {
+    /// The message was sent to the peer.
+    Sent,
+    /// The message was received from the peer.
+    Received,
+}
+
+/// An `eth` protocol message that was exchanged with a peer over an active session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerMessageEvent {
+    /// The peer the message was exchanged with.
+    pub peer_id: PeerId,
+    /// Whether the message was sent or received.
+    pub direction: PeerMessageDirection,
+    /// The negotiated `eth` version of the session.
+    pub version: EthVersion,
+    /// The ID of the message.
+    pub message_id: EthMessageID,
+    /// The RLP encoded size of the message.
+    pub size: usize,
+}
+
 /// Events produced by the `Discovery` manager.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DiscoveryEvent {
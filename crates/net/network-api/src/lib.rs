@@ -29,8 +29,8 @@ pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
 pub use events::{
-    DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider, PeerRequest,
-    PeerRequestSender,
+    DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider,
+    PeerMessageDirection, PeerMessageEvent, PeerRequest, PeerRequestSender,
 };
 
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
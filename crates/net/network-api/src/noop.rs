@@ -10,8 +10,14 @@ use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
 use reth_network_peers::NodeRecord;
 use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_tokio_util::{EventSender, EventStream};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use crate::{NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, Peers, PeersInfo};
+use crate::{
+    DiscoveryEvent, NetworkError, NetworkEvent, NetworkEventListenerProvider, NetworkInfo,
+    NetworkStatus, PeerId, PeerInfo, PeerMessageEvent, Peers, PeersInfo,
+};
 
 /// A type that implements all network trait that does nothing.
 ///
@@ -53,6 +59,21 @@ impl NetworkInfo for NoopNetwork {
     }
 }
 
+impl NetworkEventListenerProvider for NoopNetwork {
+    fn event_listener(&self) -> EventStream<NetworkEvent> {
+        EventSender::default().new_listener()
+    }
+
+    fn discovery_listener(&self) -> UnboundedReceiverStream<DiscoveryEvent> {
+        let (_, rx) = mpsc::unbounded_channel();
+        UnboundedReceiverStream::new(rx)
+    }
+
+    fn peer_message_listener(&self) -> EventStream<PeerMessageEvent> {
+        EventSender::default().new_listener()
+    }
+}
+
 impl PeersInfo for NoopNetwork {
     fn num_connected_peers(&self) -> usize {
         0
@@ -250,6 +250,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             Arc::clone(&num_active_peers),
         );
 
+        let message_events = sessions.message_events().clone();
         let swarm = Swarm::new(incoming, sessions, state);
 
         let (to_manager_tx, from_handle_rx) = mpsc::unbounded_channel();
@@ -269,6 +270,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv4,
             discv5,
             event_sender.clone(),
+            message_events,
             nat,
         );
 
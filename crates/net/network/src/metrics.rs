@@ -331,6 +331,27 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) snap_account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) snap_storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) snap_byte_codes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) snap_trie_nodes_requests_received_total: Counter,
+
+    /// Duration in seconds of call to poll
+    /// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+    pub(crate) acc_duration_poll_snap_req_handler: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
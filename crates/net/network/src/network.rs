@@ -15,8 +15,8 @@ use reth_ethereum_forks::Head;
 use reth_network_api::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerMessageEvent,
+    PeerRequest, Peers, PeersInfo,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -63,6 +63,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         discv4: Option<Discv4>,
         discv5: Option<Discv5>,
         event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
+        message_events: EventSender<PeerMessageEvent>,
         nat: Option<NatResolver>,
     ) -> Self {
         let inner = NetworkInner {
@@ -80,6 +81,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv4,
             discv5,
             event_sender,
+            message_events,
             nat,
         };
         Self { inner: Arc::new(inner) }
@@ -197,6 +199,10 @@ impl NetworkEventListenerProvider for NetworkHandle<EthNetworkPrimitives> {
         let _ = self.manager().send(NetworkHandleMessage::DiscoveryListener(tx));
         UnboundedReceiverStream::new(rx)
     }
+
+    fn peer_message_listener(&self) -> EventStream<PeerMessageEvent> {
+        self.inner.message_events.new_listener()
+    }
 }
 
 impl<N: NetworkPrimitives> NetworkProtocols for NetworkHandle<N> {
@@ -437,6 +443,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     discv5: Option<Discv5>,
     /// Sender for high level network events.
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
+    /// Sender for messages exchanged with peers.
+    message_events: EventSender<PeerMessageEvent>,
     /// The NAT resolver
     nat: Option<NatResolver>,
 }
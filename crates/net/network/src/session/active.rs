@@ -20,19 +20,21 @@ use crate::{
     },
 };
 use alloy_primitives::Sealable;
+use alloy_rlp::Encodable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use metrics::Gauge;
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, EthMessageID, NetworkPrimitives,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
-use reth_network_api::PeerRequest;
+use reth_network_api::{PeerMessageDirection, PeerMessageEvent, PeerRequest};
 use reth_network_p2p::error::RequestError;
 use reth_network_peers::PeerId;
 use reth_network_types::session::config::INITIAL_REQUEST_TIMEOUT;
 use reth_primitives_traits::Block;
+use reth_tokio_util::EventSender;
 use rustc_hash::FxHashMap;
 use tokio::{
     sync::{mpsc::error::TrySendError, oneshot},
@@ -100,6 +102,8 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message:
         Option<(PollSender<ActiveSessionMessage<N>>, ActiveSessionMessage<N>)>,
+    /// Used to notify listeners about messages exchanged with the peer.
+    pub(crate) message_events: EventSender<PeerMessageEvent>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -115,6 +119,26 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         id
     }
 
+    /// Notifies message listeners about a message exchanged with the peer.
+    ///
+    /// The size is only computed if there are listeners.
+    fn on_message_event(
+        &self,
+        direction: PeerMessageDirection,
+        message_id: EthMessageID,
+        size: impl FnOnce() -> usize,
+    ) {
+        if self.message_events.has_listeners() {
+            self.message_events.notify(PeerMessageEvent {
+                peer_id: self.remote_peer_id,
+                direction,
+                version: self.conn.version(),
+                message_id,
+                size: size(),
+            });
+        }
+    }
+
     /// Shrinks the capacity of the internal buffers.
     pub fn shrink_to_fit(&mut self) {
         self.received_requests_from_remote.shrink_to_fit();
@@ -557,8 +581,22 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                 if let Some(msg) = this.queued_outgoing.pop_front() {
                     progress = true;
                     let res = match msg {
-                        OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
-                        OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
+                        OutgoingMessage::Eth(msg) => {
+                            this.on_message_event(
+                                PeerMessageDirection::Sent,
+                                msg.message_id(),
+                                || msg.length(),
+                            );
+                            this.conn.start_send_unpin(msg)
+                        }
+                        OutgoingMessage::Broadcast(msg) => {
+                            this.on_message_event(
+                                PeerMessageDirection::Sent,
+                                msg.message_id(),
+                                || msg.length(),
+                            );
+                            this.conn.start_send_broadcast(msg)
+                        }
                     };
                     if let Err(err) = res {
                         debug!(target: "net::session", %err, remote_peer_id=?this.remote_peer_id, "failed to send message");
@@ -610,6 +648,11 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.on_message_event(
+                                    PeerMessageDirection::Received,
+                                    msg.message_id(),
+                                    || msg.length(),
+                                );
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -923,6 +966,7 @@ mod tests {
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
+                        message_events: Default::default(),
                     }
                 }
                 ev => {
@@ -1042,6 +1086,32 @@ mod tests {
         rx.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_message_events() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            client_stream.send(EthMessage::Transactions(Vec::new().into())).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let session = builder.connect_incoming(incoming).await;
+        let peer_id = session.remote_peer_id;
+        let mut events = session.message_events.new_listener();
+        tokio::task::spawn(session);
+
+        let event = events.next().await.unwrap();
+        assert_eq!(event.peer_id, peer_id);
+        assert_eq!(event.direction, PeerMessageDirection::Received);
+        assert_eq!(event.message_id, EthMessageID::Transactions);
+        assert!(event.size > 0);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_request_timeout() {
         reth_tracing::init_test_tracing();
@@ -39,10 +39,11 @@ use reth_eth_wire::{
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
-use reth_network_api::{PeerRequest, PeerRequestSender};
+use reth_network_api::{PeerMessageEvent, PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
 use reth_network_types::SessionsConfig;
 use reth_tasks::TaskSpawner;
+use reth_tokio_util::EventSender;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
 use tokio::{
@@ -113,6 +114,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     disconnections_counter: DisconnectionsCounter,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// Notifies listeners about messages exchanged with peers over active sessions.
+    message_events: EventSender<PeerMessageEvent>,
 }
 
 // === impl SessionManager ===
@@ -154,6 +157,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             extra_protocols,
             disconnections_counter: Default::default(),
             metrics: Default::default(),
+            message_events: Default::default(),
         }
     }
 
@@ -163,6 +167,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         self.fork_filter.validate(fork_id).is_ok()
     }
 
+    /// Returns the sender used to notify listeners about messages exchanged with peers.
+    pub(crate) const fn message_events(&self) -> &EventSender<PeerMessageEvent> {
+        &self.message_events
+    }
+
     /// Returns the next unique [`SessionId`].
     fn next_id(&mut self) -> SessionId {
         let id = self.next_id;
@@ -537,6 +546,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
+                    message_events: self.message_events.clone(),
                 };
 
                 self.spawn(session);
@@ -0,0 +1,959 @@
+//! Serves the `snap` protocol to peers.
+//!
+//! The protocol is announced as an additional `RLPx` sub-protocol via the [`SnapProtocolHandler`].
+//! Requests of connected peers are forwarded to the [`SnapRequestHandler`], which answers them
+//! from the hashed state and the trie tables of the database.
+//!
+//! The states of the last [`SERVED_STATES`] canonical blocks are served. States of persisted
+//! blocks are served by reverting the persisted state with the trie changesets, states of blocks
+//! that are not persisted yet by applying their in-memory state. Requests for any other state root
+//! are answered with empty responses, as required by the protocol for unavailable states.
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    cache::LruMap,
+    metered_poll_nested_stream_with_budget,
+    metrics::SnapRequestHandlerMetrics,
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    bytes::BytesMut,
+    map::{HashMap, HashSet},
+    BlockNumber, Bytes, B256, U256,
+};
+use alloy_rlp::Encodable;
+use futures::{stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol, AccountData,
+    AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+    SlimAccount, SnapMessage, StorageData, StorageRanges, TrieNodes,
+};
+use reth_network_api::Direction;
+use reth_network_peers::PeerId;
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockHashReader, BlockNumReader, DatabaseProviderFactory,
+    HeaderProvider, InMemoryTrieInputProvider, StateRangeReader,
+};
+use reth_trie::TrieInputSorted;
+use reth_trie_common::{proof::ProofNodes, Nibbles};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{self, Receiver},
+    oneshot,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, trace};
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/snap/handler.go#L35-L60>
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_CODES_SERVE: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODES_SERVE: usize = 1024;
+
+/// Maximum size of replies to data retrievals.
+const SOFT_RESPONSE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Number of entries that are read from the database at once when serving ranges.
+const RANGE_BATCH_SIZE: usize = 128;
+
+/// Number of recent canonical blocks whose state is served.
+///
+/// Peers sync to a pivot block close to the tip, so its state must remain available for a while
+/// after the tip moved on. This matches the number of recent states geth serves.
+pub const SERVED_STATES: u64 = 128;
+
+/// Maximum number of state overlays that are cached.
+///
+/// Peers request the state of the same pivot block many times, so only a few overlays are needed.
+const STATE_OVERLAY_CACHE_SIZE: u32 = 4;
+
+/// We set the max channel capacity of the `SnapRequestHandler` to 256, like the capacity of the
+/// `EthRequestHandler`.
+pub const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Manages `snap` requests on top of the p2p network.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can interact with the database.
+    client: C,
+    /// Incoming requests from the [`SnapConnection`]s of all peers.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// The state roots of the served blocks.
+    recent_roots: RecentStateRoots,
+    /// Cached overlays of the persisted state by block hash, together with the persisted block
+    /// they apply to.
+    overlays: LruMap<B256, (BlockNumber, Arc<TrieInputSorted>)>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            recent_roots: Default::default(),
+            overlays: LruMap::new(STATE_OVERLAY_CACHE_SIZE),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Creates a new handler together with the [`SnapProtocolHandler`] that forwards the requests
+    /// of peers to it.
+    ///
+    /// The protocol handler must be added to the network as `RLPx` sub-protocol.
+    pub fn with_protocol(client: C) -> (Self, SnapProtocolHandler) {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        (Self::new(client, rx), SnapProtocolHandler::new(tx))
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory<Provider: StateRangeReader + BlockNumReader>
+        + BlockNumReader
+        + HeaderProvider
+        + InMemoryTrieInputProvider,
+{
+    /// Returns the accounts of the requested range.
+    fn get_account_range(&mut self, request: &GetAccountRange) -> ProviderResult<AccountRange> {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            *request;
+        let mut response = AccountRange { request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let Some(overlay) = self.state_overlay(&provider, root_hash)? else { return Ok(response) };
+
+        let soft_limit = response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+        let mut next = Some(starting_hash);
+        'accounts: while let Some(start) = next {
+            let accounts = provider.hashed_account_range(&overlay, start, RANGE_BATCH_SIZE)?;
+            next = (accounts.len() == RANGE_BATCH_SIZE)
+                .then(|| accounts.last().and_then(|(hash, _)| next_hash(*hash)))
+                .flatten();
+
+            for (hash, account) in accounts {
+                let storage_root = provider.storage_trie_root(&overlay, hash)?;
+                let body = SlimAccount::new(
+                    account.nonce,
+                    account.balance,
+                    storage_root,
+                    account.get_bytecode_hash(),
+                );
+
+                total_bytes += hash.len() + body.length();
+                response.accounts.push(AccountData { hash, body });
+
+                // the first account after the limit is included, so the range can be proven
+                if hash >= limit_hash || total_bytes >= soft_limit {
+                    break 'accounts
+                }
+            }
+        }
+
+        // prove the starting hash and the last account
+        let mut targets = HashMap::from_iter([(starting_hash, HashSet::default())]);
+        if let Some(last) = response.accounts.last() {
+            targets.insert(last.hash, HashSet::default());
+        }
+        response.proof =
+            proof_nodes(provider.hashed_multiproof(&overlay, targets)?.account_subtree);
+
+        Ok(response)
+    }
+
+    /// Returns the storage slots of the requested accounts.
+    fn get_storage_ranges(&mut self, request: &GetStorageRanges) -> ProviderResult<StorageRanges> {
+        let mut response = StorageRanges { request_id: request.request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let Some(overlay) = self.state_overlay(&provider, request.root_hash)? else {
+            return Ok(response)
+        };
+
+        let soft_limit = request.response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+        for (idx, hashed_address) in request.account_hashes.iter().copied().enumerate() {
+            if total_bytes >= soft_limit {
+                break
+            }
+
+            // the bounds only apply to the first account
+            let (origin, limit) = if idx == 0 {
+                (
+                    hash_from_bytes(&request.starting_hash).unwrap_or_default(),
+                    hash_from_bytes(&request.limit_hash).unwrap_or(B256::repeat_byte(0xff)),
+                )
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut slots = Vec::new();
+            let mut complete = true;
+            let mut next = Some(origin);
+            'slots: while let Some(start) = next {
+                let batch = provider.hashed_storage_range(
+                    &overlay,
+                    hashed_address,
+                    start,
+                    RANGE_BATCH_SIZE,
+                )?;
+                next = (batch.len() == RANGE_BATCH_SIZE)
+                    .then(|| batch.last().and_then(|(hash, _)| next_hash(*hash)))
+                    .flatten();
+
+                for (hash, value) in batch {
+                    let data = Bytes::from(alloy_rlp::encode(value));
+                    total_bytes += hash.len() + data.len();
+                    slots.push(StorageData { hash, data });
+
+                    if hash >= limit || total_bytes >= soft_limit {
+                        // the range is incomplete if there are more slots after it
+                        complete = next.is_none() &&
+                            provider
+                                .hashed_storage_range(&overlay, hashed_address, hash, 2)?
+                                .len() <
+                                2;
+                        break 'slots
+                    }
+                }
+            }
+
+            let last = slots.last().map(|slot| slot.hash);
+            response.slots.push(slots);
+
+            // only the last served account can be incomplete, so it needs to be proven
+            if origin != B256::ZERO || !complete {
+                let targets = HashMap::from_iter([(
+                    hashed_address,
+                    std::iter::once(origin).chain(last).collect(),
+                )]);
+                let mut proof = provider.hashed_multiproof(&overlay, targets)?;
+                if let Some(storage) = proof.storages.remove(&hashed_address) {
+                    response.proof = proof_nodes(storage.subtree);
+                }
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested bytecodes.
+    fn get_byte_codes(&self, request: &GetByteCodes) -> ProviderResult<ByteCodes> {
+        let mut response = ByteCodes { request_id: request.request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let soft_limit = request.response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+        for hash in request.hashes.iter().take(MAX_CODES_SERVE) {
+            let code = if *hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else if let Some(code) = provider.bytecode_by_code_hash(*hash)? {
+                code.original_bytes()
+            } else {
+                continue
+            };
+
+            total_bytes += code.len();
+            response.codes.push(code);
+
+            if total_bytes >= soft_limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested trie nodes.
+    fn get_trie_nodes(&mut self, request: &GetTrieNodes) -> ProviderResult<TrieNodes> {
+        let mut response = TrieNodes { request_id: request.request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let Some(overlay) = self.state_overlay(&provider, request.root_hash)? else {
+            return Ok(response)
+        };
+
+        let soft_limit = request.response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+        'paths: for path_set in &request.paths {
+            let nodes = match path_set.0.as_slice() {
+                [] => break,
+                [path] => {
+                    let Some(path) = compact_to_nibbles(path) else { break };
+                    let Some(target) = path_target(&path) else { break };
+                    let targets = HashMap::from_iter([(target, HashSet::default())]);
+                    let proof = provider.hashed_multiproof(&overlay, targets)?;
+                    vec![proof.account_subtree.get(&path).cloned()]
+                }
+                [account, paths @ ..] => {
+                    if account.len() != B256::len_bytes() {
+                        break
+                    }
+                    let hashed_address = B256::from_slice(account);
+                    let Some(paths) = paths
+                        .iter()
+                        .map(|path| compact_to_nibbles(path))
+                        .collect::<Option<Vec<_>>>()
+                    else {
+                        break
+                    };
+                    let Some(slots) = paths.iter().map(path_target).collect::<Option<HashSet<_>>>()
+                    else {
+                        break
+                    };
+                    let targets = HashMap::from_iter([(hashed_address, slots)]);
+                    let mut proof = provider.hashed_multiproof(&overlay, targets)?;
+                    let Some(storage) = proof.storages.remove(&hashed_address) else { break };
+                    paths.iter().map(|path| storage.subtree.get(path).cloned()).collect()
+                }
+            };
+
+            for node in nodes {
+                // the response ends at the first unknown node
+                let Some(node) = node else { break 'paths };
+                total_bytes += node.len();
+                response.nodes.push(node);
+
+                if response.nodes.len() >= MAX_TRIE_NODES_SERVE || total_bytes >= soft_limit {
+                    break 'paths
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the overlay of the persisted state that yields the state with the given root, if
+    /// it's the state of a served block.
+    fn state_overlay(
+        &mut self,
+        provider: &C::Provider,
+        root: B256,
+    ) -> ProviderResult<Option<Arc<TrieInputSorted>>> {
+        self.recent_roots.update(&self.client)?;
+        let Some((number, hash)) = self.recent_roots.get(&root) else { return Ok(None) };
+
+        // the overlay is only valid for the persisted block it was created for
+        let persisted = provider.best_block_number()?;
+        if let Some((overlay_persisted, overlay)) = self.overlays.get(&hash) {
+            if *overlay_persisted == persisted {
+                return Ok(Some(overlay.clone()))
+            }
+        }
+
+        let input = if number <= persisted {
+            // the block may have been reorged since the roots were updated
+            if provider.block_hash(number)? != Some(hash) {
+                return Ok(None)
+            }
+            provider.revert_trie_input(number)?
+        } else if let Some(input) = self.client.in_memory_trie_input(hash, persisted)? {
+            input
+        } else {
+            return Ok(None)
+        };
+
+        let overlay = Arc::new(TrieInputSorted::from(input));
+        self.overlays.insert(hash, (persisted, overlay.clone()));
+        Ok(Some(overlay))
+    }
+
+    fn on_request(&mut self, request: IncomingSnapRequest) {
+        match request {
+            IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                self.metrics.snap_account_range_requests_received_total.increment(1);
+                let result = self.get_account_range(&request);
+                let _ = response.send(Self::or_empty(peer_id, request.request_id, result));
+            }
+            IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+                let result = self.get_storage_ranges(&request);
+                let _ = response.send(Self::or_empty(peer_id, request.request_id, result));
+            }
+            IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                self.metrics.snap_byte_codes_requests_received_total.increment(1);
+                let result = self.get_byte_codes(&request);
+                let _ = response.send(Self::or_empty(peer_id, request.request_id, result));
+            }
+            IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+                let result = self.get_trie_nodes(&request);
+                let _ = response.send(Self::or_empty(peer_id, request.request_id, result));
+            }
+        }
+    }
+
+    /// Returns the response, or an empty response if the request could not be served.
+    fn or_empty<T: Default + WithRequestId>(
+        peer_id: PeerId,
+        request_id: u64,
+        result: ProviderResult<T>,
+    ) -> T {
+        result.unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, request_id, %err, "Failed to serve snap request");
+            T::default().with_request_id(request_id)
+        })
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory<Provider: StateRangeReader + BlockNumReader>
+        + BlockNumReader
+        + HeaderProvider
+        + InMemoryTrieInputProvider
+        + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| this.on_request(incoming),
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        }
+
+        Poll::Pending
+    }
+}
+
+/// The state roots of the served blocks, taken from their headers.
+#[derive(Debug, Default)]
+struct RecentStateRoots {
+    /// Hash and state root of the served blocks by number.
+    blocks: BTreeMap<BlockNumber, (B256, B256)>,
+    /// Number of the served blocks by state root.
+    numbers: HashMap<B256, BlockNumber>,
+}
+
+impl RecentStateRoots {
+    /// Updates the roots to the last [`SERVED_STATES`] canonical blocks.
+    ///
+    /// Headers are only read for blocks that changed since the last update.
+    fn update<C: BlockNumReader + HeaderProvider>(&mut self, client: &C) -> ProviderResult<()> {
+        let best = client.best_block_number()?;
+        let lowest = best.saturating_sub(SERVED_STATES - 1);
+
+        let outdated = self
+            .blocks
+            .range(..lowest)
+            .chain(self.blocks.range(best + 1..))
+            .map(|(number, _)| *number)
+            .collect::<Vec<_>>();
+        for number in outdated {
+            self.remove(number);
+        }
+
+        for number in (lowest..=best).rev() {
+            let Some(header) = client.sealed_header(number)? else { break };
+            // all older blocks are unchanged if this one is
+            if self.blocks.get(&number).is_some_and(|(hash, _)| *hash == header.hash()) {
+                break
+            }
+            self.remove(number);
+            self.blocks.insert(number, (header.hash(), header.state_root));
+            self.numbers.insert(header.state_root, number);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the block with the given number.
+    fn remove(&mut self, number: BlockNumber) {
+        if let Some((_, root)) = self.blocks.remove(&number) {
+            if self.numbers.get(&root) == Some(&number) {
+                self.numbers.remove(&root);
+            }
+        }
+    }
+
+    /// Returns the number and hash of the served block with the given state root.
+    fn get(&self, root: &B256) -> Option<(BlockNumber, B256)> {
+        let number = *self.numbers.get(root)?;
+        self.blocks.get(&number).map(|(hash, _)| (number, *hash))
+    }
+}
+
+/// All `snap` requests of peers.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested range.
+        request: GetAccountRange,
+        /// The channel sender for the response.
+        response: oneshot::Sender<AccountRange>,
+    },
+    /// Request ranges of storage slots.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested ranges.
+        request: GetStorageRanges,
+        /// The channel sender for the response.
+        response: oneshot::Sender<StorageRanges>,
+    },
+    /// Request bytecodes.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested code hashes.
+        request: GetByteCodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<ByteCodes>,
+    },
+    /// Request trie nodes.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested trie node paths.
+        request: GetTrieNodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<TrieNodes>,
+    },
+}
+
+/// The [`ProtocolHandler`] that announces `snap/1` to peers and forwards their requests to the
+/// [`SnapRequestHandler`].
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new protocol handler that forwards requests to the given channel.
+    pub const fn new(to_request_handler: mpsc::Sender<IncomingSnapRequest>) -> Self {
+        Self { to_request_handler }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+}
+
+/// The [`ConnectionHandler`] for `snap/1`.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: Default::default(),
+        }
+    }
+}
+
+/// A pending response to a request of the peer.
+type PendingResponse = Pin<Box<dyn Future<Output = Option<SnapMessage>> + Send>>;
+
+/// A `snap/1` connection to a peer.
+///
+/// Forwards the requests of the peer to the [`SnapRequestHandler`] and yields the encoded
+/// responses.
+#[must_use = "Streams do nothing unless polled."]
+pub struct SnapConnection {
+    peer_id: PeerId,
+    conn: ProtocolConnection,
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    pending_responses: FuturesUnordered<PendingResponse>,
+}
+
+impl SnapConnection {
+    /// Forwards the request to the [`SnapRequestHandler`].
+    fn on_request(&self, message: SnapMessage) {
+        let peer_id = self.peer_id;
+        let (request, response) = match message {
+            SnapMessage::GetAccountRange(request) => {
+                let (tx, rx) = oneshot::channel();
+                let request =
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response: tx };
+                (request, rx.map(|res| res.ok().map(SnapMessage::AccountRange)).boxed())
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let (tx, rx) = oneshot::channel();
+                let request =
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response: tx };
+                (request, rx.map(|res| res.ok().map(SnapMessage::StorageRanges)).boxed())
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                let request = IncomingSnapRequest::GetByteCodes { peer_id, request, response: tx };
+                (request, rx.map(|res| res.ok().map(SnapMessage::ByteCodes)).boxed())
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                let request = IncomingSnapRequest::GetTrieNodes { peer_id, request, response: tx };
+                (request, rx.map(|res| res.ok().map(SnapMessage::TrieNodes)).boxed())
+            }
+            // we never send requests, so there are no responses to handle
+            response => {
+                trace!(target: "net::snap", peer_id=%self.peer_id, id=?response.message_id(), "Ignoring unsolicited snap response");
+                return
+            }
+        };
+
+        if self.to_request_handler.try_send(request).is_err() {
+            // the handler is overloaded, the peer will time out the request
+            trace!(target: "net::snap", peer_id=%self.peer_id, "Dropping snap request");
+            return
+        }
+        self.pending_responses.push(response);
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    let mut buf = BytesMut::new();
+                    response.encode_message(&mut buf);
+                    return Poll::Ready(Some(buf))
+                }
+                continue
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+            match SnapMessage::decode_message(&mut &msg[..]) {
+                Ok(message) => this.on_request(message),
+                Err(err) => {
+                    debug!(target: "net::snap", peer_id=%this.peer_id, %err, "Failed to decode snap message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Helper trait to create empty responses for a request.
+trait WithRequestId {
+    fn with_request_id(self, request_id: u64) -> Self;
+}
+
+macro_rules! impl_with_request_id {
+    ($($ty:ty),*) => {
+        $(
+            impl WithRequestId for $ty {
+                fn with_request_id(mut self, request_id: u64) -> Self {
+                    self.request_id = request_id;
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_with_request_id!(AccountRange, StorageRanges, ByteCodes, TrieNodes);
+
+/// Returns the hash that follows the given hash, if any.
+fn next_hash(hash: B256) -> Option<B256> {
+    U256::from_be_bytes(hash.0).checked_add(U256::from(1)).map(|next| next.to_be_bytes().into())
+}
+
+/// Returns the hash if the bytes are a 32 byte hash.
+fn hash_from_bytes(bytes: &Bytes) -> Option<B256> {
+    (bytes.len() == B256::len_bytes()).then(|| B256::from_slice(bytes))
+}
+
+/// Returns the proof nodes ordered by their path.
+fn proof_nodes(nodes: ProofNodes) -> Vec<Bytes> {
+    nodes.into_nodes_sorted().into_iter().map(|(_, node)| node).collect()
+}
+
+/// Decodes a compact (hex-prefix) encoded trie path.
+fn compact_to_nibbles(compact: &[u8]) -> Option<Nibbles> {
+    let (first, rest) = compact.split_first()?;
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // the second nibble of the first byte belongs to the path if the path is odd
+    if first & 0x10 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns a key that the node at the given path lies on, so a proof for the key contains the
+/// node.
+fn path_target(path: &Nibbles) -> Option<B256> {
+    let mut key = path.clone();
+    key.extend_from_slice_unchecked(&vec![0; 64usize.checked_sub(path.len())?]);
+    Some(B256::from_slice(&key.pack()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{keccak256, Address};
+    use reth_db::tables;
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_primitives::{Account, SealedHeader, StorageEntry};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, HashingWriter, StageCheckpointWriter,
+        StateRootProvider, TrieWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_trie::{HashedPostState, HashedStorage};
+    use reth_trie_common::{proof::verify_proof, TrieAccount};
+
+    #[test]
+    fn compact_paths() {
+        assert_eq!(compact_to_nibbles(&[0x00]), Some(Nibbles::default()));
+        assert_eq!(compact_to_nibbles(&[0x1a]), Some(Nibbles::from_nibbles([0xa])));
+        assert_eq!(compact_to_nibbles(&[0x00, 0xab]), Some(Nibbles::from_nibbles([0xa, 0xb])));
+        assert_eq!(compact_to_nibbles(&[0x3a, 0xbc]), Some(Nibbles::from_nibbles([0xa, 0xb, 0xc])));
+        assert_eq!(compact_to_nibbles(&[]), None);
+    }
+
+    #[test]
+    fn serves_proven_ranges() {
+        let factory = create_test_provider_factory();
+        let accounts = (1..=10u8)
+            .map(|idx| {
+                let account =
+                    Account { nonce: idx as u64, balance: U256::from(idx), ..Default::default() };
+                (Address::with_last_byte(idx), account)
+            })
+            .collect::<Vec<_>>();
+        let storage = (1..=5u8)
+            .map(|idx| StorageEntry { key: B256::with_last_byte(idx), value: U256::from(idx) });
+
+        // the state after block 0
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .insert_account_for_hashing(
+                accounts.iter().map(|(address, account)| (*address, Some(*account))),
+            )
+            .unwrap();
+        provider.insert_storage_for_hashing([(accounts[0].0, storage)]).unwrap();
+        let (root, updates) =
+            provider.latest().unwrap().state_root_with_updates(HashedPostState::default()).unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        provider.commit().unwrap();
+
+        // block 1 changes the balance and a storage slot of the first account
+        let (address, account) = accounts[0];
+        let changed_account = Account { balance: U256::from(100), ..account };
+        let changed_slot = StorageEntry { key: B256::with_last_byte(1), value: U256::from(100) };
+        let provider = factory.provider_rw().unwrap();
+        let (root1, updates) = provider
+            .latest()
+            .unwrap()
+            .state_root_with_updates(
+                HashedPostState::default()
+                    .with_accounts([(keccak256(address), Some(changed_account))])
+                    .with_storages([(
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            [(keccak256(changed_slot.key), changed_slot.value)],
+                        ),
+                    )]),
+            )
+            .unwrap();
+        provider.insert_account_for_hashing([(address, Some(changed_account))]).unwrap();
+        provider.insert_storage_for_hashing([(address, [changed_slot])]).unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address, info: Some(account) })
+            .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (1, address).into(),
+            StorageEntry { key: changed_slot.key, value: U256::from(1) },
+        )
+        .unwrap();
+        for (number, state_root) in [(0, root), (1, root1)] {
+            let header = SealedHeader::seal(Header { number, state_root, ..Default::default() });
+            tx.put::<tables::CanonicalHeaders>(number, header.hash()).unwrap();
+            tx.put::<tables::Headers>(number, header.unseal()).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+        provider.commit().unwrap();
+
+        let mut handler = SnapRequestHandler::with_protocol(factory).0;
+
+        // the state after block 1 is served
+        let hashed_address = keccak256(address);
+        let request = GetAccountRange {
+            request_id: 0,
+            root_hash: root1,
+            starting_hash: hashed_address,
+            limit_hash: hashed_address,
+            response_bytes: SOFT_RESPONSE_LIMIT,
+        };
+        let response = handler.get_account_range(&request).unwrap();
+        let [served] = response.accounts.as_slice() else { panic!("expected one account") };
+        assert_eq!(served.body.balance, changed_account.balance);
+        let expected = alloy_rlp::encode(TrieAccount::from((
+            changed_account,
+            served.body.storage_root().unwrap(),
+        )));
+        verify_proof(root1, Nibbles::unpack(hashed_address), Some(expected), &response.proof)
+            .unwrap();
+
+        // the state after block 0 is served by reverting block 1
+        // serve all accounts after the starting hash
+        let mut hashes = accounts.iter().map(|(address, _)| keccak256(address)).collect::<Vec<_>>();
+        hashes.sort();
+        let request = GetAccountRange {
+            request_id: 1,
+            root_hash: root,
+            starting_hash: hashes[3],
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: SOFT_RESPONSE_LIMIT,
+        };
+        let response = handler.get_account_range(&request).unwrap();
+        assert_eq!(response.request_id, 1);
+        assert_eq!(
+            response.accounts.iter().map(|account| account.hash).collect::<Vec<_>>(),
+            hashes[3..]
+        );
+
+        // the served accounts are proven
+        for &hash in &hashes {
+            let request = GetAccountRange { starting_hash: hash, limit_hash: hash, ..request };
+            let response = handler.get_account_range(&request).unwrap();
+            let [served] = response.accounts.as_slice() else { panic!("expected one account") };
+            let (_, account) =
+                accounts.iter().find(|(address, _)| keccak256(address) == hash).unwrap();
+            let storage_root = served.body.storage_root().unwrap();
+            let expected = alloy_rlp::encode(TrieAccount::from((*account, storage_root)));
+            verify_proof(root, Nibbles::unpack(hash), Some(expected), &response.proof).unwrap();
+        }
+
+        // unknown roots are not served
+        let request = GetAccountRange { root_hash: B256::ZERO, ..request };
+        assert!(handler.get_account_range(&request).unwrap().accounts.is_empty());
+
+        // serve a partial storage range of the first account
+        let hashed_address = keccak256(accounts[0].0);
+        let request = GetStorageRanges {
+            request_id: 2,
+            root_hash: root,
+            account_hashes: vec![hashed_address, hashes[1]],
+            starting_hash: Bytes::new(),
+            limit_hash: Bytes::new(),
+            response_bytes: 1,
+        };
+        let response = handler.get_storage_ranges(&request).unwrap();
+        assert_eq!(response.slots.len(), 1);
+        assert_eq!(response.slots[0].len(), 1);
+        assert!(!response.proof.is_empty());
+
+        // the root node of the account trie can be requested by its path
+        let request = GetTrieNodes {
+            request_id: 3,
+            root_hash: root,
+            paths: vec![reth_eth_wire::TriePathSet(vec![Bytes::from_static(&[0x00])])],
+            response_bytes: SOFT_RESPONSE_LIMIT,
+        };
+        let response = handler.get_trie_nodes(&request).unwrap();
+        assert_eq!(response.nodes.len(), 1);
+        assert_eq!(keccak256(&response.nodes[0]), root);
+    }
+}
@@ -10,7 +10,7 @@ use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDB, NodeTypesWithEngine};
 use reth_payload_builder_primitives::PayloadBuilder;
-use reth_provider::FullProvider;
+use reth_provider::{BadBlocks, FullProvider};
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::TransactionPool;
 use std::{future::Future, marker::PhantomData};
@@ -101,6 +101,8 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
         BeaconConsensusEngineHandle<<N::Types as NodeTypesWithEngine>::Engine>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Recently rejected blocks, recorded by the engine.
+    pub bad_blocks: BadBlocks,
 }
 
 /// Customizable node add-on types.
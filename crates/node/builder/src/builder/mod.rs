@@ -19,8 +19,8 @@ use reth_db_api::{
 };
 use reth_exex::ExExContext;
 use reth_network::{
-    transactions::TransactionsManagerConfig, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
-    NetworkHandle, NetworkManager,
+    snap_requests::SnapRequestHandler, transactions::TransactionsManagerConfig, NetworkBuilder,
+    NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes,
@@ -670,10 +670,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     where
         Pool: TransactionPool + Unpin + 'static,
     {
-        let (handle, network, txpool, eth) = builder
-            .transactions(pool, tx_config)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+        let mut builder = builder.transactions(pool, tx_config);
+        if self.config().network.serve_snap {
+            let (snap, protocol) = SnapRequestHandler::with_protocol(self.provider().clone());
+            builder.network_mut().add_rlpx_sub_protocol(protocol);
+            self.executor.spawn_critical("p2p snap request handler", snap);
+        }
+        let (handle, network, txpool, eth) =
+            builder.request_handler(self.provider().clone()).split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
@@ -1,7 +1,7 @@
 //! Pool component for the node builder.
 
 use alloy_primitives::Address;
-use reth_transaction_pool::{PoolConfig, SubPoolLimit, TransactionPool};
+use reth_transaction_pool::{AdmissionPolicies, PoolConfig, SubPoolLimit, TransactionPool};
 use std::{collections::HashSet, future::Future};
 
 use crate::{BuilderContext, FullNodeTypes};
@@ -54,6 +54,8 @@ pub struct PoolBuilderConfigOverrides {
     pub local_addresses: HashSet<Address>,
     /// Additional tasks to validate new transactions.
     pub additional_validation_tasks: Option<usize>,
+    /// Admission policies that are appended to the configured ones.
+    pub admission_policies: AdmissionPolicies,
 }
 
 impl PoolBuilderConfigOverrides {
@@ -68,6 +70,7 @@ impl PoolBuilderConfigOverrides {
             minimal_protocol_basefee,
             local_addresses,
             additional_validation_tasks: _,
+            admission_policies,
         } = self;
 
         if let Some(pending_limit) = pending_limit {
@@ -89,6 +92,7 @@ impl PoolBuilderConfigOverrides {
             config.minimal_protocol_basefee = minimal_protocol_basefee;
         }
         config.local_transactions_config.local_addresses.extend(local_addresses);
+        config.admission_policies.append(admission_policies);
 
         config
     }
@@ -25,7 +25,9 @@ use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{
+    InvalidBlockOpcodeHook, InvalidBlockPreStateHook, InvalidBlockWitnessHook,
+};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodePrimitives, FullNodeTypes, NodeTypes, NodeTypesWithDB};
 use reth_node_core::{
@@ -47,9 +49,9 @@ use reth_node_metrics::{
 use reth_primitives::Head;
 use reth_provider::{
     providers::{BlockchainProvider, BlockchainProvider2, ProviderNodeTypes, StaticFileProvider},
-    BlockHashReader, BlockNumReader, CanonStateNotificationSender, ChainSpecProvider,
+    BadBlocks, BlockHashReader, BlockNumReader, CanonStateNotificationSender, ChainSpecProvider,
     ProviderError, ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TreeViewer,
+    StaticFileProviderFactory, TreeViewer, TrieChangeSetsWriter,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
@@ -466,6 +468,17 @@ where
             rx.await??;
         }
 
+        // The trie changesets are recorded as long as their checkpoint exists, so they're removed
+        // once the stage is disabled.
+        if !self.toml_config().stages.trie_changesets.enabled {
+            let provider_rw = factory.provider_rw()?;
+            if provider_rw.get_stage_checkpoint(StageId::TrieChangeSets)?.is_some() {
+                info!(target: "reth::cli", "Removing trie changesets of the disabled stage");
+                provider_rw.clear_trie_changesets()?;
+                provider_rw.commit()?;
+            }
+        }
+
         Ok(factory)
     }
 
@@ -874,9 +887,16 @@ where
             .unwrap_or_default()
             .block_number;
 
+        // The optional log index and trie changesets stages only take part in the pipeline if
+        // they're enabled.
+        let index_logs =
+            self.toml_config().stages.index_logs.enabled.then_some(&StageId::IndexLogs);
+        let trie_changesets =
+            self.toml_config().stages.trie_changesets.enabled.then_some(&StageId::TrieChangeSets);
+
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
         // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        for stage_id in StageId::ALL.iter().skip(1).chain(index_logs).chain(trie_changesets) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -955,17 +975,41 @@ where
                         self.components().evm_config().clone(),
                         output_directory,
                         healthy_node_rpc_client.clone(),
+                    ))
+                        as Box<dyn InvalidBlockHook>,
+                    InvalidBlockHookType::PreState => Box::new(InvalidBlockPreStateHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
                     )),
-                    InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                        eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                    }
-                } as Box<dyn InvalidBlockHook>)
+                    InvalidBlockHookType::Opcode => Box::new(InvalidBlockOpcodeHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    )),
+                })
             })
-            .collect::<Result<_, _>>()?;
+            .collect::<eyre::Result<_>>()?;
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
 
+    /// Returns the buffer that rejected blocks are recorded in.
+    ///
+    /// If configured, bad blocks are persisted to and restored from the data directory.
+    pub fn bad_blocks(&self) -> eyre::Result<BadBlocks> {
+        let debug = &self.node_config().debug;
+        if debug.persist_bad_blocks {
+            return Ok(BadBlocks::with_persistence(
+                debug.bad_blocks_capacity,
+                self.data_dir().bad_blocks(),
+            )?)
+        }
+        Ok(BadBlocks::new(debug.bad_blocks_capacity))
+    }
+
     /// Returns an RPC client for the healthy node, if configured in the node config.
     fn get_healthy_node_client(&self) -> eyre::Result<Option<jsonrpsee::http_client::HttpClient>> {
         self.node_config()
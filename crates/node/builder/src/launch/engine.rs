@@ -34,6 +34,7 @@ use reth_provider::providers::{BlockchainProvider2, ProviderNodeTypes};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
+use reth_transaction_pool::TransactionPool;
 use std::sync::Arc;
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -89,7 +90,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, mut add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -208,6 +209,8 @@ where
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
+        let bad_blocks = ctx.bad_blocks()?;
+
         let mut engine_service = if ctx.is_dev() {
             let eth_service = LocalEngineService::new(
                 ctx.consensus(),
@@ -216,14 +219,20 @@ where
                 ctx.blockchain_db().clone(),
                 pruner,
                 ctx.components().payload_builder().clone(),
-                engine_tree_config,
+                // allow the local miner to revert to snapshots
+                engine_tree_config.with_unwind_canonical_header(true),
                 ctx.invalid_block_hook()?,
+                bad_blocks.clone(),
                 ctx.sync_metrics_tx(),
                 consensus_engine_tx.clone(),
                 Box::pin(consensus_engine_stream),
                 ctx.dev_mining_mode(ctx.components().pool()),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
             );
+            // blocks of the local miner are inserted without being re-executed, which is what
+            // allows the anvil namespace to modify the state
+            ctx.components().pool().state_overrides().enable();
+            add_ons.hooks_mut().install_local_miner_modules(eth_service.miner().clone());
 
             Either::Left(eth_service)
         } else {
@@ -241,6 +250,7 @@ where
                 ctx.components().payload_builder().clone(),
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
+                bad_blocks.clone(),
                 ctx.sync_metrics_tx(),
             );
 
@@ -285,6 +295,7 @@ where
             config: ctx.node_config(),
             beacon_engine_handle,
             jwt_secret,
+            bad_blocks,
         };
 
         let RpcHandle { rpc_server_handles, rpc_registry } =
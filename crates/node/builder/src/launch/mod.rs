@@ -259,6 +259,8 @@ where
             Box::pin(consensus_engine_stream),
             hooks,
         )?;
+        let bad_blocks = ctx.bad_blocks()?;
+        let beacon_consensus_engine = beacon_consensus_engine.with_bad_blocks(bad_blocks.clone());
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -291,6 +293,7 @@ where
             config: ctx.node_config(),
             beacon_engine_handle,
             jwt_secret,
+            bad_blocks,
         };
 
         let RpcHandle { rpc_server_handles, rpc_registry } =
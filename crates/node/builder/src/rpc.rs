@@ -9,6 +9,8 @@ use std::{
 
 use alloy_rpc_types::engine::ClientVersionV1;
 use futures::TryFutureExt;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
+use reth_engine_local::LocalMinerHandle;
 use reth_node_api::{
     AddOnsContext, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes, NodeTypesWithEngine,
     PayloadBuilder,
@@ -18,16 +20,19 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::PayloadStore;
-use reth_provider::providers::ProviderNodeTypes;
+use reth_provider::{providers::ProviderNodeTypes, StorageChangeSetReader};
 use reth_rpc::{
     eth::{EthApiTypes, FullEthApiServer},
-    EthApi,
+    AnvilApi, AnvilEthApi, EthApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, AnvilApiServer, AnvilEthApiServer, GanacheApiServer,
+    HardhatApiServer,
 };
-use reth_rpc_api::eth::helpers::AddDevSigners;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_tasks::TaskExecutor;
@@ -106,6 +111,55 @@ where
         self.set_extend_rpc_modules(hook);
         self
     }
+
+    /// Installs the `anvil`, `hardhat` and `evm` namespaces of dev nodes before the configured
+    /// hook is run to configure the rpc modules.
+    ///
+    /// The namespaces are backed by the given [`LocalMinerHandle`] and are only installed if
+    /// [`RethRpcModule::Anvil`] is configured, in which case the `eth` methods that send and look
+    /// up transactions are replaced with the [`AnvilEthApi`] on the same transports, so that
+    /// impersonated accounts can send transactions.
+    pub(crate) fn install_local_miner_modules(&mut self, miner: LocalMinerHandle)
+    where
+        Node: 'static,
+        Node::Provider:
+            ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks> + StorageChangeSetReader,
+        EthApi: FullEthApiServer + 'static,
+    {
+        let extend_rpc_modules = std::mem::replace(&mut self.extend_rpc_modules, Box::new(()));
+        self.set_extend_rpc_modules(move |ctx: RpcContext<'_, Node, EthApi>| {
+            let anvil_api = AnvilApi::new(
+                ctx.provider().clone(),
+                ctx.pool().clone(),
+                miner,
+                Box::new(ctx.node().task_executor().clone()),
+            );
+            let eth_api = AnvilEthApi::new(anvil_api.clone(), ctx.registry.eth_api().clone());
+            let eth_module = AnvilEthApiServer::into_rpc(eth_api);
+            let module_config = ctx.modules.module_config();
+            let (http, ws, ipc) = (
+                module_config.contains_http(&RethRpcModule::Anvil),
+                module_config.contains_ws(&RethRpcModule::Anvil),
+                module_config.contains_ipc(&RethRpcModule::Anvil),
+            );
+            if http {
+                ctx.modules.replace_http(eth_module.clone())?;
+            }
+            if ws {
+                ctx.modules.replace_ws(eth_module.clone())?;
+            }
+            if ipc {
+                ctx.modules.replace_ipc(eth_module)?;
+            }
+
+            let mut module = AnvilApiServer::into_rpc(anvil_api.clone());
+            module.merge(HardhatApiServer::into_rpc(anvil_api.clone()))?;
+            module.merge(GanacheApiServer::into_rpc(anvil_api))?;
+            ctx.modules.merge_if_module_configured(RethRpcModule::Anvil, module)?;
+
+            extend_rpc_modules.extend_rpc_modules(ctx)
+        });
+    }
 }
 
 impl<Node, EthApi> fmt::Debug for RpcHooks<Node, EthApi>
@@ -421,7 +475,7 @@ where
         let Self { eth_api_builder, engine_validator_builder, hooks, _pd: _ } = self;
 
         let engine_validator = engine_validator_builder.build(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret } = ctx;
+        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, bad_blocks } = ctx;
 
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
@@ -456,6 +510,7 @@ where
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
             .with_consensus(node.consensus().clone())
+            .with_bad_blocks(bad_blocks)
             .build_with_auth_server(module_config, engine_api, eth_api_builder);
 
         // in dev mode we generate 20 random dev-signer accounts
@@ -543,13 +598,14 @@ pub trait RethRpcAddOns<N: FullNodeComponents>:
     NodeAddOns<N, Handle = RpcHandle<N, Self::EthApi>>
 {
     /// eth API implementation.
-    type EthApi: EthApiTypes;
+    type EthApi: FullEthApiServer + 'static;
 
     /// Returns a mutable reference to RPC hooks.
     fn hooks_mut(&mut self) -> &mut RpcHooks<N, Self::EthApi>;
 }
 
-impl<N: FullNodeComponents, EthApi: EthApiTypes, EV> RethRpcAddOns<N> for RpcAddOns<N, EthApi, EV>
+impl<N: FullNodeComponents, EthApi: FullEthApiServer + 'static, EV> RethRpcAddOns<N>
+    for RpcAddOns<N, EthApi, EV>
 where
     Self: NodeAddOns<N, Handle = RpcHandle<N, EthApi>>,
 {
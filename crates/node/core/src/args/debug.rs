@@ -5,6 +5,7 @@ use clap::{
     builder::{PossibleValue, TypedValueParser},
     Arg, Args, Command,
 };
+use reth_chain_state::DEFAULT_BAD_BLOCKS_CAPACITY;
 use std::{collections::HashSet, ffi::OsStr, fmt, path::PathBuf, str::FromStr};
 use strum::{AsRefStr, EnumIter, IntoStaticStr, ParseError, VariantArray, VariantNames};
 
@@ -87,6 +88,18 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// The maximum number of recently rejected blocks to keep for `debug_getBadBlocks`.
+    #[arg(
+        long = "debug.bad-blocks-capacity",
+        help_heading = "Debug",
+        default_value_t = DEFAULT_BAD_BLOCKS_CAPACITY
+    )]
+    pub bad_blocks_capacity: usize,
+
+    /// Persist recently rejected blocks to disk, so they survive restarts.
+    #[arg(long = "debug.persist-bad-blocks", help_heading = "Debug")]
+    pub persist_bad_blocks: bool,
 }
 
 impl Default for DebugArgs {
@@ -104,6 +117,8 @@ impl Default for DebugArgs {
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            bad_blocks_capacity: DEFAULT_BAD_BLOCKS_CAPACITY,
+            persist_bad_blocks: false,
         }
     }
 }
@@ -118,6 +118,7 @@ impl PruningArgs {
                             .into_iter()
                             .collect(),
                     ),
+                    trie_changesets: None,
                 },
             }
         }
@@ -174,6 +174,8 @@ pub struct RpcServerArgs {
     /// The maximum proof window for historical proof generation.
     /// This value allows for generating historical proofs up to
     /// configured number of blocks from current tip (up to `tip - window`).
+    /// Blocks covered by the trie changesets are limited by `--rpc.trie-changesets-proof-window`
+    /// instead.
     #[arg(
         long = "rpc.eth-proof-window",
         default_value_t = constants::DEFAULT_ETH_PROOF_WINDOW,
@@ -181,6 +183,15 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// The maximum proof window for historical proof generation of blocks covered by the trie
+    /// changesets.
+    #[arg(
+        long = "rpc.trie-changesets-proof-window",
+        value_name = "BLOCKS",
+        default_value_t = constants::DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW
+    )]
+    pub rpc_trie_changesets_proof_window: u64,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
@@ -190,6 +201,10 @@ pub struct RpcServerArgs {
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
+    /// Enables `debug_dbGet`, which returns raw values of the database.
+    #[arg(long = "rpc.debug-db-get", default_value_t = false)]
+    pub rpc_debug_db_get: bool,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -328,10 +343,12 @@ impl Default for RpcServerArgs {
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_trie_changesets_proof_window: constants::DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
             builder_disallow: Default::default(),
+            rpc_debug_db_get: false,
         }
     }
 }
@@ -50,4 +50,13 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional log index stage within the pipeline.
+    ///
+    /// Indexes the blocks that contain logs by address and topic.
+    LogIndex,
+    /// The optional trie changesets stage within the pipeline.
+    ///
+    /// Records the trie nodes before each block changed them, for fast historical proofs.
+    #[value(name = "trie-changesets")]
+    TrieChangeSets,
 }
@@ -4,15 +4,18 @@ use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
 use clap::Args;
+use reth_cli_util::parse_duration_from_secs;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_MAX_BUNDLES,
+    DEFAULT_MAX_PRIVATE_TRANSACTIONS, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::time::Duration;
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -91,6 +94,28 @@ pub struct TxPoolArgs {
     /// iterators.
     #[arg(long = "txpool.max-new-pending-txs-notifications", alias = "txpool.max-new-pending-txs-notifications", default_value_t = MAX_NEW_PENDING_TXS_NOTIFICATIONS)]
     pub max_new_pending_txs_notifications: usize,
+
+    /// Max number of bundles submitted via `eth_sendBundle` to keep in the bundle pool.
+    #[arg(long = "txpool.max-bundles", default_value_t = DEFAULT_MAX_BUNDLES)]
+    pub max_bundles: usize,
+
+    /// Max number of transactions submitted via `eth_sendPrivateTransaction` to keep in the
+    /// private transaction pool.
+    #[arg(long = "txpool.max-private-txs", default_value_t = DEFAULT_MAX_PRIVATE_TRANSACTIONS)]
+    pub max_private_transactions: usize,
+
+    /// Persist all pooled transactions, including blob sidecars and private transactions, across
+    /// restarts instead of only local transactions.
+    ///
+    /// Restored transactions are revalidated before they are inserted into the pool again. Bundles
+    /// are not persisted.
+    #[arg(long = "txpool.persist")]
+    pub persist: bool,
+
+    /// Interval at which a snapshot of the pool is written while running, so it survives a crash.
+    /// A snapshot is always written on shutdown.
+    #[arg(long = "txpool.persist-interval", value_name = "SECONDS", value_parser = parse_duration_from_secs, requires = "persist")]
+    pub persist_interval: Option<Duration>,
 }
 
 impl Default for TxPoolArgs {
@@ -116,6 +141,10 @@ impl Default for TxPoolArgs {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            max_bundles: DEFAULT_MAX_BUNDLES,
+            max_private_transactions: DEFAULT_MAX_PRIVATE_TRANSACTIONS,
+            persist: false,
+            persist_interval: None,
         }
     }
 }
@@ -155,6 +184,9 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
+            max_bundles: self.max_bundles,
+            max_private_transactions: self.max_private_transactions,
+            admission_policies: Default::default(),
         }
     }
 }
@@ -188,4 +220,24 @@ mod tests {
         .args;
         assert_eq!(args.locals, vec![Address::ZERO]);
     }
+
+    #[test]
+    fn txpool_parse_persist() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.persist",
+            "--txpool.persist-interval",
+            "60",
+        ])
+        .args;
+        assert!(args.persist);
+        assert_eq!(args.persist_interval, Some(Duration::from_secs(60)));
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.persist-interval",
+            "60"
+        ])
+        .is_err());
+    }
 }
@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool snapshot file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.rlp`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,10 +358,22 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the file recently rejected blocks are persisted to for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks.json`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks.json")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the WAL directory of the remote ExEx for this chain.
+    pub fn exex_remote_wal(&self) -> PathBuf {
+        self.data_dir().join("exex/remote-wal")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
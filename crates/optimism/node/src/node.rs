@@ -276,6 +276,10 @@ where
         let Self { pool_config_overrides } = self;
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let additional_validation_tasks = pool_config_overrides
+            .additional_validation_tasks
+            .unwrap_or_else(|| ctx.config().txpool.additional_validation_tasks);
+        let pool_config = pool_config_overrides.apply(ctx.pool_config());
 
         let validator = TransactionValidationTaskExecutor::eth_builder(Arc::new(
             ctx.chain_spec().inner.clone(),
@@ -283,11 +287,8 @@ where
         .no_eip4844()
         .with_head_timestamp(ctx.head().timestamp)
         .kzg_settings(ctx.kzg_settings()?)
-        .with_additional_tasks(
-            pool_config_overrides
-                .additional_validation_tasks
-                .unwrap_or_else(|| ctx.config().txpool.additional_validation_tasks),
-        )
+        .with_admission_policies(pool_config.admission_policies.clone())
+        .with_additional_tasks(additional_validation_tasks)
         .build_with_tasks(ctx.provider().clone(), ctx.task_executor().clone(), blob_store.clone())
         .map(|validator| {
             OpTransactionValidator::new(validator)
@@ -300,7 +301,7 @@ where
             validator,
             CoinbaseTipOrdering::default(),
             blob_store,
-            pool_config_overrides.apply(ctx.pool_config()),
+            pool_config,
         );
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
@@ -310,19 +311,41 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
-
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
-                "local transactions backup task",
-                |shutdown| {
-                    reth_transaction_pool::maintain::backup_local_transactions_task(
-                        shutdown,
-                        pool.clone(),
-                        transactions_backup_config,
-                    )
-                },
-            );
+            let txpool_args = &ctx.config().txpool;
+            if txpool_args.persist {
+                let mut snapshot_config =
+                    reth_transaction_pool::maintain::TransactionPoolSnapshotConfig::new(
+                        data_dir.txpool_snapshot(),
+                    );
+                if let Some(interval) = txpool_args.persist_interval {
+                    snapshot_config = snapshot_config.with_interval(interval);
+                }
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool snapshot task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::snapshot_transaction_pool_task(
+                            shutdown,
+                            pool.clone(),
+                            snapshot_config,
+                        )
+                    },
+                );
+            } else {
+                let transactions_backup_config =
+                    reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions backup task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::backup_local_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            transactions_backup_config,
+                        )
+                    },
+                );
+            }
 
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
@@ -116,6 +116,26 @@ where
         &self,
         origin: TransactionOrigin,
         transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        self.validate_one_with(origin, transaction, false)
+    }
+
+    /// Validates a single transaction that was admitted into the pool before.
+    ///
+    /// See also [`TransactionValidator::validate_readmitted_transaction`]
+    pub fn validate_one_readmitted(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        self.validate_one_with(origin, transaction, true)
+    }
+
+    fn validate_one_with(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+        readmitted: bool,
     ) -> TransactionValidationOutcome<Tx> {
         if transaction.is_eip4844() {
             return TransactionValidationOutcome::Invalid(
@@ -124,7 +144,11 @@ where
             )
         }
 
-        let outcome = self.inner.validate_one(origin, transaction);
+        let outcome = if readmitted {
+            self.inner.validate_one_readmitted(origin, transaction)
+        } else {
+            self.inner.validate_one(origin, transaction)
+        };
 
         if !self.requires_l1_data_gas_fee() {
             // no need to check L1 gas fee
@@ -208,6 +232,14 @@ where
         self.validate_one(origin, transaction)
     }
 
+    async fn validate_readmitted_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_one_readmitted(origin, transaction)
+    }
+
     async fn validate_transactions(
         &self,
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
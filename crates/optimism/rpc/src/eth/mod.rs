@@ -9,10 +9,10 @@ mod pending_block;
 
 pub use receipt::{OpReceiptBuilder, OpReceiptFieldsBuilder};
 
-use std::{fmt, sync::Arc};
+use std::{fmt, ops::RangeInclusive, sync::Arc};
 
 use alloy_consensus::Header;
-use alloy_primitives::U256;
+use alloy_primitives::{BlockNumber, U256};
 use derive_more::Deref;
 use op_alloy_network::Optimism;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
@@ -21,7 +21,7 @@ use reth_network_api::NetworkInfo;
 use reth_node_builder::EthApiBuilderCtx;
 use reth_provider::{
     BlockNumReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider,
-    StageCheckpointReader, StateProviderFactory,
+    StageCheckpointReader, StateProviderFactory, TrieChangeSetsReader,
 };
 use reth_rpc::eth::{core::EthApiInner, DevSigner};
 use reth_rpc_eth_api::{
@@ -29,7 +29,7 @@ use reth_rpc_eth_api::{
         AddDevSigners, EthApiSpec, EthFees, EthSigner, EthState, LoadBlock, LoadFee, LoadState,
         SpawnBlocking, Trace,
     },
-    EthApiTypes, RpcNodeCore, RpcNodeCoreExt,
+    EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt,
 };
 use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
 use reth_tasks::{
@@ -88,6 +88,7 @@ where
             ctx.config.rpc_gas_cap,
             ctx.config.rpc_max_simulate_blocks,
             ctx.config.eth_proof_window,
+            ctx.config.trie_changesets_proof_window,
             blocking_task_pool,
             ctx.new_fee_history_cache(),
             ctx.evm_config.clone(),
@@ -232,12 +233,21 @@ impl<N> LoadState for OpEthApi<N> where
 impl<N> EthState for OpEthApi<N>
 where
     Self: LoadState + SpawnBlocking,
-    N: RpcNodeCore,
+    N: RpcNodeCore<Provider: TrieChangeSetsReader>,
 {
     #[inline]
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    #[inline]
+    fn max_trie_changesets_proof_window(&self) -> u64 {
+        self.inner.trie_changesets_proof_window()
+    }
+
+    fn trie_changesets_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>, Self::Error> {
+        self.inner.provider().trie_changesets_range().map_err(Self::Error::from_eth_err)
+    }
 }
 
 impl<N> EthFees for OpEthApi<N>
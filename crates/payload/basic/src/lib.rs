@@ -11,7 +11,7 @@
 use crate::metrics::PayloadBuilderMetrics;
 use alloy_consensus::constants::EMPTY_WITHDRAWALS;
 use alloy_eips::{eip4895::Withdrawals, merge::SLOT_DURATION};
-use alloy_primitives::{Bytes, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use futures_core::ready;
 use futures_util::FutureExt;
 use reth_chainspec::EthereumHardforks;
@@ -19,14 +19,18 @@ use reth_evm::state_change::post_block_withdrawals_balance_increments;
 use reth_payload_builder::{KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
-use reth_primitives::{proofs, SealedHeader};
+use reth_primitives::{proofs, SealedHeader, TransactionSignedEcRecovered};
 use reth_primitives_traits::constants::RETH_CLIENT_VERSION;
 use reth_provider::{BlockReaderIdExt, CanonStateNotification, StateProviderFactory};
 use reth_revm::cached::CachedReads;
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
-use revm::{Database, State};
+use reth_transaction_pool::{AccountOverride, BlockOverrides, PoolBundle, TransactionPool};
+use revm::{
+    primitives::{Account, AccountStatus, Bytecode, EvmState, EvmStorageSlot},
+    Database, DatabaseCommit, State,
+};
 use std::{
+    collections::HashMap,
     fmt,
     future::Future,
     ops::Deref,
@@ -900,6 +904,46 @@ impl<Pool, Client, Attributes, Payload> BuildArguments<Pool, Client, Attributes,
     }
 }
 
+impl<Pool, Client, Attributes, Payload> BuildArguments<Pool, Client, Attributes, Payload>
+where
+    Pool: TransactionPool,
+    Attributes: PayloadBuilderAttributes,
+{
+    /// Returns the bundles of the pool's [`BundlePool`](reth_transaction_pool::BundlePool) that
+    /// can be included in the payload, in the order they were submitted.
+    pub fn bundles(&self) -> Vec<PoolBundle> {
+        self.pool.bundle_pool().bundles_for_block(
+            self.config.parent_header.number + 1,
+            self.config.attributes.timestamp(),
+        )
+    }
+
+    /// Returns the transactions of the pool's
+    /// [`PrivateTransactionPool`](reth_transaction_pool::PrivateTransactionPool) that can be
+    /// included in the payload, in the order they were submitted.
+    pub fn private_transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        self.pool.private_pool().transactions_for_block(self.config.parent_header.number + 1)
+    }
+
+    /// Returns the queued transactions of impersonated accounts of the pool's
+    /// [`StateOverrides`](reth_transaction_pool::StateOverrides), in the order they were added.
+    pub fn impersonated_transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        self.pool.state_overrides().impersonated_transactions()
+    }
+
+    /// Returns the pending account overrides of the pool's
+    /// [`StateOverrides`](reth_transaction_pool::StateOverrides).
+    pub fn state_overrides(&self) -> HashMap<Address, AccountOverride> {
+        self.pool.state_overrides().overrides()
+    }
+
+    /// Returns the pending block overrides of the pool's
+    /// [`StateOverrides`](reth_transaction_pool::StateOverrides).
+    pub fn block_overrides(&self) -> BlockOverrides {
+        self.pool.state_overrides().block_overrides()
+    }
+}
+
 /// A trait for building payloads that encapsulate Ethereum transactions.
 ///
 /// This trait provides the `try_build` method to construct a transaction payload
@@ -1010,6 +1054,70 @@ where
     Ok(Some(proofs::calculate_withdrawals_root(withdrawals)))
 }
 
+/// Applies the account overrides and commits them to the _runtime_ Database and `BundleState`.
+///
+/// Blocks built with overrides can only be used by dev nodes, see
+/// [`StateOverrides`](reth_transaction_pool::StateOverrides).
+pub fn commit_state_overrides<DB>(
+    db: &mut State<DB>,
+    overrides: HashMap<Address, AccountOverride>,
+) -> Result<(), DB::Error>
+where
+    DB: Database,
+{
+    if overrides.is_empty() {
+        return Ok(())
+    }
+
+    let mut changes = EvmState::default();
+    let mut empty_with_storage = EvmState::default();
+    for (address, account_override) in overrides {
+        let mut info = db.basic(address)?.unwrap_or_default();
+        if let Some(balance) = account_override.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account_override.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = account_override.code {
+            let code = Bytecode::new_raw(code);
+            info.code_hash = code.hash_slow();
+            info.code = Some(code);
+        }
+
+        let mut account =
+            Account { info, storage: Default::default(), status: AccountStatus::Touched };
+        for (slot, value) in account_override.storage {
+            let original = db.storage(address, slot)?;
+            account.storage.insert(slot, EvmStorageSlot::new_changed(original, value));
+        }
+
+        if !account.is_empty() {
+            changes.insert(address, account);
+        } else if account.storage.is_empty() {
+            // accounts that end up empty are removed from the state, like empty accounts that
+            // are touched by a transaction
+            account.mark_selfdestruct();
+            changes.insert(address, account);
+        } else {
+            empty_with_storage.insert(address, account);
+        }
+    }
+
+    db.commit(changes);
+
+    if !empty_with_storage.is_empty() {
+        // empty accounts would be cleared under EIP-161 together with the overridden storage, so
+        // they are committed the way they were before the state clear
+        let has_state_clear = db.cache.has_state_clear;
+        db.set_state_clear_flag(false);
+        db.commit(empty_with_storage);
+        db.set_state_clear_flag(has_state_clear);
+    }
+
+    Ok(())
+}
+
 /// Checks if the new payload is better than the current best.
 ///
 /// This compares the total fees of the blocks, higher is better.
@@ -1030,3 +1138,39 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::{states::bundle_state::BundleRetention, EmptyDB};
+
+    #[test]
+    fn commit_empty_account_overrides() {
+        let mut db =
+            State::builder().with_database(EmptyDB::default()).with_bundle_update().build();
+        db.set_state_clear_flag(true);
+
+        let with_storage = Address::with_last_byte(1);
+        let without_storage = Address::with_last_byte(2);
+        let overrides = HashMap::from([
+            (
+                with_storage,
+                AccountOverride {
+                    storage: HashMap::from([(U256::from(1), U256::from(2))]),
+                    ..Default::default()
+                },
+            ),
+            (without_storage, AccountOverride { nonce: Some(0), ..Default::default() }),
+        ]);
+        commit_state_overrides(&mut db, overrides).unwrap();
+        assert!(db.cache.has_state_clear);
+
+        db.merge_transitions(BundleRetention::PlainState);
+        let account = db.bundle_state.account(&with_storage).unwrap();
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(2)));
+        assert!(db
+            .bundle_state
+            .account(&without_storage)
+            .map_or(true, |account| account.info.is_none()));
+    }
+}
@@ -11,14 +11,15 @@ use reth_prune_types::{
 };
 pub use set::SegmentSet;
 pub use static_file::{
-    Headers as StaticFileHeaders, Receipts as StaticFileReceipts,
+    AccountChangeSets as StaticFileAccountChangeSets, Headers as StaticFileHeaders,
+    Receipts as StaticFileReceipts, StorageChangeSets as StaticFileStorageChangeSets,
     Transactions as StaticFileTransactions,
 };
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
     AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    TransactionLookup, TrieChangeSets,
 };
 
 /// A segment represents a pruning of some portion of the data.
@@ -1,6 +1,6 @@
 use crate::segments::{
     AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    TrieChangeSets, UserReceipts,
 };
 use reth_db::transaction::DbTxMut;
 use reth_provider::{
@@ -9,7 +9,10 @@ use reth_provider::{
 };
 use reth_prune_types::PruneModes;
 
-use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
+use super::{
+    StaticFileAccountChangeSets, StaticFileHeaders, StaticFileReceipts,
+    StaticFileStorageChangeSets, StaticFileTransactions,
+};
 
 /// Collection of [`Segment`]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -64,6 +67,7 @@ where
             account_history,
             storage_history,
             receipts_log_filter,
+            trie_changesets,
         } = prune_modes;
 
         Self::default()
@@ -72,11 +76,17 @@ where
             // Static file transactions
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
-            .segment(StaticFileReceipts::new(static_file_provider))
+            .segment(StaticFileReceipts::new(static_file_provider.clone()))
+            // Static file account changesets
+            .segment(StaticFileAccountChangeSets::new(static_file_provider.clone()))
+            // Static file storage changesets
+            .segment(StaticFileStorageChangeSets::new(static_file_provider))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Trie changesets
+            .segment_opt(trie_changesets.map(TrieChangeSets::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
@@ -1,7 +1,11 @@
+mod account_changesets;
 mod headers;
 mod receipts;
+mod storage_changesets;
 mod transactions;
 
+pub use account_changesets::AccountChangeSets;
 pub use headers::Headers;
 pub use receipts::Receipts;
+pub use storage_changesets::StorageChangeSets;
 pub use transactions::Transactions;
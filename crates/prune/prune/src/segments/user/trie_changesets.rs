@@ -0,0 +1,199 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::B256;
+use reth_db::{tables, transaction::DbTxMut};
+use reth_db_api::models::BlockNumberHashedAddress;
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+/// Prunes the [`tables::AccountsTrieChangeSets`] and [`tables::StoragesTrieChangeSets`] tables.
+///
+/// The checkpoint is the highest block whose changesets may have been pruned in any of the tables,
+/// so the trie is never reverted with incomplete changesets. Changesets of lower blocks that are
+/// left over from an interrupted run are pruned by the next run, which always starts at the
+/// beginning of the tables.
+#[derive(Debug)]
+pub struct TrieChangeSets {
+    mode: PruneMode,
+}
+
+impl TrieChangeSets {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for TrieChangeSets
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::TrieChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range_end = match input.get_next_block_range() {
+            Some(range) => *range.end(),
+            None => {
+                trace!(target: "pruner", "No trie changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_block = input.previous_checkpoint.and_then(|c| c.block_number);
+        let (pruned_accounts, done) =
+            provider.tx_ref().prune_table_with_range::<tables::AccountsTrieChangeSets>(
+                ..=range_end,
+                &mut limiter,
+                |_| false,
+                |(block_number, _)| {
+                    last_pruned_block = last_pruned_block.max(Some(block_number));
+                },
+            )?;
+        trace!(target: "pruner", pruned = %pruned_accounts, %done, "Pruned account trie changesets");
+
+        let (pruned_storages, done) = if done {
+            provider.tx_ref().prune_table_with_range::<tables::StoragesTrieChangeSets>(
+                ..=BlockNumberHashedAddress((range_end, B256::repeat_byte(0xff))),
+                &mut limiter,
+                |_| false,
+                |(key, _)| {
+                    last_pruned_block = last_pruned_block.max(Some(key.block_number()));
+                },
+            )?
+        } else {
+            (0, false)
+        };
+        trace!(target: "pruner", pruned = %pruned_storages, %done, "Pruned storage trie changesets");
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned_accounts + pruned_storages,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: if done { Some(range_end) } else { last_pruned_block },
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, SegmentOutput, TrieChangeSets};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::DbDupCursorRW, models::BlockNumberHashedAddress, transaction::DbTxMut,
+    };
+    use reth_provider::DatabaseProviderFactory;
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneProgress, SegmentOutputCheckpoint};
+    use reth_stages::test_utils::TestStageDB;
+    use reth_trie_common::{Nibbles, StoredNibblesSubKey, TrieChangeSetsEntry};
+
+    fn entry(nibble: u8) -> TrieChangeSetsEntry {
+        TrieChangeSetsEntry {
+            nibbles: StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([nibble])),
+            node: None,
+        }
+    }
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let hashed_address = B256::with_last_byte(1);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        {
+            let mut accounts =
+                provider.tx_ref().cursor_dup_write::<tables::AccountsTrieChangeSets>().unwrap();
+            let mut storages =
+                provider.tx_ref().cursor_dup_write::<tables::StoragesTrieChangeSets>().unwrap();
+            for block_number in 1..=10 {
+                for nibble in 0..2 {
+                    accounts.append_dup(block_number, entry(nibble)).unwrap();
+                    storages
+                        .append_dup(
+                            BlockNumberHashedAddress((block_number, hashed_address)),
+                            entry(nibble),
+                        )
+                        .unwrap();
+                }
+            }
+        }
+        provider.commit().unwrap();
+
+        let segment = TrieChangeSets::new(PruneMode::Before(6));
+        let prune = |limiter: PruneLimiter, previous_checkpoint| {
+            let provider = db.factory.database_provider_rw().unwrap();
+            let input = PruneInput { previous_checkpoint, to_block: 5, limiter };
+            let output = segment.prune(&provider, input).unwrap();
+            provider.commit().unwrap();
+            output
+        };
+
+        // The account changesets of block 1 and half of block 2 are pruned, so the changesets of
+        // block 2 can't be used anymore.
+        let output = prune(PruneLimiter::default().set_deleted_entries_limit(3), None);
+        assert_matches!(
+            output,
+            SegmentOutput {
+                progress: PruneProgress::HasMoreData(_),
+                pruned: 3,
+                checkpoint: Some(SegmentOutputCheckpoint { block_number: Some(2), .. })
+            }
+        );
+        assert_eq!(db.table::<tables::AccountsTrieChangeSets>().unwrap().len(), 17);
+        assert_eq!(db.table::<tables::StoragesTrieChangeSets>().unwrap().len(), 20);
+
+        // The remaining changesets up to block 5 are pruned.
+        let checkpoint = output.checkpoint.map(|c| c.as_prune_checkpoint(segment.mode));
+        let output = prune(PruneLimiter::default(), checkpoint);
+        assert_matches!(
+            output,
+            SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned: 17,
+                checkpoint: Some(SegmentOutputCheckpoint { block_number: Some(5), .. })
+            }
+        );
+        assert!(db
+            .table::<tables::AccountsTrieChangeSets>()
+            .unwrap()
+            .iter()
+            .all(|(block_number, _)| *block_number > 5));
+        assert!(db
+            .table::<tables::StoragesTrieChangeSets>()
+            .unwrap()
+            .iter()
+            .all(|(key, _)| key.block_number() > 5));
+        assert_eq!(db.table::<tables::AccountsTrieChangeSets>().unwrap().len(), 10);
+        assert_eq!(db.table::<tables::StoragesTrieChangeSets>().unwrap().len(), 10);
+    }
+}
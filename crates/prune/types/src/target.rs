@@ -43,6 +43,9 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Trie changesets pruning configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trie_changesets: Option<PruneMode>,
 }
 
 impl PruneModes {
@@ -60,6 +63,7 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            trie_changesets: Some(PruneMode::Full),
         }
     }
 
@@ -38,7 +38,7 @@ pub trait AdminApi {
     #[subscription(
         name = "peerEvents",
         unsubscribe = "peerEvents_unsubscribe",
-        item = String
+        item = alloy_rpc_types_admin::PeerEvent
     )]
     async fn subscribe_peer_events(&self) -> jsonrpsee::core::SubscriptionResult;
 
@@ -1,8 +1,9 @@
+use alloy_json_rpc::RpcObject;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
-use alloy_rpc_types_eth::Block;
+use alloy_rpc_types_eth::{Block, TransactionRequest};
 
 /// Anvil rpc interface.
 /// https://book.getfoundry.sh/reference/anvil/#custom-methods
@@ -163,3 +164,24 @@ pub trait AnvilApi {
     #[method(name = "removePoolTransactions")]
     async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()>;
 }
+
+/// A subset of the ETH rpc interface that dev nodes serve in place of the regular methods, to
+/// support the transactions of impersonated accounts.
+///
+/// Impersonated transactions have no valid signature, so their sender can't be recovered. They
+/// are kept out of the transaction pool and their sender is looked up explicitly.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait AnvilEthApi<T: RpcObject, R: RpcObject> {
+    /// Sends a transaction, which is signed by the node if the sender is not impersonated.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<B256>;
+
+    /// Returns the information about a transaction requested by transaction hash.
+    #[method(name = "getTransactionByHash")]
+    async fn transaction_by_hash(&self, hash: B256) -> RpcResult<Option<T>>;
+
+    /// Returns the receipt of a transaction by transaction hash.
+    #[method(name = "getTransactionReceipt")]
+    async fn transaction_receipt(&self, hash: B256) -> RpcResult<Option<R>>;
+}
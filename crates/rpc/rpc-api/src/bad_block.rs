@@ -0,0 +1,19 @@
+//! Types returned by the bad block methods of the `debug` namespace.
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::Block;
+use serde::{Deserialize, Serialize};
+
+/// A block that was rejected by the node, returned by `debug_getBadBlocks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlockInfo {
+    /// Hash of the block.
+    pub hash: B256,
+    /// The block, with the hashes of its transactions.
+    pub block: Block,
+    /// RLP encoding of the block.
+    pub rlp: Bytes,
+    /// The validation error the block was rejected with.
+    pub reason: String,
+}
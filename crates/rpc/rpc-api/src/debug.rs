@@ -1,12 +1,14 @@
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{transaction::TransactionRequest, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
+use crate::{BadBlockInfo, StateDump};
+
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
@@ -33,9 +35,10 @@ pub trait DebugApi {
     #[method(name = "getRawReceipts")]
     async fn raw_receipts(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>>;
 
-    /// Returns an array of recent bad blocks that the client has seen on the network.
+    /// Returns an array of recent bad blocks that the client has seen on the network, together
+    /// with the reason they were rejected.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlockInfo>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
@@ -44,8 +47,23 @@ pub trait DebugApi {
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>>;
 
+    /// Creates a subscription that traces all blocks between two blocks (excluding start) and
+    /// emits the result of each block, in order, as soon as it is available.
+    #[subscription(
+        name = "subscribeTraceChain",
+        unsubscribe = "unsubscribeTraceChain",
+        item = BlockTraceResult
+    )]
+    async fn subscribe_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
     ///
@@ -152,16 +170,19 @@ pub trait DebugApi {
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
     /// exist in db are skipped. NB: geth by default does not store preimages.
+    ///
+    /// Reth never stores address preimages, so `incompletes` is ignored and all accounts are
+    /// returned keyed by their hashed address.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
-        block_number: BlockNumberOrTag,
+        block_id: BlockId,
         start: Bytes,
         max_results: u64,
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StateDump>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -174,9 +195,12 @@ pub trait DebugApi {
     #[method(name = "chaindbCompact")]
     async fn debug_chaindb_compact(&self) -> RpcResult<()>;
 
-    /// Returns leveldb properties of the key-value database.
+    /// Returns properties of the key-value database.
+    ///
+    /// Supported properties are `stats`, which lists the number of entries of every table, and the
+    /// name of a table, which returns the number of entries of that table.
     #[method(name = "chaindbProperty")]
-    async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String>;
 
     /// Turns on CPU profiling for the given duration and writes profile data to disk.
     #[method(name = "cpuProfile")]
@@ -193,13 +217,20 @@ pub trait DebugApi {
     async fn debug_db_ancients(&self) -> RpcResult<()>;
 
     /// Returns the raw value of a key stored in the database.
+    ///
+    /// The key is a geth database key, either hex encoded with a `0x` prefix or as a plain string.
+    /// Only the keys of canonical hashes, headers, header numbers, total difficulties and code
+    /// are supported. This method is disabled unless explicitly enabled on the node.
     #[method(name = "dbGet")]
-    async fn debug_db_get(&self, key: String) -> RpcResult<()>;
+    async fn debug_db_get(&self, key: String) -> RpcResult<Bytes>;
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
+    ///
+    /// Like geth, only the first page of accounts is returned, the remaining accounts can be
+    /// fetched with `debug_accountRange` starting at the returned `next` key.
     #[method(name = "dumpBlock")]
-    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<()>;
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump>;
 
     /// Forces garbage collection.
     #[method(name = "freeOSMemory")]
@@ -232,17 +263,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -254,8 +286,8 @@ pub trait DebugApi {
     async fn debug_intermediate_roots(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -268,8 +300,10 @@ pub trait DebugApi {
     async fn debug_mutex_profile(&self, file: String, nsec: u64) -> RpcResult<()>;
 
     /// Returns the preimage for a sha3 hash, if known.
+    ///
+    /// Reth does not store preimages, so this always returns an unsupported error.
     #[method(name = "preimage")]
-    async fn debug_preimage(&self, hash: B256) -> RpcResult<()>;
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes>;
 
     /// Retrieves a block and returns its pretty printed form.
     #[method(name = "printBlock")]
@@ -291,6 +325,8 @@ pub trait DebugApi {
 
     /// Sets the current head of the local chain by block number. Note, this is a destructive action
     /// and may severely damage your chain. Use with extreme caution.
+    ///
+    /// Reth can't rewind the chain of a running node, so this always returns an unsupported error.
     #[method(name = "setHead")]
     async fn debug_set_head(&self, number: u64) -> RpcResult<()>;
 
@@ -308,16 +344,19 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
+    ///
+    /// Writes the standard json trace of every transaction of the bad block to a separate file
+    /// and returns the paths of the written files.
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Used to obtain info about a block.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
@@ -361,8 +400,8 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
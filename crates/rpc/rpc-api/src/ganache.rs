@@ -1,5 +1,6 @@
 use alloy_primitives::U256;
 use alloy_rpc_types_anvil::MineOptions;
+use alloy_rpc_types_eth::Block;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 /// Ganache rpc interface.
@@ -22,9 +23,9 @@ pub trait GanacheApi {
     /// Mines a block independent of whether or not mining is started or stopped. Will mine an empty
     /// block if there are no available transactions to mine.
     ///
-    /// Returns "0x0". May return additional meta-data in the future.
+    /// Returns the last mined block.
     #[method(name = "mine")]
-    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String>;
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<Block>;
 
     // TODO Ganache is deprecated and this method is not implemented by Anvil and Hardhat.
     // #[method(name = "removeAccount")]
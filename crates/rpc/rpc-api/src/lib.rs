@@ -16,6 +16,7 @@
 
 mod admin;
 mod anvil;
+mod bad_block;
 mod debug;
 mod engine;
 mod ganache;
@@ -25,11 +26,15 @@ mod net;
 mod otterscan;
 mod reth;
 mod rpc;
+mod state_dump;
 mod trace;
 mod txpool;
 mod validation;
 mod web3;
 
+pub use bad_block::BadBlockInfo;
+pub use state_dump::{DumpAccount, StateDump};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -37,8 +42,11 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::{AnvilApiServer, AnvilEthApiServer},
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer},
+        ganache::GanacheApiServer,
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         net::NetApiServer,
         otterscan::OtterscanServer,
@@ -64,7 +72,7 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
-        anvil::AnvilApiClient,
+        anvil::{AnvilApiClient, AnvilEthApiClient},
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
@@ -64,22 +64,26 @@ pub trait Otterscan<T: RpcObject> {
     ) -> RpcResult<OtsBlockTransactions<T>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Only transactions of blocks in which the account or its storage changed are returned.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
         address: Address,
         block_number: u64,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Only transactions of blocks in which the account or its storage changed are returned.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
         address: Address,
         block_number: u64,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
     /// Gets the transaction hash for a certain sender address, given its nonce.
     #[method(name = "getTransactionBySenderAndNonce")]
@@ -0,0 +1,129 @@
+//! Types returned by the state dump methods of the `debug` namespace.
+//!
+//! The layout follows geth's `state.Dump`.
+
+use alloy_primitives::{hex, Bytes, B256, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// An account in a state dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Account balance, in decimal.
+    #[serde(with = "decimal")]
+    pub balance: U256,
+    /// Account nonce.
+    pub nonce: u64,
+    /// Storage root of the account.
+    pub root: B256,
+    /// Hash of the account's bytecode.
+    pub code_hash: B256,
+    /// The account's bytecode, omitted if the dump was requested without code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The account's storage keyed by hashed slot, with the values hex encoded without leading
+    /// zeros. Omitted if the dump was requested without storage.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "storage")]
+    pub storage: Option<BTreeMap<B256, U256>>,
+    /// The hashed address of the account.
+    #[serde(rename = "key")]
+    pub address_hash: B256,
+    /// The hashed slot the storage continues at, if the account has more slots than were
+    /// returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_storage_key: Option<B256>,
+}
+
+/// A page of the state at a given block, returned by `debug_dumpBlock` and `debug_accountRange`.
+///
+/// Reth does not store the preimages of hashed addresses, so the accounts are keyed by
+/// `pre(<hashed address>)`, like geth does for accounts without a known address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// State root of the dumped state, hex encoded without prefix.
+    #[serde(with = "unprefixed_hex")]
+    pub root: B256,
+    /// The accounts of this page.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The hashed address to continue from with `debug_accountRange`, if there are more
+    /// accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<B256>,
+}
+
+impl StateDump {
+    /// Returns the key of an account without a known address in [`StateDump::accounts`].
+    pub fn account_key(address_hash: B256) -> String {
+        format!("pre({address_hash})")
+    }
+}
+
+mod decimal {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_str_radix(&value, 10).map_err(serde::de::Error::custom)
+    }
+}
+
+mod unprefixed_hex {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(value: &B256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&hex::encode(value))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<B256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+mod storage {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(
+        storage: &Option<BTreeMap<B256, U256>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        storage
+            .as_ref()
+            .map(|storage| {
+                storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, hex::encode(value.to_be_bytes_trimmed_vec())))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<BTreeMap<B256, U256>>, D::Error> {
+        let Some(storage) = Option::<BTreeMap<B256, String>>::deserialize(deserializer)? else {
+            return Ok(None)
+        };
+        storage
+            .into_iter()
+            .map(|(slot, value)| {
+                let value = if value.is_empty() {
+                    U256::ZERO
+                } else {
+                    U256::from_str_radix(&value, 16).map_err(serde::de::Error::custom)?
+                };
+                Ok((slot, value))
+            })
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+}
@@ -18,9 +18,12 @@
 //! ```
 //! use alloy_consensus::Header;
 //! use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
-//! use reth_network_api::{NetworkInfo, Peers};
+//! use reth_network_api::{NetworkEventListenerProvider, NetworkInfo, Peers};
 //! use reth_primitives::TransactionSigned;
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     HashedStateReader, HistoryReader, LogIndexReader, RawTableReader, TrieChangeSetsReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -37,10 +40,16 @@
 //!     block_executor: BlockExecutor,
 //!     consensus: Consensus,
 //! ) where
-//!     Provider:
-//!         FullRpcProvider<Transaction = TransactionSigned> + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider<Transaction = TransactionSigned>
+//!         + AccountReader
+//!         + ChangeSetReader
+//!         + HistoryReader
+//!         + LogIndexReader
+//!         + HashedStateReader
+//!         + RawTableReader
+//!         + TrieChangeSetsReader,
 //!     Pool: TransactionPool + Unpin + 'static,
-//!     Network: NetworkInfo + Peers + Clone + 'static,
+//!     Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
 //!     Events: CanonStateSubscriptions + Clone + 'static,
 //!     EvmConfig: ConfigureEvm<Header = Header>,
 //!     BlockExecutor: BlockExecutorProvider,
@@ -78,9 +87,12 @@
 //! use alloy_consensus::Header;
 //! use reth_engine_primitives::EngineTypes;
 //! use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
-//! use reth_network_api::{NetworkInfo, Peers};
+//! use reth_network_api::{NetworkEventListenerProvider, NetworkInfo, Peers};
 //! use reth_primitives::TransactionSigned;
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     HashedStateReader, HistoryReader, LogIndexReader, RawTableReader, TrieChangeSetsReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -112,10 +124,16 @@
 //!     block_executor: BlockExecutor,
 //!     consensus: Consensus,
 //! ) where
-//!     Provider:
-//!         FullRpcProvider<Transaction = TransactionSigned> + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider<Transaction = TransactionSigned>
+//!         + AccountReader
+//!         + ChangeSetReader
+//!         + HistoryReader
+//!         + LogIndexReader
+//!         + HashedStateReader
+//!         + RawTableReader
+//!         + TrieChangeSetsReader,
 //!     Pool: TransactionPool + Unpin + 'static,
-//!     Network: NetworkInfo + Peers + Clone + 'static,
+//!     Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
 //!     Events: CanonStateSubscriptions + Clone + 'static,
 //!     EngineApi: EngineApiServer<EngineT>,
 //!     EngineT: EngineTypes,
@@ -187,14 +205,15 @@ use reth_chainspec::EthereumHardforks;
 use reth_consensus::Consensus;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
-use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_network_api::{noop::NoopNetwork, NetworkEventListenerProvider, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, FullRpcProvider, StateProviderFactory,
+    AccountReader, BadBlocks, BlockReader, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, EvmEnvProvider, FullRpcProvider, HashedStateReader, HistoryReader,
+    LogIndexReader, RawTableReader, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, DebugApiConfig, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi,
+    RethApi, TraceApi, TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -258,9 +277,15 @@ pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, B
     consensus: Arc<dyn Consensus>,
 ) -> Result<RpcServerHandle, RpcError>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + HashedStateReader
+        + RawTableReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm<Header = alloy_consensus::Header>,
@@ -316,6 +341,9 @@ pub struct RpcModuleBuilder<
     block_executor: BlockExecutor,
     /// The consensus implementation.
     consensus: Consensus,
+    /// Recently rejected blocks, served by the `debug_` namespace. An empty buffer is used if
+    /// not configured.
+    bad_blocks: Option<BadBlocks>,
 }
 
 // === impl RpcBuilder ===
@@ -335,7 +363,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         block_executor: BlockExecutor,
         consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config, block_executor, consensus }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks: None,
+        }
     }
 
     /// Configure the provider instance.
@@ -346,8 +384,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -357,6 +404,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -369,7 +417,15 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         P: TransactionPool + 'static,
     {
         let Self {
-            provider, network, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -380,6 +436,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -401,7 +458,15 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, executor, events, network, evm_config, block_executor, consensus, ..
+            provider,
+            executor,
+            events,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -412,6 +477,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             block_executor,
             pool: NoopTransactionPool::default(),
             consensus,
+            bad_blocks,
         }
     }
 
@@ -421,10 +487,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         network: N,
     ) -> RpcModuleBuilder<Provider, Pool, N, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
     where
-        N: NetworkInfo + Peers + 'static,
+        N: NetworkInfo + Peers + NetworkEventListenerProvider + 'static,
     {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -435,6 +509,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -456,7 +531,15 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -467,6 +550,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -478,8 +562,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -489,6 +582,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -508,8 +602,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         BlockExecutor,
         Consensus,
     > {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -519,6 +622,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -531,7 +635,15 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         E: CanonStateSubscriptions + 'static,
     {
         let Self {
-            provider, pool, executor, network, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -542,6 +654,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -553,8 +666,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, block_executor, consensus, .. } =
-            self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            events,
+            block_executor,
+            consensus,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -564,6 +686,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -575,7 +698,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         BE: BlockExecutorProvider,
     {
-        let Self { provider, network, pool, executor, events, evm_config, consensus, .. } = self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            consensus,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -585,6 +718,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
 
@@ -593,8 +727,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         self,
         consensus: C,
     ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, C> {
-        let Self { provider, network, pool, executor, events, evm_config, block_executor, .. } =
-            self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            bad_blocks,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -604,16 +747,29 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         }
     }
+
+    /// Configure the buffer of recently rejected blocks served by `debug_getBadBlocks`.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = Some(bad_blocks);
+        self
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
     RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + HashedStateReader
+        + RawTableReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm<Header = Header>,
@@ -651,6 +807,7 @@ where
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
@@ -667,6 +824,9 @@ where
             eth,
             block_executor,
         );
+        if let Some(bad_blocks) = bad_blocks {
+            registry.bad_blocks = bad_blocks;
+        }
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -726,8 +886,9 @@ where
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         } = self;
-        RpcRegistryInner::new(
+        let mut registry = RpcRegistryInner::new(
             provider,
             pool,
             network,
@@ -738,7 +899,11 @@ where
             evm_config,
             eth,
             block_executor,
-        )
+        );
+        if let Some(bad_blocks) = bad_blocks {
+            registry.bad_blocks = bad_blocks;
+        }
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -762,6 +927,7 @@ where
             evm_config,
             block_executor,
             consensus,
+            bad_blocks,
         } = self;
 
         if !module_config.is_empty() {
@@ -779,6 +945,9 @@ where
                 eth,
                 block_executor,
             );
+            if let Some(bad_blocks) = bad_blocks {
+                registry.bad_blocks = bad_blocks;
+            }
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -803,6 +972,8 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// `debug` namespace settings
+    debug: DebugApiConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -814,8 +985,12 @@ impl RpcModuleConfig {
     }
 
     /// Returns a new RPC module config given the eth namespace config
-    pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+    pub const fn new(
+        eth: EthConfig,
+        flashbots: ValidationApiConfig,
+        debug: DebugApiConfig,
+    ) -> Self {
+        Self { eth, flashbots, debug }
     }
 
     /// Get a reference to the eth namespace config
@@ -834,6 +1009,7 @@ impl RpcModuleConfig {
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
     flashbots: Option<ValidationApiConfig>,
+    debug: Option<DebugApiConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -851,10 +1027,20 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures a custom debug namespace config
+    pub const fn debug(mut self, debug: DebugApiConfig) -> Self {
+        self.debug = Some(debug);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        let Self { eth, flashbots, debug } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            flashbots: flashbots.unwrap_or_default(),
+            debug: debug.unwrap_or_default(),
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -898,6 +1084,8 @@ pub struct RpcRegistryInner<
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Recently rejected blocks, served by the `debug_` namespace
+    bad_blocks: BadBlocks,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -963,6 +1151,7 @@ where
             config,
             modules: Default::default(),
             blocking_pool_guard,
+            bad_blocks: BadBlocks::default(),
             events,
             block_executor,
         }
@@ -1012,6 +1201,11 @@ where
         &self.provider
     }
 
+    /// Returns a reference to the buffer of recently rejected blocks
+    pub const fn bad_blocks(&self) -> &BadBlocks {
+        &self.bad_blocks
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -1051,7 +1245,7 @@ where
     /// Register Admin Namespace
     pub fn register_admin(&mut self) -> &mut Self
     where
-        Network: Peers,
+        Network: Peers + NetworkEventListenerProvider,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -1069,8 +1263,14 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + HashedStateReader
+        + RawTableReader,
+    Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiServer<
             RpcTransaction<EthApi::NetworkTypes>,
@@ -1166,17 +1366,23 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&self) -> OtterscanApi<EthApi> {
+    pub fn otterscan_api(&self) -> OtterscanApi<Provider, EthApi> {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + HashedStateReader
+        + RawTableReader,
+    Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiTypes,
     BlockExecutor: BlockExecutorProvider,
@@ -1225,6 +1431,8 @@ where
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
             self.block_executor.clone(),
+            self.bad_blocks.clone(),
+            self.config.debug,
         )
     }
 
@@ -1264,9 +1472,15 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + HashedStateReader
+        + RawTableReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkEventListenerProvider + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -1365,6 +1579,8 @@ where
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                             self.block_executor.clone(),
+                            self.bad_blocks.clone(),
+                            self.config.debug,
                         )
                         .into_rpc()
                         .into(),
@@ -1374,13 +1590,10 @@ where
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
                             module
-                                .merge(
-                                    EthBundle::new(
-                                        eth_api.clone(),
-                                        self.blocking_pool_guard.clone(),
-                                    )
-                                    .into_rpc(),
-                                )
+                                .merge(EthBundleApiServer::into_rpc(EthBundle::new(
+                                    eth_api.clone(),
+                                    self.blocking_pool_guard.clone(),
+                                )))
                                 .expect("No conflicts");
 
                             module.into()
@@ -1410,7 +1623,11 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
@@ -1425,6 +1642,9 @@ where
                         )
                         .into_rpc()
                         .into(),
+                        // only available on dev nodes, installed by the node launcher which owns
+                        // the local miner
+                        RethRpcModule::Anvil => Methods::default(),
                     })
                     .clone()
             })
@@ -336,7 +336,7 @@ where
     DebugApiClient::raw_block(client, block_id).await.unwrap_err();
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    assert!(DebugApiClient::bad_blocks(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -431,28 +431,28 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction>::search_transactions_before(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction>::search_transactions_after(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    let txs = OtterscanClient::<Transaction>::search_transactions_before(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(txs.txs.is_empty());
+    assert!(txs.last_page);
+
+    let txs = OtterscanClient::<Transaction>::search_transactions_after(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(txs.txs.is_empty());
+    assert!(txs.first_page);
+
     assert!(OtterscanClient::<Transaction>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
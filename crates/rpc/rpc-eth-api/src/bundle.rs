@@ -4,10 +4,11 @@
 
 use alloy_primitives::{Bytes, B256};
 use alloy_rpc_types_mev::{
-    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
-    EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest,
+    CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle, EthCallBundleResponse,
+    EthSendBundle, PrivateTransactionRequest,
 };
 use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::bundle::CancelBundleRequest;
 
 /// A subset of the [EthBundleApi] API interface that only supports `eth_callBundle`.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
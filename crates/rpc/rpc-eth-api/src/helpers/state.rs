@@ -3,7 +3,7 @@
 
 use alloy_consensus::{constants::KECCAK_EMPTY, Header};
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
 use alloy_rpc_types_eth::{Account, EIP1186AccountProofResponse};
 use alloy_serde::JsonStorageKey;
 use futures::Future;
@@ -18,6 +18,7 @@ use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionErro
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
+use std::ops::RangeInclusive;
 
 use crate::{EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt};
 
@@ -28,6 +29,47 @@ pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns the maximum number of blocks into the past for generating state proofs with the
+    /// trie changesets.
+    ///
+    /// Reverting the trie still reads the changesets of every block up to the tip, so the cost of
+    /// a proof grows with its distance to the tip.
+    fn max_trie_changesets_proof_window(&self) -> u64 {
+        0
+    }
+
+    /// Returns the blocks whose trie changesets are recorded, see
+    /// [`TrieChangeSetsReader`](reth_provider::TrieChangeSetsReader).
+    ///
+    /// Proofs for the state before any of these blocks revert the trie instead of recomputing it,
+    /// so they are limited by the [`max_trie_changesets_proof_window`] instead of the
+    /// [`max_proof_window`](Self::max_proof_window).
+    ///
+    /// [`max_trie_changesets_proof_window`]: Self::max_trie_changesets_proof_window
+    fn trie_changesets_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Returns `true` if the trie changesets cover all blocks after the given block, so the trie
+    /// can be reverted to the state at the given block.
+    fn has_trie_changesets_after(&self, block_number: BlockNumber) -> Result<bool, Self::Error> {
+        Ok(self.trie_changesets_range()?.is_some_and(|range| *range.start() <= block_number + 1))
+    }
+
+    /// Returns `true` if a state proof can be generated for the given block, which is the given
+    /// distance away from the tip.
+    fn is_within_proof_window(
+        &self,
+        block_number: BlockNumber,
+        distance: u64,
+    ) -> Result<bool, Self::Error> {
+        if distance <= self.max_proof_window() {
+            return Ok(true)
+        }
+        Ok(distance <= self.max_trie_changesets_proof_window() &&
+            self.has_trie_changesets_after(block_number)?)
+    }
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this will
@@ -111,8 +153,8 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .block_number_for_id(block_id)
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
-            let max_window = self.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
+            let distance = chain_info.best_number.saturating_sub(block_number);
+            if !self.is_within_proof_window(block_number, distance)? {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
@@ -146,8 +188,8 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .block_number_for_id(block_id)
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
-            let max_window = this.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
+            let distance = chain_info.best_number.saturating_sub(block_number);
+            if !this.is_within_proof_window(block_number, distance)? {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
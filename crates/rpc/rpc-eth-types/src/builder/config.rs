@@ -8,6 +8,7 @@ use crate::{
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
     DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
 };
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,9 @@ pub struct EthConfig {
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of blocks into the past for generating state proofs.
     pub eth_proof_window: u64,
+    /// The maximum number of blocks into the past for generating state proofs with the trie
+    /// changesets.
+    pub trie_changesets_proof_window: u64,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
@@ -60,6 +64,7 @@ impl Default for EthConfig {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            trie_changesets_proof_window: DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
             max_tracing_requests: default_max_tracing_requests(),
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
@@ -121,6 +126,13 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum proof window for historical proof generation with the trie
+    /// changesets.
+    pub const fn trie_changesets_proof_window(mut self, window: u64) -> Self {
+        self.trie_changesets_proof_window = window;
+        self
+    }
+
     /// Configures the number of getproof requests
     pub const fn proof_permits(mut self, permits: usize) -> Self {
         self.proof_permits = permits;
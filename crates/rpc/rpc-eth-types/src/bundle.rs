@@ -0,0 +1,15 @@
+//! Request types of the `eth_` bundle methods that differ from the ones in
+//! `alloy_rpc_types_mev`.
+
+use serde::{Deserialize, Serialize};
+
+/// Request for `eth_cancelBundle`.
+///
+/// Bundles are cancelled by the `replacementUuid` they were submitted with, see
+/// <https://docs.flashbots.net/flashbots-auction/searchers/advanced/bundle-cancellations>.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelBundleRequest {
+    /// The replacement UUID of the bundle to cancel.
+    pub replacement_uuid: String,
+}
@@ -9,6 +9,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 pub mod builder;
+pub mod bundle;
 pub mod cache;
 pub mod error;
 pub mod fee_history;
@@ -55,6 +55,10 @@ pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 /// second block time, and a month on a 2 second block time.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 2;
 
+/// The default historical proof window for blocks covered by the trie changesets. Equivalent to
+/// roughly a week of data on a 12 second block time.
+pub const DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW: u64 = 7 * 24 * 60 * 60 / 12;
+
 /// GPO specific constants
 pub mod gas_oracle {
     use alloy_primitives::U256;
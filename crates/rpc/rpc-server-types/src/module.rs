@@ -269,6 +269,8 @@ pub enum RethRpcModule {
     Ots,
     /// `flashbots_` module
     Flashbots,
+    /// `anvil_`, `hardhat_` and `evm_` modules, only available on dev nodes
+    Anvil,
 }
 
 // === impl RethRpcModule ===
@@ -318,6 +320,7 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "flashbots" => Self::Flashbots,
+            "anvil" => Self::Anvil,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
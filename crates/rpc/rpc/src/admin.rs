@@ -2,14 +2,21 @@ use std::sync::Arc;
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
-    EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
-    Ports, ProtocolInfo,
+    EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerEvent, PeerEventType, PeerInfo,
+    PeerNetworkInfo, PeerProtocolInfo, Ports, ProtocolInfo,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage,
+};
 use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
-use reth_network_peers::{id2pk, AnyNode, NodeRecord};
+use reth_network_api::{
+    NetworkEvent, NetworkEventListenerProvider, NetworkInfo, PeerMessageDirection,
+    PeerMessageEvent, Peers,
+};
+use reth_network_peers::{id2pk, AnyNode, NodeRecord, PeerId};
 use reth_network_types::PeerKind;
 use reth_primitives::EthereumHardfork;
 use reth_rpc_api::AdminApiServer;
@@ -35,7 +42,7 @@ impl<N, ChainSpec> AdminApi<N, ChainSpec> {
 #[async_trait]
 impl<N, ChainSpec> AdminApiServer for AdminApi<N, ChainSpec>
 where
-    N: NetworkInfo + Peers + 'static,
+    N: NetworkInfo + Peers + NetworkEventListenerProvider + 'static,
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
 {
     /// Handler for `admin_addPeer`
@@ -180,11 +187,83 @@ where
     }
 
     /// Handler for `admin_peerEvents`
-    async fn subscribe_peer_events(
-        &self,
-        _pending: jsonrpsee::PendingSubscriptionSink,
-    ) -> jsonrpsee::core::SubscriptionResult {
-        Err("admin_peerEvents is not implemented yet".into())
+    async fn subscribe_peer_events(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        // subscribe before accepting so that no events are missed
+        let sessions = self
+            .network
+            .event_listener()
+            .filter_map(|event| futures::future::ready(session_peer_event(event)));
+        let messages = self.network.peer_message_listener().map(message_peer_event);
+        let mut events = futures::stream::select(sessions, messages);
+
+        let sink = pending.accept().await?;
+        loop {
+            tokio::select! {
+                _ = sink.closed() => break,
+                maybe_event = events.next() => {
+                    let Some(event) = maybe_event else { break };
+                    let msg = SubscriptionMessage::from_json(&event)?;
+                    if sink.send(msg).await.is_err() {
+                        break
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the peer identifier in the same format as `admin_peers`.
+fn peer_event_id(peer_id: PeerId) -> String {
+    id2pk(peer_id).map(|pk| pk.to_string()).unwrap_or_else(|_| peer_id.to_string())
+}
+
+/// Converts a session related [`NetworkEvent`] into a geth style [`PeerEvent`].
+///
+/// Returns `None` for events that don't concern a connection.
+fn session_peer_event(event: NetworkEvent) -> Option<PeerEvent> {
+    let event = match event {
+        NetworkEvent::SessionEstablished { peer_id, remote_addr, .. } => PeerEvent {
+            kind: PeerEventType::Add,
+            peer: peer_event_id(peer_id),
+            error: None,
+            protocol: None,
+            msg_code: None,
+            msg_size: None,
+            local_address: None,
+            remote_address: Some(remote_addr),
+        },
+        NetworkEvent::SessionClosed { peer_id, reason } => PeerEvent {
+            kind: PeerEventType::Drop,
+            peer: peer_event_id(peer_id),
+            error: reason.map(|reason| reason.to_string()),
+            protocol: None,
+            msg_code: None,
+            msg_size: None,
+            local_address: None,
+            remote_address: None,
+        },
+        NetworkEvent::PeerAdded(_) | NetworkEvent::PeerRemoved(_) => return None,
+    };
+    Some(event)
+}
+
+/// Converts a [`PeerMessageEvent`] into a geth style [`PeerEvent`].
+fn message_peer_event(event: PeerMessageEvent) -> PeerEvent {
+    let kind = match event.direction {
+        PeerMessageDirection::Sent => PeerEventType::MsgSend,
+        PeerMessageDirection::Received => PeerEventType::MsgRecv,
+    };
+    PeerEvent {
+        kind,
+        peer: peer_event_id(event.peer_id),
+        error: None,
+        protocol: Some("eth".to_string()),
+        msg_code: Some(event.message_id as u64),
+        msg_size: Some(event.size.try_into().unwrap_or(u32::MAX)),
+        local_address: None,
+        remote_address: None,
     }
 }
 
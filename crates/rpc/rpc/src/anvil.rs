@@ -0,0 +1,1294 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use alloy_consensus::{ReceiptEnvelope, Transaction};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_network::TransactionBuilder;
+use alloy_primitives::{Address, Bytes, PrimitiveSignature as Signature, TxKind, B256, U256};
+use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeEnvironment, NodeInfo};
+use alloy_rpc_types_eth::{
+    Block, BlockTransactionsKind, Log, ReceiptWithBloom, TransactionReceipt, TransactionRequest,
+};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::{Mutex, RwLock};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks, Head};
+use reth_engine_local::{LocalMinerHandle, MiningMode};
+use reth_primitives::{
+    Receipt, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TxType,
+};
+use reth_provider::{
+    BlockReaderIdExt, HeaderProvider, HistoryReader, ReceiptProvider, StateProviderFactory,
+    StorageChangeSetReader, TransactionVariant, TransactionsProvider,
+};
+use reth_rpc_api::{AnvilApiServer, AnvilEthApiServer, GanacheApiServer, HardhatApiServer};
+use reth_rpc_eth_api::{
+    helpers::{EthCall, EthTransactions, FullEthApi},
+    FromEthApiError, RpcReceipt, RpcTransaction,
+};
+use reth_rpc_eth_types::{EthApiError, SignError, TransactionSource};
+use reth_rpc_server_types::result::{internal_rpc_err, ToRpcResult};
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{AccountOverride, StateOverrides, TransactionPool};
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+use tracing::trace;
+
+use crate::eth::helpers::types::EthTxBuilder;
+
+/// The maximum number of blocks that can be mined with a single `anvil_mine` or `hardhat_mine`
+/// call.
+pub const MAX_MINED_BLOCKS: u64 = 1_000;
+
+/// `anvil`, `hardhat` and `evm` API implementation for dev nodes.
+///
+/// Blocks are built by the local miner, which is controlled through the [`LocalMinerHandle`].
+/// State modifications are applied as [`StateOverrides`] at the start of the next block, which is
+/// mined right away, so they are part of the canonical chain like any other change. Blocks built
+/// with overrides can't be re-executed, so this API must only be installed on dev nodes.
+///
+/// Transactions of impersonated accounts are sent through the [`AnvilEthApi`], which must replace
+/// the corresponding methods of the `eth` API.
+#[derive(Clone)]
+pub struct AnvilApi<Provider, Pool> {
+    inner: Arc<AnvilApiInner<Provider, Pool>>,
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool>
+where
+    Pool: TransactionPool,
+{
+    /// Creates a new instance of `AnvilApi`.
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        miner: LocalMinerHandle,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let state_overrides = pool.state_overrides();
+        let inner = AnvilApiInner {
+            provider,
+            pool,
+            state_overrides,
+            miner,
+            task_spawner,
+            instance_id: B256::random(),
+            snapshots: Default::default(),
+            impersonation: Default::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns `true` if the account is impersonated with `anvil_impersonateAccount`.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        let impersonation = self.inner.impersonation.read();
+        impersonation.auto || impersonation.accounts.contains(address)
+    }
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Mines the given number of blocks and returns their hashes.
+    ///
+    /// At most [`MAX_MINED_BLOCKS`] can be mined at once.
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<Vec<B256>> {
+        let blocks = blocks.map_or(1, |blocks| blocks.saturating_to());
+        if blocks > MAX_MINED_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "can't mine more than {MAX_MINED_BLOCKS} blocks at once"
+            ))
+            .into())
+        }
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.inner
+            .miner
+            .mine(blocks, interval)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Mines blocks according to the given options and returns their hashes.
+    async fn mine_with_options(&self, opts: Option<MineOptions>) -> RpcResult<Vec<B256>> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks),
+            MineOptions::Timestamp(timestamp) => (timestamp, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.set_next_block_timestamp(timestamp).await?;
+        }
+        self.mine(blocks.map(U256::from), None).await
+    }
+
+    /// Sets the mining mode of the miner.
+    fn set_automine(&self, enabled: bool) -> RpcResult<()> {
+        let mode =
+            if enabled { MiningMode::instant(self.inner.pool.clone()) } else { MiningMode::Manual };
+        self.inner.miner.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Returns `true` if blocks are mined as soon as a transaction is received.
+    async fn automine(&self) -> RpcResult<bool> {
+        self.inner.miner.is_automine().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Applies the change with the given id by mining a block.
+    async fn apply_override(&self, id: u64) -> RpcResult<()> {
+        self.mine(None, None).await?;
+        self.inner.state_overrides.on_applied(id);
+        Ok(())
+    }
+
+    async fn set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        let id = self.inner.state_overrides.set_balance(address, balance).map_err(overrides_err)?;
+        self.apply_override(id).await
+    }
+
+    async fn set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        let id = self.inner.state_overrides.set_code(address, code).map_err(overrides_err)?;
+        self.apply_override(id).await
+    }
+
+    async fn set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = nonce.try_into().map_err(|_| EthApiError::InvalidParams("nonce".into()))?;
+        let id = self.inner.state_overrides.set_nonce(address, nonce).map_err(overrides_err)?;
+        self.apply_override(id).await
+    }
+
+    async fn set_storage_at(&self, address: Address, slot: U256, value: B256) -> RpcResult<()> {
+        let id = self
+            .inner
+            .state_overrides
+            .set_storage(address, slot, value.into())
+            .map_err(overrides_err)?;
+        self.apply_override(id).await
+    }
+
+    fn set_coinbase(&self, coinbase: Address) -> RpcResult<()> {
+        self.inner.state_overrides.set_coinbase(coinbase).map_err(overrides_err)
+    }
+
+    fn set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<()> {
+        let gas_limit =
+            gas_limit.try_into().map_err(|_| EthApiError::InvalidParams("gas limit".into()))?;
+        self.inner.state_overrides.set_gas_limit(gas_limit).map_err(overrides_err)
+    }
+
+    fn set_next_block_base_fee(&self, base_fee: U256) -> RpcResult<()> {
+        let base_fee =
+            base_fee.try_into().map_err(|_| EthApiError::InvalidParams("base fee".into()))?;
+        self.inner.state_overrides.set_next_base_fee(base_fee).map_err(overrides_err)
+    }
+
+    fn set_prev_randao(&self, prev_randao: B256) -> RpcResult<()> {
+        self.inner.state_overrides.set_next_prev_randao(prev_randao).map_err(overrides_err)
+    }
+
+    /// Starts or stops impersonating the account.
+    fn impersonate(&self, address: Address, enabled: bool) {
+        let mut impersonation = self.inner.impersonation.write();
+        if enabled {
+            impersonation.accounts.insert(address);
+        } else {
+            impersonation.accounts.remove(&address);
+        }
+    }
+
+    /// Resets the chain to its genesis block.
+    ///
+    /// All transactions, overrides and snapshots are removed. Forking is not supported.
+    async fn reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        if fork.is_some_and(|fork| fork.json_rpc_url.is_some()) {
+            return Err(EthApiError::Unsupported("forking is not supported").into())
+        }
+
+        let genesis_hash = self.inner.provider.chain_spec().genesis_hash();
+        self.inner
+            .miner
+            .revert_to(genesis_hash)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        let all = self.inner.pool.all_transactions();
+        let transactions =
+            all.pending.iter().chain(all.queued.iter()).map(|tx| *tx.hash()).collect();
+        self.inner.pool.remove_transactions(transactions);
+        self.inner.state_overrides.clear();
+        *self.inner.snapshots.lock() = Default::default();
+        trace!(target: "rpc::anvil", %genesis_hash, "Reset to genesis");
+
+        Ok(())
+    }
+
+    /// Returns the JSON encoded [`DumpedState`] of all accounts that exist in the latest state.
+    ///
+    /// The state is collected on a blocking task, see [`Self::try_dump_state`].
+    async fn dump_state(&self) -> RpcResult<Bytes> {
+        let (tx, rx) = oneshot::channel();
+        let this = Self { inner: self.inner.clone() };
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(this.try_dump_state());
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Collects the [`DumpedState`] of all accounts that exist in the latest state.
+    ///
+    /// The accounts are collected from the genesis allocation and the account and storage history
+    /// of the chain.
+    fn try_dump_state(&self) -> RpcResult<Bytes> {
+        let provider = &self.inner.provider;
+        let latest = provider.best_block_number().to_rpc_result()?;
+
+        let mut slots = BTreeMap::<Address, BTreeSet<B256>>::new();
+        for (address, account) in &provider.chain_spec().genesis().alloc {
+            slots
+                .entry(*address)
+                .or_default()
+                .extend(account.storage.iter().flatten().map(|(slot, _)| *slot));
+        }
+        if latest > 0 {
+            for address in provider.modified_accounts(1..=latest).to_rpc_result()? {
+                slots.entry(address).or_default();
+            }
+            for block in 1..=latest {
+                for (key, entry) in provider.storage_changeset(block).to_rpc_result()? {
+                    slots.entry(key.address()).or_default().insert(entry.key);
+                }
+            }
+        }
+
+        let state = provider.latest().to_rpc_result()?;
+        let mut accounts = BTreeMap::new();
+        for (address, slots) in slots {
+            let Some(account) = state.basic_account(address).to_rpc_result()? else { continue };
+            let code = match account.bytecode_hash {
+                Some(hash) => state
+                    .bytecode_by_hash(hash)
+                    .to_rpc_result()?
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default(),
+                None => Bytes::new(),
+            };
+            let mut storage = BTreeMap::new();
+            for slot in slots {
+                let value = state.storage(address, slot).to_rpc_result()?.unwrap_or_default();
+                if !value.is_zero() {
+                    storage.insert(slot.into(), value);
+                }
+            }
+            accounts.insert(
+                address,
+                DumpedAccount { nonce: account.nonce, balance: account.balance, code, storage },
+            );
+        }
+
+        serde_json::to_vec(&DumpedState { accounts })
+            .map(Into::into)
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Applies the accounts of a JSON encoded [`DumpedState`] by mining a block.
+    async fn load_state(&self, state: Bytes) -> RpcResult<()> {
+        let state: DumpedState = serde_json::from_slice(&state)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        let accounts = state.accounts.into_iter().map(|(address, account)| {
+            let DumpedAccount { nonce, balance, code, storage } = account;
+            let account = AccountOverride {
+                balance: Some(balance),
+                nonce: Some(nonce),
+                code: Some(code),
+                storage: storage.into_iter().collect(),
+            };
+            (address, account)
+        });
+        let id = self.inner.state_overrides.set_accounts(accounts).map_err(overrides_err)?;
+        self.apply_override(id).await
+    }
+
+    async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()> {
+        self.inner
+            .miner
+            .set_next_block_timestamp(timestamp)
+            .await
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()).into())
+    }
+
+    async fn increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.inner
+            .miner
+            .increase_time(seconds.saturating_to())
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    async fn set_time(&self, timestamp: u64) -> RpcResult<i64> {
+        self.inner.miner.set_time(timestamp).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    async fn set_block_timestamp_interval(&self, seconds: Option<u64>) -> RpcResult<bool> {
+        self.inner
+            .miner
+            .set_block_timestamp_interval(seconds)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Takes a snapshot of the current head and returns its id.
+    fn snapshot(&self) -> RpcResult<U256> {
+        let latest = self
+            .inner
+            .provider
+            .latest_header()
+            .to_rpc_result()?
+            .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += U256::from(1);
+        snapshots.snapshots.insert(id, (latest.number, latest.hash()));
+        Ok(id)
+    }
+
+    /// Reverts the chain to the snapshot with the given id.
+    ///
+    /// The snapshot and all snapshots taken after it are removed. Pool transactions that can no
+    /// longer be executed on top of the reverted state are removed, see
+    /// [`Self::remove_invalidated_transactions`].
+    ///
+    /// Returns `false` if the snapshot doesn't exist.
+    async fn revert(&self, id: U256) -> RpcResult<bool> {
+        let (number, hash) = {
+            let mut snapshots = self.inner.snapshots.lock();
+            let Some(snapshot) = snapshots.snapshots.get(&id).copied() else { return Ok(false) };
+            snapshots.snapshots.split_off(&id);
+            snapshot
+        };
+
+        self.inner.miner.revert_to(hash).await.map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        let removed = self.remove_invalidated_transactions(hash)?;
+        trace!(target: "rpc::anvil", %id, number, %hash, removed, "Reverted to snapshot");
+
+        Ok(true)
+    }
+
+    /// Removes the pool transactions and queued transactions of impersonated accounts that were
+    /// invalidated by reverting to the block with the given hash and returns the number of removed
+    /// transactions.
+    ///
+    /// The transactions of the reverted blocks are not reinjected, so a sender whose nonce was
+    /// reverted is left with a nonce gap in the pool. Starting at the sender's nonce in the
+    /// reverted state, all transactions after the first one that either doesn't continue the
+    /// nonce sequence or can't be paid for with the reverted balance are removed. The remaining
+    /// transactions are kept, they are still valid.
+    fn remove_invalidated_transactions(&self, hash: B256) -> RpcResult<usize> {
+        let pool = &self.inner.pool;
+        let overrides = &self.inner.state_overrides;
+        let state = self.inner.provider.state_by_block_hash(hash).to_rpc_result()?;
+
+        // the nonce, cost and hash of the transactions by sender
+        let mut transactions = HashMap::<Address, Vec<(u64, U256, B256)>>::default();
+        for sender in pool.unique_senders() {
+            let entries = pool
+                .get_transactions_by_sender(sender)
+                .into_iter()
+                .map(|tx| (tx.nonce(), *tx.cost(), *tx.hash()));
+            transactions.entry(sender).or_default().extend(entries);
+        }
+        let impersonated = overrides.impersonated_transactions();
+        for tx in &impersonated {
+            let cost = U256::from(tx.max_fee_per_gas()) * U256::from(tx.gas_limit()) + tx.value();
+            transactions.entry(tx.signer()).or_default().push((tx.nonce(), cost, tx.hash()));
+        }
+
+        let mut invalidated = HashSet::new();
+        for (sender, mut transactions) in transactions {
+            let mut next_nonce = state.account_nonce(sender).to_rpc_result()?.unwrap_or_default();
+            let mut balance = state.account_balance(sender).to_rpc_result()?.unwrap_or_default();
+
+            transactions.sort_unstable_by_key(|(nonce, _, _)| *nonce);
+            let mut transactions = transactions.into_iter();
+            for (nonce, cost, hash) in transactions.by_ref() {
+                if nonce != next_nonce || cost > balance {
+                    invalidated.insert(hash);
+                    break
+                }
+                next_nonce += 1;
+                balance -= cost;
+            }
+            invalidated.extend(transactions.map(|(_, _, hash)| hash));
+        }
+
+        let impersonated = impersonated
+            .iter()
+            .map(|tx| tx.hash())
+            .filter(|hash| invalidated.remove(hash))
+            .collect::<Vec<_>>();
+        let removed = overrides.remove_impersonated_transactions(&impersonated).len();
+        Ok(removed + pool.remove_transactions(invalidated.into_iter().collect()).len())
+    }
+
+    /// Removes the transaction from the pool or the queued transactions of impersonated accounts,
+    /// returns `true` if it was found.
+    fn drop_transaction(&self, tx_hash: B256) -> bool {
+        let removed = self.inner.pool.remove_transactions(vec![tx_hash]);
+        let impersonated = self.inner.state_overrides.remove_impersonated_transactions(&[tx_hash]);
+        !removed.is_empty() || !impersonated.is_empty()
+    }
+
+    /// Returns the RPC representation of the block with the given hash.
+    fn block(&self, hash: B256) -> RpcResult<Block> {
+        let provider = &self.inner.provider;
+        let block = provider
+            .block_with_senders(hash.into(), TransactionVariant::WithHash)
+            .to_rpc_result()?
+            .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+        let total_difficulty = provider
+            .header_td(&hash)
+            .to_rpc_result()?
+            .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+        reth_rpc_types_compat::block::from_block(
+            block,
+            total_difficulty,
+            BlockTransactionsKind::Full,
+            Some(hash),
+            &EthTxBuilder,
+        )
+        .map_err(Into::into)
+    }
+
+    fn metadata(&self) -> RpcResult<Metadata> {
+        let provider = &self.inner.provider;
+        let latest = provider.latest_header().to_rpc_result()?.unwrap_or_default();
+        Ok(Metadata {
+            client_version: format!("reth/v{}", env!("CARGO_PKG_VERSION")),
+            chain_id: provider.chain_spec().chain().id(),
+            instance_id: self.inner.instance_id,
+            latest_block_number: latest.number,
+            latest_block_hash: latest.hash(),
+            forked_network: None,
+            snapshots: self.inner.snapshots.lock().snapshots.clone(),
+        })
+    }
+
+    fn node_info(&self) -> RpcResult<NodeInfo> {
+        let provider = &self.inner.provider;
+        let chain_spec = provider.chain_spec();
+        let latest = provider.latest_header().to_rpc_result()?.unwrap_or_default();
+        let head = Head {
+            number: latest.number,
+            hash: latest.hash(),
+            difficulty: latest.difficulty,
+            total_difficulty: provider
+                .header_td(&latest.hash())
+                .to_rpc_result()?
+                .unwrap_or_default(),
+            timestamp: latest.timestamp,
+        };
+        let hard_fork = chain_spec
+            .forks_iter()
+            .filter(|(_, condition)| condition.active_at_head(&head))
+            .last()
+            .map(|(fork, _)| fork.name().to_string())
+            .unwrap_or_default();
+        let base_fee = U256::from(latest.base_fee_per_gas.unwrap_or_default());
+
+        Ok(NodeInfo {
+            current_block_number: latest.number,
+            current_block_timestamp: latest.timestamp,
+            current_block_hash: latest.hash(),
+            hard_fork,
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee,
+                chain_id: chain_spec.chain().id(),
+                gas_limit: U256::from(latest.gas_limit),
+                gas_price: base_fee,
+            },
+            fork_config: Default::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> AnvilApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, "Serving anvil_impersonateAccount");
+        self.impersonate(address, true);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, "Serving anvil_stopImpersonatingAccount");
+        self.impersonate(address, false);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_autoImpersonateAccount");
+        self.inner.impersonation.write().auto = enabled;
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_getAutomine");
+        self.automine().await
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?blocks, ?interval, "Serving anvil_mine");
+        self.mine(blocks, interval).await.map(|_| ())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_setAutomine");
+        self.set_automine(enabled)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", interval, "Serving anvil_setIntervalMining");
+        let mode = if interval == 0 {
+            MiningMode::Manual
+        } else {
+            MiningMode::interval(Duration::from_secs(interval))
+        };
+        self.inner.miner.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        trace!(target: "rpc::anvil", %tx_hash, "Serving anvil_dropTransaction");
+        Ok(self.drop_transaction(tx_hash).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?fork, "Serving anvil_reset");
+        self.reset(fork).await
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("forking is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, %balance, "Serving anvil_setBalance");
+        self.set_balance(address, balance).await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, "Serving anvil_setCode");
+        self.set_code(address, code).await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, %nonce, "Serving anvil_setNonce");
+        self.set_nonce(address, nonce).await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", %address, %slot, %value, "Serving anvil_setStorageAt");
+        self.set_storage_at(address, slot, value).await.map(|_| true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, "Serving anvil_setCoinbase");
+        self.set_coinbase(address)
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("the chain id is set by the chain spec").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        // logging is configured on startup
+        Ok(())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(MIN_GAS_PRICE_UNSUPPORTED.into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %base_fee, "Serving anvil_setNextBlockBaseFeePerGas");
+        self.set_next_block_base_fee(base_fee)
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        trace!(target: "rpc::anvil", timestamp, "Serving anvil_setTime");
+        self.set_time(timestamp).await.map(|offset| offset.unsigned_abs())
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        trace!(target: "rpc::anvil", "Serving anvil_dumpState");
+        self.dump_state().await
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_loadState");
+        self.load_state(state).await.map(|_| true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        trace!(target: "rpc::anvil", "Serving anvil_nodeInfo");
+        self.node_info()
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        trace!(target: "rpc::anvil", "Serving anvil_metadata");
+        self.metadata()
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        trace!(target: "rpc::anvil", "Serving anvil_snapshot");
+        self.snapshot()
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", %id, "Serving anvil_revert");
+        self.revert(id).await
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        trace!(target: "rpc::anvil", %seconds, "Serving anvil_increaseTime");
+        self.increase_time(seconds).await
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setNextBlockTimestamp");
+        self.set_next_block_timestamp(seconds).await
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", %gas_limit, "Serving anvil_setBlockGasLimit");
+        self.set_block_gas_limit(gas_limit).map(|_| true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setBlockTimestampInterval");
+        self.set_block_timestamp_interval(Some(seconds)).await.map(|_| ())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_removeBlockTimestampInterval");
+        self.set_block_timestamp_interval(None).await
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        trace!(target: "rpc::anvil", ?opts, "Serving anvil_mine_detailed");
+        let hashes = self.mine_with_options(opts).await?;
+        hashes.into_iter().map(|hash| self.block(hash)).collect()
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        // traces are always available through the `debug` and `trace` namespaces
+        Ok(())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", %address, "Serving anvil_removePoolTransactions");
+        self.inner.pool.remove_transactions_by_sender(address);
+        let overrides = &self.inner.state_overrides;
+        let impersonated = overrides
+            .impersonated_transactions()
+            .into_iter()
+            .filter(|tx| tx.signer() == address)
+            .map(|tx| tx.hash())
+            .collect::<Vec<_>>();
+        overrides.remove_impersonated_transactions(&impersonated);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> HardhatApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `hardhat_dropTransaction`
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        trace!(target: "rpc::hardhat", %tx_hash, "Serving hardhat_dropTransaction");
+        Ok(self.drop_transaction(tx_hash))
+    }
+
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, "Serving hardhat_impersonateAccount");
+        self.impersonate(address, true);
+        Ok(())
+    }
+
+    /// Handler for `hardhat_getAutomine`
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::hardhat", "Serving hardhat_getAutomine");
+        self.automine().await
+    }
+
+    /// Handler for `hardhat_metadata`
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        trace!(target: "rpc::hardhat", "Serving hardhat_metadata");
+        self.metadata()
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", ?blocks, ?interval, "Serving hardhat_mine");
+        self.mine(blocks, interval).await.map(|_| ())
+    }
+
+    /// Handler for `hardhat_reset`
+    async fn hardhat_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", ?fork, "Serving hardhat_reset");
+        self.reset(fork).await
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, %balance, "Serving hardhat_setBalance");
+        self.set_balance(address, balance).await
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, "Serving hardhat_setCode");
+        self.set_code(address, code).await
+    }
+
+    /// Handler for `hardhat_setCoinbase`
+    async fn hardhat_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, "Serving hardhat_setCoinbase");
+        self.set_coinbase(address)
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`
+    async fn hardhat_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        // logging is configured on startup
+        Ok(())
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`
+    async fn hardhat_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(MIN_GAS_PRICE_UNSUPPORTED.into())
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %base_fee_per_gas, "Serving hardhat_setNextBlockBaseFeePerGas");
+        self.set_next_block_base_fee(base_fee_per_gas)
+    }
+
+    /// Handler for `hardhat_setPrevRandao`
+    async fn hardhat_set_prev_randao(&self, prev_randao: B256) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %prev_randao, "Serving hardhat_setPrevRandao");
+        self.set_prev_randao(prev_randao)
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, %nonce, "Serving hardhat_setNonce");
+        self.set_nonce(address, nonce).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, %slot, %value, "Serving hardhat_setStorageAt");
+        self.set_storage_at(address, slot, value).await
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", %address, "Serving hardhat_stopImpersonatingAccount");
+        self.impersonate(address, false);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> GanacheApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        trace!(target: "rpc::evm", %seconds, "Serving evm_increaseTime");
+        self.increase_time(seconds).await
+    }
+
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<Block> {
+        trace!(target: "rpc::evm", ?opts, "Serving evm_mine");
+        let hashes = self.mine_with_options(opts).await?;
+        let hash = hashes.last().copied().ok_or_else(|| internal_rpc_err("no block was mined"))?;
+        self.block(hash)
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::evm", %snapshot_id, "Serving evm_revert");
+        self.revert(snapshot_id).await
+    }
+
+    /// Handler for `evm_setTime`
+    async fn evm_set_time(&self, timestamp: u64) -> RpcResult<bool> {
+        trace!(target: "rpc::evm", timestamp, "Serving evm_setTime");
+        self.set_time(timestamp).await.map(|_| true)
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        trace!(target: "rpc::evm", "Serving evm_snapshot");
+        self.snapshot()
+    }
+}
+
+/// `eth` API methods of dev nodes that handle the transactions of impersonated accounts.
+///
+/// Transactions of impersonated accounts can't be signed, so they are not submitted to the pool.
+/// They are queued with their sender in the [`StateOverrides`] instead, from where the payload
+/// builder includes them in the next block. Mined transactions of impersonated accounts have a
+/// placeholder signature, so their sender is read from the database. All other requests are
+/// forwarded to the `eth` API.
+#[derive(Clone)]
+pub struct AnvilEthApi<Provider, Pool, EthApi> {
+    /// The `anvil` API that tracks the impersonated accounts.
+    anvil: AnvilApi<Provider, Pool>,
+    /// The `eth` API that handles all other requests.
+    eth_api: EthApi,
+}
+
+impl<Provider, Pool, EthApi> AnvilEthApi<Provider, Pool, EthApi> {
+    /// Creates a new instance of `AnvilEthApi`.
+    pub const fn new(anvil: AnvilApi<Provider, Pool>, eth_api: EthApi) -> Self {
+        Self { anvil, eth_api }
+    }
+}
+
+impl<Provider, Pool, EthApi> AnvilEthApi<Provider, Pool, EthApi>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+    EthApi: FullEthApi + 'static,
+{
+    /// Queues the transaction of an impersonated account and returns its hash.
+    ///
+    /// The nonce defaults to the next nonce after the account's queued transactions. A block is
+    /// mined right away if automine is enabled.
+    async fn send_impersonated_transaction(
+        &self,
+        from: Address,
+        mut request: TransactionRequest,
+    ) -> RpcResult<B256> {
+        let overrides = &self.anvil.inner.state_overrides;
+        let provider = &self.anvil.inner.provider;
+
+        if request.nonce.is_none() {
+            let nonce = provider
+                .latest()
+                .to_rpc_result()?
+                .account_nonce(from)
+                .to_rpc_result()?
+                .unwrap_or_default();
+            let queued = overrides
+                .impersonated_transactions()
+                .into_iter()
+                .filter(|tx| tx.signer() == from)
+                .map(|tx| tx.nonce() + 1)
+                .max();
+            request.nonce = Some(queued.map_or(nonce, |queued| queued.max(nonce)));
+        }
+        request.chain_id = Some(provider.chain_spec().chain().id());
+        if request.gas.is_none() {
+            let gas =
+                EthCall::estimate_gas_at(&self.eth_api, request.clone(), BlockId::pending(), None)
+                    .await
+                    .map_err(Into::into)?;
+            request.set_gas_limit(gas.to());
+        }
+
+        let transaction = request
+            .build_typed_tx()
+            .map_err(|_| EthApiError::Signing(SignError::InvalidTransactionRequest))?;
+        // `r` is zero so that no sender can be recovered, `s` is the sender so that the same
+        // transaction of different senders has different hashes
+        let signature = Signature::new(U256::ZERO, U256::from_be_slice(from.as_slice()), false);
+        let transaction =
+            TransactionSigned::new_unhashed(transaction.into(), signature).with_signer(from);
+
+        let hash = overrides.add_impersonated_transaction(transaction).map_err(overrides_err)?;
+        trace!(target: "rpc::anvil", %from, %hash, "Queued impersonated transaction");
+        if self.anvil.automine().await? {
+            self.anvil.mine(None, None).await?;
+        }
+
+        Ok(hash)
+    }
+
+    /// Returns the mined transaction of an impersonated account with the given hash.
+    ///
+    /// Returns `None` if the transaction doesn't exist or its sender can be recovered from its
+    /// signature.
+    async fn mined_impersonated_transaction(
+        &self,
+        hash: B256,
+    ) -> Result<Option<(TransactionSignedEcRecovered, TransactionMeta)>, EthApi::Error> {
+        self.eth_api
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let Some((tx, meta)) = provider
+                    .transaction_by_hash_with_meta(hash)
+                    .map_err(EthApi::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                if tx.recover_signer_unchecked().is_some() {
+                    return Ok(None)
+                }
+                let Some(id) =
+                    provider.transaction_id(hash).map_err(EthApi::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                let sender = provider
+                    .transaction_sender(id)
+                    .map_err(EthApi::Error::from_eth_err)?
+                    .ok_or(EthApiError::InvalidTransactionSignature)
+                    .map_err(EthApi::Error::from_eth_err)?;
+                Ok(Some((tx.with_signer(sender), meta)))
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool, EthApi>
+    AnvilEthApiServer<RpcTransaction<EthApi::NetworkTypes>, RpcReceipt<EthApi::NetworkTypes>>
+    for AnvilEthApi<Provider, Pool, EthApi>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + HistoryReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + 'static,
+    EthApi: FullEthApi + 'static,
+{
+    /// Handler for `eth_sendTransaction`
+    async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<B256> {
+        trace!(target: "rpc::anvil", ?request, "Serving eth_sendTransaction");
+        match request.from {
+            Some(from) if self.anvil.is_impersonated(&from) => {
+                self.send_impersonated_transaction(from, request).await
+            }
+            _ => Ok(EthTransactions::send_transaction(&self.eth_api, request)
+                .await
+                .map_err(Into::into)?),
+        }
+    }
+
+    /// Handler for `eth_getTransactionByHash`
+    async fn transaction_by_hash(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<RpcTransaction<EthApi::NetworkTypes>>> {
+        trace!(target: "rpc::anvil", ?hash, "Serving eth_getTransactionByHash");
+        let tx = if let Some(tx) = self.anvil.inner.state_overrides.impersonated_transaction(&hash)
+        {
+            Some(TransactionSource::Pool(tx))
+        } else if let Some((transaction, meta)) =
+            self.mined_impersonated_transaction(hash).await.map_err(Into::into)?
+        {
+            Some(TransactionSource::Block {
+                transaction,
+                index: meta.index,
+                block_hash: meta.block_hash,
+                block_number: meta.block_number,
+                base_fee: meta.base_fee,
+            })
+        } else {
+            EthTransactions::transaction_by_hash(&self.eth_api, hash).await.map_err(Into::into)?
+        };
+        Ok(tx
+            .map(|tx| tx.into_transaction(self.eth_api.tx_resp_builder()))
+            .transpose()
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `eth_getTransactionReceipt`
+    async fn transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<RpcReceipt<EthApi::NetworkTypes>>> {
+        trace!(target: "rpc::anvil", ?hash, "Serving eth_getTransactionReceipt");
+        if self.anvil.inner.state_overrides.impersonated_transaction(&hash).is_some() {
+            return Ok(None)
+        }
+        let Some((tx, meta)) =
+            self.mined_impersonated_transaction(hash).await.map_err(Into::into)?
+        else {
+            return EthTransactions::transaction_receipt(&self.eth_api, hash)
+                .await
+                .map_err(Into::into)
+        };
+
+        let Some(receipt) = self.eth_api.provider().receipt_by_hash(hash).to_rpc_result()? else {
+            return Ok(None)
+        };
+        let all_receipts = self
+            .eth_api
+            .cache()
+            .get_receipts(meta.block_hash)
+            .await
+            .to_rpc_result()?
+            .ok_or(EthApiError::HeaderNotFound(meta.block_hash.into()))?;
+        let receipt = impersonated_receipt(&tx, meta, &receipt, &all_receipts);
+
+        // the receipt of the network is a superset of the ethereum receipt
+        serde_json::to_value(receipt)
+            .and_then(serde_json::from_value)
+            .map(Some)
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+impl<Provider, Pool> std::fmt::Debug for AnvilApi<Provider, Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Pool, EthApi> std::fmt::Debug for AnvilEthApi<Provider, Pool, EthApi> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilEthApi").finish_non_exhaustive()
+    }
+}
+
+struct AnvilApiInner<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The transaction pool.
+    pool: Pool,
+    /// The pending state overrides, shared with the payload builder through the pool.
+    state_overrides: StateOverrides,
+    /// Handle to the local miner.
+    miner: LocalMinerHandle,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Random id of this instance, reported by `anvil_metadata`.
+    instance_id: B256,
+    /// The snapshots that can be reverted to.
+    snapshots: Mutex<Snapshots>,
+    /// The impersonated accounts.
+    impersonation: RwLock<Impersonation>,
+}
+
+/// Snapshots of the chain, taken with `evm_snapshot`.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// The id of the next snapshot.
+    next_id: U256,
+    /// The number and hash of the head block for each snapshot.
+    snapshots: BTreeMap<U256, (u64, B256)>,
+}
+
+/// The accounts impersonated with `anvil_impersonateAccount`.
+#[derive(Debug, Default)]
+struct Impersonation {
+    /// The impersonated accounts.
+    accounts: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    auto: bool,
+}
+
+/// The state returned by `anvil_dumpState` and accepted by `anvil_loadState`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DumpedState {
+    /// The accounts by address.
+    pub accounts: BTreeMap<Address, DumpedAccount>,
+}
+
+/// An account of a [`DumpedState`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DumpedAccount {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The code of the account.
+    pub code: Bytes,
+    /// The non-zero storage slots of the account.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// The error of the `setMinGasPrice` methods, the pool only supports EIP-1559 fee markets.
+const MIN_GAS_PRICE_UNSUPPORTED: EthApiError =
+    EthApiError::Unsupported("the min gas price is not supported when EIP-1559 is active");
+
+/// Converts a [`StateOverridesError`](reth_transaction_pool::StateOverridesError) into an RPC
+/// error.
+fn overrides_err(
+    err: reth_transaction_pool::StateOverridesError,
+) -> jsonrpsee::types::ErrorObject<'static> {
+    EthApiError::InvalidParams(err.to_string()).into()
+}
+
+/// Builds the receipt of a mined transaction of an impersonated account.
+///
+/// Same as [`EthReceiptBuilder`](reth_rpc_eth_types::EthReceiptBuilder), except that the sender
+/// is not recovered from the placeholder signature.
+fn impersonated_receipt(
+    transaction: &TransactionSignedEcRecovered,
+    meta: TransactionMeta,
+    receipt: &Receipt,
+    all_receipts: &[Receipt],
+) -> TransactionReceipt {
+    let from = transaction.signer();
+    let prev_receipts = &all_receipts[..(meta.index as usize).min(all_receipts.len())];
+    let gas_used = receipt.cumulative_gas_used -
+        prev_receipts.last().map_or(0, |prev_receipt| prev_receipt.cumulative_gas_used);
+    let num_logs = prev_receipts.iter().map(|prev_receipt| prev_receipt.logs.len()).sum::<usize>();
+
+    let logs = receipt
+        .logs
+        .iter()
+        .enumerate()
+        .map(|(tx_log_idx, log)| Log {
+            inner: log.clone(),
+            block_hash: Some(meta.block_hash),
+            block_number: Some(meta.block_number),
+            block_timestamp: Some(meta.timestamp),
+            transaction_hash: Some(meta.tx_hash),
+            transaction_index: Some(meta.index),
+            log_index: Some((num_logs + tx_log_idx) as u64),
+            removed: false,
+        })
+        .collect();
+    let receipt_with_bloom = ReceiptWithBloom {
+        receipt: alloy_rpc_types_eth::Receipt {
+            status: receipt.success.into(),
+            cumulative_gas_used: receipt.cumulative_gas_used as u128,
+            logs,
+        },
+        logs_bloom: receipt.bloom_slow(),
+    };
+    let inner = match receipt.tx_type {
+        TxType::Legacy => ReceiptEnvelope::Legacy(receipt_with_bloom),
+        TxType::Eip2930 => ReceiptEnvelope::Eip2930(receipt_with_bloom),
+        TxType::Eip1559 => ReceiptEnvelope::Eip1559(receipt_with_bloom),
+        TxType::Eip4844 => ReceiptEnvelope::Eip4844(receipt_with_bloom),
+        TxType::Eip7702 => ReceiptEnvelope::Eip7702(receipt_with_bloom),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    };
+
+    let (contract_address, to) = match transaction.kind() {
+        TxKind::Create => (Some(from.create(transaction.nonce())), None),
+        TxKind::Call(to) => (None, Some(to)),
+    };
+    let blob_gas_used = transaction.blob_gas_used();
+
+    TransactionReceipt {
+        inner,
+        transaction_hash: meta.tx_hash,
+        transaction_index: Some(meta.index),
+        block_hash: Some(meta.block_hash),
+        block_number: Some(meta.block_number),
+        from,
+        to,
+        gas_used: gas_used as u128,
+        contract_address,
+        effective_gas_price: transaction.effective_gas_price(meta.base_fee),
+        blob_gas_price: blob_gas_used
+            .and_then(|_| meta.excess_blob_gas.map(revm_primitives::calc_blob_gasprice)),
+        blob_gas_used: blob_gas_used.map(u128::from),
+        authorization_list: transaction.authorization_list().map(|list| list.to_vec()),
+    }
+}
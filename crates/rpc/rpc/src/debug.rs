@@ -1,46 +1,77 @@
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{hex, Address, BlockNumber, Bytes, B256, U256, U64};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, Bundle, StateContext,
+    TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
-    call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
+    call::FlatCallFrame, BlockTraceResult, DefaultFrame, FourByteFrame, GethDebugBuiltInTracerType,
     GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{Stream, StreamExt, TryStreamExt};
+use jsonrpsee::{
+    core::RpcResult, server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink,
+};
 use reth_chainspec::EthereumHardforks;
+use reth_db::Tables;
 use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     ConfigureEvmEnv,
 };
 use reth_primitives::{Block, BlockExt, SealedBlockWithSenders};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProofProvider, StateProviderFactory,
-    TransactionVariant,
+    BadBlocks, BlockReaderIdExt, ChainSpecProvider, HashedAccountStorage, HashedStateReader,
+    HeaderProvider, HistoryReader, RawTableReader, StateProofProvider, StateProviderFactory,
+    StateRootProvider, TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{BadBlockInfo, DebugApiServer, DumpAccount, StateDump};
 use reth_rpc_eth_api::{
     helpers::{EthApiSpec, EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_types_compat::block::from_block_with_tx_hashes;
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_trie::HashedPostState;
 use revm::{
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write},
+    ops::RangeInclusive,
+    path::Path,
+    sync::Arc,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::debug;
+
+/// The number of blocks that are traced concurrently by `debug_traceChain`.
+const TRACE_CHAIN_CONCURRENCY: usize = 16;
+
+/// The maximum number of blocks traced by a single `debug_traceChain` call, since all traces are
+/// collected in memory. `debug_subscribeTraceChain` streams the traces and isn't limited.
+const TRACE_CHAIN_MAX_BLOCKS: u64 = 100;
+
+/// The maximum number of accounts returned by a single `debug_accountRange` or `debug_dumpBlock`
+/// call.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of storage slots returned per account by `debug_accountRange` and
+/// `debug_dumpBlock`.
+const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 1024;
 
 /// `debug` API implementation.
 ///
@@ -58,9 +89,17 @@ impl<Provider, Eth, BlockExecutor> DebugApi<Provider, Eth, BlockExecutor> {
         eth: Eth,
         blocking_task_guard: BlockingTaskGuard,
         block_executor: BlockExecutor,
+        bad_blocks: BadBlocks,
+        config: DebugApiConfig,
     ) -> Self {
-        let inner =
-            Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard, block_executor });
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            block_executor,
+            bad_blocks,
+            config,
+        });
         Self { inner }
     }
 
@@ -78,6 +117,9 @@ where
         + HeaderProvider
         + ChainSpecProvider<ChainSpec: EthereumHardforks>
         + StateProviderFactory
+        + HistoryReader
+        + HashedStateReader
+        + RawTableReader
         + 'static,
     Eth: EthApiTypes + TraceExt + 'static,
     BlockExecutor: BlockExecutorProvider,
@@ -195,6 +237,95 @@ where
         .await
     }
 
+    /// Replays a block that was rejected as invalid and returns the trace of each transaction.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self
+            .inner
+            .bad_blocks
+            .get(&block_hash)
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?
+            .block;
+
+        let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
+
+        let block = block
+            .try_seal_with_senders()
+            .map_err(|_| EthApiError::InvalidTransactionSignature)
+            .map_err(Eth::Error::from_eth_err)?;
+
+        self.trace_block(Arc::new(block), cfg, block_env, opts).await
+    }
+
+    /// Returns a stream that traces all blocks between `start_exclusive` and `end_inclusive`.
+    ///
+    /// Blocks are traced concurrently on the blocking task pool, but the results are yielded in
+    /// block order.
+    pub fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> Result<impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static, Eth::Error>
+    {
+        let blocks = self.trace_chain_range(start_exclusive, end_inclusive)?;
+        Ok(self.trace_chain_blocks(blocks, opts))
+    }
+
+    /// Returns the range of blocks between `start_exclusive` and `end_inclusive`.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<RangeInclusive<BlockNumber>, Eth::Error> {
+        let provider = &self.inner.provider;
+        let start = provider
+            .convert_block_number(start_exclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(start_exclusive.into()))?;
+        let end = provider
+            .convert_block_number(end_inclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(end_inclusive.into()))?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block {start} must be lower than end block {end}"
+            ))
+            .into())
+        }
+
+        Ok(start + 1..=end)
+    }
+
+    /// Returns a stream that traces the given blocks, see [`Self::debug_trace_chain`].
+    fn trace_chain_blocks(
+        &self,
+        blocks: RangeInclusive<BlockNumber>,
+        opts: GethDebugTracingOptions,
+    ) -> impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static {
+        let this = self.clone();
+        futures::stream::iter(blocks)
+            .map(move |number| {
+                let this = this.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .inner
+                        .provider
+                        .block_hash(number)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+                }
+            })
+            .buffered(TRACE_CHAIN_CONCURRENCY)
+    }
+
     /// Replays a block and returns the trace of each transaction.
     pub async fn debug_trace_block(
         &self,
@@ -631,6 +762,235 @@ where
             .await
     }
 
+    /// Re-executes the block with the given hash and returns the state root after each
+    /// transaction.
+    ///
+    /// The block is looked up in the canonical chain, sidechains and the rejected bad blocks.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().block_with_senders(block_hash.into()).await? {
+            Some(block) => block,
+            None => {
+                let block = self
+                    .inner
+                    .bad_blocks
+                    .get(&block_hash)
+                    .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?
+                    .block
+                    .try_seal_with_senders()
+                    .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                Arc::new(block)
+            }
+        };
+        let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state_provider| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state_provider))
+                    .with_bundle_update()
+                    .build();
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &cfg, &block_env)?;
+
+                let mut roots = Vec::with_capacity(block.body.transactions.len());
+                for (signer, tx) in block.transactions_with_sender() {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            this.eth_api().evm_config().tx_env(tx, *signer),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                    db.merge_transitions(BundleRetention::PlainState);
+
+                    let hashed_state = HashedPostState::from_bundle_state(&db.bundle_state.state);
+                    roots.push(
+                        state_provider
+                            .state_root(hashed_state)
+                            .map_err(Eth::Error::from_eth_err)?,
+                    );
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns all accounts that were modified in the blocks `start + 1..=end`, or in block
+    /// `start` if no end block is given.
+    pub fn debug_get_modified_accounts(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end {
+            None => start..=start,
+            Some(end) if start < end => start + 1..=end,
+            Some(end) => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block ({start}) must be less than end block ({end})"
+                ))
+                .into())
+            }
+        };
+
+        let provider = &self.inner.provider;
+        let last = *range.end();
+        provider
+            .block_hash(last)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(last.into()))?;
+
+        let accounts = provider.modified_accounts(range).map_err(Eth::Error::from_eth_err)?;
+        Ok(accounts.into_iter().collect())
+    }
+
+    /// Dumps up to `limit` accounts of the state at the given block, starting at the `start`
+    /// hashed address.
+    ///
+    /// At most [`ACCOUNT_RANGE_MAX_STORAGE_SLOTS`] storage slots are returned per account.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: B256,
+        limit: usize,
+        nocode: bool,
+        nostorage: bool,
+    ) -> Result<StateDump, Eth::Error> {
+        let header = self
+            .inner
+            .provider
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let provider = &this.inner.provider;
+
+                // fetch one more account to find out where the next page starts
+                let storage_limit = if nostorage { 0 } else { ACCOUNT_RANGE_MAX_STORAGE_SLOTS };
+                let mut accounts = provider
+                    .hashed_accounts_range(
+                        header.number,
+                        start,
+                        limit.saturating_add(1),
+                        storage_limit,
+                    )
+                    .map_err(Eth::Error::from_eth_err)?;
+                let next = if accounts.len() > limit {
+                    accounts.pop().map(|account| account.hashed_address)
+                } else {
+                    None
+                };
+
+                // bytecode is not versioned, so it can be read from the latest state
+                let state = provider.latest().map_err(Eth::Error::from_eth_err)?;
+
+                let mut dump = BTreeMap::new();
+                for HashedAccountStorage {
+                    hashed_address,
+                    account,
+                    storage_root,
+                    storage,
+                    next_storage_slot,
+                } in accounts
+                {
+                    let code = match account.bytecode_hash {
+                        Some(code_hash) if !nocode => state
+                            .bytecode_by_hash(code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes()),
+                        _ => None,
+                    };
+                    let account = DumpAccount {
+                        balance: account.balance,
+                        nonce: account.nonce,
+                        root: storage_root,
+                        code_hash: account.get_bytecode_hash(),
+                        code,
+                        storage: (!nostorage).then(|| storage.into_iter().collect()),
+                        address_hash: hashed_address,
+                        next_storage_key: next_storage_slot,
+                    };
+                    dump.insert(StateDump::account_key(hashed_address), account);
+                }
+
+                Ok(StateDump { root: header.state_root, accounts: dump, next })
+            })
+            .await
+    }
+
+    /// Returns the value stored under a geth database key, see `debug_dbGet`.
+    ///
+    /// The keys of canonical hashes, headers, header numbers, total difficulties and code are
+    /// mapped to the corresponding data, the values are encoded like geth encodes them.
+    fn db_get(&self, key: &[u8]) -> RpcResult<Option<Bytes>> {
+        let provider = &self.inner.provider;
+        let number = |key: &[u8]| {
+            let mut number = [0; 8];
+            number.copy_from_slice(&key[..8]);
+            BlockNumber::from_be_bytes(number)
+        };
+        let Some((prefix, key)) = key.split_first() else {
+            return Err(EthApiError::InvalidParams("empty key".to_string()).into())
+        };
+
+        let value = match (prefix, key.len()) {
+            // `h` + number + `n`: canonical hash
+            (b'h', 9) if key[8] == b'n' => provider
+                .block_hash(number(key))
+                .to_rpc_result()?
+                .map(|hash| Bytes::copy_from_slice(hash.as_slice())),
+            // `h` + number + hash: header
+            (b'h', 40) => provider
+                .header(&B256::from_slice(&key[8..]))
+                .to_rpc_result()?
+                .filter(|header| header.number == number(key))
+                .map(|header| alloy_rlp::encode(header).into()),
+            // `h` + number + hash + `t`: total difficulty
+            (b'h', 41) if key[40] == b't' => {
+                let hash = B256::from_slice(&key[8..40]);
+                if provider.block_number(hash).to_rpc_result()? == Some(number(key)) {
+                    provider
+                        .header_td(&hash)
+                        .to_rpc_result()?
+                        .map(|td| alloy_rlp::encode(td).into())
+                } else {
+                    None
+                }
+            }
+            // `H` + hash: header number
+            (b'H', 32) => provider
+                .block_number(B256::from_slice(key))
+                .to_rpc_result()?
+                .map(|number| Bytes::copy_from_slice(&number.to_be_bytes())),
+            // `c` + hash: code
+            (b'c', 32) => provider
+                .latest()
+                .to_rpc_result()?
+                .bytecode_by_hash(B256::from_slice(key))
+                .to_rpc_result()?
+                .map(|code| code.original_bytes()),
+            _ => {
+                return Err(EthApiError::Unsupported(
+                    "only the keys of canonical hashes, headers, header numbers, total \
+                     difficulties and code are supported",
+                )
+                .into())
+            }
+        };
+        Ok(value)
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// It optionally takes fused inspector ([`TracingInspector::fused`]) to avoid re-creating the
@@ -797,6 +1157,9 @@ where
         + HeaderProvider
         + ChainSpecProvider<ChainSpec: EthereumHardforks>
         + StateProviderFactory
+        + HistoryReader
+        + HashedStateReader
+        + RawTableReader
         + 'static,
     Eth: EthApiSpec + EthTransactions + TraceExt + 'static,
     BlockExecutor: BlockExecutorProvider,
@@ -872,17 +1235,87 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlockInfo>> {
+        let mut blocks = Vec::new();
+        for bad in self.inner.bad_blocks.blocks() {
+            let hash = bad.hash();
+            let rlp = alloy_rlp::encode(&bad.block).into();
+            let Some(block) = bad.block.seal_with_senders::<Block>() else { continue };
+            let mut block = from_block_with_tx_hashes(block.unseal(), U256::ZERO, Some(hash));
+            // bad blocks are never part of the chain, so there is no total difficulty
+            block.header.total_difficulty = None;
+            blocks.push(BadBlockInfo { hash, block, rlp, reason: bad.error });
+        }
+        Ok(blocks)
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        let blocks = self.trace_chain_range(start_exclusive, end_inclusive).map_err(Into::into)?;
+        if blocks.end() - blocks.start() >= TRACE_CHAIN_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {TRACE_CHAIN_MAX_BLOCKS} blocks, use debug_subscribeTraceChain for larger ranges"
+            ))
+            .into())
+        }
+
+        self.trace_chain_blocks(blocks, opts.unwrap_or_default())
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Handler for `debug_subscribeTraceChain`
+    ///
+    /// If tracing a block fails, the subscription is closed with an error notification.
+    async fn subscribe_trace_chain(
+        &self,
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let stream = match Self::debug_trace_chain(
+            self,
+            start_exclusive,
+            end_inclusive,
+            opts.unwrap_or_default(),
+        ) {
+            Ok(stream) => stream,
+            Err(err) => {
+                pending.reject(err.into()).await;
+                return Ok(())
+            }
+        };
+
+        // The subscription runs in its own task, and an error returned after the subscription
+        // was accepted is sent to the subscriber before the subscription is closed.
+        let sink = pending.accept().await?;
+        let mut stream = std::pin::pin!(stream);
+        loop {
+            let result = tokio::select! {
+                _ = sink.closed() => return Ok(()),
+                result = stream.next() => result,
+            };
+            let Some(result) = result else { return Ok(()) };
+            let result = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    let err: ErrorObject<'static> = err.into();
+                    debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                    return Err(err.message().into())
+                }
+            };
+            let msg = SubscriptionMessage::from_json(&result)?;
+            if sink.send(msg).await.is_err() {
+                return Ok(())
+            }
+        }
     }
 
     /// Handler for `debug_traceBlock`
@@ -969,16 +1402,31 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
         _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<StateDump> {
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key must be at most 32 bytes, got {}",
+                start.len()
+            ))
+            .into())
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+        let limit = (max_results as usize).min(ACCOUNT_RANGE_MAX_RESULTS);
+
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(self, block_id, start_key, limit, nocode, nostorage)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -989,8 +1437,20 @@ where
         Ok(())
     }
 
-    async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_chaindbProperty`
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String> {
+        let provider = &self.inner.provider;
+        if property == "stats" {
+            let mut stats = String::new();
+            for table in Tables::ALL {
+                let entries = provider.table_entries(*table).to_rpc_result()?;
+                stats.push_str(&format!("{table}: {entries}\n"));
+            }
+            return Ok(stats)
+        }
+
+        let table = property.parse::<Tables>().map_err(EthApiError::InvalidParams)?;
+        Ok(provider.table_entries(table).to_rpc_result()?.to_string())
     }
 
     async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1005,12 +1465,30 @@ where
         Ok(())
     }
 
-    async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dbGet`
+    async fn debug_db_get(&self, key: String) -> RpcResult<Bytes> {
+        if !self.inner.config.db_get {
+            return Err(EthApiError::Unsupported(
+                "debug_dbGet is disabled, enable it with `--rpc.debug-db-get`",
+            )
+            .into())
+        }
+        // like geth, keys with a `0x` prefix are hex encoded
+        let key = match key.strip_prefix("0x") {
+            Some(key) => {
+                hex::decode(key).map_err(|err| EthApiError::InvalidParams(err.to_string()))?
+            }
+            None => key.into_bytes(),
+        };
+        self.db_get(&key)?.ok_or_else(|| internal_rpc_err("not found"))
     }
 
-    async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dumpBlock`
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(self, number, B256::ZERO, ACCOUNT_RANGE_MAX_RESULTS, false, false)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
@@ -1033,32 +1511,46 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let block_number = |hash: B256| -> RpcResult<BlockNumber> {
+            Ok(self
+                .inner
+                .provider
+                .block_number(hash)
+                .to_rpc_result()?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?)
+        };
+        let start = block_number(start_hash)?;
+        let end = end_hash.map(block_number).transpose()?;
+        Self::debug_get_modified_accounts(self, start, end).map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts(self, start_number, end_number).map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        _opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1069,8 +1561,11 @@ where
         Ok(())
     }
 
-    async fn debug_preimage(&self, _hash: B256) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_preimage`
+    ///
+    /// Reth does not store the preimages of hashed keys.
+    async fn debug_preimage(&self, _hash: B256) -> RpcResult<Bytes> {
+        Err(EthApiError::Unsupported("preimages are not stored").into())
     }
 
     async fn debug_print_block(&self, _number: u64) -> RpcResult<()> {
@@ -1089,8 +1584,12 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_setHead`
     async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+        Err(EthApiError::Unsupported(
+            "debug_setHead is not supported, stop the node and run `reth stage unwind` instead",
+        )
+        .into())
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1105,12 +1604,39 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        let Some(block) = self.inner.bad_blocks.get(&block_hash).map(|bad| bad.block) else {
+            return Err(EthApiError::HeaderNotFound(block_hash.into()).into())
+        };
+
+        // always use the struct logger, which produces the standard json trace
+        let config = opts.unwrap_or_default().config;
+        let opts = GethDebugTracingOptions { config, ..Default::default() };
+        let results =
+            Self::debug_trace_bad_block(self, block_hash, opts).await.map_err(Into::into)?;
+
+        let mut files = Vec::with_capacity(results.len());
+        for (index, (result, tx)) in results.into_iter().zip(&block.body.transactions).enumerate() {
+            let TraceResult::Success { result: GethTrace::Default(frame), .. } = result else {
+                continue
+            };
+            let path = std::env::temp_dir().join(format!(
+                "block_{}-{index}-{}.jsonl",
+                hex::encode_prefixed(&block_hash[..4]),
+                hex::encode_prefixed(&tx.hash()[..4]),
+            ));
+            write_standard_trace(&path, &frame)
+                .map_err(|err| internal_rpc_err(format!("{}: {err}", path.display())))?;
+            files.push(path.display().to_string());
+        }
+
+        Ok(files)
     }
 
     async fn debug_standard_trace_block_to_file(
@@ -1148,12 +1674,16 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1177,6 +1707,23 @@ where
     }
 }
 
+/// Writes the struct logs of the given trace as json lines, followed by a summary line.
+fn write_standard_trace(path: &Path, frame: &DefaultFrame) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for log in &frame.struct_logs {
+        serde_json::to_writer(&mut writer, log)?;
+        writer.write_all(b"\n")?;
+    }
+    let summary = serde_json::json!({
+        "output": frame.return_value,
+        "gasUsed": U64::from(frame.gas),
+        "pass": !frame.failed,
+    });
+    serde_json::to_writer(&mut writer, &summary)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
 impl<Provider, Eth, BlockExecutor> std::fmt::Debug for DebugApi<Provider, Eth, BlockExecutor> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1198,4 +1745,15 @@ struct DebugApiInner<Provider, Eth, BlockExecutor> {
     blocking_task_guard: BlockingTaskGuard,
     /// block executor for debug & trace apis
     block_executor: BlockExecutor,
+    /// Recently rejected blocks
+    bad_blocks: BadBlocks,
+    /// Configuration of the `debug` namespace
+    config: DebugApiConfig,
+}
+
+/// Configuration for the `debug` API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugApiConfig {
+    /// Whether `debug_dbGet`, which returns raw values of the database, is enabled.
+    pub db_get: bool,
 }
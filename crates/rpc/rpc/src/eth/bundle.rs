@@ -1,8 +1,11 @@
 //! `Eth` bundle implementation and helpers.
 
 use alloy_consensus::Transaction as _;
-use alloy_primitives::{Keccak256, U256};
-use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use alloy_primitives::{Bytes, Keccak256, B256, U256};
+use alloy_rpc_types_mev::{
+    CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle, EthCallBundleResponse,
+    EthCallBundleTransactionResult, EthSendBundle, PrivateTransactionRequest,
+};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthChainSpec;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
@@ -11,10 +14,16 @@ use reth_provider::{ChainSpecProvider, HeaderProvider};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_eth_api::{
     helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer, FromEthApiError, FromEvmError, RpcNodeCore,
+    EthBundleApiServer, EthCallBundleApiServer, FromEthApiError, FromEvmError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{
+    bundle::CancelBundleRequest, utils::recover_raw_transaction, EthApiError,
+    RpcInvalidTransactionError,
 };
-use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::{
+    PoolBundle, PoolTransaction, PrivateTransactionPoolError, TransactionPool,
+};
 use revm::{
     db::{CacheDB, DatabaseCommit, DatabaseRef},
     primitives::{ResultAndState, TxEnv},
@@ -256,6 +265,94 @@ where
             })
             .await
     }
+
+    /// Adds a bundle to the local bundle pool, from which the payload builder includes it at the
+    /// top of the targeted block.
+    ///
+    /// A bundle with the same replacement UUID as a previously sent bundle replaces it.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            )
+            .into())
+        }
+
+        let transactions = txs
+            .into_iter()
+            .map(|tx| recover_raw_transaction(tx).map(|tx| tx.into_ecrecovered_transaction()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bundle = PoolBundle::new(transactions, block_number)
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+
+        let bundle_hash = self
+            .eth_api()
+            .pool()
+            .bundle_pool()
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Validates a transaction and adds it to the local private transaction pool.
+    ///
+    /// Private transactions are never propagated to peers, the payload builder includes them
+    /// until `max_block_number`.
+    pub async fn send_private_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> Result<B256, Eth::Error> {
+        let recovered = recover_raw_transaction(tx)?;
+        let transaction =
+            <Eth::Pool as TransactionPool>::Transaction::from_pooled(recovered.into());
+        let hash = self
+            .eth_api()
+            .pool()
+            .add_private_transaction(transaction, max_block_number)
+            .await
+            .map_err(|err| match err {
+                PrivateTransactionPoolError::Invalid(err) => EthApiError::from(err),
+                err => EthApiError::InvalidParams(err.to_string()),
+            })?;
+        Ok(hash)
+    }
+
+    /// Removes a transaction from the local private transaction pool.
+    ///
+    /// Returns `true` if the transaction was tracked.
+    pub fn cancel_private_transaction(&self, tx_hash: B256) -> bool {
+        self.eth_api().pool().private_pool().cancel_transaction(&tx_hash)
+    }
+
+    /// Removes a bundle from the local bundle pool.
+    ///
+    /// The bundle is identified by its replacement UUID only, so that a bundle can't be
+    /// cancelled by anyone who merely knows its hash.
+    pub fn cancel_bundle(&self, request: CancelBundleRequest) -> Result<(), EthApiError> {
+        self.eth_api()
+            .pool()
+            .bundle_pool()
+            .cancel_bundle(&request.replacement_uuid)
+            .map(|_| ())
+            .ok_or_else(|| {
+                EthApiError::InvalidParams(
+                    EthBundleError::BundleNotFound(request.replacement_uuid).to_string(),
+                )
+            })
+    }
 }
 
 #[async_trait::async_trait]
@@ -268,6 +365,44 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).await.map_err(Into::into)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Self::call_bundle(self, request).await.map_err(Into::into)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Self::cancel_bundle(self, request).map_err(Into::into)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Self::send_private_transaction(self, request.tx, request.max_block_number)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        Self::send_private_transaction(self, bytes, None).await.map_err(Into::into)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(Self::cancel_private_transaction(self, request.tx_hash))
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -303,4 +438,7 @@ pub enum EthBundleError {
     /// [`MAX_BLOB_GAS_PER_BLOCK`].
     #[error("blob gas usage exceeds the limit of {MAX_BLOB_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if no pending bundle was submitted with the replacement UUID to cancel.
+    #[error("no bundle with replacementUuid {0}")]
+    BundleNotFound(String),
 }
@@ -64,6 +64,7 @@ where
         gas_cap: impl Into<GasCap>,
         max_simulate_blocks: u64,
         eth_proof_window: u64,
+        trie_changesets_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
@@ -78,6 +79,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            trie_changesets_proof_window,
             blocking_task_pool,
             fee_history_cache,
             evm_config,
@@ -116,6 +118,7 @@ where
             ctx.config.rpc_gas_cap,
             ctx.config.rpc_max_simulate_blocks,
             ctx.config.eth_proof_window,
+            ctx.config.trie_changesets_proof_window,
             blocking_task_pool,
             ctx.new_fee_history_cache(),
             ctx.evm_config.clone(),
@@ -235,6 +238,9 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum number of blocks into the past for generating state proofs with the trie
+    /// changesets.
+    trie_changesets_proof_window: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -267,6 +273,7 @@ where
         gas_cap: impl Into<GasCap>,
         max_simulate_blocks: u64,
         eth_proof_window: u64,
+        trie_changesets_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
@@ -294,6 +301,7 @@ where
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
             eth_proof_window,
+            trie_changesets_proof_window,
             starting_block,
             task_spawner: Box::new(task_spawner),
             pending_block: Default::default(),
@@ -396,6 +404,13 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         self.eth_proof_window
     }
 
+    /// The maximum number of blocks into the past for generating state proofs with the trie
+    /// changesets.
+    #[inline]
+    pub const fn trie_changesets_proof_window(&self) -> u64 {
+        self.trie_changesets_proof_window
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -424,6 +439,7 @@ mod tests {
     };
     use reth_rpc_server_types::constants::{
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+        DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
     };
     use reth_tasks::pool::BlockingTaskPool;
     use reth_testing_utils::{generators, generators::Rng};
@@ -458,6 +474,7 @@ mod tests {
             gas_cap,
             DEFAULT_MAX_SIMULATE_BLOCKS,
             DEFAULT_ETH_PROOF_WINDOW,
+            DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
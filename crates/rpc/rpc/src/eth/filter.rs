@@ -1,7 +1,7 @@
 //! `eth_` `Filter` RPC handler implementation
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     iter::StepBy,
     ops::RangeInclusive,
@@ -18,7 +18,7 @@ use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_primitives::{Receipt, SealedBlockWithSenders, TransactionSignedEcRecovered};
-use reth_provider::{BlockIdReader, BlockReader, ProviderError};
+use reth_provider::{BlockIdReader, BlockReader, LogIndexReader, ProviderError};
 use reth_rpc_eth_api::{
     EthApiTypes, EthFilterApiServer, FullEthApiTypes, RpcTransaction, TransactionCompat,
 };
@@ -144,7 +144,7 @@ where
 
 impl<Provider, Pool, Eth> EthFilter<Provider, Pool, Eth>
 where
-    Provider: BlockReader + BlockIdReader + 'static,
+    Provider: BlockReader + BlockIdReader + LogIndexReader + 'static,
     Pool: TransactionPool<Transaction: 'static> + 'static,
     Eth: FullEthApiTypes,
 {
@@ -244,7 +244,7 @@ where
 impl<Provider, Pool, Eth> EthFilterApiServer<RpcTransaction<Eth::NetworkTypes>>
     for EthFilter<Provider, Pool, Eth>
 where
-    Provider: BlockReader + BlockIdReader + 'static,
+    Provider: BlockReader + BlockIdReader + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     Eth: FullEthApiTypes + 'static,
 {
@@ -367,7 +367,7 @@ struct EthFilterInner<Provider, Pool, Tx> {
 
 impl<Provider, Pool, Tx> EthFilterInner<Provider, Pool, Tx>
 where
-    Provider: BlockReader + BlockIdReader + 'static,
+    Provider: BlockReader + BlockIdReader + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -471,6 +471,42 @@ where
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let is_multi_block_range = from_block != to_block;
+
+        // use the log index for the part of the range it covers, if available
+        let mut bloom_scan_from = from_block;
+        if let Some((blocks, last_indexed)) =
+            self.indexed_log_blocks(filter, from_block, to_block)?
+        {
+            for number in blocks {
+                let header = self
+                    .provider
+                    .sealed_header(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    header.num_hash(),
+                    header.timestamp,
+                    chain_info.best_number,
+                )
+                .await?;
+
+                if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                    return Err(EthFilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+                }
+            }
+
+            bloom_scan_from = last_indexed + 1;
+        }
+
+        if bloom_scan_from > to_block {
+            return Ok(all_logs)
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -478,7 +514,7 @@ where
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(bloom_scan_from..=to_block, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
@@ -497,30 +533,19 @@ where
                             .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    let num_hash = BlockNumHash::new(header.number, block_hash);
-                    if let Some((receipts, maybe_block)) =
-                        self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
-                    {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            maybe_block
-                                .map(|block| ProviderOrBlock::Block(block))
-                                .unwrap_or_else(|| ProviderOrBlock::Provider(&self.provider)),
-                            &filter_params,
-                            num_hash,
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        BlockNumHash::new(header.number, block_hash),
+                        header.timestamp,
+                        chain_info.best_number,
+                    )
+                    .await?;
+
+                    if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                        return Err(EthFilterError::QueryExceedsMaxResults(
+                            self.max_logs_per_response,
+                        ))
                     }
                 }
             }
@@ -529,6 +554,84 @@ where
         Ok(all_logs)
     }
 
+    /// Returns the blocks in the given range that contain logs of the filter's addresses and
+    /// topics according to the log index, together with the last block the index covers.
+    ///
+    /// Returns `None` if the filter matches any log, or if the log index is not maintained or
+    /// doesn't cover the start of the range.
+    fn indexed_log_blocks(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<(BTreeSet<u64>, u64)>, EthFilterError> {
+        if filter.address.is_empty() && filter.topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+
+        let Some(checkpoint) = self.provider.log_index_checkpoint()? else { return Ok(None) };
+        if checkpoint < from_block {
+            return Ok(None)
+        }
+        let last_indexed = to_block.min(checkpoint);
+        let range = from_block..=last_indexed;
+
+        // each non-empty address or topic set narrows the candidates, the values within a set are
+        // alternatives
+        let mut candidates: Option<BTreeSet<u64>> = None;
+        let mut narrow = |blocks: BTreeSet<u64>| {
+            candidates = Some(match candidates.take() {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        };
+
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider.log_address_blocks(*address, range.clone())?);
+            }
+            narrow(blocks);
+        }
+
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider.log_topic_blocks(*topic, range.clone())?);
+            }
+            narrow(blocks);
+        }
+
+        Ok(candidates.map(|blocks| (blocks, last_indexed)))
+    }
+
+    /// Appends the logs of the given block that match the filter.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        num_hash: BlockNumHash,
+        timestamp: u64,
+        best_number: u64,
+    ) -> Result<(), EthFilterError> {
+        if let Some((receipts, maybe_block)) =
+            self.receipts_and_maybe_block(&num_hash, best_number).await?
+        {
+            append_matching_block_logs(
+                all_logs,
+                maybe_block
+                    .map(|block| ProviderOrBlock::Block(block))
+                    .unwrap_or_else(|| ProviderOrBlock::Provider(&self.provider)),
+                filter_params,
+                num_hash,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Retrieves receipts and block from cache if near the tip (4 blocks), otherwise only receipts.
     async fn receipts_and_maybe_block(
         &self,
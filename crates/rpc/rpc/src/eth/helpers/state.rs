@@ -1,23 +1,34 @@
 //! Contains RPC handler implementations specific to state.
 
+use alloy_primitives::BlockNumber;
 use reth_chainspec::EthereumHardforks;
-use reth_provider::{ChainSpecProvider, StateProviderFactory};
+use reth_provider::{ChainSpecProvider, StateProviderFactory, TrieChangeSetsReader};
 use reth_transaction_pool::TransactionPool;
 
 use reth_rpc_eth_api::{
     helpers::{EthState, LoadState, SpawnBlocking},
-    RpcNodeCore,
+    FromEthApiError, RpcNodeCore,
 };
+use std::ops::RangeInclusive;
 
 use crate::EthApi;
 
 impl<Provider, Pool, Network, EvmConfig> EthState for EthApi<Provider, Pool, Network, EvmConfig>
 where
     Self: LoadState + SpawnBlocking,
+    Provider: TrieChangeSetsReader,
 {
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn max_trie_changesets_proof_window(&self) -> u64 {
+        self.inner.trie_changesets_proof_window()
+    }
+
+    fn trie_changesets_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>, Self::Error> {
+        self.inner.provider().trie_changesets_range().map_err(Self::Error::from_eth_err)
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig> where
@@ -43,6 +54,7 @@ mod tests {
     };
     use reth_rpc_server_types::constants::{
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+        DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
     };
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
@@ -63,6 +75,7 @@ mod tests {
             ETHEREUM_BLOCK_GAS_LIMIT,
             DEFAULT_MAX_SIMULATE_BLOCKS,
             DEFAULT_ETH_PROOF_WINDOW,
+            DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
             evm_config,
@@ -90,6 +103,7 @@ mod tests {
             ETHEREUM_BLOCK_GAS_LIMIT,
             DEFAULT_MAX_SIMULATE_BLOCKS,
             DEFAULT_ETH_PROOF_WINDOW + 1,
+            DEFAULT_TRIE_CHANGESETS_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
             evm_config,
@@ -33,6 +33,7 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
 mod debug;
 mod engine;
 pub mod eth;
@@ -46,7 +47,8 @@ mod validation;
 mod web3;
 
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use anvil::{AnvilApi, AnvilEthApi, DumpedAccount, DumpedState, MAX_MINED_BLOCKS};
+pub use debug::{DebugApi, DebugApiConfig};
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
 pub use net::NetApi;
@@ -12,6 +12,7 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_provider::HistoryReader;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
@@ -24,23 +25,29 @@ use revm_inspectors::{
     transfer::{TransferInspector, TransferKind},
 };
 use revm_primitives::{ExecutionResult, SignedAuthorization};
+use std::ops::RangeInclusive;
 
 const API_LEVEL: u64 = 8;
 
+/// The number of blocks that are looked up in the history indices at once when searching for
+/// transactions of an address.
+const SEARCH_WINDOW: u64 = 100_000;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
     }
 }
 
-impl<Eth> OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
 where
     Eth: FullEthApiTypes,
 {
@@ -58,11 +65,177 @@ where
 
         Ok(BlockDetails::new(block, Default::default(), U256::from(total_fees)))
     }
+
+    /// Constructs an `OtsTransactionReceipt` from a receipt, stripping the logs.
+    fn ots_receipt(
+        receipt: RpcReceipt<Eth::NetworkTypes>,
+        tx_ty: u8,
+        timestamp: Option<u64>,
+    ) -> OtsTransactionReceipt {
+        let inner = OtsReceipt {
+            status: receipt.status(),
+            cumulative_gas_used: receipt.cumulative_gas_used() as u64,
+            logs: None,
+            logs_bloom: None,
+            r#type: tx_ty,
+        };
+
+        let receipt = TransactionReceipt {
+            inner,
+            transaction_hash: receipt.transaction_hash(),
+            transaction_index: receipt.transaction_index(),
+            block_hash: receipt.block_hash(),
+            block_number: receipt.block_number(),
+            gas_used: receipt.gas_used(),
+            effective_gas_price: receipt.effective_gas_price(),
+            blob_gas_used: receipt.blob_gas_used(),
+            blob_gas_price: receipt.blob_gas_price(),
+            from: receipt.from(),
+            to: receipt.to(),
+            contract_address: receipt.contract_address(),
+            authorization_list: receipt.authorization_list().map(<[SignedAuthorization]>::to_vec),
+        };
+
+        OtsTransactionReceipt { receipt, timestamp }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: HistoryReader + 'static,
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+        > + TraceExt
+        + 'static,
+{
+    /// Returns all transactions of the block in which the given address appears, together with
+    /// their receipts.
+    ///
+    /// An address appears in a transaction if it is the sender, the recipient or the target of
+    /// any internal call, creation or self-destruct.
+    async fn address_transactions_in_block(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let block_id = BlockId::from(block_number);
+        let appearances = self
+            .eth
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |_tx_info, inspector, _, _, _| {
+                    Ok(inspector.traces().nodes().iter().any(|node| {
+                        node.trace.caller == address ||
+                            node.trace.address == address ||
+                            node.trace.selfdestruct_refund_target == Some(address)
+                    }))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default();
+
+        if !appearances.contains(&true) {
+            return Ok(Default::default())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        let timestamp = Some(block.header.timestamp);
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(appearances)
+            .filter_map(|((tx, receipt), appears)| appears.then_some((tx, receipt)))
+            .map(|(tx, receipt)| {
+                let tx_ty = tx.ty();
+                (tx, Self::ots_receipt(receipt, tx_ty, timestamp))
+            })
+            .unzip())
+    }
+
+    /// Searches the given block range for transactions in which the address appears, until at
+    /// least `page_size` transactions are found.
+    ///
+    /// Candidate blocks are taken from the account and storage history indices and then traced
+    /// to find the actual transactions. Blocks are always included as a whole, so a page can
+    /// contain more than `page_size` transactions.
+    ///
+    /// NOTE: The indices only record state changes, so transactions in which the address is
+    /// called without its account or storage being changed, e.g. a call that only reads state,
+    /// are not found unless the address changed in the same block. Finding those would require
+    /// tracing every block of the range.
+    ///
+    /// Results are in the search order, also within a block. Returns `true` as the last element if
+    /// there are more candidate blocks in the range that were not searched.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<u64>,
+        ascending: bool,
+        page_size: usize,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>, bool)> {
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+
+        let (start, end) = range.into_inner();
+        let mut remaining = (start <= end).then_some((start, end));
+        while let Some((start, end)) = remaining {
+            // split off the next window in search direction
+            let window = if ascending {
+                let window_end = start.saturating_add(SEARCH_WINDOW - 1).min(end);
+                remaining = (window_end < end).then(|| (window_end + 1, end));
+                start..=window_end
+            } else {
+                let window_start = end.saturating_sub(SEARCH_WINDOW - 1).max(start);
+                remaining = (window_start > start).then(|| (start, window_start - 1));
+                window_start..=end
+            };
+
+            let blocks =
+                self.provider.account_history_blocks(address, window).map_err(EthApiError::from)?;
+            let blocks: Box<dyn Iterator<Item = u64> + Send> = if ascending {
+                Box::new(blocks.into_iter())
+            } else {
+                Box::new(blocks.into_iter().rev())
+            };
+
+            for block_number in blocks {
+                if txs.len() >= page_size {
+                    return Ok((txs, receipts, true))
+                }
+                let (mut block_txs, mut block_receipts) =
+                    self.address_transactions_in_block(address, block_number).await?;
+                if !ascending {
+                    block_txs.reverse();
+                    block_receipts.reverse();
+                }
+                txs.extend(block_txs);
+                receipts.extend(block_receipts);
+            }
+        }
+
+        Ok((txs, receipts, false))
+    }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>> for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>>
+    for OtterscanApi<Provider, Eth>
 where
+    Provider: HistoryReader + 'static,
     Eth: EthApiServer<
             RpcTransaction<Eth::NetworkTypes>,
             RpcBlock<Eth::NetworkTypes>,
@@ -240,35 +413,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Transaction::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used() as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                    authorization_list: receipt
-                        .authorization_list()
-                        .map(<[SignedAuthorization]>::to_vec),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| Self::ots_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -278,23 +423,59 @@ where
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Searches backwards from the given block (exclusive), or from the tip if the block number
+    /// is 0. Results are sorted from the newest to the oldest transaction.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let first_page = block_number == 0;
+        let end = if first_page {
+            self.eth.block_number()?.saturating_to()
+        } else {
+            // the search range is empty if we are already at genesis
+            let Some(end) = block_number.checked_sub(1) else {
+                return Ok(TransactionsWithReceipts {
+                    txs: Vec::new(),
+                    receipts: Vec::new(),
+                    first_page,
+                    last_page: true,
+                })
+            };
+            end
+        };
+
+        let (txs, receipts, has_more) =
+            self.search_transactions(address, 0..=end, false, page_size).await?;
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: !has_more })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Searches forwards from the given block (exclusive), or from genesis if the block number
+    /// is 0. Results are sorted from the newest to the oldest transaction.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let last_page = block_number == 0;
+        let start = if last_page { 0 } else { block_number.saturating_add(1) };
+        let end = self.eth.block_number()?.saturating_to();
+
+        let (mut txs, mut receipts, has_more) =
+            self.search_transactions(address, start..=end, true, page_size).await?;
+
+        // otterscan expects the newest transactions first
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page: !has_more, last_page })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -98,11 +98,58 @@ where
     E: BlockExecutorProvider,
 {
     /// Validates the given block and a [`BidTrace`] against it.
+    ///
+    /// The block must be built on top of the latest block.
     pub async fn validate_message_against_block(
         &self,
         block: SealedBlockWithSenders,
         message: BidTrace,
         registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        let latest_header =
+            self.provider.latest_header()?.ok_or_else(|| ValidationApiError::MissingLatestBlock)?;
+
+        if latest_header.hash() != block.header.parent_hash {
+            return Err(ConsensusError::ParentHashMismatch(
+                GotExpected { got: block.header.parent_hash, expected: latest_header.hash() }
+                    .into(),
+            )
+            .into())
+        }
+
+        self.validate_message_against_parent(block, message, registered_gas_limit, latest_header)
+            .await
+    }
+
+    /// Validates the given block and a [`BidTrace`] against it.
+    ///
+    /// Unlike [`Self::validate_message_against_block`], the block can be built on top of any known
+    /// block, so that historical submissions can be validated against the state of their parent.
+    pub async fn validate_historical_message_against_block(
+        &self,
+        block: SealedBlockWithSenders,
+        message: BidTrace,
+        registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        let parent_hash = block.header.parent_hash;
+        let parent_header = self
+            .provider
+            .header(&parent_hash)?
+            .map(|header| SealedHeader::new(header, parent_hash))
+            .ok_or(ConsensusError::ParentUnknown { hash: parent_hash })?;
+
+        self.validate_message_against_parent(block, message, registered_gas_limit, parent_header)
+            .await
+    }
+
+    /// Validates the given block and a [`BidTrace`] against it, executing the block on top of the
+    /// state of the given parent.
+    async fn validate_message_against_parent(
+        &self,
+        block: SealedBlockWithSenders,
+        message: BidTrace,
+        registered_gas_limit: u64,
+        parent_header: SealedHeader,
     ) -> Result<(), ValidationApiError> {
         self.validate_message_against_header(&block.header, &message)?;
 
@@ -129,23 +176,13 @@ where
             }
         }
 
-        let latest_header =
-            self.provider.latest_header()?.ok_or_else(|| ValidationApiError::MissingLatestBlock)?;
-
-        if latest_header.hash() != block.header.parent_hash {
-            return Err(ConsensusError::ParentHashMismatch(
-                GotExpected { got: block.header.parent_hash, expected: latest_header.hash() }
-                    .into(),
-            )
-            .into())
-        }
-        self.consensus.validate_header_against_parent(&block.header, &latest_header)?;
-        self.validate_gas_limit(registered_gas_limit, &latest_header, &block.header)?;
+        self.consensus.validate_header_against_parent(&block.header, &parent_header)?;
+        self.validate_gas_limit(registered_gas_limit, &parent_header, &block.header)?;
 
-        let latest_header_hash = latest_header.hash();
-        let state_provider = self.provider.state_by_block_hash(latest_header_hash)?;
+        let parent_hash = parent_header.hash();
+        let state_provider = self.provider.state_by_block_hash(parent_hash)?;
 
-        let mut request_cache = self.cached_reads(latest_header_hash).await;
+        let mut request_cache = self.cached_reads(parent_hash).await;
 
         let cached_db = request_cache.as_db_mut(StateProviderDatabase::new(&state_provider));
         let executor = self.executor_provider.executor(cached_db);
@@ -166,7 +203,7 @@ where
         )?;
 
         // update the cached reads
-        self.update_cached_reads(latest_header_hash, request_cache).await;
+        self.update_cached_reads(parent_hash, request_cache).await;
 
         if let Some(account) = accessed_blacklisted {
             return Err(ValidationApiError::Blacklist(account))
@@ -341,6 +378,25 @@ where
         Ok(versioned_hashes)
     }
 
+    /// Core logic for validating the builder submissions v1 and v2
+    ///
+    /// These submissions don't carry blobs and are validated against the state of their parent,
+    /// because they are used to replay historical, pre-Cancun, submissions.
+    async fn validate_pre_cancun_builder_submission(
+        &self,
+        payload: ExecutionPayload,
+        message: BidTrace,
+        registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        let block = self
+            .payload_validator
+            .ensure_well_formed_payload(payload, ExecutionPayloadSidecar::none())?
+            .try_seal_with_senders()
+            .map_err(|_| ValidationApiError::InvalidTransactionSignature)?;
+
+        self.validate_historical_message_against_block(block, message, registered_gas_limit).await
+    }
+
     /// Core logic for validating the builder submission v3
     async fn validate_builder_submission_v3(
         &self,
@@ -409,18 +465,50 @@ where
         + 'static,
     E: BlockExecutorProvider,
 {
+    /// Validates a block submitted to the relay
     async fn validate_builder_submission_v1(
         &self,
-        _request: BuilderBlockValidationRequest,
+        request: BuilderBlockValidationRequest,
     ) -> RpcResult<()> {
-        Err(internal_rpc_err("unimplemented"))
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = this
+                .validate_pre_cancun_builder_submission(
+                    ExecutionPayload::V1(request.request.execution_payload),
+                    request.request.message,
+                    request.registered_gas_limit,
+                )
+                .await
+                .map_err(|err| internal_rpc_err(err.to_string()));
+            let _ = tx.send(result);
+        }));
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))?
     }
 
+    /// Validates a block submitted to the relay
     async fn validate_builder_submission_v2(
         &self,
-        _request: BuilderBlockValidationRequestV2,
+        request: BuilderBlockValidationRequestV2,
     ) -> RpcResult<()> {
-        Err(internal_rpc_err("unimplemented"))
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = this
+                .validate_pre_cancun_builder_submission(
+                    ExecutionPayload::V2(request.request.execution_payload),
+                    request.request.message,
+                    request.registered_gas_limit,
+                )
+                .await
+                .map_err(|err| internal_rpc_err(err.to_string()));
+            let _ = tx.send(result);
+        }));
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))?
     }
 
     /// Validates a block submitted to the relay
@@ -474,8 +562,8 @@ pub struct ValidationApiInner<Provider: ChainSpecProvider, E> {
     disallow: HashSet<Address>,
     /// Cached state reads to avoid redundant disk I/O across multiple validation attempts
     /// targeting the same state. Stores a tuple of (`block_hash`, `cached_reads`) for the
-    /// latest head block state. Uses async `RwLock` to safely handle concurrent validation
-    /// requests.
+    /// state of the parent of the most recently validated block. Uses async `RwLock` to safely
+    /// handle concurrent validation requests.
     cached_state: RwLock<(B256, CachedReads)>,
     /// Task spawner for blocking operations
     task_spawner: Box<dyn TaskSpawner>,
@@ -521,3 +609,188 @@ pub enum ValidationApiError {
     #[error(transparent)]
     Payload(#[from] PayloadError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::EMPTY_ROOT_HASH, Header, EMPTY_OMMER_ROOT_HASH};
+    use alloy_primitives::FixedBytes;
+    use alloy_rpc_types_beacon::relay::{SignedBidSubmissionV1, SignedBidSubmissionV2};
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthChainSpec, EthereumHardfork, Hardforks};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_evm::execute::BasicBlockExecutorProvider;
+    use reth_evm_ethereum::execute::{EthExecutionStrategyFactory, EthExecutorProvider};
+    use reth_primitives::{BlockBody, SealedBlock};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_types_compat::engine::payload::{block_to_payload_v1, block_to_payload_v2};
+    use reth_tasks::TokioTaskExecutor;
+
+    const GAS_LIMIT: u64 = 30_000_000;
+
+    fn validation_api(
+        provider: MockEthProvider,
+    ) -> ValidationApi<MockEthProvider, BasicBlockExecutorProvider<EthExecutionStrategyFactory>>
+    {
+        let chain_spec = provider.chain_spec.clone();
+        ValidationApi::new(
+            provider,
+            Arc::new(EthBeaconConsensus::new(chain_spec.clone())),
+            EthExecutorProvider::ethereum(chain_spec),
+            ValidationApiConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        )
+    }
+
+    /// Returns a provider with a chain of three empty blocks.
+    fn provider() -> (MockEthProvider, Vec<SealedHeader>) {
+        let chain_spec: Arc<ChainSpec> =
+            Arc::new(ChainSpecBuilder::mainnet().paris_activated().build());
+        let provider = MockEthProvider { chain_spec, ..Default::default() };
+
+        let mut headers = Vec::<SealedHeader>::new();
+        for number in 0..3 {
+            let header = headers.last().map_or_else(
+                || Header {
+                    gas_limit: GAS_LIMIT,
+                    base_fee_per_gas: Some(1_000_000_000),
+                    ..Default::default()
+                },
+                |parent| child_header(&provider, parent),
+            );
+            let block = SealedBlock::new(SealedHeader::seal(header), BlockBody::default());
+            assert_eq!(block.number, number);
+            provider.add_block(block.hash(), block.clone().unseal());
+            headers.push(block.header);
+        }
+
+        (provider, headers)
+    }
+
+    /// Returns the header of an empty block on top of the given parent.
+    fn child_header(provider: &MockEthProvider, parent: &SealedHeader) -> Header {
+        let timestamp = parent.timestamp + 12;
+        Header {
+            parent_hash: parent.hash(),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            // the state root computed by the mock provider
+            state_root: B256::ZERO,
+            transactions_root: EMPTY_ROOT_HASH,
+            receipts_root: EMPTY_ROOT_HASH,
+            number: parent.number + 1,
+            gas_limit: GAS_LIMIT,
+            timestamp,
+            base_fee_per_gas: parent
+                .next_block_base_fee(provider.chain_spec.base_fee_params_at_timestamp(timestamp)),
+            ..Default::default()
+        }
+    }
+
+    fn bid_trace(block: &SealedBlock) -> BidTrace {
+        BidTrace {
+            parent_hash: block.parent_hash,
+            block_hash: block.hash(),
+            gas_limit: block.gas_limit,
+            gas_used: block.gas_used,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn validates_pre_cancun_submissions_against_their_parent() {
+        let (provider, headers) = provider();
+        let api = validation_api(provider.clone());
+
+        // built on top of the first block, which is not the latest block
+        let block = SealedBlock::new(
+            SealedHeader::seal(child_header(&provider, &headers[0])),
+            BlockBody::default(),
+        );
+
+        let request = BuilderBlockValidationRequest {
+            request: SignedBidSubmissionV1 {
+                message: bid_trace(&block),
+                execution_payload: block_to_payload_v1(block.clone()),
+                signature: FixedBytes::ZERO,
+            },
+            registered_gas_limit: GAS_LIMIT,
+        };
+        BlockSubmissionValidationApiServer::validate_builder_submission_v1(&api, request)
+            .await
+            .unwrap();
+
+        // v2 submissions carry withdrawals, which require shanghai
+        let mut header = child_header(&provider, &headers[0]);
+        header.timestamp =
+            provider.chain_spec.fork(EthereumHardfork::Shanghai).as_timestamp().unwrap();
+        header.withdrawals_root = Some(EMPTY_ROOT_HASH);
+        let block = SealedBlock::new(
+            SealedHeader::seal(header),
+            BlockBody { withdrawals: Some(Default::default()), ..Default::default() },
+        );
+
+        let request = BuilderBlockValidationRequestV2 {
+            request: SignedBidSubmissionV2 {
+                message: bid_trace(&block),
+                execution_payload: block_to_payload_v2(block.clone()),
+                signature: FixedBytes::ZERO,
+            },
+            registered_gas_limit: GAS_LIMIT,
+        };
+        BlockSubmissionValidationApiServer::validate_builder_submission_v2(&api, request)
+            .await
+            .unwrap();
+
+        // live submissions must be built on top of the latest block
+        let message = bid_trace(&block);
+        assert!(matches!(
+            api.validate_message_against_block(
+                block.seal_with_senders().unwrap(),
+                message,
+                GAS_LIMIT
+            )
+            .await,
+            Err(ValidationApiError::Consensus(ConsensusError::ParentHashMismatch(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_pre_cancun_submissions() {
+        let (provider, headers) = provider();
+        let api = validation_api(provider.clone());
+
+        let block = SealedBlock::new(
+            SealedHeader::seal(child_header(&provider, &headers[1])),
+            BlockBody::default(),
+        );
+        let mut message = bid_trace(&block);
+        message.gas_used += 1;
+        assert!(matches!(
+            api.validate_pre_cancun_builder_submission(
+                ExecutionPayload::V1(block_to_payload_v1(block)),
+                message,
+                GAS_LIMIT
+            )
+            .await,
+            Err(ValidationApiError::GasUsedMismatch(_))
+        ));
+
+        let mut unknown_parent = headers[2].clone().unseal();
+        unknown_parent.timestamp += 1;
+        let unknown_parent = SealedHeader::seal(unknown_parent);
+        let block = SealedBlock::new(
+            SealedHeader::seal(child_header(&provider, &unknown_parent)),
+            BlockBody::default(),
+        );
+        let message = bid_trace(&block);
+        assert!(matches!(
+            api.validate_pre_cancun_builder_submission(
+                ExecutionPayload::V1(block_to_payload_v1(block)),
+                message,
+                GAS_LIMIT
+            )
+            .await,
+            Err(ValidationApiError::Consensus(ConsensusError::ParentUnknown { hash }))
+                if hash == unknown_parent.hash()
+        ));
+    }
+}
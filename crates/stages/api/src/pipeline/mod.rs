@@ -250,6 +250,10 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     ///   [`StageId::Execution`]
     /// - [`StaticFileSegment::Transactions`](reth_static_file_types::StaticFileSegment::Transactions)
     ///   -> [`StageId::Bodies`]
+    /// - [`StaticFileSegment::AccountChangeSets`](reth_static_file_types::StaticFileSegment::AccountChangeSets)
+    ///   -> [`StageId::IndexAccountHistory`]
+    /// - [`StaticFileSegment::StorageChangeSets`](reth_static_file_types::StaticFileSegment::StorageChangeSets)
+    ///   -> [`StageId::IndexStorageHistory`]
     ///
     /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
     /// lock is occupied.
@@ -289,6 +293,12 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
         let mut provider_rw = self.provider_factory.database_provider_rw()?;
 
+        // Stages unwind the changesets from the database, so the ones that have been moved to
+        // static files need to be restored first.
+        provider_rw.unwind_changesets_from_static_files(to)?;
+        UnifiedStorageWriter::commit_unwind(provider_rw)?;
+        provider_rw = self.provider_factory.database_provider_rw()?;
+
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
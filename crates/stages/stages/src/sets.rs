@@ -36,8 +36,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogsStage, IndexStorageHistoryStage, MerkleStage,
+        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage, TrieChangeSetsStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -425,9 +426,11 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
+    TrieChangeSetsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
-        StageSetBuilder::default()
+        let builder = StageSetBuilder::default()
             .add_stage(TransactionLookupStage::new(
                 self.stages_config.transaction_lookup,
                 self.stages_config.etl.clone(),
@@ -442,6 +445,18 @@ where
                 self.stages_config.index_account_history,
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
-            ))
+            ));
+
+        // the log index and the trie changesets are optional
+        let builder = if self.stages_config.index_logs.enabled {
+            builder.add_stage(IndexLogsStage::new(self.stages_config.index_logs))
+        } else {
+            builder
+        };
+        if self.stages_config.trie_changesets.enabled {
+            builder.add_stage(TrieChangeSetsStage::new())
+        } else {
+            builder
+        }
     }
 }
@@ -0,0 +1,171 @@
+use reth_config::config::IndexLogsConfig;
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_primitives::Receipts;
+use reth_provider::{
+    DBProvider, LogIndexWriter, ProviderError, ReceiptProvider, StageCheckpointWriter,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::info;
+
+/// The log index stage indexes the blocks that contain logs by the address and the topics of the
+/// logs. For more information on index sharding take a look at [`tables::LogAddressHistory`].
+///
+/// The stage is optional. Once it has run, the index is maintained whenever blocks are written to
+/// or removed from the database, see [`LogIndexWriter`].
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self::new(IndexLogsConfig::default())
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + ReceiptProvider + LogIndexWriter + StageCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync the index starts at genesis, which has no logs. This also marks the log
+        // index as maintained.
+        if input.checkpoint().block_number == 0 {
+            provider.tx_ref().clear::<tables::LogAddressHistory>()?;
+            provider.tx_ref().clear::<tables::LogTopicHistory>()?;
+            provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(0))?;
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        info!(target: "sync::stages::index_logs::exec", ?range, "Indexing logs");
+        let receipts = range
+            .clone()
+            .map(|block| {
+                let receipts = provider
+                    .receipts_by_block(block.into())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?;
+                Ok(receipts.into_iter().map(Some).collect())
+            })
+            .collect::<Result<Receipts, ProviderError>>()?;
+        provider.append_log_indices(*range.start(), &receipts)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        provider.unwind_log_indices_above(input.unwind_to)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{Address, BlockNumber, B256};
+    use reth_provider::{DatabaseProviderFactory, LogIndexReader};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_log, random_receipt, BlockRangeParams,
+    };
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // every third block after genesis contains a log of the address
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(1);
+        let mut tx_number = 0;
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for (idx, transaction) in block.body.transactions.iter().enumerate() {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(1));
+                if idx == 0 && block.number > 0 && block.number % 3 == 0 {
+                    let mut log = random_log(&mut rng, Some(address), Some(1));
+                    log.data.topics_mut()[0] = topic;
+                    receipt.logs.push(log);
+                }
+                receipts.push((tx_number, receipt));
+                tx_number += 1;
+            }
+        }
+        db.insert_receipts(receipts).unwrap();
+
+        let expected = |range: std::ops::RangeInclusive<BlockNumber>| {
+            range.filter(|block| *block > 0 && block % 3 == 0).collect::<BTreeSet<_>>()
+        };
+
+        // the index is built in multiple runs
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(provider.log_index_checkpoint().unwrap(), None);
+        let mut stage = IndexLogsStage { commit_threshold: 8 };
+        let mut input = ExecInput { target: Some(20), checkpoint: None };
+        loop {
+            let output = stage.execute(&provider, input).unwrap();
+            input.checkpoint = Some(output.checkpoint);
+            if output.done {
+                break
+            }
+        }
+        assert_eq!(input.checkpoint().block_number, 20);
+        assert_eq!(provider.log_index_checkpoint().unwrap(), Some(20));
+        assert_eq!(provider.log_address_blocks(address, 0..=20).unwrap(), expected(0..=20));
+        assert_eq!(provider.log_topic_blocks(topic, 4..=15).unwrap(), expected(4..=15));
+
+        // blocks are removed from the index on unwind
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(20), unwind_to: 10, bad_block: None };
+        stage.unwind(&provider, input).unwrap();
+        assert_eq!(provider.log_index_checkpoint().unwrap(), Some(10));
+        assert_eq!(provider.log_address_blocks(address, 0..=20).unwrap(), expected(0..=10));
+        assert_eq!(provider.log_topic_blocks(topic, 0..=20).unwrap(), expected(0..=10));
+
+        // the index is maintained when receipts are appended
+        let receipts = (11..=20)
+            .map(|block| {
+                let receipts = provider.receipts_by_block(block.into()).unwrap().unwrap();
+                receipts.into_iter().map(Some).collect()
+            })
+            .collect::<Receipts>();
+        provider.append_log_indices(11, &receipts).unwrap();
+        assert_eq!(provider.log_index_checkpoint().unwrap(), Some(20));
+        assert_eq!(provider.log_address_blocks(address, 0..=20).unwrap(), expected(0..=20));
+    }
+}
@@ -0,0 +1,161 @@
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{
+    DBProvider, StageCheckpointReader, StageCheckpointWriter, TrieChangeSetsWriter,
+};
+use reth_stages_api::{
+    CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput, IndexHistoryCheckpoint, Stage,
+    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::info;
+
+/// The trie changesets stage marks the trie changesets as maintained. For more information take a
+/// look at [`tables::AccountsTrieChangeSets`].
+///
+/// The changesets can't be recovered for blocks whose trie has been computed by the
+/// [`MerkleStage`](crate::stages::MerkleStage), because the merkle stage updates the trie for a
+/// whole range of blocks at once. The stage therefore only starts recording after the trie it
+/// finds, and the changesets are written whenever single blocks are written to the database, see
+/// [`TrieChangeSetsWriter`].
+///
+/// The stage is optional.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct TrieChangeSetsStage;
+
+impl TrieChangeSetsStage {
+    /// Create new instance of [`TrieChangeSetsStage`].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<Provider> Stage<Provider> for TrieChangeSetsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + TrieChangeSetsWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::TrieChangeSets
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        let target = input.target();
+        let recorded = provider
+            .get_stage_checkpoint(StageId::TrieChangeSets)?
+            .and_then(|checkpoint| checkpoint.index_history_stage_checkpoint())
+            .map(|checkpoint| checkpoint.block_range);
+
+        // The trie has been moved to the target without recording the changesets of the blocks in
+        // between, so recording starts over.
+        if recorded.is_none_or(|range| range.to != target) {
+            info!(target: "sync::stages::trie_changesets::exec", ?target, "Resetting trie changesets");
+            provider.tx_ref().clear::<tables::AccountsTrieChangeSets>()?;
+            provider.tx_ref().clear::<tables::StoragesTrieChangeSets>()?;
+            provider.save_stage_checkpoint(
+                StageId::TrieChangeSets,
+                StageCheckpoint::new(target).with_index_history_stage_checkpoint(
+                    IndexHistoryCheckpoint {
+                        block_range: CheckpointBlockRange { from: target + 1, to: target },
+                        progress: EntitiesCheckpoint::default(),
+                    },
+                ),
+            )?;
+        }
+
+        Ok(ExecOutput::done(StageCheckpoint::new(target)))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        provider.unwind_trie_changesets_above(input.unwind_to)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::B256;
+    use reth_provider::{DatabaseProviderFactory, TrieChangeSetsReader, TrieWriter};
+    use reth_trie::{
+        updates::{StorageTrieUpdates, TrieUpdates},
+        BranchNodeCompact, Nibbles, TrieMask,
+    };
+    use reth_trie_db::DatabaseTrieUpdates;
+
+    fn node(hash: u8) -> BranchNodeCompact {
+        BranchNodeCompact::new(
+            TrieMask::new(0b11),
+            TrieMask::new(0),
+            TrieMask::new(0b10),
+            vec![B256::repeat_byte(hash)],
+            None,
+        )
+    }
+
+    #[test]
+    fn execute_record_and_unwind() {
+        let db = TestStageDB::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let hashed_address = B256::with_last_byte(1);
+        let [first, second, third] = [[0x1], [0x2], [0x3]].map(Nibbles::from_nibbles_unchecked);
+
+        // the trie at genesis
+        provider
+            .write_trie_updates(&TrieUpdates::from_nodes([(first.clone(), node(1))], [], []))
+            .unwrap();
+        provider.save_stage_checkpoint(StageId::MerkleExecute, StageCheckpoint::new(0)).unwrap();
+        assert_eq!(provider.trie_changesets_range().unwrap(), None);
+
+        // recording starts after the trie
+        let mut stage = TrieChangeSetsStage::new();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(0), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput::done(StageCheckpoint::new(0)));
+        assert!(provider.trie_changesets_range().unwrap().is_some_and(|range| range.is_empty()));
+
+        // the trie nodes are recorded before block 1 changes them
+        let updates = TrieUpdates::from_nodes(
+            [(first.clone(), node(2)), (second.clone(), node(3))],
+            [],
+            [(
+                hashed_address,
+                StorageTrieUpdates::from_nodes(false, [(third.clone(), node(4))], []),
+            )],
+        );
+        provider.write_trie_changesets(1..=1, &updates).unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        provider.save_stage_checkpoint(StageId::MerkleExecute, StageCheckpoint::new(1)).unwrap();
+        assert_eq!(provider.trie_changesets_range().unwrap(), Some(1..=1));
+        assert_eq!(
+            TrieUpdates::from_reverts(provider.tx_ref(), 1).unwrap(),
+            TrieUpdates::from_nodes(
+                [(first, node(1))],
+                [second],
+                [(hashed_address, StorageTrieUpdates::from_nodes(false, [], [third]))],
+            )
+        );
+
+        // the changesets are removed on unwind
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(1), unwind_to: 0, bad_block: None };
+        stage.unwind(&provider, input).unwrap();
+        provider.save_stage_checkpoint(StageId::MerkleExecute, StageCheckpoint::new(0)).unwrap();
+        assert!(provider.trie_changesets_range().unwrap().is_some_and(|range| range.is_empty()));
+        assert_eq!(
+            TrieUpdates::from_reverts(provider.tx_ref(), 1).unwrap(),
+            TrieUpdates::default()
+        );
+    }
+}
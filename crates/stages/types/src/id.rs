@@ -20,6 +20,16 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage that indexes the logs by address and topic.
+    ///
+    /// It's not part of [`StageId::ALL`], because the index is only maintained if the stage is
+    /// enabled.
+    IndexLogs,
+    /// Optional stage that keeps the trie changesets contiguous up to the tip of the trie.
+    ///
+    /// It's not part of [`StageId::ALL`], because the trie changesets are only maintained if the
+    /// stage is enabled.
+    TrieChangeSets,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -75,6 +85,8 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexLogs => "IndexLogs",
+            Self::TrieChangeSets => "TrieChangeSets",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -119,6 +131,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::TrieChangeSets.to_string(), "TrieChangeSets");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
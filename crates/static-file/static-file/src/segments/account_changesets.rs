@@ -0,0 +1,46 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::AccountChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct AccountChangeSets;
+
+impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for AccountChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::AccountChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::AccountChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        let mut changesets_walker = changesets_cursor.walk_range(block_range.clone())?.peekable();
+
+        // Blocks without account changes are appended with an empty changeset.
+        for block in block_range {
+            let mut changeset = Vec::new();
+            while let Some((_, account)) = changesets_walker
+                .next_if(|entry| entry.as_ref().map_or(true, |(number, _)| *number == block))
+                .transpose()?
+            {
+                changeset.push(account);
+            }
+
+            static_file_writer.append_account_changeset(block, changeset)?;
+        }
+
+        Ok(())
+    }
+}
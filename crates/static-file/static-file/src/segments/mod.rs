@@ -9,6 +9,12 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod account_changesets;
+pub use account_changesets::AccountChangeSets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangeSets;
+
 use alloy_primitives::BlockNumber;
 use reth_provider::StaticFileProviderFactory;
 use reth_static_file_types::StaticFileSegment;
@@ -136,6 +136,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -174,15 +180,23 @@ where
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints = [
+            StageId::Headers,
+            StageId::Execution,
+            StageId::Bodies,
+            StageId::IndexAccountHistory,
+            StageId::IndexStorageHistory,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            account_changesets: stages_checkpoints[3],
+            storage_changesets: stages_checkpoints[4],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -222,6 +236,28 @@ where
                     finalized_block_number,
                 )
             }),
+            // StaticFile changesets only if the history is not pruned according to the user
+            // configuration
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -251,12 +287,18 @@ mod tests {
     use crate::static_file_producer::{
         StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
     };
-    use alloy_primitives::{B256, U256};
+    use alloy_primitives::{Address, B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::tables;
+    use reth_db_api::{
+        database::Database,
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives_traits::StorageEntry;
     use reth_provider::{
-        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
-        ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ChangeSetReader,
+        ProviderError, ProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
     };
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
@@ -291,6 +333,16 @@ mod tests {
             TestStageDB::insert_header(None, &tx, &block.header, U256::ZERO)
                 .expect("insert block header");
         }
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+        )
+        .expect("insert account changeset");
+        tx.put::<tables::StorageChangeSets>(
+            (2, Address::with_last_byte(1)).into(),
+            StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) },
+        )
+        .expect("insert storage changeset");
         tx.commit().expect("commit tx");
 
         let mut receipts = Vec::new();
@@ -318,6 +370,8 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                account_changesets: Some(1),
+                storage_changesets: Some(1),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -325,13 +379,21 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                account_changesets: Some(0..=1),
+                storage_changesets: Some(0..=1),
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                account_changesets: Some(1),
+                storage_changesets: Some(1),
+            }
         );
 
         let targets = static_file_producer
@@ -339,6 +401,8 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -346,13 +410,35 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                account_changesets: Some(2..=3),
+                storage_changesets: Some(2..=3),
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+            }
+        );
+
+        let static_file_provider = provider_factory.static_file_provider();
+        assert_eq!(
+            static_file_provider.account_block_changeset(1).unwrap(),
+            vec![AccountBeforeTx { address: Address::with_last_byte(1), info: None }]
+        );
+        assert_eq!(static_file_provider.account_block_changeset(2).unwrap(), vec![]);
+        assert_eq!(
+            static_file_provider.storage_changeset(2).unwrap(),
+            vec![(
+                BlockNumberAddress((2, Address::with_last_byte(1))),
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) }
+            )]
         );
 
         let targets = static_file_producer
@@ -360,6 +446,8 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -367,7 +455,9 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(
@@ -376,7 +466,13 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+            }
         );
     }
 
@@ -404,6 +500,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
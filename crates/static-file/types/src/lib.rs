@@ -33,6 +33,12 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest static file block of account changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest static file block of storage changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub storage_changesets: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -42,6 +48,8 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => self.headers,
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
+            StaticFileSegment::AccountChangeSets => self.account_changesets,
+            StaticFileSegment::StorageChangeSets => self.storage_changesets,
         }
     }
 
@@ -51,17 +59,32 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => &mut self.headers,
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
+            StaticFileSegment::AccountChangeSets => &mut self.account_changesets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_changesets,
         }
     }
 
     /// Returns the minimum block of all segments.
     pub fn min(&self) -> Option<u64> {
-        [self.headers, self.transactions, self.receipts].iter().filter_map(|&option| option).min()
+        self.iter().min()
     }
 
     /// Returns the maximum block of all segments.
     pub fn max(&self) -> Option<u64> {
-        [self.headers, self.transactions, self.receipts].iter().filter_map(|&option| option).max()
+        self.iter().max()
+    }
+
+    /// Returns an iterator over the highest blocks of all segments that have static files.
+    fn iter(&self) -> impl Iterator<Item = u64> {
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.account_changesets,
+            self.storage_changesets,
+        ]
+        .into_iter()
+        .flatten()
     }
 }
 
@@ -74,12 +97,20 @@ pub struct StaticFileTargets {
     pub receipts: Option<RangeInclusive<BlockNumber>>,
     /// Targeted range of transactions.
     pub transactions: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of account changesets.
+    pub account_changesets: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of storage changesets.
+    pub storage_changesets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
     /// Returns `true` if any of the targets are [Some].
     pub const fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some()
     }
 
     /// Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -89,6 +120,8 @@ impl StaticFileTargets {
             (self.headers.as_ref(), static_files.headers),
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
+            (self.account_changesets.as_ref(), static_files.account_changesets),
+            (self.storage_changesets.as_ref(), static_files.storage_changesets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -118,8 +151,13 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_highest() {
-        let files =
-            HighestStaticFiles { headers: Some(100), receipts: Some(200), transactions: None };
+        let files = HighestStaticFiles {
+            headers: Some(100),
+            receipts: Some(200),
+            transactions: None,
+            account_changesets: Some(50),
+            storage_changesets: None,
+        };
 
         // Test for headers segment
         assert_eq!(files.highest(StaticFileSegment::Headers), Some(100));
@@ -129,6 +167,10 @@ mod tests {
 
         // Test for transactions segment
         assert_eq!(files.highest(StaticFileSegment::Transactions), None);
+
+        // Test for changeset segments
+        assert_eq!(files.highest(StaticFileSegment::AccountChangeSets), Some(50));
+        assert_eq!(files.highest(StaticFileSegment::StorageChangeSets), None);
     }
 
     #[test]
@@ -150,12 +192,19 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_min() {
-        let files =
-            HighestStaticFiles { headers: Some(300), receipts: Some(100), transactions: None };
+        let files = HighestStaticFiles {
+            headers: Some(300),
+            receipts: Some(100),
+            transactions: None,
+            ..Default::default()
+        };
 
         // Minimum value among the available segments
         assert_eq!(files.min(), Some(100));
 
+        let files = HighestStaticFiles { account_changesets: Some(50), ..files };
+        assert_eq!(files.min(), Some(50));
+
         let empty_files = HighestStaticFiles::default();
         // No values, should return None
         assert_eq!(empty_files.min(), None);
@@ -163,8 +212,12 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_max() {
-        let files =
-            HighestStaticFiles { headers: Some(300), receipts: Some(100), transactions: Some(500) };
+        let files = HighestStaticFiles {
+            headers: Some(300),
+            receipts: Some(100),
+            transactions: Some(500),
+            ..Default::default()
+        };
 
         // Maximum value among the available segments
         assert_eq!(files.max(), Some(500));
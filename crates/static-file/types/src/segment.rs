@@ -34,6 +34,14 @@ pub enum StaticFileSegment {
     #[strum(serialize = "receipts")]
     /// Static File segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "account-changesets"))]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "storage-changesets"))]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -43,6 +51,8 @@ impl StaticFileSegment {
             Self::Headers => "headers",
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
+            Self::AccountChangeSets => "account-changesets",
+            Self::StorageChangeSets => "storage-changesets",
         }
     }
 
@@ -55,7 +65,10 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
         }
     }
 
@@ -123,11 +136,22 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_changesets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if the segment is `StaticFileSegment::Receipts` or
     /// `StaticFileSegment::Transactions`.
     pub const fn is_tx_based(&self) -> bool {
         matches!(self, Self::Receipts | Self::Transactions)
     }
+
+    /// Returns `true` if the segment has one row per block, i.e. it's not transaction based.
+    pub const fn is_block_based(&self) -> bool {
+        !self.is_tx_based()
+    }
 }
 
 /// A segment header that contains information common to all segments. Used for storage.
@@ -228,40 +252,26 @@ impl SegmentHeader {
 
     /// Increments tx end range depending on segment
     pub fn increment_tx(&mut self) {
-        match self.segment {
-            StaticFileSegment::Headers => (),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                if let Some(tx_range) = &mut self.tx_range {
-                    tx_range.end += 1;
-                } else {
-                    self.tx_range = Some(SegmentRangeInclusive::new(0, 0));
-                }
+        if self.segment.is_tx_based() {
+            if let Some(tx_range) = &mut self.tx_range {
+                tx_range.end += 1;
+            } else {
+                self.tx_range = Some(SegmentRangeInclusive::new(0, 0));
             }
         }
     }
 
     /// Removes `num` elements from end of tx or block range.
     pub fn prune(&mut self, num: u64) {
-        match self.segment {
-            StaticFileSegment::Headers => {
-                if let Some(range) = &mut self.block_range {
-                    if num > range.end - range.start {
-                        self.block_range = None;
-                    } else {
-                        range.end = range.end.saturating_sub(num);
-                    }
-                };
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                if let Some(range) = &mut self.tx_range {
-                    if num > range.end - range.start {
-                        self.tx_range = None;
-                    } else {
-                        range.end = range.end.saturating_sub(num);
-                    }
-                };
+        let range =
+            if self.segment.is_block_based() { &mut self.block_range } else { &mut self.tx_range };
+        if let Some(inner) = range {
+            if num > inner.end - inner.start {
+                *range = None;
+            } else {
+                inner.end = inner.end.saturating_sub(num);
             }
-        };
+        }
     }
 
     /// Sets a new `block_range`.
@@ -286,9 +296,10 @@ impl SegmentHeader {
 
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> Option<u64> {
-        match self.segment {
-            StaticFileSegment::Headers => self.block_start(),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => self.tx_start(),
+        if self.segment.is_block_based() {
+            self.block_start()
+        } else {
+            self.tx_start()
         }
     }
 }
@@ -365,6 +376,18 @@ mod tests {
                 "static_file_transactions_1123233_11223233",
                 None,
             ),
+            (
+                StaticFileSegment::AccountChangeSets,
+                500_000..=999_999,
+                "static_file_account-changesets_500000_999999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                0..=499_999,
+                "static_file_storage-changesets_0_499999",
+                None,
+            ),
             (
                 StaticFileSegment::Headers,
                 2..=30,
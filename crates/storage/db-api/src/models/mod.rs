@@ -25,7 +25,8 @@ pub mod storage_sharded_key;
 pub use accounts::*;
 pub use blocks::*;
 pub use reth_db_models::{
-    AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
+    AccountBeforeTx, ClientVersion, StorageBeforeTx, StoredAccountChangeSet,
+    StoredBlockBodyIndices, StoredBlockWithdrawals, StoredStorageChangeSet,
 };
 pub use sharded_key::ShardedKey;
 
@@ -221,11 +222,14 @@ impl_compression_for_compact!(
     StoredNibbles,
     StoredNibblesSubKey,
     StorageTrieEntry,
+    TrieChangeSetsEntry,
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
     Bytecode,
     AccountBeforeTx,
+    StoredAccountChangeSet,
+    StoredStorageChangeSet,
     TransactionSignedNoHash,
     TransactionSigned,
     CompactU256,
@@ -1,8 +1,9 @@
 use reth_codecs::{add_arbitrary_tests, Compact};
 use serde::Serialize;
 
-use alloy_primitives::{bytes::Buf, Address};
+use alloy_primitives::{bytes::Buf, Address, B256, U256};
 use reth_primitives_traits::Account;
+use std::ops::Range;
 
 /// Account as it is saved in the database.
 ///
@@ -48,3 +49,268 @@ impl Compact for AccountBeforeTx {
         (Self { address, info }, buf)
     }
 }
+
+/// Storage slot as it is saved in the storage changesets of static files.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Storage value before the transaction.
+    pub value: U256,
+}
+
+// The address and the key are encoded in full, so that an encoded changeset can be searched for
+// them, see [`StoredStorageChangeSet::find_storage`].
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        buf.put_slice(self.key.as_slice());
+        self.value.to_compact(buf) + 52
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let key = B256::from_slice(&buf[20..52]);
+        buf.advance(52);
+
+        let (value, buf) = U256::from_compact(buf, len - 52);
+        (Self { address, key, value }, buf)
+    }
+}
+
+/// Account changeset of a block as it is saved in static files.
+///
+/// The accounts are sorted by address and encoded after an index of their offsets, so that a
+/// single account can be looked up without decoding the whole changeset, see
+/// [`Self::find_account`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredAccountChangeSet {
+    /// Accounts changed in the block, with their state before the change.
+    pub accounts: Vec<AccountBeforeTx>,
+}
+
+impl StoredAccountChangeSet {
+    /// Decodes the account with the given address from an encoded changeset, without decoding
+    /// the other accounts.
+    pub fn find_account(buf: &[u8], address: Address) -> Option<AccountBeforeTx> {
+        let (entries, _) = IndexedEntries::new(buf);
+        let index = entries.range(address.as_slice()).next()?;
+        Some(entries.decode(index))
+    }
+}
+
+impl Compact for StoredAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        IndexedEntries::to_compact(&self.accounts, buf)
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (entries, buf) = IndexedEntries::new(buf);
+        (Self { accounts: (0..entries.len()).map(|index| entries.decode(index)).collect() }, buf)
+    }
+}
+
+/// Storage changeset of a block as it is saved in static files.
+///
+/// The storage slots are sorted by address and key and encoded after an index of their offsets,
+/// so that a single slot can be looked up without decoding the whole changeset, see
+/// [`Self::find_storage`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredStorageChangeSet {
+    /// Storage slots changed in the block, with their value before the change.
+    pub storages: Vec<StorageBeforeTx>,
+}
+
+impl StoredStorageChangeSet {
+    /// Decodes the storage slot with the given address and key from an encoded changeset, without
+    /// decoding the other slots.
+    pub fn find_storage(buf: &[u8], address: Address, key: B256) -> Option<StorageBeforeTx> {
+        let (entries, _) = IndexedEntries::new(buf);
+        let index = entries.range(&[address.as_slice(), key.as_slice()].concat()).next()?;
+        Some(entries.decode(index))
+    }
+
+    /// Decodes the storage slots of the given address from an encoded changeset, without decoding
+    /// the slots of other addresses.
+    pub fn find_storages(buf: &[u8], address: Address) -> Vec<StorageBeforeTx> {
+        let (entries, _) = IndexedEntries::new(buf);
+        entries.range(address.as_slice()).map(|index| entries.decode(index)).collect()
+    }
+}
+
+impl Compact for StoredStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        IndexedEntries::to_compact(&self.storages, buf)
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (entries, buf) = IndexedEntries::new(buf);
+        (Self { storages: (0..entries.len()).map(|index| entries.decode(index)).collect() }, buf)
+    }
+}
+
+/// Encoded entries of a changeset.
+///
+/// The number of entries and the offset at which each entry ends are encoded before the entries,
+/// so that any entry can be decoded on its own.
+struct IndexedEntries<'a> {
+    ends: &'a [u8],
+    entries: &'a [u8],
+}
+
+impl<'a> IndexedEntries<'a> {
+    /// Encodes the entries after their offsets.
+    fn to_compact<T, B>(entries: &[T], buf: &mut B) -> usize
+    where
+        T: Compact,
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut encoded = Vec::new();
+        let mut ends = Vec::with_capacity(entries.len());
+        for entry in entries {
+            entry.to_compact(&mut encoded);
+            ends.push(encoded.len() as u32);
+        }
+
+        buf.put_u32(ends.len() as u32);
+        for end in &ends {
+            buf.put_u32(*end);
+        }
+        buf.put_slice(&encoded);
+        4 + ends.len() * 4 + encoded.len()
+    }
+
+    /// Splits the encoded entries off the buffer, and returns them with the remaining buffer.
+    fn new(mut buf: &'a [u8]) -> (Self, &'a [u8]) {
+        let len = buf.get_u32() as usize;
+        let (ends, buf) = buf.split_at(len * 4);
+        let mut this = Self { ends, entries: &[] };
+        let (entries, buf) = buf.split_at(if len == 0 { 0 } else { this.end(len - 1) });
+        this.entries = entries;
+        (this, buf)
+    }
+
+    /// Returns the number of entries.
+    const fn len(&self) -> usize {
+        self.ends.len() / 4
+    }
+
+    /// Returns the offset at which the entry with the given index ends.
+    fn end(&self, index: usize) -> usize {
+        let end = &self.ends[index * 4..index * 4 + 4];
+        u32::from_be_bytes([end[0], end[1], end[2], end[3]]) as usize
+    }
+
+    /// Returns the encoding of the entry with the given index.
+    fn get(&self, index: usize) -> &'a [u8] {
+        let start = if index == 0 { 0 } else { self.end(index - 1) };
+        &self.entries[start..self.end(index)]
+    }
+
+    /// Decodes the entry with the given index.
+    fn decode<T: Compact>(&self, index: usize) -> T {
+        let entry = self.get(index);
+        T::from_compact(entry, entry.len()).0
+    }
+
+    /// Returns the indices of the entries whose encoding starts with the given prefix, if the
+    /// entries are sorted by it.
+    fn range(&self, prefix: &[u8]) -> Range<usize> {
+        let entry_prefix = |index| {
+            let entry = self.get(index);
+            &entry[..prefix.len().min(entry.len())]
+        };
+        self.partition_point(|index| entry_prefix(index) < prefix)..
+            self.partition_point(|index| entry_prefix(index) <= prefix)
+    }
+
+    /// Returns the index of the first entry for which the predicate is false, if it's true for
+    /// all entries before and false for all entries after it.
+    fn partition_point(&self, predicate: impl Fn(usize) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if predicate(mid) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_in_changesets() {
+        let accounts = (0..10u8)
+            .map(|byte| AccountBeforeTx {
+                address: Address::with_last_byte(byte * 2),
+                info: (byte % 2 == 0).then(|| Account { nonce: byte.into(), ..Default::default() }),
+            })
+            .collect::<Vec<_>>();
+        let mut buf = Vec::new();
+        StoredAccountChangeSet { accounts: accounts.clone() }.to_compact(&mut buf);
+
+        for account in &accounts {
+            assert_eq!(
+                StoredAccountChangeSet::find_account(&buf, account.address).as_ref(),
+                Some(account)
+            );
+        }
+        assert_eq!(StoredAccountChangeSet::find_account(&buf, Address::with_last_byte(3)), None);
+        assert_eq!(StoredAccountChangeSet::find_account(&[0, 0, 0, 0], Address::ZERO), None);
+
+        let storages = (0..4u8)
+            .flat_map(|address| {
+                (0..3u8).map(move |key| StorageBeforeTx {
+                    address: Address::with_last_byte(address),
+                    key: B256::with_last_byte(key * 2),
+                    value: U256::from(key),
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut buf = Vec::new();
+        StoredStorageChangeSet { storages: storages.clone() }.to_compact(&mut buf);
+
+        for storage in &storages {
+            assert_eq!(
+                StoredStorageChangeSet::find_storage(&buf, storage.address, storage.key).as_ref(),
+                Some(storage)
+            );
+        }
+        assert_eq!(
+            StoredStorageChangeSet::find_storage(
+                &buf,
+                Address::with_last_byte(1),
+                B256::with_last_byte(1)
+            ),
+            None
+        );
+        assert_eq!(
+            StoredStorageChangeSet::find_storages(&buf, Address::with_last_byte(2)),
+            storages[6..9]
+        );
+        assert!(StoredStorageChangeSet::find_storages(&buf, Address::with_last_byte(4)).is_empty());
+    }
+}
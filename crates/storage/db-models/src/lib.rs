@@ -2,7 +2,9 @@
 
 /// Accounts
 pub mod accounts;
-pub use accounts::AccountBeforeTx;
+pub use accounts::{
+    AccountBeforeTx, StorageBeforeTx, StoredAccountChangeSet, StoredStorageChangeSet,
+};
 
 /// Blocks
 pub mod blocks;
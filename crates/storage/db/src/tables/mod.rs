@@ -23,7 +23,7 @@ use alloy_consensus::Header;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
 use reth_db_api::{
     models::{
-        accounts::BlockNumberAddress,
+        accounts::{BlockNumberAddress, BlockNumberHashedAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, ShardedKey, StoredBlockBodyIndices,
@@ -35,7 +35,9 @@ use reth_primitives::{Account, Bytecode, Receipt, StorageEntry, TransactionSigne
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
-use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, TrieChangeSetsEntry,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -419,6 +421,24 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks that contain logs emitted by an address.
+    ///
+    /// The shards are laid out like the ones of [`AccountsHistory`]. The table is only populated
+    /// if the log index is maintained, see `reth_stages::stages::IndexLogsStage`.
+    table LogAddressHistory {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain logs with a topic, at any topic position.
+    ///
+    /// The shards are laid out like the ones of [`AccountsHistory`]. The table is only populated
+    /// if the log index is maintained, see `reth_stages::stages::IndexLogsStage`.
+    table LogTopicHistory {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -469,6 +489,26 @@ tables! {
         type SubKey = StoredNibblesSubKey;
     }
 
+    /// Stores the account trie nodes as they were before a block changed them.
+    ///
+    /// The table is only populated if trie changesets are maintained, see
+    /// `reth_stages::stages::TrieChangeSetsStage`.
+    table AccountsTrieChangeSets {
+        type Key = BlockNumber;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the storage trie nodes as they were before a block changed them.
+    ///
+    /// The table is only populated if trie changesets are maintained, see
+    /// `reth_stages::stages::TrieChangeSetsStage`.
+    table StoragesTrieChangeSets {
+        type Key = BlockNumberHashedAddress;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
     /// Stores the transaction sender for each canonical transaction.
     /// It is needed to speed up execution stage and allows fetching signer without doing
     /// transaction signed recovery
@@ -167,6 +167,12 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds an already set up compressor. Useful for sharing trained dictionaries across jars.
+    pub fn with_compressor(mut self, compressor: Compressors) -> Self {
+        self.compressor = Some(compressor);
+        self
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -541,9 +547,31 @@ mod tests {
                 );
                 row_index += 1;
             }
+
+            // Values must have been compressed with their column dictionary
+            let reader = loaded_nippy.open_data_reader().unwrap();
+            for column in 0..num_columns {
+                let value = reader.data(
+                    reader.offset(column).unwrap() as usize..
+                        reader.offset(column + 1).unwrap() as usize,
+                );
+                assert!(zstd::zstd_safe::get_dict_id_from_frame(value).is_some());
+            }
         } else {
             panic!("Expected Zstd compressor")
         }
+
+        // Reopened jars keep compressing with their dictionaries
+        let mut writer =
+            NippyJarWriter::new(NippyJar::load_without_header(file_path.path()).unwrap()).unwrap();
+        writer.append_column(Some(Ok(&col1[0]))).unwrap();
+        writer.append_column(Some(Ok(&col2[0]))).unwrap();
+        writer.commit().unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let row = cursor.row_by_number(num_rows as usize).unwrap().unwrap();
+        assert_eq!((row[0], row[1]), (col1[0].as_slice(), col2[0].as_slice()));
     }
 
     #[test]
@@ -1,12 +1,13 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
@@ -24,7 +25,6 @@ pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
 ///
 /// ## Data file layout
 /// The data file is represented just as a sequence of bytes of data without any delimiters
-#[derive(Debug)]
 pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     /// Associated [`NippyJar`], containing all necessary configurations for data
     /// handling.
@@ -35,6 +35,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Per-column zstd compressors, if the jar uses compression dictionaries.
+    dictionary_compressors: Option<Vec<Compressor<'static>>>,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -45,6 +47,25 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     dirty: bool,
 }
 
+impl<H: NippyJarHeader> std::fmt::Debug for NippyJarWriter<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NippyJarWriter")
+            .field("jar", &self.jar)
+            .field("data_file", &self.data_file)
+            .field("offsets_file", &self.offsets_file)
+            .field("tmp_buf", &self.tmp_buf)
+            .field(
+                "dictionary_compressors",
+                &self.dictionary_compressors.as_ref().map(|compressors| compressors.len()),
+            )
+            .field("uncompressed_row_size", &self.uncompressed_row_size)
+            .field("offsets", &self.offsets)
+            .field("column", &self.column)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
 impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Creates a [`NippyJarWriter`] from [`NippyJar`].
     ///
@@ -70,11 +91,17 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             (jar, data_file.expect("qed"), offsets_file.expect("qed"))
         };
 
+        let dictionary_compressors = match &jar.compressor {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => zstd.compressors()?,
+            _ => None,
+        };
+
         let mut writer = Self {
             jar,
             data_file,
             offsets_file,
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
@@ -227,7 +254,15 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let len = if let Some(compressor) = self
+            .dictionary_compressors
+            .as_mut()
+            .and_then(|compressors| compressors.get_mut(self.column))
+        {
+            let compressed = compressor.compress(value)?;
+            self.data_file.write_all(&compressed)?;
+            compressed.len()
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
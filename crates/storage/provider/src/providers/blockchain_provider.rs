@@ -4,10 +4,12 @@ use crate::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
     ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, EvmEnvProvider, FullProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    DatabaseProviderFactory, EvmEnvProvider, FullProvider, HashedAccountStorage, HashedStateReader,
+    HeaderProvider, HistoryReader, InMemoryTrieInputProvider, LogIndexReader, ProviderError,
+    ProviderFactory, PruneCheckpointReader, RawTableReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TrieChangeSetsReader,
+    WithdrawalsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{
@@ -21,7 +23,7 @@ use reth_chain_state::{
     MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
-use reth_db::{models::BlockNumberAddress, transaction::DbTx, Database};
+use reth_db::{models::BlockNumberAddress, transaction::DbTx, Database, Tables};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_execution_types::ExecutionOutcome;
@@ -34,8 +36,10 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{DBProvider, StorageChangeSetReader};
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::TrieInput;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeSet,
     ops::{Add, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
@@ -735,6 +739,93 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider2<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HistoryReader for BlockchainProvider2<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.consistent_provider()?.account_history_blocks(address, range)
+    }
+
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.consistent_provider()?.modified_accounts(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> HashedStateReader for BlockchainProvider2<N> {
+    fn hashed_accounts_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        self.consistent_provider()?.hashed_accounts_range(block_number, start, limit, storage_limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> RawTableReader for BlockchainProvider2<N> {
+    fn table_entries(&self, table: Tables) -> ProviderResult<usize> {
+        self.database.provider()?.table_entries(table)
+    }
+}
+
+impl<N: ProviderNodeTypes> InMemoryTrieInputProvider for BlockchainProvider2<N> {
+    fn in_memory_trie_input(
+        &self,
+        block_hash: B256,
+        persisted_block: BlockNumber,
+    ) -> ProviderResult<Option<TrieInput>> {
+        let Some(state) = self.canonical_in_memory_state.state_by_hash(block_hash) else {
+            return Ok(None)
+        };
+        // the blocks between the persisted block and the in-memory blocks are missing
+        if state.anchor().number > persisted_block {
+            return Ok(None)
+        }
+
+        let mut input = TrieInput::default();
+        let blocks = state.chain().collect::<Vec<_>>();
+        for block in blocks.into_iter().rev().filter(|block| block.number() > persisted_block) {
+            let block = block.block_ref();
+            input.append_cached_ref(block.trie_updates(), block.hashed_state());
+        }
+        Ok(Some(input))
+    }
+}
+
+impl<N: ProviderNodeTypes> TrieChangeSetsReader for BlockchainProvider2<N> {
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.trie_changesets_range()
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider2<N> {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.log_index_checkpoint()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider2<N> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
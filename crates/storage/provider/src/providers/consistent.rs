@@ -2,7 +2,8 @@ use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
     providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
     BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
-    HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    HashedAccountStorage, HashedStateReader, HeaderProvider, HistoryReader, ProviderError,
+    PruneCheckpointReader, RawTableReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
     TransactionsProvider, WithdrawalsProvider,
 };
@@ -14,7 +15,7 @@ use alloy_eips::{
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProviderRef};
 use reth_chainspec::{ChainInfo, EthereumHardforks};
-use reth_db::models::BlockNumberAddress;
+use reth_db::{models::BlockNumberAddress, Tables};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_execution_types::{BundleStateInit, ExecutionOutcome, RevertsInit};
@@ -27,12 +28,13 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{DatabaseProviderFactory, StateProvider, StorageChangeSetReader};
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::HashedPostState;
 use revm::{
     db::states::PlainStorageRevert,
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
 };
 use std::{
-    collections::{hash_map, HashMap},
+    collections::{hash_map, BTreeSet, HashMap},
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
@@ -1494,6 +1496,89 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HistoryReader for ConsistentProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        let mut blocks = self.storage_provider.account_history_blocks(address, range.clone())?;
+
+        // In-memory blocks are not indexed yet, so check their bundle state directly.
+        for state in self.head_block.iter().flat_map(|b| b.chain()) {
+            let number = state.number();
+            if range.contains(&number) &&
+                state.block_ref().execution_output.bundle.state.contains_key(&address)
+            {
+                blocks.insert(number);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = self.storage_provider.modified_accounts(range.clone())?;
+
+        // In-memory blocks don't have changesets yet, so check their bundle state directly.
+        for state in self.head_block.iter().flat_map(|b| b.chain()) {
+            if range.contains(&state.number()) {
+                accounts.extend(state.block_ref().execution_output.bundle.state.keys().copied());
+            }
+        }
+
+        Ok(accounts)
+    }
+}
+
+impl<N: ProviderNodeTypes> ConsistentProvider<N> {
+    /// Returns the overlay that needs to be applied on top of the latest persisted hashed state
+    /// to get the hashed state after the given block.
+    ///
+    /// For in-memory blocks, this is the hashed state of all in-memory blocks up to the given
+    /// block on top of the state of their persisted anchor.
+    fn hashed_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<HashedPostState> {
+        let Some(state) =
+            self.head_block.as_ref().and_then(|b| b.block_on_chain(block_number.into()))
+        else {
+            return self.storage_provider.hashed_state_reverts(block_number)
+        };
+
+        let mut hashed_state = self.storage_provider.hashed_state_reverts(state.anchor().number)?;
+        for block_state in state.chain().collect::<Vec<_>>().into_iter().rev() {
+            hashed_state.extend_ref(block_state.block_ref().hashed_state());
+        }
+        Ok(hashed_state)
+    }
+}
+
+impl<N: ProviderNodeTypes> HashedStateReader for ConsistentProvider<N> {
+    fn hashed_accounts_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        let overlay = self.hashed_state_overlay(block_number)?;
+        self.storage_provider.hashed_accounts_range_with_overlay(
+            overlay,
+            start,
+            limit,
+            storage_limit,
+        )
+    }
+}
+
+impl<N: ProviderNodeTypes> RawTableReader for ConsistentProvider<N> {
+    fn table_entries(&self, table: Tables) -> ProviderResult<usize> {
+        self.storage_provider.table_entries(table)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
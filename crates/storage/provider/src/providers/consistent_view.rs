@@ -1,5 +1,6 @@
-use crate::{BlockNumReader, DatabaseProviderFactory, HeaderProvider};
-use alloy_primitives::B256;
+use crate::{BlockHashReader, BlockNumReader, DatabaseProviderFactory, HeaderProvider};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{map::HashMap, BlockNumber, B256};
 use reth_errors::ProviderError;
 use reth_primitives::GotExpected;
 use reth_storage_api::{BlockReader, DBProvider};
@@ -7,6 +8,7 @@ use reth_storage_errors::provider::ProviderResult;
 
 use reth_trie::HashedPostState;
 use reth_trie_db::DatabaseHashedPostState;
+use std::sync::Arc;
 
 pub use reth_storage_errors::provider::ConsistentViewError;
 
@@ -25,10 +27,15 @@ pub use reth_storage_errors::provider::ConsistentViewError;
 /// 1) have a failover for when the state changes and handle [`ConsistentViewError::Inconsistent`]
 ///    appropriately.
 /// 2) be sure that the state does not change.
+///
+/// Alternatively, the view can be created with [`ConsistentDbView::new_with_overlay`] for callers
+/// that overlay the state of blocks that are being written to the database.
 #[derive(Clone, Debug)]
 pub struct ConsistentDbView<Factory> {
     factory: Factory,
     tip: Option<B256>,
+    /// Blocks that the persisted state is allowed to be at, keyed by block number.
+    overlay: Option<Arc<HashMap<BlockNumber, B256>>>,
 }
 
 impl<Factory> ConsistentDbView<Factory>
@@ -37,7 +44,28 @@ where
 {
     /// Creates new consistent database view.
     pub const fn new(factory: Factory, tip: Option<B256>) -> Self {
-        Self { factory, tip }
+        Self { factory, tip, overlay: None }
+    }
+
+    /// Creates new database view over the state of the anchor block that stays consistent while
+    /// the database advances along the given blocks on top of it.
+    ///
+    /// The consumer is expected to overlay the state of all given blocks on top of the database
+    /// state. Since the overlay contains the latest values of all changed keys, combining it with
+    /// the persisted state at the anchor or at any of the overlay blocks results in the same state.
+    /// This allows reading the state while the overlay blocks are being persisted.
+    ///
+    /// Reverts are not supported by this view, see [`Self::revert_state`].
+    pub fn new_with_overlay(
+        factory: Factory,
+        anchor: BlockNumHash,
+        overlay: impl IntoIterator<Item = BlockNumHash>,
+    ) -> Self {
+        let overlay = std::iter::once(anchor)
+            .chain(overlay)
+            .map(|block| (block.number, block.hash))
+            .collect::<HashMap<_, _>>();
+        Self { factory, tip: Some(anchor.hash), overlay: Some(Arc::new(overlay)) }
     }
 
     /// Creates new consistent database view with latest tip.
@@ -49,7 +77,20 @@ where
     }
 
     /// Retrieve revert hashed state down to the given block hash.
+    ///
+    /// Returns [`ConsistentViewError::Inconsistent`] if the view was created with an overlay, since
+    /// the reverts would depend on the block the persisted state is at.
     pub fn revert_state(&self, block_hash: B256) -> ProviderResult<HashedPostState> {
+        if self.overlay.is_some() {
+            if self.tip == Some(block_hash) {
+                return Ok(HashedPostState::default())
+            }
+            return Err(ConsistentViewError::Inconsistent {
+                tip: GotExpected { got: Some(block_hash), expected: self.tip },
+            }
+            .into())
+        }
+
         let provider = self.provider_ro()?;
         let block_number = provider
             .block_number(block_hash)?
@@ -68,6 +109,21 @@ where
         // Create a new provider.
         let provider_ro = self.factory.database_provider_ro()?;
 
+        if let Some(overlay) = &self.overlay {
+            // The blocks are committed to the database together with the stage checkpoints, so the
+            // best block number identifies the persisted state that this provider observes.
+            // Static files are committed first and can't be used for the check.
+            let best_block_number = provider_ro.best_block_number()?;
+            let tip = provider_ro.block_hash(best_block_number)?;
+            if tip.is_none() || overlay.get(&best_block_number) != tip.as_ref() {
+                return Err(ConsistentViewError::Inconsistent {
+                    tip: GotExpected { got: tip, expected: self.tip },
+                }
+                .into())
+            }
+            return Ok(provider_ro)
+        }
+
         // Check that the latest stored header number matches the number
         // that consistent view was initialized with.
         // The mismatch can happen if a new block was appended while
@@ -96,3 +152,56 @@ where
         Ok(provider_ro)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn overlay_view_follows_persisted_blocks() {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        let persist = |index: usize| {
+            let provider_rw = factory.provider_rw().unwrap();
+            let block = blocks[index].clone();
+            let number = block.number;
+            provider_rw.insert_historical_block(block.seal_with_senders().unwrap()).unwrap();
+            provider_rw.update_pipeline_stages(number, false).unwrap();
+            provider_rw.commit().unwrap();
+        };
+        persist(0);
+
+        let view = ConsistentDbView::new_with_overlay(
+            factory.clone(),
+            blocks[0].num_hash(),
+            blocks[1..3].iter().map(|block| block.num_hash()),
+        );
+        let strict_view = ConsistentDbView::new_with_latest_tip(factory.clone()).unwrap();
+        assert!(view.provider_ro().is_ok());
+        assert!(strict_view.provider_ro().is_ok());
+        assert_eq!(view.revert_state(blocks[0].hash()).unwrap(), HashedPostState::default());
+        assert!(view.revert_state(blocks[1].hash()).is_err());
+
+        // The persisted state advances along the overlay blocks
+        persist(1);
+        assert!(view.provider_ro().is_ok());
+        assert!(strict_view.provider_ro().is_err());
+        persist(2);
+        assert!(view.provider_ro().is_ok());
+
+        // The persisted state is past the overlay
+        persist(3);
+        assert!(matches!(
+            view.provider_ro(),
+            Err(ProviderError::ConsistentView(error))
+                if matches!(*error, ConsistentViewError::Inconsistent { .. })
+        ));
+    }
+}
@@ -3,9 +3,10 @@ use crate::{
     to_range,
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    InMemoryTrieInputProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
+    StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{
@@ -28,6 +29,7 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::TryIntoHistoricalStateProvider;
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::TrieInput;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
     ops::{RangeBounds, RangeInclusive},
@@ -38,6 +40,7 @@ use tokio::sync::watch;
 use tracing::trace;
 
 mod provider;
+pub(crate) use provider::trie_changesets_range;
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
 
 use super::ProviderNodeTypes;
@@ -334,6 +337,16 @@ impl<N: ProviderNodeTypes> BlockHashReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> InMemoryTrieInputProvider for ProviderFactory<N> {
+    fn in_memory_trie_input(
+        &self,
+        _block_hash: B256,
+        _persisted_block: BlockNumber,
+    ) -> ProviderResult<Option<TrieInput>> {
+        Ok(None)
+    }
+}
+
 impl<N: ProviderNodeTypes> BlockNumReader for ProviderFactory<N> {
     fn chain_info(&self) -> ProviderResult<ChainInfo> {
         self.provider()?.chain_info()
@@ -645,10 +658,12 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HashedAccountStorage,
+        HashedStateReader, HeaderSyncGapProvider, HistoryReader, HistoryWriter, StorageLocation,
+        TransactionsProvider,
     };
-    use alloy_primitives::{TxNumber, B256, U256};
+    use alloy_consensus::constants::EMPTY_ROOT_HASH;
+    use alloy_primitives::{Address, TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use rand::Rng;
     use reth_chainspec::ChainSpecBuilder;
@@ -657,11 +672,13 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_primitives::StaticFileSegment;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{Account, StaticFileSegment, StorageEntry};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
-    use std::{ops::RangeInclusive, sync::Arc};
+    use reth_trie::root::storage_root;
+    use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -821,4 +838,99 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn account_history_blocks() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        provider
+            .insert_account_history_index([(address, vec![1, 5, 10]), (other, vec![2, 3])])
+            .unwrap();
+        provider
+            .insert_storage_history_index([
+                ((address, B256::with_last_byte(1)), vec![4, 5]),
+                ((address, B256::with_last_byte(2)), vec![12]),
+                ((other, B256::with_last_byte(1)), vec![6]),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=u64::MAX).unwrap(),
+            BTreeSet::from([1, 4, 5, 10, 12])
+        );
+        assert_eq!(
+            provider.account_history_blocks(address, 2..=10).unwrap(),
+            BTreeSet::from([4, 5, 10])
+        );
+        assert!(provider.account_history_blocks(Address::ZERO, 0..=u64::MAX).unwrap().is_empty());
+
+        // slots with multiple shards are only read from the shards that overlap the range
+        let sharded = Address::with_last_byte(3);
+        provider
+            .insert_storage_history_index([
+                ((sharded, B256::with_last_byte(1)), (1..=5000).collect::<Vec<_>>()),
+                ((sharded, B256::with_last_byte(2)), vec![3000, 6000]),
+                ((sharded, B256::repeat_byte(0xff)), vec![7]),
+            ])
+            .unwrap();
+        assert_eq!(
+            provider.account_history_blocks(sharded, 2999..=3001).unwrap(),
+            BTreeSet::from([2999, 3000, 3001])
+        );
+        assert_eq!(
+            provider.account_history_blocks(sharded, 5000..=6000).unwrap(),
+            BTreeSet::from([5000, 6000])
+        );
+        assert_eq!(
+            provider.account_history_blocks(sharded, 5..=7).unwrap(),
+            BTreeSet::from([5, 6, 7])
+        );
+    }
+
+    #[test]
+    fn hashed_accounts_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let accounts = (1..=3)
+            .map(|i| (B256::with_last_byte(i), Account { nonce: i as u64, ..Default::default() }))
+            .collect::<Vec<_>>();
+        for (hashed_address, account) in &accounts {
+            provider.tx_ref().put::<tables::HashedAccounts>(*hashed_address, *account).unwrap();
+        }
+        let slots = (8..=9)
+            .map(|i| StorageEntry { key: B256::with_last_byte(i), value: U256::from(i) })
+            .collect::<Vec<_>>();
+        for slot in &slots {
+            provider.tx_ref().put::<tables::HashedStorages>(accounts[1].0, *slot).unwrap();
+        }
+
+        assert_eq!(
+            provider.hashed_accounts_range(0, B256::with_last_byte(2), 10, 1).unwrap(),
+            vec![
+                HashedAccountStorage {
+                    hashed_address: accounts[1].0,
+                    account: accounts[1].1,
+                    storage_root: storage_root(slots.iter().map(|slot| (slot.key, slot.value))),
+                    storage: vec![(slots[0].key, slots[0].value)],
+                    next_storage_slot: Some(slots[1].key),
+                },
+                HashedAccountStorage {
+                    hashed_address: accounts[2].0,
+                    account: accounts[2].1,
+                    storage_root: EMPTY_ROOT_HASH,
+                    storage: vec![],
+                    next_storage_slot: None,
+                },
+            ]
+        );
+        assert_eq!(provider.hashed_accounts_range(0, B256::ZERO, 1, 0).unwrap().len(), 1);
+        assert_matches!(
+            provider.hashed_accounts_range(1, B256::ZERO, 1, 0),
+            Err(ProviderError::StateForNumberNotFound(1))
+        );
+    }
 }
@@ -12,13 +12,15 @@ use crate::{
     writer::UnifiedStorageWriter,
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
-    DBProvider, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap,
-    HeaderSyncGapProvider, HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter,
-    LatestStateProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
-    PruneCheckpointReader, PruneCheckpointWriter, RevertsInit, StageCheckpointReader,
-    StateChangeWriter, StateProviderBox, StateReader, StateWriter, StaticFileProviderFactory,
-    StatsReader, StorageLocation, StorageReader, StorageTrieWriter, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+    DBProvider, EvmEnvProvider, HashedAccountStorage, HashedStateReader, HashingWriter,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider,
+    HistoricalStateProviderRef, HistoryReader, HistoryWriter, LatestStateProvider,
+    LatestStateProviderRef, LogIndexReader, LogIndexWriter, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, RawTableReader, RevertsInit,
+    StageCheckpointReader, StateChangeWriter, StateProviderBox, StateRangeReader, StateReader,
+    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, StorageReader,
+    StorageTrieWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    TrieChangeSetsReader, TrieChangeSetsWriter, TrieWriter, WithdrawalsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{
@@ -30,14 +32,15 @@ use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_db::{
-    cursor::DbDupCursorRW, tables, BlockNumberList, PlainAccountState, PlainStorageState,
+    cursor::DbDupCursorRW, tables, tables_to_generic, BlockNumberList, PlainAccountState,
+    PlainStorageState, Tables,
 };
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        BlockNumberHashedAddress, ShardedKey, StoredBlockBodyIndices,
     },
     table::Table,
     transaction::{DbTx, DbTxMut},
@@ -49,20 +52,31 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_node_types::{BlockTy, NodeTypes, TxTy};
 use reth_primitives::{
     Account, Block, BlockBody, BlockExt, BlockWithSenders, Bytecode, GotExpected, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry,
+    Receipts, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry,
     TransactionMeta, TransactionSigned, TransactionSignedNoHash,
 };
 use reth_primitives_traits::{BlockBody as _, SignedTransaction};
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
-use reth_stages_types::{StageCheckpoint, StageId};
+use reth_stages_types::{
+    CheckpointBlockRange, EntitiesCheckpoint, IndexHistoryCheckpoint, StageCheckpoint, StageId,
+};
 use reth_storage_api::{StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    metrics::{TrieRootMetrics, TrieType},
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
+    proof::Proof,
+    trie_cursor::InMemoryTrieCursorFactory,
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostState, HashedPostStateSorted, MultiProof, Nibbles, StateRoot, StorageRoot,
+    StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, TrieChangeSetsEntry, TrieInput,
+    TrieInputSorted,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseProof, DatabaseStateRoot, DatabaseStorageTrieCursor,
+    DatabaseTrieCursorFactory, DatabaseTrieUpdates,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm::{
     db::states::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset},
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
@@ -244,6 +258,46 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Moves the changesets of the blocks above the given block from static files back to the
+    /// [`tables::AccountChangeSets`] and [`tables::StorageChangeSets`] tables, so that they can
+    /// be unwound from the database.
+    ///
+    /// The static files are only pruned on commit, which must happen after the database commit,
+    /// see [`UnifiedStorageWriter::commit_unwind`](crate::writer::UnifiedStorageWriter::commit_unwind).
+    pub fn unwind_changesets_from_static_files(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(highest_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest_block| *highest_block > block)
+        {
+            for block_number in block + 1..=highest_block {
+                for account in self.static_file_provider.account_block_changeset(block_number)? {
+                    self.tx.put::<tables::AccountChangeSets>(block_number, account)?;
+                }
+            }
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::AccountChangeSets)?
+                .prune_changesets(highest_block - block)?;
+        }
+
+        if let Some(highest_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest_block| *highest_block > block)
+        {
+            for block_number in block + 1..=highest_block {
+                for (key, entry) in self.static_file_provider.storage_changeset(block_number)? {
+                    self.tx.put::<tables::StorageChangeSets>(key, entry)?;
+                }
+            }
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::StorageChangeSets)?
+                .prune_changesets(highest_block - block)?;
+        }
+
+        Ok(())
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -935,18 +989,83 @@ impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Calls the closure with each account changeset entry of the given blocks, in block order.
+    ///
+    /// The changesets of the blocks that have been moved to static files are read from there.
+    fn for_each_account_changeset(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        mut f: impl FnMut(BlockNumber, AccountBeforeTx),
+    ) -> ProviderResult<()> {
+        let mut range = to_range(range);
+        if let Some(highest_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+        {
+            let end = range.end.min(highest_block + 1);
+            for block_number in range.start..end {
+                for account in self.static_file_provider.account_block_changeset(block_number)? {
+                    f(block_number, account);
+                }
+            }
+            range.start = range.start.max(end);
+        }
+
+        if !range.is_empty() {
+            for entry in self.tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(range)? {
+                let (block_number, account) = entry?;
+                f(block_number, account);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls the closure with each storage changeset entry of the given blocks, in block order.
+    ///
+    /// The changesets of the blocks that have been moved to static files are read from there.
+    fn for_each_storage_changeset(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        mut f: impl FnMut(BlockNumberAddress, StorageEntry),
+    ) -> ProviderResult<()> {
+        let mut range = to_range(range);
+        if let Some(highest_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+        {
+            let end = range.end.min(highest_block + 1);
+            for block_number in range.start..end {
+                for (key, storage) in self.static_file_provider.storage_changeset(block_number)? {
+                    f(key, storage);
+                }
+            }
+            range.start = range.start.max(end);
+        }
+
+        if !range.is_empty() {
+            let range = BlockNumberAddress::range(range.start..=range.end - 1);
+            for entry in self.tx.cursor_read::<tables::StorageChangeSets>()?.walk_range(range)? {
+                let (key, storage) = entry?;
+                f(key, storage);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     fn changed_accounts_with_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        let mut accounts = BTreeSet::new();
+        self.for_each_account_changeset(range, |_, account_before| {
+            accounts.insert(account_before.address);
+        })?;
+        Ok(accounts)
     }
 
     fn basic_accounts(
@@ -964,33 +1083,414 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
-            BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
-                accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
-            },
-        )?;
+        let mut account_transitions = BTreeMap::<Address, Vec<u64>>::new();
+        self.for_each_account_changeset(range, |index, account| {
+            account_transitions.entry(account.address).or_default().push(index);
+        })?;
 
         Ok(account_transitions)
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> HistoryReader for DatabaseProvider<TX, N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        let mut blocks = BTreeSet::new();
+
+        // Account shards are sorted by their highest block number, so we can seek directly to the
+        // first shard that may contain blocks from the range.
+        let mut account_history = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        for entry in account_history.walk(Some(ShardedKey::new(address, *range.start())))? {
+            let (key, list) = entry?;
+            if key.key != address {
+                break
+            }
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+            if key.highest_block_number >= *range.end() {
+                break
+            }
+        }
+
+        // Storage shards are keyed by slot first, so every slot of the account is visited, but only
+        // the shards of a slot that may contain blocks from the range are read.
+        let mut storage_history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut entry =
+            storage_history.seek(StorageShardedKey::new(address, B256::ZERO, *range.start()))?;
+        while let Some((key, list)) = entry {
+            if key.address != address {
+                break
+            }
+            let slot = key.sharded_key.key;
+            if key.sharded_key.highest_block_number < *range.start() {
+                // first shard of the next slot, skip to the shards of the range
+                entry =
+                    storage_history.seek(StorageShardedKey::new(address, slot, *range.start()))?;
+                continue
+            }
+
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+
+            entry = if key.sharded_key.highest_block_number < *range.end() {
+                storage_history.next()?
+            } else if let Some(next_slot) = U256::from_be_bytes(slot.0).checked_add(U256::from(1)) {
+                // the remaining shards of the slot are after the range
+                storage_history.seek(StorageShardedKey::new(
+                    address,
+                    next_slot.into(),
+                    *range.start(),
+                ))?
+            } else {
+                None
+            };
+        }
+
+        Ok(blocks)
+    }
+
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = self.changed_accounts_with_range(range.clone())?;
+        accounts.extend(self.changed_storages_with_range(range)?.into_keys());
+        Ok(accounts)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> TrieChangeSetsReader for DatabaseProvider<TX, N> {
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        trie_changesets_range(&self.tx)
+    }
+}
+
+/// Returns the blocks whose trie changesets are recorded, if they reach the tip of the trie.
+///
+/// See [`TrieChangeSetsReader::trie_changesets_range`].
+pub(crate) fn trie_changesets_range<TX: DbTx>(
+    tx: &TX,
+) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+    let Some(range) = tx
+        .get::<tables::StageCheckpoints>(StageId::TrieChangeSets.to_string())?
+        .and_then(|checkpoint| checkpoint.index_history_stage_checkpoint())
+        .map(|checkpoint| checkpoint.block_range)
+    else {
+        return Ok(None)
+    };
+
+    // The trie is at the checkpoint of the merkle stage, which may have moved on without
+    // recording the changesets, e.g. during pipeline sync.
+    let trie_block = tx
+        .get::<tables::StageCheckpoints>(StageId::MerkleExecute.to_string())?
+        .unwrap_or_default()
+        .block_number;
+    if range.to != trie_block {
+        return Ok(None)
+    }
+
+    // The changesets of pruned blocks may be incomplete.
+    let from = tx
+        .get::<tables::PruneCheckpoints>(PruneSegment::TrieChangeSets)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(range.from, |pruned| range.from.max(pruned + 1));
+
+    Ok(Some(from..=range.to))
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> LogIndexReader for DatabaseProvider<TX, N> {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.get_stage_checkpoint(StageId::IndexLogs)?.map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.sharded_index_blocks::<_, tables::LogAddressHistory>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.sharded_index_blocks::<_, tables::LogTopicHistory>(topic, range)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the blocks of the range from the shards of the key in the given index.
+    fn sharded_index_blocks<K, T>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = BTreeSet::new();
+
+        // Shards are sorted by their highest block number, so we can seek directly to the first
+        // shard that may contain blocks from the range.
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(key.clone(), *range.start())))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes + 'static> DatabaseProvider<TX, N> {
+    /// Returns the hashed state reverts that need to be applied on top of the latest hashed state
+    /// to get the hashed state after the given block.
+    ///
+    /// Changesets of blocks that have been moved to static files are read from there.
+    pub fn hashed_state_reverts(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<HashedPostState> {
+        let tip = self.best_block_number()?;
+        if block_number > tip {
+            return Err(ProviderError::StateForNumberNotFound(block_number))
+        }
+        if block_number == tip {
+            return Ok(HashedPostState::default())
+        }
+
+        // Reverts are read from the changesets, which are pruned together with the history.
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            let pruned = self.get_prune_checkpoint(segment)?.and_then(|c| c.block_number);
+            if pruned.is_some_and(|pruned| block_number <= pruned) {
+                return Err(ProviderError::StateAtBlockPruned(block_number))
+            }
+        }
+
+        // The historical state provider at the next block reverts to the state after this block.
+        HistoricalStateProviderRef::new(self, block_number + 1).revert_state()
+    }
+
+    /// Returns up to `limit` hashed accounts starting at `start`, read from the latest hashed
+    /// state with the given overlay applied.
+    ///
+    /// Storage roots are computed from the storage trie, only the changed parts of the trie are
+    /// recomputed. At most `storage_limit` storage slots are read per account.
+    pub fn hashed_accounts_range_with_overlay(
+        &self,
+        overlay: HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        let mut storage_prefix_sets = overlay.construct_prefix_sets().freeze().storage_prefix_sets;
+        let overlay = overlay.into_sorted();
+        let cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(&self.tx), &overlay);
+        let mut account_cursor = cursor_factory.hashed_account_cursor()?;
+
+        let mut accounts = Vec::new();
+        let mut entry = if limit == 0 { None } else { account_cursor.seek(start)? };
+        while let Some((hashed_address, account)) = entry {
+            let storage_root = StorageRoot::new_hashed(
+                DatabaseTrieCursorFactory::new(&self.tx),
+                cursor_factory.clone(),
+                hashed_address,
+                TrieRootMetrics::new(TrieType::Storage),
+            )
+            .with_prefix_set(storage_prefix_sets.remove(&hashed_address).unwrap_or_default())
+            .root()
+            .map_err(|err| ProviderError::Database(err.into()))?;
+
+            let mut storage_cursor = cursor_factory.hashed_storage_cursor(hashed_address)?;
+            let mut storage = Vec::new();
+            let mut next_storage_slot = None;
+            let mut slot =
+                if storage_limit == 0 { None } else { storage_cursor.seek(B256::ZERO)? };
+            while let Some(entry) = slot {
+                if storage.len() == storage_limit {
+                    next_storage_slot = Some(entry.0);
+                    break
+                }
+                storage.push(entry);
+                slot = storage_cursor.next()?;
+            }
+
+            accounts.push(HashedAccountStorage {
+                hashed_address,
+                account,
+                storage_root,
+                storage,
+                next_storage_slot,
+            });
+            if accounts.len() == limit {
+                break
+            }
+            entry = account_cursor.next()?;
+        }
+
+        Ok(accounts)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes + 'static> HashedStateReader for DatabaseProvider<TX, N> {
+    fn hashed_accounts_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        let overlay = self.hashed_state_reverts(block_number)?;
+        self.hashed_accounts_range_with_overlay(overlay, start, limit, storage_limit)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes + 'static> StateRangeReader for DatabaseProvider<TX, N> {
+    fn revert_trie_input(&self, block_number: BlockNumber) -> ProviderResult<TrieInput> {
+        let revert_state = self.hashed_state_reverts(block_number)?;
+        // The trie changesets of the next block contain the trie nodes after this block.
+        if trie_changesets_range(&self.tx)?.is_some_and(|range| *range.start() <= block_number + 1)
+        {
+            let revert_nodes = TrieUpdates::from_reverts(&self.tx, block_number + 1)?;
+            Ok(TrieInput::new(revert_nodes, revert_state, Default::default()))
+        } else {
+            Ok(TrieInput::from_state(revert_state))
+        }
+    }
+
+    fn storage_trie_root(
+        &self,
+        overlay: &TrieInputSorted,
+        hashed_address: B256,
+    ) -> ProviderResult<B256> {
+        let prefix_set = overlay
+            .prefix_sets
+            .storage_prefix_sets
+            .get(&hashed_address)
+            .cloned()
+            .unwrap_or_default()
+            .freeze();
+        StorageRoot::new_hashed(
+            InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(&self.tx),
+                &overlay.nodes,
+            ),
+            HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(&self.tx),
+                &overlay.state,
+            ),
+            hashed_address,
+            TrieRootMetrics::new(TrieType::Storage),
+        )
+        .with_prefix_set(prefix_set)
+        .root()
+        .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn hashed_account_range(
+        &self,
+        overlay: &TrieInputSorted,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &overlay.state,
+        )
+        .hashed_account_cursor()?;
+        let mut accounts = Vec::new();
+        let mut entry = if limit == 0 { None } else { cursor.seek(start)? };
+        while let Some(account) = entry {
+            accounts.push(account);
+            if accounts.len() == limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+        Ok(accounts)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        overlay: &TrieInputSorted,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &overlay.state,
+        )
+        .hashed_storage_cursor(hashed_address)?;
+        let mut slots = Vec::new();
+        let mut entry = if limit == 0 { None } else { cursor.seek(start)? };
+        while let Some(slot) = entry {
+            slots.push(slot);
+            if slots.len() == limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+        Ok(slots)
+    }
+
+    fn hashed_multiproof(
+        &self,
+        overlay: &TrieInputSorted,
+        targets: alloy_primitives::map::HashMap<B256, alloy_primitives::map::HashSet<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        Proof::from_tx(&self.tx)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(&self.tx),
+                &overlay.nodes,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(&self.tx),
+                &overlay.state,
+            ))
+            .with_prefix_sets_mut(overlay.prefix_sets.clone())
+            .multiproof(targets)
+            .map_err(Into::<ProviderError>::into)
+    }
+
+    fn bytecode_by_code_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.tx.get::<tables::Bytecodes>(code_hash)?)
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
-        let range = block_number..=block_number;
-        let storage_range = BlockNumberAddress::range(range);
-        self.tx
-            .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                |static_file| static_file.storage_changeset(block_number).map(Some),
+                || {
+                    let range = block_number..=block_number;
+                    let storage_range = BlockNumberAddress::range(range);
+                    self.tx
+                        .cursor_dup_read::<tables::StorageChangeSets>()?
+                        .walk_range(storage_range)?
+                        .map(|result| -> ProviderResult<_> { Ok(result?) })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
     }
 }
 
@@ -999,15 +1499,25 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |static_file| static_file.account_block_changeset(block_number).map(Some),
+                || {
+                    let range = block_number..=block_number;
+                    self.tx
+                        .cursor_read::<tables::AccountChangeSets>()?
+                        .walk_range(range)?
+                        .map(|result| -> ProviderResult<_> {
+                            let (_, account_before) = result?;
+                            Ok(account_before)
+                        })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
     }
 }
 
@@ -1832,36 +2342,27 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
-            // fold all storages and save its old state so we can remove it from HashedStorage
-            // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
+        let mut accounts = BTreeMap::<Address, BTreeSet<B256>>::new();
+        self.for_each_storage_changeset(
+            range,
+            |BlockNumberAddress((_, address)), storage_entry| {
                 accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            },
+        )?;
+        Ok(accounts)
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let mut storage_changeset_lists = BTreeMap::<(Address, B256), Vec<u64>>::new();
+        self.for_each_storage_changeset(range, |index, storage| {
+            storage_changeset_lists
+                .entry((index.address(), storage.key))
+                .or_default()
+                .push(index.block_number());
+        })?;
 
         Ok(storage_changeset_lists)
     }
@@ -2692,6 +3193,251 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> TrieChangeSetsWriter for DatabaseProvider<TX, N> {
+    fn write_trie_changesets(
+        &self,
+        blocks: RangeInclusive<BlockNumber>,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::TrieChangeSets)? else {
+            return Ok(())
+        };
+        let (first_block, last_block) = (*blocks.start(), *blocks.end());
+
+        // The changes of a block range can't be attributed to the individual blocks, and a block
+        // that doesn't follow the recorded ones leaves a gap. Either way, the trie can no longer
+        // be reverted across the recorded blocks.
+        let Some(recorded) = checkpoint
+            .index_history_stage_checkpoint()
+            .map(|checkpoint| checkpoint.block_range)
+            .filter(|range| first_block == last_block && range.to + 1 == first_block)
+        else {
+            self.tx.clear::<tables::AccountsTrieChangeSets>()?;
+            self.tx.clear::<tables::StoragesTrieChangeSets>()?;
+            return self.save_trie_changesets_checkpoint(last_block + 1, last_block)
+        };
+
+        // Record the current value of every account trie node that is changed by the updates.
+        let mut account_trie_cursor = self.tx.cursor_read::<tables::AccountsTrie>()?;
+        let mut account_changes = BTreeMap::new();
+        let updated_nodes = trie_updates.account_nodes_ref().iter().map(|(k, v)| (k, Some(v)));
+        let removed_nodes = trie_updates.removed_nodes_ref().iter().map(|k| (k, None));
+        for (nibbles, updated_node) in removed_nodes.chain(updated_nodes) {
+            if nibbles.is_empty() {
+                continue
+            }
+            let node = account_trie_cursor
+                .seek_exact(StoredNibbles(nibbles.clone()))?
+                .map(|(_, node)| node);
+            account_changes.insert(nibbles.clone(), (node, updated_node));
+        }
+
+        let mut cursor = self.tx.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for (nibbles, (node, updated_node)) in account_changes {
+            if node.as_ref() != updated_node {
+                cursor.append_dup(
+                    first_block,
+                    TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )?;
+            }
+        }
+
+        // Record the current value of every storage trie node that is changed by the updates. If
+        // the storage trie is deleted, all of its nodes are changed.
+        let mut storage_trie_cursor = self.tx.cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut cursor = self.tx.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        let mut storage_tries = Vec::from_iter(trie_updates.storage_tries_ref());
+        storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (hashed_address, storage_trie_updates) in storage_tries {
+            let mut storage_changes = BTreeMap::new();
+            if storage_trie_updates.is_deleted() {
+                for entry in storage_trie_cursor.walk_dup(Some(*hashed_address), None)? {
+                    let (_, StorageTrieEntry { nibbles, node }) = entry?;
+                    storage_changes.insert(nibbles.0, Some(node));
+                }
+            }
+
+            let updated_nodes = storage_trie_updates.storage_nodes_ref().keys();
+            let removed_nodes = storage_trie_updates.removed_nodes_ref().iter();
+            for nibbles in removed_nodes.chain(updated_nodes) {
+                if nibbles.is_empty() || storage_changes.contains_key(nibbles) {
+                    continue
+                }
+                let node = if storage_trie_updates.is_deleted() {
+                    None
+                } else {
+                    storage_trie_cursor
+                        .seek_by_key_subkey(*hashed_address, StoredNibblesSubKey(nibbles.clone()))?
+                        .filter(|entry| &entry.nibbles.0 == nibbles)
+                        .map(|entry| entry.node)
+                };
+                // Nodes that remain unchanged don't need to be recorded.
+                if !storage_trie_updates.is_deleted() &&
+                    node.as_ref() == storage_trie_updates.storage_nodes_ref().get(nibbles)
+                {
+                    continue
+                }
+                storage_changes.insert(nibbles.clone(), node);
+            }
+
+            let key = BlockNumberHashedAddress((first_block, *hashed_address));
+            for (nibbles, node) in storage_changes {
+                cursor.append_dup(
+                    key,
+                    TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )?;
+            }
+        }
+
+        self.save_trie_changesets_checkpoint(recorded.from, last_block)
+    }
+
+    fn unwind_trie_changesets_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(recorded) = self
+            .get_stage_checkpoint(StageId::TrieChangeSets)?
+            .and_then(|checkpoint| checkpoint.index_history_stage_checkpoint())
+            .map(|checkpoint| checkpoint.block_range)
+        else {
+            return Ok(())
+        };
+        if recorded.to <= block {
+            return Ok(())
+        }
+
+        self.remove::<tables::AccountsTrieChangeSets>(block + 1..)?;
+        self.remove::<tables::StoragesTrieChangeSets>(
+            BlockNumberHashedAddress((block + 1, B256::ZERO))..,
+        )?;
+
+        self.save_trie_changesets_checkpoint(recorded.from.min(block + 1), block)
+    }
+
+    fn clear_trie_changesets(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::AccountsTrieChangeSets>()?;
+        self.tx.clear::<tables::StoragesTrieChangeSets>()?;
+        self.tx.delete::<tables::StageCheckpoints>(StageId::TrieChangeSets.to_string(), None)?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Saves the checkpoint of the trie changesets, which records the range of blocks whose
+    /// changesets are available.
+    fn save_trie_changesets_checkpoint(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> ProviderResult<()> {
+        self.save_stage_checkpoint(
+            StageId::TrieChangeSets,
+            StageCheckpoint::new(to).with_index_history_stage_checkpoint(IndexHistoryCheckpoint {
+                block_range: CheckpointBlockRange { from, to },
+                progress: EntitiesCheckpoint::default(),
+            }),
+        )
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> LogIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn append_log_indices(
+        &self,
+        first_block: BlockNumber,
+        receipts: &Receipts,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.log_index_checkpoint()? else { return Ok(()) };
+        if receipts.is_empty() || checkpoint + 1 < first_block {
+            return Ok(())
+        }
+        let last_block = first_block + receipts.len() as u64 - 1;
+        if last_block <= checkpoint {
+            return Ok(())
+        }
+
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        for (block, receipts) in (first_block..).zip(receipts.iter()) {
+            if block <= checkpoint {
+                continue
+            }
+            for log in receipts.iter().flatten().flat_map(|receipt| &receipt.logs) {
+                let blocks = addresses.entry(log.address).or_default();
+                if blocks.last() != Some(&block) {
+                    blocks.push(block);
+                }
+                for topic in log.topics() {
+                    let blocks = topics.entry(*topic).or_default();
+                    if blocks.last() != Some(&block) {
+                        blocks.push(block);
+                    }
+                }
+            }
+        }
+
+        self.append_history_index::<_, tables::LogAddressHistory>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicHistory>(topics, ShardedKey::new)?;
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(last_block))?;
+
+        Ok(())
+    }
+
+    fn unwind_log_indices_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.log_index_checkpoint()? else { return Ok(()) };
+        if checkpoint <= block {
+            return Ok(())
+        }
+
+        // The keys of the removed blocks are looked up in their receipts.
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        for number in block + 1..=checkpoint {
+            for receipt in self.receipts_by_block(number.into())?.unwrap_or_default() {
+                for log in receipt.logs {
+                    addresses.insert(log.address);
+                    topics.extend(log.topics().iter().copied());
+                }
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressHistory>()?;
+        for address in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressHistory, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                block + 1,
+                |sharded_key| sharded_key.key == address,
+            )?;
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicHistory>()?;
+        for topic in topics {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicHistory, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                block + 1,
+                |sharded_key| sharded_key.key == topic,
+            )?;
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(block))?;
+
+        Ok(())
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> StateReader for DatabaseProvider<TX, N> {
     fn get_state(&self, block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
         self.get_state(block..=block)
@@ -2706,10 +3452,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         block: BlockNumber,
         remove_transactions_from: StorageLocation,
     ) -> ProviderResult<Chain> {
+        self.unwind_changesets_from_static_files(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
 
+        // the log index is unwound from the receipts, so it has to happen before removing them
+        self.unwind_log_indices_above(block)?;
+        self.unwind_trie_changesets_above(block)?;
+
         // get execution res
         let execution_state = self.take_state(range.clone())?;
 
@@ -2730,10 +3482,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         block: BlockNumber,
         remove_transactions_from: StorageLocation,
     ) -> ProviderResult<()> {
+        self.unwind_changesets_from_static_files(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
 
+        // the log index is unwound from the receipts, so it has to happen before removing them
+        self.unwind_log_indices_above(block)?;
+        self.unwind_trie_changesets_above(block)?;
+
         // remove execution res
         self.remove_state(range)?;
 
@@ -3040,12 +3798,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
         // Must be written after blocks because of the receipt lookup.
         // TODO: should _these_ be moved to storagewriter? seems like storagewriter should be
         // _above_ db provider
+        // Index the logs before the receipts are moved into storage.
+        self.append_log_indices(execution_outcome.first_block, &execution_outcome.receipts)?;
         let mut storage_writer = UnifiedStorageWriter::from_database(self);
         storage_writer.write_to_storage(execution_outcome, OriginalValuesKnown::No)?;
         durations_recorder.record_relative(metrics::Action::InsertState);
 
         // insert hashes and intermediate merkle nodes
         self.write_hashed_state(&hashed_state)?;
+        // The trie changesets record the trie nodes before they are overwritten.
+        self.write_trie_changesets(first_number..=last_block_number, &trie_updates)?;
         self.write_trie_updates(&trie_updates)?;
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
@@ -3102,6 +3864,12 @@ impl<TX: DbTx + 'static, N: NodeTypes> StatsReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> RawTableReader for DatabaseProvider<TX, N> {
+    fn table_entries(&self, table: Tables) -> ProviderResult<usize> {
+        tables_to_generic!(table, |Table| self.count_entries::<Table>())
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvider<TX, N> {
     fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
         let mut finalized_blocks = self
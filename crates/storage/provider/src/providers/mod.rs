@@ -2,10 +2,12 @@ use crate::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
     CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, FullExecutionDataProvider, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    DatabaseProviderFactory, EvmEnvProvider, FullExecutionDataProvider, HashedAccountStorage,
+    HashedStateReader, HeaderProvider, HistoryReader, InMemoryTrieInputProvider, LogIndexReader,
+    ProviderError, PruneCheckpointReader, RawTableReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    TransactionVariant, TransactionsProvider, TreeViewer, TrieChangeSetsReader,
+    WithdrawalsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{
@@ -20,7 +22,7 @@ use reth_blockchain_tree_api::{
 };
 use reth_chain_state::{ChainInfoTracker, ForkChoiceNotifications, ForkChoiceSubscriptions};
 use reth_chainspec::{ChainInfo, EthereumHardforks};
-use reth_db::table::Value;
+use reth_db::{table::Value, Tables};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_node_types::{FullNodePrimitives, NodeTypes, NodeTypesWithDB, TxTy};
@@ -31,9 +33,10 @@ use reth_primitives::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::TrieInput;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
@@ -46,8 +49,8 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    restore_static_file_backup, StaticFileAccess, StaticFileJarProvider, StaticFileProvider,
+    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriter, STATIC_FILE_BACKUP_DIR,
 };
 
 mod state;
@@ -957,6 +960,84 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+// The blockchain tree writes canonical blocks, including their history indices, to the database
+// when they are made canonical. Only side chain blocks are kept in memory, so the database covers
+// the whole canonical chain.
+impl<N: ProviderNodeTypes> HistoryReader for BlockchainProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.database.provider()?.account_history_blocks(address, range)
+    }
+
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.database.provider()?.modified_accounts(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> HashedStateReader for BlockchainProvider<N> {
+    fn hashed_accounts_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        self.database.provider()?.hashed_accounts_range(block_number, start, limit, storage_limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> RawTableReader for BlockchainProvider<N> {
+    fn table_entries(&self, table: Tables) -> ProviderResult<usize> {
+        self.database.provider()?.table_entries(table)
+    }
+}
+
+// The tree writes canonical blocks to the database when they are made canonical, so there are no
+// in-memory canonical blocks.
+impl<N: ProviderNodeTypes> InMemoryTrieInputProvider for BlockchainProvider<N> {
+    fn in_memory_trie_input(
+        &self,
+        _block_hash: B256,
+        _persisted_block: BlockNumber,
+    ) -> ProviderResult<Option<TrieInput>> {
+        Ok(None)
+    }
+}
+
+impl<N: ProviderNodeTypes> TrieChangeSetsReader for BlockchainProvider<N> {
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.trie_changesets_range()
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider<N> {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.log_index_checkpoint()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
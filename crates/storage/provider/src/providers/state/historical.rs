@@ -1,20 +1,24 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    ProviderError, StateProvider, StateRootProvider,
+    providers::{database::trie_changesets_range, state::macros::delegate_provider_impls},
+    AccountReader, BlockHashReader, ChangeSetReader, ProviderError, StateProvider,
+    StateRootProvider, StaticFileProviderFactory, StorageChangeSetReader,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
 use alloy_primitives::{
+    keccak256,
     map::{HashMap, HashSet},
-    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     transaction::DbTx,
 };
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StaticFileSegment};
 use reth_storage_api::{BlockNumReader, DBProvider, StateProofProvider, StorageRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -25,7 +29,7 @@ use reth_trie::{
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieUpdates, DatabaseTrieWitness,
 };
 use std::fmt::Debug;
 
@@ -40,6 +44,9 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets of blocks that have been moved to static files are read from the
+/// [`StaticFileSegment::AccountChangeSets`] and [`StaticFileSegment::StorageChangeSets`] segments.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -58,7 +65,9 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, Provider> {
+impl<'b, Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProviderRef<'b, Provider>
+{
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
         Self { provider, block_number, lowest_available_blocks: Default::default() }
@@ -115,8 +124,25 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         Ok(tip.saturating_sub(self.block_number) > limit)
     }
 
+    /// Prepends the revert hashed state of this history provider to the trie input.
+    ///
+    /// If the trie changesets cover this block, the trie nodes as they were at this block are
+    /// prepended as well, so the trie doesn't need to be recomputed for the reverted state.
+    fn prepend_reverts(&self, input: &mut TrieInput) -> ProviderResult<()> {
+        let revert_state = self.revert_state()?;
+        if trie_changesets_range(self.tx())?
+            .is_some_and(|range| *range.start() <= self.block_number)
+        {
+            let revert_nodes = TrieUpdates::from_reverts(self.tx(), self.block_number)?;
+            input.prepend_cached(revert_nodes, revert_state);
+        } else {
+            input.prepend(revert_state);
+        }
+        Ok(())
+    }
+
     /// Retrieve revert hashed state for this history provider.
-    fn revert_state(&self) -> ProviderResult<HashedPostState> {
+    pub(crate) fn revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -131,7 +157,61 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
             );
         }
 
-        Ok(HashedPostState::from_reverts(self.tx(), self.block_number)?)
+        let highest_account_block =
+            self.highest_static_file_changeset_block(StaticFileSegment::AccountChangeSets);
+        let highest_storage_block =
+            self.highest_static_file_changeset_block(StaticFileSegment::StorageChangeSets);
+        if highest_account_block.is_none() && highest_storage_block.is_none() {
+            return Ok(HashedPostState::from_reverts(self.tx(), self.block_number)?)
+        }
+
+        // The changesets in static files take precedence over the ones in the database, because
+        // the value before the first change after the block is the one to revert to.
+        let next_block = |highest_block: Option<BlockNumber>| {
+            highest_block.map_or(self.block_number, |highest_block| highest_block + 1)
+        };
+        let mut revert_state = HashedPostState::from_reverts(
+            self.tx(),
+            next_block(highest_account_block).min(next_block(highest_storage_block)),
+        )?;
+
+        let static_file_provider = self.provider.static_file_provider();
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
+        for block_number in self.block_number..next_block(highest_account_block) {
+            for AccountBeforeTx { address, info } in
+                static_file_provider.account_block_changeset(block_number)?
+            {
+                accounts.entry(address).or_insert(info);
+            }
+        }
+        for block_number in self.block_number..next_block(highest_storage_block) {
+            for (BlockNumberAddress((_, address)), storage) in
+                static_file_provider.storage_changeset(block_number)?
+            {
+                storages.entry(address).or_default().entry(storage.key).or_insert(storage.value);
+            }
+        }
+        revert_state.extend(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (keccak256(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        });
+
+        Ok(revert_state)
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -148,7 +228,37 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let Some(highest_static_file_block) =
+            self.highest_static_file_changeset_block(StaticFileSegment::StorageChangeSets)
+        else {
+            return Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        };
+
+        let mut revert_storage =
+            HashedStorage::from_reverts(self.tx(), address, highest_static_file_block + 1)?;
+
+        let static_file_provider = self.provider.static_file_provider();
+        let mut storage = HashMap::<B256, U256>::default();
+        for block_number in self.block_number..=highest_static_file_block {
+            for entry in static_file_provider.address_storage_changeset(block_number, address)? {
+                storage.entry(keccak256(entry.key)).or_insert(entry.value);
+            }
+        }
+        revert_storage.extend(&HashedStorage::from_iter(false, storage));
+
+        Ok(revert_storage)
+    }
+
+    /// Returns the highest block of the changeset static file segment, if it includes the block of
+    /// this provider.
+    fn highest_static_file_changeset_block(
+        &self,
+        segment: StaticFileSegment,
+    ) -> Option<BlockNumber> {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .filter(|highest_block| *highest_block >= self.block_number)
     }
 
     fn history_info<T, K>(
@@ -233,29 +343,41 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provider> {
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProviderRef<'_, Provider>
+{
     fn tx(&self) -> &Provider::Tx {
         self.provider.tx_ref()
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> AccountReader
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> AccountReader
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
-                .ok_or(ProviderError::AccountChangesetNotFound {
-                    block_number: changeset_block_number,
-                    address,
-                })?
-                .info),
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let static_file_provider = self.provider.static_file_provider();
+                let account_before = if static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+                    .is_some_and(|highest_block| highest_block >= changeset_block_number)
+                {
+                    static_file_provider.account_changeset_entry(changeset_block_number, address)?
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::AccountChangeSets>()?
+                        .seek_by_key_subkey(changeset_block_number, address)?
+                        .filter(|acc| acc.address == address)
+                };
+                Ok(account_before
+                    .ok_or(ProviderError::AccountChangesetNotFound {
+                        block_number: changeset_block_number,
+                        address,
+                    })?
+                    .info)
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
                 Ok(self.tx().get::<tables::PlainAccountState>(address)?)
             }
@@ -263,8 +385,8 @@ impl<Provider: DBProvider + BlockNumReader> AccountReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + BlockHashReader>
+    BlockHashReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
@@ -280,18 +402,15 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StateRootProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StateRootProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
-        let mut revert_state = self.revert_state()?;
-        revert_state.extend(hashed_state);
-        StateRoot::overlay_root(self.tx(), revert_state)
-            .map_err(|err| ProviderError::Database(err.into()))
+        self.state_root_from_nodes(TrieInput::from_state(hashed_state))
     }
 
     fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         StateRoot::overlay_root_from_nodes(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
@@ -300,23 +419,20 @@ impl<Provider: DBProvider + BlockNumReader> StateRootProvider
         &self,
         hashed_state: HashedPostState,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        let mut revert_state = self.revert_state()?;
-        revert_state.extend(hashed_state);
-        StateRoot::overlay_root_with_updates(self.tx(), revert_state)
-            .map_err(|err| ProviderError::Database(err.into()))
+        self.state_root_from_nodes_with_updates(TrieInput::from_state(hashed_state))
     }
 
     fn state_root_from_nodes_with_updates(
         &self,
         mut input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         StateRoot::overlay_root_from_nodes_with_updates(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StorageRootProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StorageRootProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
@@ -343,7 +459,7 @@ impl<Provider: DBProvider + BlockNumReader> StorageRootProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StateProofProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StateProofProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
@@ -353,7 +469,7 @@ impl<Provider: DBProvider + BlockNumReader> StateProofProvider
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_account_proof(self.tx(), input, address, slots)
             .map_err(Into::<ProviderError>::into)
     }
@@ -363,7 +479,7 @@ impl<Provider: DBProvider + BlockNumReader> StateProofProvider
         mut input: TrieInput,
         targets: HashMap<B256, HashSet<B256>>,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(Into::<ProviderError>::into)
     }
 
@@ -372,13 +488,13 @@ impl<Provider: DBProvider + BlockNumReader> StateProofProvider
         mut input: TrieInput,
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         TrieWitness::overlay_witness(self.tx(), input, target).map_err(Into::<ProviderError>::into)
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + BlockHashReader>
+    StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -388,18 +504,33 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
-                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
-                        block_number: changeset_block_number,
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let static_file_provider = self.provider.static_file_provider();
+                let entry = if static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+                    .is_some_and(|highest_block| highest_block >= changeset_block_number)
+                {
+                    static_file_provider.storage_changeset_entry(
+                        changeset_block_number,
                         address,
-                        storage_key: Box::new(storage_key),
-                    })?
-                    .value,
-            )),
+                        storage_key,
+                    )?
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::StorageChangeSets>()?
+                        .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                };
+                Ok(Some(
+                    entry
+                        .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                            block_number: changeset_block_number,
+                            address,
+                            storage_key: Box::new(storage_key),
+                        })?
+                        .value,
+                ))
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
                 .tx()
                 .cursor_dup_read::<tables::PlainStorageState>()?
@@ -428,7 +559,9 @@ pub struct HistoricalStateProvider<Provider> {
     lowest_available_blocks: LowestAvailableBlocks,
 }
 
-impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProvider<Provider>
+{
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
         Self { provider, block_number, lowest_available_blocks: Default::default() }
@@ -464,7 +597,7 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + BlockHashReader]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -500,6 +633,7 @@ mod tests {
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StaticFileProviderFactory,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db::{tables, BlockNumberList};
@@ -517,7 +651,9 @@ mod tests {
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[allow(dead_code)]
-    const fn assert_historical_state_provider<T: DBProvider + BlockNumReader + BlockHashReader>() {
+    const fn assert_historical_state_provider<
+        T: DBProvider + BlockNumReader + StaticFileProviderFactory + BlockHashReader,
+    >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
 
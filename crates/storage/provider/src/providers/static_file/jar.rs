@@ -3,8 +3,8 @@ use super::{
     LoadedJarRef,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
-    TransactionsProvider,
+    to_range, BlockHashReader, BlockNumReader, ChangeSetReader, HeaderProvider, ReceiptProvider,
+    StorageChangeSetReader, TransactionsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
@@ -12,13 +12,19 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
 use reth_chainspec::ChainInfo;
 use reth_db::{
     static_file::{
-        BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor,
-        TDWithHashMask, TotalDifficultyMask, TransactionMask,
+        AccountChangeSetMask, BlockHashMask, ColumnSelectorOne, HeaderMask, HeaderWithHashMask,
+        ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask, TotalDifficultyMask,
+        TransactionMask,
     },
     table::Decompress,
 };
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StoredAccountChangeSet, StoredStorageChangeSet,
+};
 use reth_node_types::NodePrimitives;
-use reth_primitives::{transaction::recover_signers, Receipt, SealedHeader, TransactionMeta};
+use reth_primitives::{
+    transaction::recover_signers, Receipt, SealedHeader, StorageEntry, TransactionMeta,
+};
 use reth_primitives_traits::SignedTransaction;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -338,3 +344,87 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> ReceiptProvide
         Ok(receipts)
     }
 }
+
+impl<N: NodePrimitives> StaticFileJarProvider<'_, N> {
+    /// Returns the account with the given address from the account changeset of the block,
+    /// without decoding the rest of the changeset.
+    pub fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        let mut cursor = self.cursor()?;
+        Ok(cursor
+            .get(block_number.into(), AccountChangeSetMask::MASK)?
+            .and_then(|row| StoredAccountChangeSet::find_account(row[0], address)))
+    }
+
+    /// Returns the storage slot with the given address and key from the storage changeset of the
+    /// block, without decoding the rest of the changeset.
+    pub fn storage_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        let mut cursor = self.cursor()?;
+        Ok(cursor
+            .get(block_number.into(), StorageChangeSetMask::MASK)?
+            .and_then(|row| StoredStorageChangeSet::find_storage(row[0], address, storage_key))
+            .map(|storage| StorageEntry { key: storage.key, value: storage.value }))
+    }
+
+    /// Returns the storage slots of the given address from the storage changeset of the block,
+    /// without decoding the slots of other addresses.
+    pub fn address_storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        let mut cursor = self.cursor()?;
+        Ok(cursor
+            .get(block_number.into(), StorageChangeSetMask::MASK)?
+            .map(|row| StoredStorageChangeSet::find_storages(row[0], address))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|storage| StorageEntry { key: storage.key, value: storage.value })
+            .collect())
+    }
+}
+
+impl<N: NodePrimitives> ChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask>(block_number.into())?
+            .map(|changeset| changeset.accounts)
+            .unwrap_or_default())
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .map(|changeset| {
+                changeset
+                    .storages
+                    .into_iter()
+                    .map(|storage| {
+                        (
+                            BlockNumberAddress((block_number, storage.address)),
+                            StorageEntry { key: storage.key, value: storage.value },
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
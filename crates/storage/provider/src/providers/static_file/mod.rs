@@ -12,7 +12,38 @@ mod metrics;
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, path::Path, sync::Arc};
+use tracing::warn;
+
+/// Directory inside the static files directory that the files of a static file are moved to
+/// while they're replaced with rewritten ones, e.g. by `reth db static-file recompress`.
+///
+/// If it exists when the static files are opened, the replacement was interrupted and the files
+/// in it are moved back, see [`restore_static_file_backup`].
+pub const STATIC_FILE_BACKUP_DIR: &str = "recompress-backup";
+
+/// Moves back the original files left in the [`STATIC_FILE_BACKUP_DIR`] by an interrupted
+/// replacement, so that the data, offsets and configuration files of every static file match
+/// again.
+///
+/// The backup directory is only removed once all replacements are complete, so restoring it rolls
+/// the static files back to their state before the replacement.
+pub fn restore_static_file_backup(directory: &Path) -> ProviderResult<()> {
+    let backup_dir = directory.join(STATIC_FILE_BACKUP_DIR);
+    if !backup_dir.exists() {
+        return Ok(())
+    }
+
+    let fs_error = |err: reth_fs_util::FsPathError| ProviderError::FsPathError(err.to_string());
+    for entry in reth_fs_util::read_dir(&backup_dir).map_err(fs_error)? {
+        let entry = entry.map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+        warn!(target: "provider::static_file", file = ?entry.file_name(), "Restoring static file from interrupted replacement");
+        reth_fs_util::rename(entry.path(), directory.join(entry.file_name())).map_err(fs_error)?;
+    }
+    reth_fs_util::remove_dir_all(&backup_dir).map_err(fs_error)?;
+
+    Ok(())
+}
 
 /// Alias type for each specific `NippyJar`.
 type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment), LoadedJar>;
@@ -56,23 +87,33 @@ impl Deref for LoadedJar {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
+        test_utils::create_test_provider_factory, AccountExtReader, HeaderProvider,
+        StaticFileProviderFactory, StorageReader,
     };
     use alloy_consensus::{Header, Transaction};
-    use alloy_primitives::{BlockHash, TxNumber, B256, U256};
+    use alloy_primitives::{Address, BlockHash, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::{
-        test_utils::create_test_static_files_dir, CanonicalHeaders, HeaderNumbers,
+        tables, test_utils::create_test_static_files_dir, CanonicalHeaders, HeaderNumbers,
         HeaderTerminalDifficulties, Headers,
     };
-    use reth_db_api::transaction::DbTxMut;
+    use reth_db_api::{
+        models::{AccountBeforeTx, StorageBeforeTx},
+        transaction::DbTxMut,
+    };
     use reth_primitives::{
         static_file::{find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE},
-        EthPrimitives, Receipt, TransactionSigned,
+        Account, EthPrimitives, Receipt, StorageEntry, TransactionSigned,
     };
     use reth_storage_api::{ReceiptProvider, TransactionsProvider};
     use reth_testing_utils::generators::{self, random_header_range};
-    use std::{fmt::Debug, fs, ops::Range, path::Path};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        fmt::Debug,
+        fs,
+        ops::Range,
+        path::Path,
+    };
 
     fn assert_eyre<T: PartialEq + Debug>(got: T, expected: T, msg: &str) -> eyre::Result<()> {
         if got != expected {
@@ -549,4 +590,93 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_changeset_lookup() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        let address = |byte| Address::with_last_byte(byte);
+        let account = |byte, nonce| AccountBeforeTx {
+            address: address(byte),
+            info: Some(Account { nonce, ..Default::default() }),
+        };
+        let storage = |byte, slot| StorageBeforeTx {
+            address: address(byte),
+            key: B256::with_last_byte(slot),
+            value: U256::from(slot),
+        };
+
+        // The changesets of blocks 0 and 1 are in static files, appended in any order.
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            writer.append_account_changeset(0, vec![account(3, 0), account(1, 0)]).unwrap();
+            writer.append_account_changeset(1, vec![account(2, 1)]).unwrap();
+            writer.commit().unwrap();
+
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            writer
+                .append_storage_changeset(0, vec![storage(2, 1), storage(1, 2), storage(1, 1)])
+                .unwrap();
+            writer.append_storage_changeset(1, vec![]).unwrap();
+            writer.commit().unwrap();
+        }
+
+        // The changesets of block 2 are in the database.
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::AccountChangeSets>(2, account(4, 2)).unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::StorageChangeSets>(
+                (2, address(4)).into(),
+                StorageEntry { key: B256::with_last_byte(3), value: U256::from(3) },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            static_file_provider.account_changeset_entry(0, address(3)).unwrap(),
+            Some(account(3, 0))
+        );
+        assert_eq!(static_file_provider.account_changeset_entry(0, address(2)).unwrap(), None);
+        assert_eq!(
+            static_file_provider
+                .storage_changeset_entry(0, address(1), B256::with_last_byte(2))
+                .unwrap(),
+            Some(StorageEntry { key: B256::with_last_byte(2), value: U256::from(2) })
+        );
+        assert_eq!(
+            static_file_provider.address_storage_changeset(0, address(1)).unwrap(),
+            vec![
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) },
+                StorageEntry { key: B256::with_last_byte(2), value: U256::from(2) },
+            ]
+        );
+        assert!(static_file_provider.address_storage_changeset(1, address(1)).unwrap().is_empty());
+
+        // Changesets are read from static files and the database.
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.changed_accounts_with_range(1..).unwrap(),
+            BTreeSet::from([address(2), address(4)])
+        );
+        assert_eq!(
+            provider.changed_accounts_and_blocks_with_range(0..=2).unwrap(),
+            BTreeMap::from([
+                (address(1), vec![0]),
+                (address(2), vec![1]),
+                (address(3), vec![0]),
+                (address(4), vec![2]),
+            ])
+        );
+        assert_eq!(
+            provider.changed_storages_with_range(0..=2).unwrap(),
+            BTreeMap::from([
+                (address(1), BTreeSet::from([B256::with_last_byte(1), B256::with_last_byte(2)])),
+                (address(2), BTreeSet::from([B256::with_last_byte(1)])),
+                (address(4), BTreeSet::from([B256::with_last_byte(3)])),
+            ])
+        );
+    }
 }
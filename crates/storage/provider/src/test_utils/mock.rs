@@ -2,8 +2,10 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     ChainSpecProvider, ChangeSetReader, DatabaseProvider, EthStorage, EvmEnvProvider,
-    HeaderProvider, ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory,
-    StateReader, StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    HashedAccountStorage, HashedStateReader, HeaderProvider, HistoryReader, LogIndexReader,
+    RawTableReader, ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory,
+    StateReader, StateRootProvider, TransactionVariant, TransactionsProvider, TrieChangeSetsReader,
+    WithdrawalsProvider,
 };
 use alloy_consensus::{constants::EMPTY_ROOT_HASH, Header};
 use alloy_eips::{
@@ -17,7 +19,10 @@ use alloy_primitives::{
 };
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
-use reth_db::mock::{DatabaseMock, TxMock};
+use reth_db::{
+    mock::{DatabaseMock, TxMock},
+    Tables,
+};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_execution_types::ExecutionOutcome;
@@ -38,7 +43,7 @@ use reth_trie::{
 use reth_trie_db::MerklePatriciaTrie;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -815,6 +820,69 @@ impl ChangeSetReader for MockEthProvider {
     }
 }
 
+impl HistoryReader for MockEthProvider {
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn modified_accounts(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::new())
+    }
+}
+
+impl HashedStateReader for MockEthProvider {
+    fn hashed_accounts_range(
+        &self,
+        _block_number: BlockNumber,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        Ok(Vec::new())
+    }
+}
+
+impl RawTableReader for MockEthProvider {
+    fn table_entries(&self, _table: Tables) -> ProviderResult<usize> {
+        Ok(0)
+    }
+}
+
+impl TrieChangeSetsReader for MockEthProvider {
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+}
+
+impl LogIndexReader for MockEthProvider {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+}
+
 impl StateReader for MockEthProvider {
     fn get_state(&self, _block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
         Ok(None)
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     ops::{RangeBounds, RangeInclusive},
     path::PathBuf,
     sync::Arc,
@@ -18,6 +19,7 @@ use reth_chain_state::{
     ForkChoiceSubscriptions,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
+use reth_db::Tables;
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_errors::ProviderError;
 use reth_evm::ConfigureEvmEnv;
@@ -39,10 +41,12 @@ use crate::{
     providers::StaticFileProvider,
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HashedAccountStorage, HashedStateReader,
+    HeaderProvider, HistoryReader, InMemoryTrieInputProvider, LogIndexReader,
+    PruneCheckpointReader, RawTableReader, ReceiptProviderIdExt, StageCheckpointReader,
+    StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TrieChangeSetsReader,
+    WithdrawalsProvider,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -329,6 +333,79 @@ impl ChangeSetReader for NoopProvider {
     }
 }
 
+impl HistoryReader for NoopProvider {
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn modified_accounts(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::new())
+    }
+}
+
+impl HashedStateReader for NoopProvider {
+    fn hashed_accounts_range(
+        &self,
+        _block_number: BlockNumber,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>> {
+        Ok(Vec::new())
+    }
+}
+
+impl RawTableReader for NoopProvider {
+    fn table_entries(&self, _table: Tables) -> ProviderResult<usize> {
+        Ok(0)
+    }
+}
+
+impl InMemoryTrieInputProvider for NoopProvider {
+    fn in_memory_trie_input(
+        &self,
+        _block_hash: B256,
+        _persisted_block: BlockNumber,
+    ) -> ProviderResult<Option<TrieInput>> {
+        Ok(None)
+    }
+}
+
+impl TrieChangeSetsReader for NoopProvider {
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+}
+
+impl LogIndexReader for NoopProvider {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>> {
+        Ok(BTreeSet::new())
+    }
+}
+
 impl StateRootProvider for NoopProvider {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
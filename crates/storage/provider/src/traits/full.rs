@@ -1,9 +1,11 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TransactionsProvider,
+    AccountReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, EvmEnvProvider, HashedStateReader, HeaderProvider, HistoryReader,
+    InMemoryTrieInputProvider, LogIndexReader, RawTableReader, StageCheckpointReader,
+    StateProviderFactory, StateRangeReader, StaticFileProviderFactory, TransactionsProvider,
+    TrieChangeSetsReader,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_chainspec::EthereumHardforks;
@@ -11,7 +13,7 @@ use reth_node_types::{NodeTypesWithDB, TxTy};
 
 /// Helper trait to unify all provider traits for simplicity.
 pub trait FullProvider<N: NodeTypesWithDB>:
-    DatabaseProviderFactory<DB = N::DB>
+    DatabaseProviderFactory<DB = N::DB, Provider: StateRangeReader + BlockNumReader>
     + StaticFileProviderFactory<Primitives = N::Primitives>
     + BlockReaderIdExt<Transaction = TxTy<N>>
     + AccountReader
@@ -19,6 +21,12 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + EvmEnvProvider
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + HistoryReader
+    + LogIndexReader
+    + TrieChangeSetsReader
+    + InMemoryTrieInputProvider
+    + HashedStateReader
+    + RawTableReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions
     + StageCheckpointReader
@@ -29,7 +37,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
 }
 
 impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
-    T: DatabaseProviderFactory<DB = N::DB>
+    T: DatabaseProviderFactory<DB = N::DB, Provider: StateRangeReader + BlockNumReader>
         + StaticFileProviderFactory<Primitives = N::Primitives>
         + BlockReaderIdExt<Transaction = TxTy<N>>
         + AccountReader
@@ -37,6 +45,12 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + EvmEnvProvider
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + HistoryReader
+        + LogIndexReader
+        + TrieChangeSetsReader
+        + InMemoryTrieInputProvider
+        + HashedStateReader
+        + RawTableReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions
         + StageCheckpointReader
@@ -1,8 +1,8 @@
 use crate::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter as SfWriter},
     writer::static_file::StaticFileWriter,
-    BlockExecutionWriter, BlockWriter, HistoryWriter, StateChangeWriter, StateWriter,
-    StaticFileProviderFactory, StorageLocation, TrieWriter,
+    BlockExecutionWriter, BlockWriter, HistoryWriter, LogIndexWriter, StateChangeWriter,
+    StateWriter, StaticFileProviderFactory, StorageLocation, TrieChangeSetsWriter, TrieWriter,
 };
 use alloy_consensus::Header;
 use alloy_primitives::{BlockNumber, B256, U256};
@@ -152,7 +152,9 @@ where
         + TransactionsProviderExt
         + StateChangeWriter
         + TrieWriter
+        + TrieChangeSetsWriter
         + HistoryWriter
+        + LogIndexWriter
         + StageCheckpointWriter
         + BlockExecutionWriter
         + AsRef<ProviderDB>
@@ -201,6 +203,8 @@ where
             // Write state and changesets to the database.
             // Must be written after blocks because of the receipt lookup.
             let execution_outcome = block.execution_outcome().clone();
+            self.database()
+                .append_log_indices(execution_outcome.first_block, &execution_outcome.receipts)?;
             state_writer.write_to_storage(execution_outcome, OriginalValuesKnown::No)?;
 
             // insert hashes and intermediate merkle nodes
@@ -208,6 +212,10 @@ where
                 let trie_updates = block.trie_updates().clone();
                 let hashed_state = block.hashed_state();
                 self.database().write_hashed_state(&hashed_state.clone().into_sorted())?;
+                self.database().write_trie_changesets(
+                    block.block.number..=block.block.number,
+                    &trie_updates,
+                )?;
                 self.database().write_trie_updates(&trie_updates)?;
             }
         }
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use auto_impl::auto_impl;
 use reth_db::models::{AccountBeforeTx, BlockNumberAddress};
 use reth_primitives::{Account, StorageEntry};
@@ -8,6 +8,38 @@ use std::{
     ops::{RangeBounds, RangeInclusive},
 };
 
+/// A hashed account of the state together with its storage root and storage slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedAccountStorage {
+    /// Hashed address of the account.
+    pub hashed_address: B256,
+    /// The account.
+    pub account: Account,
+    /// Storage root of the account.
+    pub storage_root: B256,
+    /// Non-zero storage slots of the account keyed by hashed slot, ordered by hashed slot.
+    pub storage: Vec<(B256, U256)>,
+    /// The hashed slot the storage continues at, if not all slots were read.
+    pub next_storage_slot: Option<B256>,
+}
+
+/// Hashed state reader
+#[auto_impl(&, Arc, Box)]
+pub trait HashedStateReader: Send + Sync {
+    /// Returns up to `limit` accounts of the state after the given block together with up to
+    /// `storage_limit` of their non-zero storage slots.
+    ///
+    /// Accounts are ordered by hashed address starting at the `start` hashed address, storage
+    /// slots are ordered by hashed slot.
+    fn hashed_accounts_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<Vec<HashedAccountStorage>>;
+}
+
 /// Hashing Writer
 #[auto_impl(&, Arc, Box)]
 pub trait HashingWriter: Send + Sync {
@@ -3,7 +3,34 @@ use auto_impl::auto_impl;
 use reth_db::models::{AccountBeforeTx, BlockNumberAddress};
 use reth_primitives::StorageEntry;
 use reth_storage_errors::provider::ProviderResult;
-use std::ops::{RangeBounds, RangeInclusive};
+use std::{
+    collections::BTreeSet,
+    ops::{RangeBounds, RangeInclusive},
+};
+
+/// History reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send + Sync {
+    /// Returns all blocks in the given range in which the account, or any of its storage slots,
+    /// changed.
+    ///
+    /// The blocks are looked up in the account and storage history indices.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>;
+
+    /// Returns all accounts whose info or storage changed in the given block range.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+}
 
 /// History Writer
 #[auto_impl(&, Arc, Box)]
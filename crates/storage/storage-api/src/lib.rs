@@ -63,6 +63,12 @@ pub mod noop;
 mod history;
 pub use history::*;
 
+mod log_index;
+pub use log_index::*;
+
+mod trie_changesets;
+pub use trie_changesets::*;
+
 mod hashing;
 pub use hashing::*;
 mod stats;
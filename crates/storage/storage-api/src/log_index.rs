@@ -0,0 +1,58 @@
+use alloy_primitives::{Address, BlockNumber, B256};
+use auto_impl::auto_impl;
+use reth_primitives::Receipts;
+use reth_storage_errors::provider::ProviderResult;
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+/// Log index reader
+///
+/// The log index maps the addresses and topics of logs to the blocks that contain them. It's
+/// optional and only maintained if the [`IndexLogs`](reth_stages_types::StageId::IndexLogs) stage
+/// is enabled.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the highest block that is covered by the log index.
+    ///
+    /// Returns `None` if the log index is not maintained.
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns all blocks in the given range that contain a log emitted by the address.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>;
+
+    /// Returns all blocks in the given range that contain a log with the topic, at any topic
+    /// position.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>;
+}
+
+/// Log index writer
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Inserts the logs of the receipts into the log index and advances its checkpoint.
+    ///
+    /// The receipts are grouped by block, starting at `first_block`. Blocks that are already
+    /// indexed are skipped. This is a no-op if the log index is not maintained or doesn't cover
+    /// the blocks before `first_block`.
+    fn append_log_indices(
+        &self,
+        first_block: BlockNumber,
+        receipts: &Receipts,
+    ) -> ProviderResult<()>;
+
+    /// Removes all blocks above the given block from the log index.
+    ///
+    /// The receipts of the removed blocks must still be available. This is a no-op if the log
+    /// index is not maintained.
+    fn unwind_log_indices_above(&self, block: BlockNumber) -> ProviderResult<()>;
+}
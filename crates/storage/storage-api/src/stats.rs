@@ -1,3 +1,4 @@
+use reth_db::Tables;
 use reth_db_api::table::Table;
 use reth_storage_errors::provider::ProviderResult;
 
@@ -8,3 +9,12 @@ pub trait StatsReader: Send + Sync {
     /// route to different data sources other than [Table].
     fn count_entries<T: Table>(&self) -> ProviderResult<usize>;
 }
+
+/// The trait for reading statistics of database tables selected at run-time.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait RawTableReader: Send + Sync {
+    /// Fetch the number of entries in the given table.
+    ///
+    /// See also [`StatsReader::count_entries`].
+    fn table_entries(&self, table: Tables) -> ProviderResult<usize>;
+}
@@ -1,11 +1,13 @@
 use alloy_primitives::{
     map::{HashMap, HashSet},
-    Address, Bytes, B256,
+    Address, BlockNumber, Bytes, B256, U256,
 };
+use reth_primitives::{Account, Bytecode};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     AccountProof, HashedPostState, HashedStorage, MultiProof, StorageProof, TrieInput,
+    TrieInputSorted,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -86,6 +88,72 @@ pub trait StateProofProvider: Send + Sync {
     ) -> ProviderResult<HashMap<B256, Bytes>>;
 }
 
+/// A type that can read ranges of the hashed state together with nodes of the state trie, as
+/// needed to serve the state to syncing peers.
+///
+/// All methods read the persisted state with the given overlay applied, so the state of recent
+/// blocks can be served, see [`StateRangeReader::revert_trie_input`] and
+/// [`InMemoryTrieInputProvider`]. The results are consistent with each other as long as they are
+/// read from the same transaction.
+pub trait StateRangeReader: Send + Sync {
+    /// Returns the trie input that reverts the persisted state to the state after the given
+    /// block.
+    ///
+    /// The input is empty for the last persisted block.
+    fn revert_trie_input(&self, block_number: BlockNumber) -> ProviderResult<TrieInput>;
+
+    /// Returns the root of the storage trie of the given hashed address.
+    fn storage_trie_root(
+        &self,
+        overlay: &TrieInputSorted,
+        hashed_address: B256,
+    ) -> ProviderResult<B256>;
+
+    /// Returns up to `limit` accounts ordered by hashed address, starting at the `start` hashed
+    /// address.
+    fn hashed_account_range(
+        &self,
+        overlay: &TrieInputSorted,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>>;
+
+    /// Returns up to `limit` non-zero storage slots of the account ordered by hashed slot,
+    /// starting at the `start` hashed slot.
+    fn hashed_storage_range(
+        &self,
+        overlay: &TrieInputSorted,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Generates a [`MultiProof`] for the target hashed accounts and hashed storage slots.
+    fn hashed_multiproof(
+        &self,
+        overlay: &TrieInputSorted,
+        targets: HashMap<B256, HashSet<B256>>,
+    ) -> ProviderResult<MultiProof>;
+
+    /// Returns the bytecode with the given code hash.
+    fn bytecode_by_code_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
+}
+
+/// A type that can return the state of canonical blocks that have not been persisted yet.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait InMemoryTrieInputProvider: Send + Sync {
+    /// Returns the trie input that applies the state of the in-memory canonical block with the
+    /// given hash, and of all its ancestors above the persisted block, to the persisted state.
+    ///
+    /// Returns `None` if the block is not an in-memory canonical block or if it doesn't descend
+    /// from the given persisted block.
+    fn in_memory_trie_input(
+        &self,
+        block_hash: B256,
+        persisted_block: BlockNumber,
+    ) -> ProviderResult<Option<TrieInput>>;
+}
+
 /// Trie Writer
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait TrieWriter: Send + Sync {
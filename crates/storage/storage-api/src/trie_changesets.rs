@@ -0,0 +1,46 @@
+use alloy_primitives::BlockNumber;
+use auto_impl::auto_impl;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::updates::TrieUpdates;
+use std::ops::RangeInclusive;
+
+/// Trie changesets reader
+///
+/// Trie changesets record the trie nodes before each block changed them, so that the trie can be
+/// reverted without recalculating it. They are optional and only maintained if the
+/// [`TrieChangeSets`](reth_stages_types::StageId::TrieChangeSets) stage is enabled.
+#[auto_impl(&, Arc, Box)]
+pub trait TrieChangeSetsReader: Send + Sync {
+    /// Returns the blocks whose trie changesets are recorded, if they reach the tip of the trie.
+    ///
+    /// The trie can be reverted to the state before any block of the range. The range is empty if
+    /// no block has been recorded yet. Blocks whose changesets may have been pruned are not part
+    /// of the range. Returns `None` if the trie changesets are not maintained or lag behind the
+    /// trie.
+    fn trie_changesets_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+}
+
+/// Trie changesets writer
+#[auto_impl(&, Arc, Box)]
+pub trait TrieChangeSetsWriter: Send + Sync {
+    /// Records the trie nodes that are changed by the trie updates of the given blocks and
+    /// advances the trie changesets checkpoint.
+    ///
+    /// Must be called before the trie updates are written. Changesets can only be recorded for a
+    /// single block that follows the recorded ones. Otherwise, the recorded changesets can no
+    /// longer be used to revert the trie, so they are cleared and recording starts over after the
+    /// given blocks. This is a no-op if the trie changesets are not maintained.
+    fn write_trie_changesets(
+        &self,
+        blocks: RangeInclusive<BlockNumber>,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<()>;
+
+    /// Removes the trie changesets of all blocks above the given block.
+    ///
+    /// This is a no-op if the trie changesets are not maintained.
+    fn unwind_trie_changesets_above(&self, block: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes all trie changesets and their checkpoint, so they are no longer maintained.
+    fn clear_trie_changesets(&self) -> ProviderResult<()>;
+}
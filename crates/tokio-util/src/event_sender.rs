@@ -39,6 +39,11 @@ impl<T: Clone + Send + Sync + 'static> EventSender<T> {
     pub fn new_listener(&self) -> EventStream<T> {
         EventStream::new(self.sender.subscribe())
     }
+
+    /// Returns `true` if there is at least one active listener.
+    pub fn has_listeners(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
 }
 
 #[cfg(test)]
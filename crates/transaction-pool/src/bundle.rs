@@ -0,0 +1,431 @@
+//! Pool for transaction bundles that target a specific block.
+//!
+//! Bundles are submitted via `eth_sendBundle` and are meant to be included atomically at the top
+//! of their target block by the payload builder. A bundle can be replaced or cancelled using its
+//! replacement UUID and is dropped once its target block has been mined.
+
+use crate::config::DEFAULT_MAX_BUNDLES;
+use alloy_consensus::Transaction;
+use alloy_primitives::{Keccak256, TxHash, B256};
+use parking_lot::RwLock;
+use reth_primitives::TransactionSignedEcRecovered;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tracing::trace;
+
+/// A bundle of transactions that should be included atomically at the top of a target block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle {
+    /// The hash of the bundle, the keccak256 hash of all transaction hashes.
+    hash: B256,
+    /// The transactions of the bundle in execution order.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// The block number the bundle is valid for.
+    pub block_number: u64,
+    /// The minimum timestamp of the block the bundle is valid for.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of the block the bundle is valid for.
+    pub max_timestamp: Option<u64>,
+    /// Hashes of transactions that are allowed to revert.
+    pub reverting_tx_hashes: Vec<TxHash>,
+    /// UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl PoolBundle {
+    /// Creates a new bundle of the given transactions that targets the given block.
+    pub fn new(transactions: Vec<TransactionSignedEcRecovered>, block_number: u64) -> Self {
+        let mut hasher = Keccak256::new();
+        for tx in &transactions {
+            hasher.update(tx.hash());
+        }
+        Self {
+            hash: hasher.finalize(),
+            transactions,
+            block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Sets the timestamp range the bundle is valid for.
+    pub const fn with_timestamps(
+        mut self,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+    ) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<TxHash>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Sets the UUID that can be used to replace or cancel the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns the hash of the bundle.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// Returns the sum of the gas limits of all transactions in the bundle.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.gas_limit()).sum()
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns `true` if the bundle can be included in a block with the given number and
+    /// timestamp.
+    pub fn is_valid_for(&self, block_number: u64, timestamp: u64) -> bool {
+        self.block_number == block_number &&
+            self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// Thrown if the bundle does not contain any transactions.
+    #[error("bundle missing txs")]
+    EmptyBundle,
+    /// Thrown if the bundle contains a blob transaction.
+    ///
+    /// The sidecars of bundle transactions are not tracked, so they can't be included in a
+    /// payload.
+    #[error("blob transaction {0} is not supported in bundles")]
+    BlobTransaction(TxHash),
+    /// Thrown if the bundle targets a block that has already been mined.
+    #[error("bundle targets block {target} but the current block is {current}")]
+    StaleBundle {
+        /// The block the bundle targets.
+        target: u64,
+        /// The current canonical block.
+        current: u64,
+    },
+    /// Thrown if the bundle can never be included because its timestamp range is empty.
+    #[error("bundle minTimestamp {min} is greater than maxTimestamp {max}")]
+    InvalidTimestampRange {
+        /// The minimum timestamp of the bundle.
+        min: u64,
+        /// The maximum timestamp of the bundle.
+        max: u64,
+    },
+    /// Thrown if the pool already holds the maximum number of bundles.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A pool of bundles that target upcoming blocks.
+///
+/// Bundles are kept until their target block is mined or they are cancelled. A bundle that is
+/// submitted with the same replacement UUID as an existing bundle replaces it.
+///
+/// This type is cheap to clone, all clones share the same pool.
+#[derive(Debug, Clone)]
+pub struct BundlePool {
+    inner: Arc<RwLock<BundlePoolInner>>,
+}
+
+impl BundlePool {
+    /// Creates a new pool that keeps at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self { inner: Arc::new(RwLock::new(BundlePoolInner::new(max_bundles))) }
+    }
+
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If the bundle has a replacement UUID that is already tracked, the previous bundle is
+    /// replaced.
+    pub fn add_bundle(&self, bundle: PoolBundle) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if let Some(tx) = bundle.transactions.iter().find(|tx| tx.is_eip4844()) {
+            return Err(BundlePoolError::BlobTransaction(tx.hash()))
+        }
+        if let (Some(min), Some(max)) = (bundle.min_timestamp, bundle.max_timestamp) {
+            if min > max {
+                return Err(BundlePoolError::InvalidTimestampRange { min, max })
+            }
+        }
+        self.inner.write().insert(bundle)
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    ///
+    /// Returns the cancelled bundle, if any.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> Option<PoolBundle> {
+        let mut inner = self.inner.write();
+        let hash = inner.by_uuid.get(replacement_uuid).copied()?;
+        trace!(target: "txpool::bundles", %hash, replacement_uuid, "cancelled bundle");
+        inner.remove(&hash)
+    }
+
+    /// Removes the bundle with the given hash.
+    ///
+    /// Returns the removed bundle, if any.
+    pub fn remove_bundle(&self, hash: &B256) -> Option<PoolBundle> {
+        self.inner.write().remove(hash)
+    }
+
+    /// Returns the bundle with the given hash, if it is tracked.
+    pub fn get(&self, hash: &B256) -> Option<PoolBundle> {
+        self.inner.read().bundles.get(hash).cloned()
+    }
+
+    /// Returns all bundles that can be included in a block with the given number and timestamp,
+    /// in the order they were submitted.
+    pub fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<PoolBundle> {
+        let inner = self.inner.read();
+        inner
+            .by_block
+            .get(&block_number)
+            .into_iter()
+            .flatten()
+            .filter_map(|hash| inner.bundles.get(hash))
+            .filter(|bundle| bundle.is_valid_for(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the pool with a new canonical block.
+    ///
+    /// This drops all bundles that target this or an earlier block, or whose maximum timestamp
+    /// has passed.
+    pub fn on_canonical_block(&self, block_number: u64, timestamp: u64) {
+        let mut inner = self.inner.write();
+        let mut expired = inner.set_current_block(block_number);
+        expired.extend(
+            inner
+                .bundles
+                .values()
+                .filter(|bundle| bundle.max_timestamp.is_some_and(|max| max < timestamp))
+                .map(|bundle| bundle.hash),
+        );
+
+        for hash in expired {
+            inner.remove(&hash);
+        }
+    }
+
+    /// Sets the current canonical block, e.g. when the pool is initialized with the best block.
+    ///
+    /// This drops all bundles that target this or an earlier block.
+    pub fn set_current_block(&self, block_number: u64) {
+        let mut inner = self.inner.write();
+        for hash in inner.set_current_block(block_number) {
+            inner.remove(&hash);
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+}
+
+impl Default for BundlePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+#[derive(Debug)]
+struct BundlePoolInner {
+    /// Maximum number of tracked bundles.
+    max_bundles: usize,
+    /// The most recent canonical block the pool was updated with.
+    current_block: u64,
+    /// All tracked bundles by their hash.
+    bundles: HashMap<B256, PoolBundle>,
+    /// Bundle hashes by their target block, in submission order.
+    by_block: BTreeMap<u64, Vec<B256>>,
+    /// Bundle hashes by their replacement UUID.
+    by_uuid: HashMap<String, B256>,
+}
+
+impl BundlePoolInner {
+    fn new(max_bundles: usize) -> Self {
+        Self {
+            max_bundles,
+            current_block: 0,
+            bundles: Default::default(),
+            by_block: Default::default(),
+            by_uuid: Default::default(),
+        }
+    }
+
+    /// Sets the current block and returns the hashes of all bundles that target this or an
+    /// earlier block.
+    fn set_current_block(&mut self, block_number: u64) -> Vec<B256> {
+        self.current_block = block_number;
+
+        let mut expired = Vec::new();
+        while let Some(entry) = self.by_block.first_entry() {
+            if *entry.key() > block_number {
+                break
+            }
+            expired.extend(entry.remove());
+        }
+        expired
+    }
+
+    fn insert(&mut self, bundle: PoolBundle) -> Result<B256, BundlePoolError> {
+        if bundle.block_number <= self.current_block {
+            return Err(BundlePoolError::StaleBundle {
+                target: bundle.block_number,
+                current: self.current_block,
+            })
+        }
+
+        let hash = bundle.hash;
+        let replaced = bundle.replacement_uuid.as_ref().and_then(|uuid| self.by_uuid.get(uuid));
+        if let Some(replaced) = replaced.copied() {
+            trace!(target: "txpool::bundles", %hash, %replaced, "replacing bundle");
+            self.remove(&replaced);
+        } else if self.bundles.contains_key(&hash) {
+            // the same bundle can be cancelled through any uuid it was submitted with
+            if let Some(uuid) = bundle.replacement_uuid {
+                self.by_uuid.insert(uuid, hash);
+            }
+            return Ok(hash)
+        } else if self.bundles.len() >= self.max_bundles {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        // drop the entry of an identical bundle that was submitted under another uuid
+        self.remove(&hash);
+
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_uuid.insert(uuid.clone(), hash);
+        }
+        self.by_block.entry(bundle.block_number).or_default().push(hash);
+        self.bundles.insert(hash, bundle);
+        Ok(hash)
+    }
+
+    fn remove(&mut self, hash: &B256) -> Option<PoolBundle> {
+        let bundle = self.bundles.remove(hash)?;
+        self.by_uuid.retain(|_, bundle_hash| bundle_hash != hash);
+        if let Some(hashes) = self.by_block.get_mut(&bundle.block_number) {
+            hashes.retain(|h| h != hash);
+            if hashes.is_empty() {
+                self.by_block.remove(&bundle.block_number);
+            }
+        }
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransaction, PoolTransaction};
+
+    fn bundle(nonce: u64, block_number: u64) -> PoolBundle {
+        let tx = MockTransaction::eip1559().with_nonce(nonce).into_consensus();
+        PoolBundle::new(vec![tx], block_number)
+    }
+
+    #[test]
+    fn replace_and_cancel() {
+        let pool = BundlePool::default();
+        let first = bundle(0, 1).with_replacement_uuid(Some("uuid".to_string()));
+        let second = bundle(1, 2).with_replacement_uuid(Some("uuid".to_string()));
+
+        let first_hash = pool.add_bundle(first).unwrap();
+        let second_hash = pool.add_bundle(second).unwrap();
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&first_hash).is_none());
+        assert!(pool.bundles_for_block(1, 0).is_empty());
+        assert_eq!(pool.bundles_for_block(2, 0)[0].hash(), second_hash);
+
+        assert!(pool.cancel_bundle(&second_hash.to_string()).is_none());
+        assert_eq!(pool.cancel_bundle("uuid").unwrap().hash(), second_hash);
+        assert!(pool.cancel_bundle("uuid").is_none());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn cancel_identical_bundle_by_either_uuid() {
+        let pool = BundlePool::default();
+        let bundle = bundle(0, 1);
+        let hash = pool
+            .add_bundle(bundle.clone().with_replacement_uuid(Some("first".to_string())))
+            .unwrap();
+        assert_eq!(
+            pool.add_bundle(bundle.with_replacement_uuid(Some("second".to_string()))),
+            Ok(hash)
+        );
+        assert_eq!(pool.len(), 1);
+
+        assert_eq!(pool.cancel_bundle("second").unwrap().hash(), hash);
+        assert!(pool.cancel_bundle("first").is_none());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn expires_bundles() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(0, 1)).unwrap();
+        pool.add_bundle(bundle(1, 2)).unwrap();
+        pool.add_bundle(bundle(2, 3).with_timestamps(None, Some(10))).unwrap();
+
+        pool.on_canonical_block(1, 5);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(
+            pool.add_bundle(bundle(3, 1)),
+            Err(BundlePoolError::StaleBundle { target: 1, current: 1 })
+        );
+
+        pool.on_canonical_block(2, 11);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejects_bundles_before_current_block() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(0, 100)).unwrap();
+        pool.add_bundle(bundle(1, 101)).unwrap();
+
+        pool.set_current_block(100);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(
+            pool.add_bundle(bundle(2, 50)),
+            Err(BundlePoolError::StaleBundle { target: 50, current: 100 })
+        );
+    }
+
+    #[test]
+    fn filters_by_timestamp() {
+        let pool = BundlePool::new(2);
+        pool.add_bundle(bundle(0, 1).with_timestamps(Some(10), Some(20))).unwrap();
+        pool.add_bundle(bundle(1, 1)).unwrap();
+        assert_eq!(pool.add_bundle(bundle(2, 1)), Err(BundlePoolError::PoolFull));
+
+        assert_eq!(pool.bundles_for_block(1, 5).len(), 1);
+        assert_eq!(pool.bundles_for_block(1, 15).len(), 2);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    validate::AdmissionPolicies,
     PoolSize, TransactionOrigin,
 };
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
@@ -27,6 +28,15 @@ pub const DEFAULT_PRICE_BUMP: u128 = 10;
 /// This enforces that a blob transaction requires a 100% price bump to be replaced
 pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 
+/// Default maximum number of bundles kept in the bundle pool.
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// Default maximum number of transactions kept in the private transaction pool.
+pub const DEFAULT_MAX_PRIVATE_TRANSACTIONS: usize = 1024;
+
+/// Default number of blocks a private transaction is kept for if no max block number is given.
+pub const DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS: u64 = 25;
+
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
@@ -58,6 +68,12 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
+    /// Max number of bundles kept in the bundle pool.
+    pub max_bundles: usize,
+    /// Max number of transactions kept in the private transaction pool.
+    pub max_private_transactions: usize,
+    /// Additional policies a transaction must pass to be admitted into the pool.
+    pub admission_policies: AdmissionPolicies,
 }
 
 impl PoolConfig {
@@ -86,6 +102,9 @@ impl Default for PoolConfig {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            max_bundles: DEFAULT_MAX_BUNDLES,
+            max_private_transactions: DEFAULT_MAX_PRIVATE_TRANSACTIONS,
+            admission_policies: Default::default(),
         }
     }
 }
@@ -164,22 +164,27 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{BundlePool, BundlePoolError, PoolBundle},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_MAX_BUNDLES,
+        DEFAULT_MAX_PRIVATE_TRANSACTIONS, DEFAULT_PRICE_BUMP,
+        DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+        MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
-    error::PoolResult,
+    error::{PoolError, PoolResult},
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    overrides::{AccountOverride, BlockOverrides, StateOverrides, StateOverridesError},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         TransactionEvent, TransactionEvents,
     },
+    private::{PrivateTransaction, PrivateTransactionPool, PrivateTransactionPoolError},
     traits::*,
     validate::{
-        EthTransactionValidator, TransactionValidationOutcome, TransactionValidationTaskExecutor,
-        TransactionValidator, ValidPoolTransaction,
+        AdmissionPolicies, AdmissionPolicy, EthTransactionValidator, TransactionValidationOutcome,
+        TransactionValidationTaskExecutor, TransactionValidator, ValidPoolTransaction,
     },
 };
 
@@ -187,10 +192,13 @@ pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
+pub mod overrides;
 pub mod pool;
+pub mod private;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 pub mod identifier;
 mod ordering;
@@ -262,6 +270,30 @@ where
         (hash, outcome)
     }
 
+    /// Adds the validated transaction to the [`PrivateTransactionPool`].
+    fn add_validated_private_transaction(
+        &self,
+        hash: TxHash,
+        outcome: TransactionValidationOutcome<V::Transaction>,
+        max_block_number: Option<u64>,
+    ) -> Result<TxHash, PrivateTransactionPoolError>
+    where
+        V::Transaction: EthPoolTransaction,
+    {
+        let transaction = match outcome {
+            TransactionValidationOutcome::Valid { transaction, .. } => {
+                transaction.into_transaction().into_consensus().into()
+            }
+            TransactionValidationOutcome::Invalid(_, err) => {
+                return Err(PoolError::new(hash, err).into())
+            }
+            TransactionValidationOutcome::Error(_, err) => {
+                return Err(PoolError::other(hash, err).into())
+            }
+        };
+        self.pool.private_pool().add_transaction(transaction, max_block_number)
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.pool.len()
@@ -370,6 +402,24 @@ where
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
+    async fn readmit_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let validated = futures_util::future::join_all(
+            transactions
+                .into_iter()
+                .map(|tx| self.pool.validator().validate_readmitted_transaction(origin, tx)),
+        )
+        .await;
+
+        self.pool.add_transactions(origin, validated)
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
@@ -588,6 +638,41 @@ where
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
     }
+
+    fn bundle_pool(&self) -> BundlePool {
+        self.pool.bundle_pool().clone()
+    }
+
+    fn private_pool(&self) -> PrivateTransactionPool {
+        self.pool.private_pool().clone()
+    }
+
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> Result<TxHash, PrivateTransactionPoolError> {
+        let (hash, outcome) = self.validate(TransactionOrigin::Private, transaction).await;
+        self.add_validated_private_transaction(hash, outcome, max_block_number)
+    }
+
+    async fn readmit_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> Result<TxHash, PrivateTransactionPoolError> {
+        let hash = *transaction.hash();
+        let outcome = self
+            .pool
+            .validator()
+            .validate_readmitted_transaction(TransactionOrigin::Private, transaction)
+            .await;
+        self.add_validated_private_transaction(hash, outcome, max_block_number)
+    }
+
+    fn state_overrides(&self) -> StateOverrides {
+        self.pool.state_overrides().clone()
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -5,10 +5,14 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
 };
-use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    BlockNumberOrTag,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -18,8 +22,8 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_execution_types::ChangedAccount;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
-    PooledTransactionsElementEcRecovered, SealedHeader, TransactionSigned,
-    TransactionSignedEcRecovered,
+    PooledTransactionsElement, PooledTransactionsElementEcRecovered, SealedHeader,
+    TransactionSigned, TransactionSignedEcRecovered,
 };
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -27,10 +31,12 @@ use std::{
     borrow::Borrow,
     collections::HashSet,
     hash::{Hash, Hasher},
+    io::Write,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::Instant};
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
@@ -67,6 +73,30 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Settings for the transaction pool snapshot task
+#[derive(Debug, Clone)]
+pub struct TransactionPoolSnapshotConfig {
+    /// Path to the snapshot file
+    pub path: PathBuf,
+    /// Interval at which snapshots are written while running.
+    ///
+    /// A snapshot is always written on shutdown.
+    pub interval: Option<Duration>,
+}
+
+impl TransactionPoolSnapshotConfig {
+    /// Returns a config that only writes a snapshot on shutdown.
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, interval: None }
+    }
+
+    /// Sets the interval at which snapshots are written while running.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -363,7 +393,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let _ = pool
+                    .readmit_transactions(TransactionOrigin::External, pruned_old_transactions)
+                    .await;
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
@@ -662,18 +694,273 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Version of the transaction pool snapshot format.
+const POOL_SNAPSHOT_VERSION: u8 = 2;
+
+/// Snapshot of all transactions in the pool, as written to disk.
+///
+/// Bundles of the [`BundlePool`](crate::BundlePool) are not included, because they target a
+/// specific block and are usually stale by the time the node is back up.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct TransactionPoolSnapshot {
+    /// Format version, see [`POOL_SNAPSHOT_VERSION`].
+    version: u8,
+    /// Transactions of all sub-pools.
+    transactions: Vec<SnapshotTransaction>,
+    /// Transactions of the [`PrivateTransactionPool`](crate::PrivateTransactionPool), in
+    /// submission order.
+    private_transactions: Vec<SnapshotPrivateTransaction>,
+}
+
+/// A transaction of a [`TransactionPoolSnapshot`].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct SnapshotTransaction {
+    /// Origin of the transaction, see [`origin_to_u8`].
+    origin: u8,
+    /// EIP-2718 encoded pooled transaction, which includes the sidecar of blob transactions.
+    transaction: Bytes,
+}
+
+/// A transaction of the [`PrivateTransactionPool`](crate::PrivateTransactionPool) in a
+/// [`TransactionPoolSnapshot`].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct SnapshotPrivateTransaction {
+    /// The highest block number the transaction can be included in.
+    max_block_number: u64,
+    /// EIP-2718 encoded transaction, private transactions are never blob transactions.
+    transaction: Bytes,
+}
+
+const fn origin_to_u8(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+const fn origin_from_u8(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Loads a snapshot of the pool from a file and inserts its transactions back into the pool.
+///
+/// Every transaction goes through validation again, so the ones that became invalid while the
+/// node was down are dropped. Private transactions are added back to the
+/// [`PrivateTransactionPool`](crate::PrivateTransactionPool) with their max block number. The file
+/// is kept, since it's overwritten by the next snapshot.
+async fn load_and_reinsert_pool_snapshot<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Loading transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let snapshot: TransactionPoolSnapshot = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    if snapshot.version != POOL_SNAPSHOT_VERSION {
+        warn!(target: "txpool", snapshot_file =?file_path, version=%snapshot.version, "Ignoring transaction pool snapshot with unsupported version");
+        return Ok(())
+    }
+
+    let mut local = Vec::new();
+    let mut external = Vec::new();
+    let mut private = Vec::new();
+    let mut num_undecodable = 0;
+    for entry in snapshot.transactions {
+        let (Some(origin), Some(transaction)) = (
+            origin_from_u8(entry.origin),
+            PooledTransactionsElement::decode_2718(&mut entry.transaction.as_ref())
+                .ok()
+                .and_then(|tx| tx.try_into_ecrecovered().ok()),
+        ) else {
+            num_undecodable += 1;
+            continue
+        };
+
+        let transaction = <P::Transaction as PoolTransaction>::from_pooled(transaction.into());
+        match origin {
+            TransactionOrigin::Local => local.push(transaction),
+            TransactionOrigin::External => external.push(transaction),
+            TransactionOrigin::Private => private.push(transaction),
+        }
+    }
+
+    let mut num_reinserted = 0;
+    let mut num_invalid = 0;
+    for (origin, transactions) in [
+        (TransactionOrigin::Local, local),
+        (TransactionOrigin::External, external),
+        (TransactionOrigin::Private, private),
+    ] {
+        if transactions.is_empty() {
+            continue
+        }
+        for result in pool.readmit_transactions(origin, transactions).await {
+            if result.is_ok() {
+                num_reinserted += 1;
+            } else {
+                num_invalid += 1;
+            }
+        }
+    }
+
+    for entry in snapshot.private_transactions {
+        let Some(transaction) =
+            PooledTransactionsElement::decode_2718(&mut entry.transaction.as_ref())
+                .ok()
+                .and_then(|tx| tx.try_into_ecrecovered().ok())
+        else {
+            num_undecodable += 1;
+            continue
+        };
+
+        let transaction = <P::Transaction as PoolTransaction>::from_pooled(transaction.into());
+        if pool.readmit_private_transaction(transaction, Some(entry.max_block_number)).await.is_ok()
+        {
+            num_reinserted += 1;
+        } else {
+            num_invalid += 1;
+        }
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, num_reinserted, num_invalid, num_undecodable, "Reinserted transactions from pool snapshot");
+    Ok(())
+}
+
+/// Writes a snapshot of all transactions in the pool to a file.
+///
+/// Encoding the transactions, fetching the blob sidecars and writing the file happen on a
+/// blocking thread. The file is replaced atomically, so a crash while writing leaves the previous
+/// snapshot intact.
+async fn save_pool_snapshot<P>(pool: P, file_path: PathBuf)
+where
+    P: TransactionPool + 'static,
+{
+    let result = tokio::task::spawn_blocking(move || write_pool_snapshot(&pool, &file_path)).await;
+    if let Err(err) = result {
+        warn!(target: "txpool", %err, "Transaction pool snapshot task failed");
+    }
+}
+
+fn write_pool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    // by origin, which covers every sub-pool, including parked blob transactions
+    let transactions =
+        [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+            .into_iter()
+            .flat_map(|origin| pool.get_transactions_by_origin(origin))
+            .filter_map(|tx| {
+                // includes the blob sidecar from the blob store
+                let pooled = pool.get_pooled_transaction_element(*tx.hash())?;
+                Some(SnapshotTransaction {
+                    origin: origin_to_u8(tx.origin),
+                    transaction: pooled.encoded_2718().into(),
+                })
+            })
+            .collect::<Vec<_>>();
+    let private_transactions = pool
+        .private_pool()
+        .all_transactions()
+        .into_iter()
+        .map(|tx| SnapshotPrivateTransaction {
+            max_block_number: tx.max_block_number,
+            transaction: tx.transaction.encoded_2718().into(),
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = transactions.len() + private_transactions.len();
+    let buf = alloy_rlp::encode(TransactionPoolSnapshot {
+        version: POOL_SNAPSHOT_VERSION,
+        transactions,
+        private_transactions,
+    });
+    debug!(target: "txpool", snapshot_file =?file_path, num_txs, "Saving transaction pool snapshot");
+
+    let result = file_path
+        .parent()
+        .map(reth_fs_util::create_dir_all)
+        .transpose()
+        .and_then(|_| reth_fs_util::atomic_write_file(file_path, |file| file.write_all(&buf)));
+
+    match result {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file =?file_path, num_txs, "Wrote transaction pool snapshot");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file =?file_path, "Failed to write transaction pool snapshot");
+        }
+    }
+}
+
+/// Task which persists all transactions of the pool, including blob sidecars and private
+/// transactions, across restarts.
+///
+/// On boot up, the transactions of the last snapshot are revalidated and inserted into the pool.
+/// A new snapshot is written on shutdown and, if configured, periodically while running, so most
+/// of the pool also survives a crash.
+pub async fn snapshot_transaction_pool_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionPoolSnapshotConfig,
+) where
+    P: TransactionPool + Clone + 'static,
+{
+    let TransactionPoolSnapshotConfig { path, interval } = config;
+
+    if let Err(err) = load_and_reinsert_pool_snapshot(pool.clone(), &path).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let graceful_guard = if let Some(interval) = interval {
+        let mut shutdown = std::pin::pin!(shutdown);
+        let mut interval = tokio::time::interval_at(Instant::now() + interval, interval);
+        loop {
+            tokio::select! {
+                guard = &mut shutdown => break guard,
+                _ = interval.tick() => save_pool_snapshot(pool.clone(), path.clone()).await,
+            }
+        }
+    } else {
+        shutdown.await
+    };
+
+    save_pool_snapshot(pool, path).await;
+
+    drop(graceful_guard)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::{BlobStore, InMemoryBlobStore},
+        validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
+    use alloy_consensus::{SidecarBuilder, SimpleCoder, TxEip4844};
     use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_primitives::{hex, B256, U256};
     use reth_chainspec::MAINNET;
     use reth_fs_util as fs;
-    use reth_primitives::PooledTransactionsElement;
+    use reth_primitives::{sign_message, PooledTransactionsElement, Transaction};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -736,6 +1023,94 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_and_load_pool_snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("txpool-snapshot").with_extension(EXTENSION);
+        let provider = MockEthProvider::default();
+
+        // blob transactions, their sidecars are only kept in the blob store
+        let mut sidecar = SidecarBuilder::<SimpleCoder>::new();
+        sidecar.ingest(b"blob");
+        let sidecar = sidecar.build().unwrap();
+        let signed_blob_tx = |key: u8, max_fee_per_blob_gas: u128| {
+            let transaction = Transaction::Eip4844(TxEip4844 {
+                chain_id: 1,
+                gas_limit: 21_000,
+                max_fee_per_gas: 100_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                max_fee_per_blob_gas,
+                blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+                ..Default::default()
+            });
+            let signature =
+                sign_message(B256::with_last_byte(key), transaction.signature_hash()).unwrap();
+            let transaction = PooledTransactionsElement::try_from_blob_transaction(
+                TransactionSigned::new_unhashed(transaction, signature),
+                sidecar.clone(),
+            )
+            .unwrap()
+            .try_into_ecrecovered()
+            .unwrap();
+            provider.add_account(transaction.signer(), ExtendedAccount::new(0, U256::MAX));
+            EthPooledTransaction::from(transaction)
+        };
+        let blob_tx = signed_blob_tx(1, 100_000_000_000);
+        // parked in the blob sub-pool, since its blob fee cap is below the pending blob fee
+        let parked_blob_tx = signed_blob_tx(2, 1_000_000_000);
+
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let private_tx: EthPooledTransaction = tx.try_into_ecrecovered().unwrap().into();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        txpool.set_block_info(BlockInfo {
+            pending_blob_fee: Some(10_000_000_000),
+            ..txpool.block_info()
+        });
+        txpool.add_transaction(TransactionOrigin::External, blob_tx.clone()).await.unwrap();
+        txpool.add_transaction(TransactionOrigin::Local, parked_blob_tx.clone()).await.unwrap();
+        txpool.add_private_transaction(private_tx.clone(), Some(10)).await.unwrap();
+        let all = txpool.all_transactions();
+        assert!(all.pending.iter().chain(&all.queued).all(|tx| tx.hash() != parked_blob_tx.hash()));
+
+        save_pool_snapshot(txpool, snapshot_path.clone()).await;
+
+        // the restored pool starts with an empty blob store
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build::<_, EthPooledTransaction, _>(provider, blob_store.clone());
+        let restored_pool = Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+        load_and_reinsert_pool_snapshot(restored_pool.clone(), &snapshot_path).await.unwrap();
+
+        let restored = restored_pool.get(blob_tx.hash()).expect("transaction to be restored");
+        assert_eq!(restored.origin, TransactionOrigin::External);
+        assert_eq!(blob_store.get(*blob_tx.hash()).unwrap().as_deref(), Some(&sidecar));
+
+        let restored = restored_pool.get(parked_blob_tx.hash()).expect("parked blob tx restored");
+        assert_eq!(restored.origin, TransactionOrigin::Local);
+        assert_eq!(blob_store.get(*parked_blob_tx.hash()).unwrap().as_deref(), Some(&sidecar));
+
+        let restored_private =
+            restored_pool.private_pool().get(private_tx.hash()).expect("private tx restored");
+        assert_eq!(restored_private.max_block_number, 10);
+        assert!(restored_pool.get(private_tx.hash()).is_none());
+
+        // the snapshot is kept until it's overwritten
+        assert!(snapshot_path.exists());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -6,6 +6,7 @@
 use crate::{
     blobstore::BlobStoreError,
     error::PoolError,
+    private::PrivateTransactionPoolError,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
         TransactionListenerKind,
@@ -307,6 +308,15 @@ impl TransactionPool for NoopTransactionPool {
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         Ok(vec![None; versioned_hashes.len()])
     }
+
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        _max_block_number: Option<u64>,
+    ) -> Result<TxHash, PrivateTransactionPoolError> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))).into())
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
@@ -0,0 +1,385 @@
+//! State overrides for dev chains.
+//!
+//! Overrides are set via the cheatcode namespaces (`anvil_setBalance`, `hardhat_setCode`, ...) and
+//! are applied by the local payload builder at the start of the next block, so they become part of
+//! the canonical state. They are kept separately from the regular pool, next to the
+//! [`BundlePool`](crate::BundlePool) and the
+//! [`PrivateTransactionPool`](crate::PrivateTransactionPool), because the pool is the only handle
+//! that is shared between the RPC and the payload builder.
+//!
+//! Transactions of impersonated accounts are queued here as well. They have no valid signature, so
+//! they never enter the regular pool and are executed with their recorded sender instead.
+//!
+//! Blocks built with overrides can't be re-executed by other nodes, because the overridden state is
+//! not the result of any transaction and impersonated transactions have no valid signature. They
+//! are only valid on dev chains, where the local miner hands the executed blocks directly to the
+//! engine. For this reason the overrides are disabled until [`StateOverrides::enable`] is called by
+//! the dev node, and no overrides are returned or accepted while disabled.
+
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use parking_lot::RwLock;
+use reth_primitives::TransactionSignedEcRecovered;
+use std::{collections::HashMap, sync::Arc};
+
+/// The overridden fields of a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    /// The balance the account should have.
+    pub balance: Option<U256>,
+    /// The nonce the account should have.
+    pub nonce: Option<u64>,
+    /// The code the account should have.
+    pub code: Option<Bytes>,
+    /// Storage slots that should be set.
+    pub storage: HashMap<U256, U256>,
+}
+
+impl AccountOverride {
+    /// Applies the given override on top of this one.
+    fn extend(&mut self, other: Self) {
+        let Self { balance, nonce, code, storage } = other;
+        if balance.is_some() {
+            self.balance = balance;
+        }
+        if nonce.is_some() {
+            self.nonce = nonce;
+        }
+        if code.is_some() {
+            self.code = code;
+        }
+        self.storage.extend(storage);
+    }
+}
+
+/// Overrides of the environment of the blocks built by the local payload builder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockOverrides {
+    /// The beneficiary of all following blocks.
+    pub coinbase: Option<Address>,
+    /// The gas limit of all following blocks.
+    pub gas_limit: Option<u64>,
+    /// The base fee of the next block.
+    pub base_fee: Option<u64>,
+    /// The prev randao of the next block.
+    pub prev_randao: Option<B256>,
+}
+
+/// Errors that can occur when setting [`StateOverrides`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum StateOverridesError {
+    /// Thrown if the overrides were not enabled, because the node is not a dev node.
+    #[error("state overrides are only supported by dev nodes")]
+    Disabled,
+}
+
+/// A set of pending account and block overrides.
+///
+/// Every account change is assigned an increasing id. Once the overrides have been included in a
+/// block, they can be removed with [`StateOverrides::on_applied`]. Accounts that were modified
+/// again in the meantime are kept, so that they are applied to the next block as well.
+///
+/// This type is cheap to clone, all clones share the same overrides.
+#[derive(Debug, Clone, Default)]
+pub struct StateOverrides {
+    inner: Arc<RwLock<StateOverridesInner>>,
+}
+
+impl StateOverrides {
+    /// Allows overrides to be set and applied.
+    ///
+    /// This must only be called by dev nodes, see the [module docs](self).
+    pub fn enable(&self) {
+        self.inner.write().enabled = true;
+    }
+
+    /// Returns `true` if overrides can be set and applied.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.read().enabled
+    }
+
+    /// Sets the balance of the account and returns the id of the change.
+    pub fn set_balance(&self, address: Address, balance: U256) -> Result<u64, StateOverridesError> {
+        self.update(address, |account| account.balance = Some(balance))
+    }
+
+    /// Sets the nonce of the account and returns the id of the change.
+    pub fn set_nonce(&self, address: Address, nonce: u64) -> Result<u64, StateOverridesError> {
+        self.update(address, |account| account.nonce = Some(nonce))
+    }
+
+    /// Sets the code of the account and returns the id of the change.
+    pub fn set_code(&self, address: Address, code: Bytes) -> Result<u64, StateOverridesError> {
+        self.update(address, |account| account.code = Some(code))
+    }
+
+    /// Sets a storage slot of the account and returns the id of the change.
+    pub fn set_storage(
+        &self,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) -> Result<u64, StateOverridesError> {
+        self.update(address, |account| {
+            account.storage.insert(slot, value);
+        })
+    }
+
+    /// Applies the overrides of all given accounts and returns the id of the change.
+    pub fn set_accounts(
+        &self,
+        accounts: impl IntoIterator<Item = (Address, AccountOverride)>,
+    ) -> Result<u64, StateOverridesError> {
+        let mut inner = self.inner.write();
+        let id = inner.next_id()?;
+        for (address, account_override) in accounts {
+            let (account, last_modified) = inner.accounts.entry(address).or_default();
+            account.extend(account_override);
+            *last_modified = id;
+        }
+        Ok(id)
+    }
+
+    fn update(
+        &self,
+        address: Address,
+        f: impl FnOnce(&mut AccountOverride),
+    ) -> Result<u64, StateOverridesError> {
+        let mut inner = self.inner.write();
+        let id = inner.next_id()?;
+        let (account, last_modified) = inner.accounts.entry(address).or_default();
+        f(account);
+        *last_modified = id;
+        Ok(id)
+    }
+
+    /// Sets the beneficiary of all following blocks.
+    pub fn set_coinbase(&self, coinbase: Address) -> Result<(), StateOverridesError> {
+        self.update_block(|block| block.coinbase = Some(coinbase))
+    }
+
+    /// Sets the gas limit of all following blocks.
+    pub fn set_gas_limit(&self, gas_limit: u64) -> Result<(), StateOverridesError> {
+        self.update_block(|block| block.gas_limit = Some(gas_limit))
+    }
+
+    /// Sets the base fee of the next block.
+    pub fn set_next_base_fee(&self, base_fee: u64) -> Result<(), StateOverridesError> {
+        self.update_block(|block| block.base_fee = Some(base_fee))
+    }
+
+    /// Sets the prev randao of the next block.
+    pub fn set_next_prev_randao(&self, prev_randao: B256) -> Result<(), StateOverridesError> {
+        self.update_block(|block| block.prev_randao = Some(prev_randao))
+    }
+
+    fn update_block(&self, f: impl FnOnce(&mut BlockOverrides)) -> Result<(), StateOverridesError> {
+        let mut inner = self.inner.write();
+        if !inner.enabled {
+            return Err(StateOverridesError::Disabled)
+        }
+        f(&mut inner.block);
+        Ok(())
+    }
+
+    /// Returns all pending account overrides.
+    pub fn overrides(&self) -> HashMap<Address, AccountOverride> {
+        let inner = self.inner.read();
+        if !inner.enabled {
+            return HashMap::default()
+        }
+        inner.accounts.iter().map(|(address, (account, _))| (*address, account.clone())).collect()
+    }
+
+    /// Returns the overrides of the next block.
+    pub fn block_overrides(&self) -> BlockOverrides {
+        let inner = self.inner.read();
+        if !inner.enabled {
+            return BlockOverrides::default()
+        }
+        inner.block
+    }
+
+    /// Queues a transaction of an impersonated account for the next blocks and returns its hash.
+    ///
+    /// The transaction is executed with its recorded sender and kept until it's mined or removed.
+    pub fn add_impersonated_transaction(
+        &self,
+        transaction: TransactionSignedEcRecovered,
+    ) -> Result<TxHash, StateOverridesError> {
+        let mut inner = self.inner.write();
+        if !inner.enabled {
+            return Err(StateOverridesError::Disabled)
+        }
+        let hash = transaction.hash();
+        if !inner.impersonated.iter().any(|tx| tx.hash() == hash) {
+            inner.impersonated.push(transaction);
+        }
+        Ok(hash)
+    }
+
+    /// Returns the queued transactions of impersonated accounts in the order they were added.
+    pub fn impersonated_transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        let inner = self.inner.read();
+        if !inner.enabled {
+            return Vec::new()
+        }
+        inner.impersonated.clone()
+    }
+
+    /// Returns the queued transaction of an impersonated account with the given hash.
+    pub fn impersonated_transaction(&self, hash: &TxHash) -> Option<TransactionSignedEcRecovered> {
+        self.inner.read().impersonated.iter().find(|tx| tx.hash() == *hash).cloned()
+    }
+
+    /// Removes the queued transactions of impersonated accounts with the given hashes and returns
+    /// the hashes of the removed transactions.
+    pub fn remove_impersonated_transactions(&self, hashes: &[TxHash]) -> Vec<TxHash> {
+        let mut removed = Vec::new();
+        self.inner.write().impersonated.retain(|tx| {
+            let remove = hashes.contains(&tx.hash());
+            if remove {
+                removed.push(tx.hash());
+            }
+            !remove
+        });
+        removed
+    }
+
+    /// Removes all account overrides that were not modified after the change with the given id.
+    ///
+    /// This should be called once a block that includes the change was made canonical.
+    pub fn on_applied(&self, id: u64) {
+        self.inner.write().accounts.retain(|_, (_, last_modified)| *last_modified > id);
+    }
+
+    /// Removes the block overrides that only apply to the next block and the mined transactions of
+    /// impersonated accounts.
+    ///
+    /// This is called for every new canonical block.
+    pub fn on_canonical_block(&self, mined_transactions: &[TxHash]) {
+        let mut inner = self.inner.write();
+        inner.block.base_fee = None;
+        inner.block.prev_randao = None;
+        inner.impersonated.retain(|tx| !mined_transactions.contains(&tx.hash()));
+    }
+
+    /// Removes all overrides and queued transactions of impersonated accounts.
+    pub fn clear(&self) {
+        let mut inner = self.inner.write();
+        inner.accounts.clear();
+        inner.block = BlockOverrides::default();
+        inner.impersonated.clear();
+    }
+
+    /// Returns the number of accounts with pending overrides.
+    pub fn len(&self) -> usize {
+        self.inner.read().accounts.len()
+    }
+
+    /// Returns `true` if there are no pending account overrides.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().accounts.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+struct StateOverridesInner {
+    /// Whether overrides can be set and applied.
+    enabled: bool,
+    /// The id of the most recent change.
+    last_id: u64,
+    /// The overrides by account, with the id of the last change to the account.
+    accounts: HashMap<Address, (AccountOverride, u64)>,
+    /// The overrides of the block environment.
+    block: BlockOverrides,
+    /// The queued transactions of impersonated accounts, in the order they were added.
+    impersonated: Vec<TransactionSignedEcRecovered>,
+}
+
+impl StateOverridesInner {
+    /// Returns the id of a new change.
+    fn next_id(&mut self) -> Result<u64, StateOverridesError> {
+        if !self.enabled {
+            return Err(StateOverridesError::Disabled)
+        }
+        self.last_id += 1;
+        Ok(self.last_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::TransactionSigned;
+
+    #[test]
+    fn keeps_overrides_modified_after_applied() {
+        let overrides = StateOverrides::default();
+        overrides.enable();
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+
+        overrides.set_balance(first, U256::from(1)).unwrap();
+        let applied = overrides.set_storage(second, U256::ZERO, U256::from(2)).unwrap();
+        overrides.set_nonce(first, 3).unwrap();
+
+        let pending = overrides.overrides();
+        assert_eq!(pending[&first].balance, Some(U256::from(1)));
+        assert_eq!(pending[&first].nonce, Some(3));
+        assert_eq!(pending[&second].storage[&U256::ZERO], U256::from(2));
+
+        overrides.on_applied(applied);
+        assert_eq!(overrides.overrides().keys().collect::<Vec<_>>(), vec![&first]);
+
+        overrides.on_applied(u64::MAX);
+        assert!(overrides.is_empty());
+    }
+
+    #[test]
+    fn disabled_overrides() {
+        let overrides = StateOverrides::default();
+        let address = Address::with_last_byte(1);
+        assert_eq!(
+            overrides.set_balance(address, U256::from(1)),
+            Err(StateOverridesError::Disabled)
+        );
+        assert_eq!(overrides.set_coinbase(address), Err(StateOverridesError::Disabled));
+        assert!(overrides.overrides().is_empty());
+
+        overrides.enable();
+        overrides.set_coinbase(address).unwrap();
+        overrides.set_next_base_fee(7).unwrap();
+        assert_eq!(
+            overrides.block_overrides(),
+            BlockOverrides { coinbase: Some(address), base_fee: Some(7), ..Default::default() }
+        );
+
+        overrides.on_canonical_block(&[]);
+        assert_eq!(
+            overrides.block_overrides(),
+            BlockOverrides { coinbase: Some(address), ..Default::default() }
+        );
+    }
+
+    #[test]
+    fn removes_mined_impersonated_transactions() {
+        let overrides = StateOverrides::default();
+        let transaction = TransactionSignedEcRecovered::from_signed_transaction(
+            TransactionSigned::default(),
+            Address::with_last_byte(1),
+        );
+        assert_eq!(
+            overrides.add_impersonated_transaction(transaction.clone()),
+            Err(StateOverridesError::Disabled)
+        );
+
+        overrides.enable();
+        let hash = overrides.add_impersonated_transaction(transaction.clone()).unwrap();
+        overrides.add_impersonated_transaction(transaction).unwrap();
+        assert_eq!(overrides.impersonated_transactions().len(), 1);
+        assert!(overrides.impersonated_transaction(&hash).is_some());
+
+        overrides.on_canonical_block(&[hash]);
+        assert!(overrides.impersonated_transactions().is_empty());
+    }
+}
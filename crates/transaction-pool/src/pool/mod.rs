@@ -100,8 +100,11 @@ use tracing::{debug, trace, warn};
 mod events;
 use crate::{
     blobstore::BlobStore,
+    bundle::BundlePool,
     metrics::BlobStoreMetrics,
+    overrides::StateOverrides,
     pool::txpool::UpdateOutcome,
+    private::PrivateTransactionPool,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
     validate::ValidTransaction,
 };
@@ -156,6 +159,12 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles that target upcoming blocks.
+    bundle_pool: BundlePool,
+    /// Transactions that are only included by the local payload builder.
+    private_pool: PrivateTransactionPool,
+    /// Account overrides that are applied by the local payload builder.
+    state_overrides: StateOverrides,
 }
 
 // === impl PoolInner ===
@@ -173,6 +182,9 @@ where
             validator,
             event_listener: Default::default(),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            bundle_pool: BundlePool::new(config.max_bundles),
+            private_pool: PrivateTransactionPool::new(config.max_private_transactions),
+            state_overrides: Default::default(),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
@@ -187,6 +199,21 @@ where
         &self.blob_store
     }
 
+    /// Returns the pool of bundles.
+    pub(crate) const fn bundle_pool(&self) -> &BundlePool {
+        &self.bundle_pool
+    }
+
+    /// Returns the pool of private transactions.
+    pub(crate) const fn private_pool(&self) -> &PrivateTransactionPool {
+        &self.private_pool
+    }
+
+    /// Returns the pending state overrides.
+    pub(crate) const fn state_overrides(&self) -> &StateOverrides {
+        &self.state_overrides
+    }
+
     /// Returns stats about the size of the pool.
     pub(crate) fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -198,6 +225,8 @@ where
     }
     /// Sets the currently tracked block
     pub(crate) fn set_block_info(&self, info: BlockInfo) {
+        self.bundle_pool.set_current_block(info.last_seen_block_number);
+        self.private_pool.on_canonical_block(info.last_seen_block_number, &[]);
         self.pool.write().set_block_info(info)
     }
 
@@ -389,6 +418,9 @@ where
             new_tip, changed_accounts, mined_transactions, update_kind, ..
         } = update;
         self.validator.on_new_head_block(new_tip);
+        self.bundle_pool.on_canonical_block(new_tip.number, new_tip.timestamp);
+        self.private_pool.on_canonical_block(new_tip.number, &mined_transactions);
+        self.state_overrides.on_canonical_block(&mined_transactions);
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
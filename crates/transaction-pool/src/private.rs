@@ -0,0 +1,256 @@
+//! Pool for private transactions.
+//!
+//! Private transactions are submitted via `eth_sendPrivateTransaction` and are only meant to be
+//! included by the local payload builder. They are validated like regular transactions, see
+//! [`TransactionPool::add_private_transaction`](crate::TransactionPool::add_private_transaction),
+//! but kept separately from the regular pool, so they are never propagated to peers or reported by
+//! the `txpool_` namespace.
+
+use crate::{
+    config::{DEFAULT_MAX_PRIVATE_TRANSACTIONS, DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS},
+    error::PoolError,
+    pool::PENDING_TX_LISTENER_BUFFER_SIZE,
+};
+use alloy_primitives::TxHash;
+use parking_lot::RwLock;
+use reth_primitives::TransactionSignedEcRecovered;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::trace;
+
+/// A transaction that should be included by the local payload builder without being propagated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateTransaction {
+    /// The transaction.
+    pub transaction: TransactionSignedEcRecovered,
+    /// The highest block number the transaction can be included in.
+    pub max_block_number: u64,
+    /// Submission order of the transaction.
+    submission_id: u64,
+}
+
+/// Errors that can occur when adding a transaction to the [`PrivateTransactionPool`].
+#[derive(Debug, thiserror::Error)]
+pub enum PrivateTransactionPoolError {
+    /// Thrown if the transaction failed validation.
+    #[error(transparent)]
+    Invalid(#[from] PoolError),
+    /// Thrown if the transaction is a blob transaction.
+    ///
+    /// The sidecars of private transactions are not tracked, so they can't be included in a
+    /// payload.
+    #[error("blob transaction {0} can't be sent privately")]
+    BlobTransaction(TxHash),
+    /// Thrown if the max block number of the transaction has already been mined.
+    #[error("maxBlockNumber {max_block_number} is not after the current block {current}")]
+    Expired {
+        /// The highest block the transaction can be included in.
+        max_block_number: u64,
+        /// The current canonical block.
+        current: u64,
+    },
+    /// Thrown if the pool already holds the maximum number of transactions.
+    #[error("private transaction pool is full")]
+    PoolFull,
+}
+
+/// A pool of transactions that are only included by the local payload builder.
+///
+/// Transactions are kept until they are mined, cancelled, or their max block number has passed.
+///
+/// This type is cheap to clone, all clones share the same pool.
+#[derive(Debug, Clone)]
+pub struct PrivateTransactionPool {
+    inner: Arc<RwLock<PrivateTransactionPoolInner>>,
+}
+
+impl PrivateTransactionPool {
+    /// Creates a new pool that keeps at most `max_transactions` transactions.
+    pub fn new(max_transactions: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(PrivateTransactionPoolInner {
+                max_transactions,
+                current_block: 0,
+                next_submission_id: 0,
+                transactions: Default::default(),
+                listeners: Vec::new(),
+            })),
+        }
+    }
+
+    /// Adds a transaction to the pool and returns its hash.
+    ///
+    /// The transaction is expected to be validated already. It is dropped after
+    /// `max_block_number`. If no max block number is given, the transaction is kept for
+    /// [`DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS`] blocks.
+    pub fn add_transaction(
+        &self,
+        transaction: TransactionSignedEcRecovered,
+        max_block_number: Option<u64>,
+    ) -> Result<TxHash, PrivateTransactionPoolError> {
+        let hash = transaction.hash();
+        if transaction.is_eip4844() {
+            return Err(PrivateTransactionPoolError::BlobTransaction(hash))
+        }
+
+        let mut inner = self.inner.write();
+        let current = inner.current_block;
+        let max_block_number =
+            max_block_number.unwrap_or(current + DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS);
+        if max_block_number <= current {
+            return Err(PrivateTransactionPoolError::Expired { max_block_number, current })
+        }
+        if !inner.transactions.contains_key(&hash) &&
+            inner.transactions.len() >= inner.max_transactions
+        {
+            return Err(PrivateTransactionPoolError::PoolFull)
+        }
+
+        let submission_id = inner.next_submission_id;
+        inner.next_submission_id += 1;
+        inner
+            .transactions
+            .insert(hash, PrivateTransaction { transaction, max_block_number, submission_id });
+        inner
+            .listeners
+            .retain(|listener| !matches!(listener.try_send(hash), Err(TrySendError::Closed(_))));
+        Ok(hash)
+    }
+
+    /// Returns a receiver that yields the hashes of all transactions added to the pool.
+    pub fn new_transactions_listener(&self) -> mpsc::Receiver<TxHash> {
+        let (tx, rx) = mpsc::channel(PENDING_TX_LISTENER_BUFFER_SIZE);
+        self.inner.write().listeners.push(tx);
+        rx
+    }
+
+    /// Removes the transaction with the given hash.
+    ///
+    /// Returns `true` if the transaction was tracked.
+    pub fn cancel_transaction(&self, hash: &TxHash) -> bool {
+        let cancelled = self.inner.write().transactions.remove(hash).is_some();
+        if cancelled {
+            trace!(target: "txpool::private", %hash, "cancelled private transaction");
+        }
+        cancelled
+    }
+
+    /// Returns the transaction with the given hash, if it is tracked.
+    pub fn get(&self, hash: &TxHash) -> Option<PrivateTransaction> {
+        self.inner.read().transactions.get(hash).cloned()
+    }
+
+    /// Returns all transactions that can be included in a block with the given number, in the
+    /// order they were submitted.
+    pub fn transactions_for_block(&self, block_number: u64) -> Vec<TransactionSignedEcRecovered> {
+        let inner = self.inner.read();
+        let mut transactions = inner
+            .transactions
+            .values()
+            .filter(|tx| tx.max_block_number >= block_number)
+            .collect::<Vec<_>>();
+        transactions.sort_unstable_by_key(|tx| tx.submission_id);
+        transactions.into_iter().map(|tx| tx.transaction.clone()).collect()
+    }
+
+    /// Returns all transactions in the order they were submitted.
+    pub fn all_transactions(&self) -> Vec<PrivateTransaction> {
+        let inner = self.inner.read();
+        let mut transactions = inner.transactions.values().cloned().collect::<Vec<_>>();
+        transactions.sort_unstable_by_key(|tx| tx.submission_id);
+        transactions
+    }
+
+    /// Updates the pool with a new canonical block.
+    ///
+    /// This drops all mined transactions and all transactions that can no longer be included.
+    pub fn on_canonical_block(&self, block_number: u64, mined_transactions: &[TxHash]) {
+        let mut inner = self.inner.write();
+        inner.current_block = block_number;
+        for hash in mined_transactions {
+            inner.transactions.remove(hash);
+        }
+        inner.transactions.retain(|_, tx| tx.max_block_number > block_number);
+    }
+
+    /// Returns the number of transactions in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().transactions.len()
+    }
+
+    /// Returns `true` if the pool contains no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().transactions.is_empty()
+    }
+}
+
+impl Default for PrivateTransactionPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PRIVATE_TRANSACTIONS)
+    }
+}
+
+#[derive(Debug)]
+struct PrivateTransactionPoolInner {
+    /// Maximum number of tracked transactions.
+    max_transactions: usize,
+    /// The most recent canonical block the pool was updated with.
+    current_block: u64,
+    /// The submission id of the next transaction.
+    next_submission_id: u64,
+    /// All tracked transactions by their hash.
+    transactions: HashMap<TxHash, PrivateTransaction>,
+    /// Listeners for newly added transactions.
+    listeners: Vec<mpsc::Sender<TxHash>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransaction, PoolTransaction};
+    use assert_matches::assert_matches;
+
+    fn transaction(nonce: u64) -> TransactionSignedEcRecovered {
+        MockTransaction::eip1559().with_nonce(nonce).into_consensus()
+    }
+
+    #[test]
+    fn expires_and_cancels() {
+        let pool = PrivateTransactionPool::default();
+        let mut listener = pool.new_transactions_listener();
+        let first = pool.add_transaction(transaction(0), Some(1)).unwrap();
+        let second = pool.add_transaction(transaction(1), None).unwrap();
+        let third = pool.add_transaction(transaction(2), Some(5)).unwrap();
+
+        let hashes = |block| {
+            pool.transactions_for_block(block).iter().map(|tx| tx.hash()).collect::<Vec<_>>()
+        };
+        assert_eq!(hashes(1), vec![first, second, third]);
+        assert_eq!(listener.try_recv(), Ok(first));
+        assert_eq!(hashes(6), vec![second]);
+
+        pool.on_canonical_block(1, &[third]);
+        assert_eq!(hashes(2), vec![second]);
+        assert_matches!(
+            pool.add_transaction(transaction(3), Some(1)),
+            Err(PrivateTransactionPoolError::Expired { max_block_number: 1, current: 1 })
+        );
+
+        assert!(pool.cancel_transaction(&second));
+        assert!(!pool.cancel_transaction(&second));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn respects_capacity() {
+        let pool = PrivateTransactionPool::new(1);
+        let tx = transaction(0);
+        let hash = pool.add_transaction(tx.clone(), None).unwrap();
+        pool.add_transaction(tx, Some(10)).unwrap();
+        assert_eq!(pool.get(&hash).unwrap().max_block_number, 10);
+        assert_matches!(
+            pool.add_transaction(transaction(1), None),
+            Err(PrivateTransactionPoolError::PoolFull)
+        );
+    }
+}
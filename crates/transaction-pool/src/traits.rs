@@ -1,7 +1,10 @@
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::{InvalidPoolTransactionError, PoolResult},
+    overrides::StateOverrides,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    private::{PrivateTransactionPool, PrivateTransactionPoolError},
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -114,6 +117,24 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
 
+    /// Adds back transactions that were admitted into the pool before, e.g. the transactions of
+    /// reorged blocks or of a pool snapshot.
+    ///
+    /// The transactions are validated against the current state like new transactions, but are
+    /// not subject to node-local rate limits, see
+    /// [`TransactionValidator::validate_readmitted_transaction`](crate::TransactionValidator::validate_readmitted_transaction).
+    ///
+    /// By default, this is the same as [`Self::add_transactions`].
+    ///
+    /// Consumer: Pool maintenance
+    fn readmit_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send {
+        self.add_transactions(origin, transactions)
+    }
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -470,6 +491,58 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
+
+    /// Returns the [`BundlePool`] that holds bundles targeting upcoming blocks.
+    ///
+    /// By default, this is an empty pool that doesn't accept any bundles.
+    fn bundle_pool(&self) -> BundlePool {
+        BundlePool::new(0)
+    }
+
+    /// Returns the [`PrivateTransactionPool`] that holds transactions which are only included by
+    /// the local payload builder.
+    ///
+    /// By default, this is an empty pool that doesn't accept any transactions.
+    fn private_pool(&self) -> PrivateTransactionPool {
+        PrivateTransactionPool::new(0)
+    }
+
+    /// Validates the transaction as a [`TransactionOrigin::Private`] transaction and adds it to the
+    /// [`PrivateTransactionPool`].
+    ///
+    /// By default, private transactions are not supported and
+    /// [`PrivateTransactionPoolError::PoolFull`] is returned.
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction(
+        &self,
+        _transaction: Self::Transaction,
+        _max_block_number: Option<u64>,
+    ) -> impl Future<Output = Result<TxHash, PrivateTransactionPoolError>> + Send {
+        futures_util::future::ready(Err(PrivateTransactionPoolError::PoolFull))
+    }
+
+    /// Adds back a private transaction that was admitted into the [`PrivateTransactionPool`]
+    /// before, e.g. one of a pool snapshot.
+    ///
+    /// Like [`Self::readmit_transactions`], the transaction is not subject to node-local rate
+    /// limits. By default, this is the same as [`Self::add_private_transaction`].
+    ///
+    /// Consumer: Pool maintenance
+    fn readmit_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> impl Future<Output = Result<TxHash, PrivateTransactionPoolError>> + Send {
+        self.add_private_transaction(transaction, max_block_number)
+    }
+
+    /// Returns the [`StateOverrides`] that are applied by the local payload builder.
+    ///
+    /// By default, no overrides are applied.
+    fn state_overrides(&self) -> StateOverrides {
+        StateOverrides::default()
+    }
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -0,0 +1,518 @@
+//! Admission policies for the transaction pool.
+//!
+//! Admission policies are node-local rules that decide whether a transaction is accepted into the
+//! pool at all, e.g. address allow/deny lists or per-sender rate limits. They are checked by the
+//! [`EthTransactionValidator`](crate::validate::EthTransactionValidator) after the stateless
+//! consensus checks and before any state is read.
+//!
+//! Policies are composed with [`AdmissionPolicies`], which can be configured via
+//! [`PoolConfig::admission_policies`](crate::PoolConfig::admission_policies).
+
+use crate::{error::PoolTransactionError, traits::PoolTransaction, TransactionOrigin};
+use alloy_primitives::{Address, FixedBytes, TxHash};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A 4-byte function selector.
+pub type Selector = FixedBytes<4>;
+
+/// The fields of a transaction an [`AdmissionPolicy`] can inspect.
+#[derive(Debug, Clone, Copy)]
+pub struct AdmissionCandidate<'a> {
+    /// Where the transaction originated from.
+    pub origin: TransactionOrigin,
+    /// Hash of the transaction.
+    pub hash: &'a TxHash,
+    /// Recovered sender of the transaction.
+    pub sender: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// Calldata of the transaction.
+    pub input: &'a [u8],
+    /// The priority fee of the transaction, or the gas price for legacy transactions.
+    pub priority_fee: u128,
+    /// Encoded size of the transaction.
+    pub size: usize,
+    /// Whether the transaction was admitted into the pool before and is added back, e.g. after a
+    /// reorg or from a pool snapshot.
+    pub readmitted: bool,
+}
+
+impl<'a> AdmissionCandidate<'a> {
+    /// Creates the candidate for the given transaction.
+    pub fn new<T: PoolTransaction>(origin: TransactionOrigin, transaction: &'a T) -> Self {
+        Self {
+            origin,
+            hash: transaction.hash(),
+            sender: transaction.sender(),
+            to: transaction.to(),
+            input: transaction.input(),
+            priority_fee: transaction.priority_fee_or_price(),
+            size: transaction.size(),
+            readmitted: false,
+        }
+    }
+
+    /// Marks the candidate as readmitted, see [`Self::readmitted`].
+    pub const fn readmitted(mut self) -> Self {
+        self.readmitted = true;
+        self
+    }
+
+    /// Returns the function selector if this is a contract call with at least 4 bytes of input.
+    pub fn selector(&self) -> Option<Selector> {
+        self.to?;
+        self.input.get(..4).map(Selector::from_slice)
+    }
+}
+
+/// A rule that decides whether a transaction is admitted into the pool.
+///
+/// Policies may keep internal state (see [`SenderRateLimitPolicy`]), so they are shared behind an
+/// [`Arc`] and must synchronize internally.
+pub trait AdmissionPolicy: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction must not be admitted into the pool.
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError>;
+}
+
+/// An ordered chain of [`AdmissionPolicy`]s.
+///
+/// A transaction is admitted if every policy accepts it, policies are checked in the order they
+/// were added and the first rejection is returned.
+///
+/// This type is cheap to clone, all clones share the same policies.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionPolicies {
+    policies: Vec<Arc<dyn AdmissionPolicy>>,
+}
+
+impl AdmissionPolicies {
+    /// Creates an empty chain that admits all transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a policy to the chain.
+    pub fn with_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.push(policy);
+        self
+    }
+
+    /// Appends a policy to the chain.
+    pub fn push(&mut self, policy: impl AdmissionPolicy + 'static) {
+        self.policies.push(Arc::new(policy));
+    }
+
+    /// Appends an already shared policy to the chain.
+    pub fn push_shared(&mut self, policy: Arc<dyn AdmissionPolicy>) {
+        self.policies.push(policy);
+    }
+
+    /// Appends all policies of `other` to the chain.
+    pub fn append(&mut self, other: Self) {
+        self.policies.extend(other.policies);
+    }
+
+    /// Returns the number of policies in the chain.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Returns `true` if the chain has no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+}
+
+impl AdmissionPolicy for AdmissionPolicies {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        self.policies.iter().try_for_each(|policy| policy.check(candidate))
+    }
+}
+
+/// Restricts the senders and recipients of transactions.
+///
+/// If an allow list is set, only transactions from these senders are admitted. Transactions from
+/// or to an address on the deny list are always rejected.
+#[derive(Debug, Clone, Default)]
+pub struct AddressListPolicy {
+    allowed_senders: Option<HashSet<Address>>,
+    denied: HashSet<Address>,
+}
+
+impl AddressListPolicy {
+    /// Only admits transactions from the given senders.
+    pub fn allow_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.allowed_senders.get_or_insert_with(Default::default).extend(senders);
+        self
+    }
+
+    /// Rejects all transactions from or to the given addresses.
+    pub fn deny(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.denied.extend(addresses);
+        self
+    }
+}
+
+impl AdmissionPolicy for AddressListPolicy {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        if self.denied.contains(&candidate.sender) {
+            return Err(AdmissionError::SenderDenied(candidate.sender))
+        }
+        if let Some(to) = candidate.to.filter(|to| self.denied.contains(to)) {
+            return Err(AdmissionError::RecipientDenied(to))
+        }
+        if self.allowed_senders.as_ref().is_some_and(|allowed| !allowed.contains(&candidate.sender))
+        {
+            return Err(AdmissionError::SenderDenied(candidate.sender))
+        }
+        Ok(())
+    }
+}
+
+/// Limits how many transactions a single sender can submit within a time window.
+///
+/// Every checked transaction counts towards the limit, regardless of whether it is accepted by the
+/// remaining validation. [`TransactionOrigin::Local`] transactions and
+/// [readmitted](AdmissionCandidate::readmitted) transactions are exempt.
+#[derive(Debug)]
+pub struct SenderRateLimitPolicy {
+    max_transactions: usize,
+    window: Duration,
+    windows: Mutex<RateLimitWindows>,
+}
+
+impl SenderRateLimitPolicy {
+    /// Admits at most `max_transactions` per sender within every `window`.
+    pub fn new(max_transactions: usize, window: Duration) -> Self {
+        Self { max_transactions, window, windows: Default::default() }
+    }
+
+    fn check_at(&self, sender: Address, now: Instant) -> Result<(), AdmissionError> {
+        let mut windows = self.windows.lock();
+        windows.expire(now, self.window);
+
+        let count = windows.count(sender, now);
+        if *count >= self.max_transactions {
+            return Err(AdmissionError::RateLimited(sender))
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+impl AdmissionPolicy for SenderRateLimitPolicy {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        if candidate.origin.is_local() || candidate.readmitted {
+            return Ok(())
+        }
+        self.check_at(candidate.sender, Instant::now())
+    }
+}
+
+/// The current rate limit windows of all senders.
+#[derive(Debug, Default)]
+struct RateLimitWindows {
+    /// Transactions seen in the current window, by sender.
+    senders: HashMap<Address, usize>,
+    /// Start of the current window of every sender, in the order the windows started, so expired
+    /// windows can be removed without scanning all senders.
+    starts: VecDeque<(Instant, Address)>,
+}
+
+impl RateLimitWindows {
+    /// Removes all windows that started at least `window` before `now`.
+    fn expire(&mut self, now: Instant, window: Duration) {
+        while let Some(&(start, sender)) = self.starts.front() {
+            if now.saturating_duration_since(start) < window {
+                break
+            }
+            self.starts.pop_front();
+            self.senders.remove(&sender);
+        }
+    }
+
+    /// Returns the transaction count of the sender's window, starting a new window if there is
+    /// none.
+    fn count(&mut self, sender: Address, now: Instant) -> &mut usize {
+        self.senders.entry(sender).or_insert_with(|| {
+            self.starts.push_back((now, sender));
+            0
+        })
+    }
+}
+
+/// Enforces a minimum priority fee depending on the [`TransactionOrigin`].
+///
+/// For legacy transactions the gas price is used as the priority fee.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinPriorityFeePolicy {
+    local: Option<u128>,
+    external: Option<u128>,
+    private: Option<u128>,
+}
+
+impl MinPriorityFeePolicy {
+    /// Sets the minimum priority fee for transactions of the given origin.
+    pub const fn with_minimum(mut self, origin: TransactionOrigin, minimum: u128) -> Self {
+        match origin {
+            TransactionOrigin::Local => self.local = Some(minimum),
+            TransactionOrigin::External => self.external = Some(minimum),
+            TransactionOrigin::Private => self.private = Some(minimum),
+        }
+        self
+    }
+
+    /// Returns the minimum priority fee for transactions of the given origin.
+    pub const fn minimum(&self, origin: TransactionOrigin) -> Option<u128> {
+        match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        }
+    }
+}
+
+impl AdmissionPolicy for MinPriorityFeePolicy {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        match self.minimum(candidate.origin) {
+            Some(minimum) if candidate.priority_fee < minimum => {
+                Err(AdmissionError::PriorityFeeTooLow { fee: candidate.priority_fee, minimum })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects contract calls to denied function selectors.
+///
+/// Selectors can be denied for calls to any contract or only for calls to a specific contract.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorFilterPolicy {
+    denied: HashSet<(Option<Address>, Selector)>,
+}
+
+impl SelectorFilterPolicy {
+    /// Rejects calls to the selector on any contract.
+    pub fn deny(mut self, selector: Selector) -> Self {
+        self.denied.insert((None, selector));
+        self
+    }
+
+    /// Rejects calls to the selector on the given contract.
+    pub fn deny_for(mut self, contract: Address, selector: Selector) -> Self {
+        self.denied.insert((Some(contract), selector));
+        self
+    }
+}
+
+impl AdmissionPolicy for SelectorFilterPolicy {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        let Some(selector) = candidate.selector() else { return Ok(()) };
+        if self.denied.contains(&(None, selector)) ||
+            self.denied.contains(&(candidate.to, selector))
+        {
+            return Err(AdmissionError::SelectorDenied(selector))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with more calldata than the configured limit.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSizePolicy {
+    max_size: usize,
+}
+
+impl MaxCalldataSizePolicy {
+    /// Admits transactions with at most `max_size` bytes of calldata.
+    pub const fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl AdmissionPolicy for MaxCalldataSizePolicy {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionError> {
+        if candidate.input.len() > self.max_size {
+            return Err(AdmissionError::CalldataTooLarge {
+                size: candidate.input.len(),
+                limit: self.max_size,
+            })
+        }
+        Ok(())
+    }
+}
+
+/// Errors returned by an [`AdmissionPolicy`].
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionError {
+    /// The sender is not allowed to submit transactions.
+    #[error("sender {0} is not allowed")]
+    SenderDenied(Address),
+    /// Transactions to this recipient are not allowed.
+    #[error("recipient {0} is not allowed")]
+    RecipientDenied(Address),
+    /// The sender submitted too many transactions.
+    #[error("sender {0} exceeded its rate limit")]
+    RateLimited(Address),
+    /// The priority fee is below the minimum for the transaction's origin.
+    #[error("priority fee {fee} is below the minimum {minimum}")]
+    PriorityFeeTooLow {
+        /// Priority fee of the transaction.
+        fee: u128,
+        /// Minimum required priority fee.
+        minimum: u128,
+    },
+    /// Calls to this function selector are not allowed.
+    #[error("calls to selector {0} are not allowed")]
+    SelectorDenied(Selector),
+    /// The transaction has too much calldata.
+    #[error("calldata size {size} exceeds limit {limit}")]
+    CalldataTooLarge {
+        /// Calldata size of the transaction.
+        size: usize,
+        /// Maximum allowed calldata size.
+        limit: usize,
+    },
+    /// Any other error raised by a custom policy.
+    #[error(transparent)]
+    Other(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl PoolTransactionError for AdmissionError {
+    fn is_bad_transaction(&self) -> bool {
+        // admission policies are node-local, peers that relay these transactions are not at fault
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, fixed_bytes};
+
+    const SENDER: Address = address!("1000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("2000000000000000000000000000000000000002");
+    const TRANSFER: Selector = fixed_bytes!("a9059cbb");
+
+    const fn candidate(input: &[u8]) -> AdmissionCandidate<'_> {
+        AdmissionCandidate {
+            origin: TransactionOrigin::External,
+            hash: &TxHash::ZERO,
+            sender: SENDER,
+            to: Some(CONTRACT),
+            input,
+            priority_fee: 10,
+            size: 100,
+            readmitted: false,
+        }
+    }
+
+    #[test]
+    fn address_lists() {
+        let tx = candidate(&[]);
+        assert!(AddressListPolicy::default().check(&tx).is_ok());
+        assert!(AddressListPolicy::default().allow_senders([SENDER]).check(&tx).is_ok());
+        assert!(matches!(
+            AddressListPolicy::default().allow_senders([CONTRACT]).check(&tx),
+            Err(AdmissionError::SenderDenied(SENDER))
+        ));
+        assert!(matches!(
+            AddressListPolicy::default().deny([SENDER]).check(&tx),
+            Err(AdmissionError::SenderDenied(SENDER))
+        ));
+        assert!(matches!(
+            AddressListPolicy::default().deny([CONTRACT]).check(&tx),
+            Err(AdmissionError::RecipientDenied(CONTRACT))
+        ));
+    }
+
+    #[test]
+    fn sender_rate_limit() {
+        let policy = SenderRateLimitPolicy::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(policy.check_at(SENDER, now).is_ok());
+        assert!(policy.check_at(SENDER, now).is_ok());
+        assert!(matches!(policy.check_at(SENDER, now), Err(AdmissionError::RateLimited(SENDER))));
+        assert!(policy.check_at(CONTRACT, now).is_ok());
+
+        // a new window starts once the previous one expired
+        assert!(policy.check_at(SENDER, now + Duration::from_secs(10)).is_ok());
+        assert!(policy.check_at(SENDER, now + Duration::from_secs(10)).is_ok());
+        assert!(policy.check_at(SENDER, now + Duration::from_secs(19)).is_err());
+
+        // expired windows are removed
+        assert!(policy.check_at(CONTRACT, now + Duration::from_secs(20)).is_ok());
+        let windows = policy.windows.lock();
+        assert_eq!(windows.senders.len(), 1);
+        assert_eq!(windows.starts.len(), 1);
+        drop(windows);
+
+        // local and readmitted transactions are exempt
+        let local = AdmissionCandidate { origin: TransactionOrigin::Local, ..candidate(&[]) };
+        let readmitted = candidate(&[]).readmitted();
+        for _ in 0..3 {
+            assert!(policy.check(&local).is_ok());
+            assert!(policy.check(&readmitted).is_ok());
+        }
+    }
+
+    #[test]
+    fn min_priority_fee_by_origin() {
+        let policy = MinPriorityFeePolicy::default()
+            .with_minimum(TransactionOrigin::External, 11)
+            .with_minimum(TransactionOrigin::Private, 5);
+
+        assert!(matches!(
+            policy.check(&candidate(&[])),
+            Err(AdmissionError::PriorityFeeTooLow { fee: 10, minimum: 11 })
+        ));
+        for origin in [TransactionOrigin::Local, TransactionOrigin::Private] {
+            assert!(policy.check(&AdmissionCandidate { origin, ..candidate(&[]) }).is_ok());
+        }
+    }
+
+    #[test]
+    fn selector_filter() {
+        let input = [TRANSFER.as_slice(), &[0u8; 64]].concat();
+        let tx = candidate(&input);
+
+        assert!(matches!(
+            SelectorFilterPolicy::default().deny(TRANSFER).check(&tx),
+            Err(AdmissionError::SelectorDenied(TRANSFER))
+        ));
+        assert!(SelectorFilterPolicy::default().deny_for(SENDER, TRANSFER).check(&tx).is_ok());
+        assert!(SelectorFilterPolicy::default().deny_for(CONTRACT, TRANSFER).check(&tx).is_err());
+
+        // contract creations and short calldata have no selector
+        let create = AdmissionCandidate { to: None, ..tx };
+        assert!(SelectorFilterPolicy::default().deny(TRANSFER).check(&create).is_ok());
+        assert!(SelectorFilterPolicy::default()
+            .deny(TRANSFER)
+            .check(&candidate(&input[..3]))
+            .is_ok());
+    }
+
+    #[test]
+    fn chain_returns_first_rejection() {
+        let policies = AdmissionPolicies::new()
+            .with_policy(MaxCalldataSizePolicy::new(2))
+            .with_policy(AddressListPolicy::default().deny([SENDER]));
+        assert_eq!(policies.len(), 2);
+
+        assert!(matches!(
+            policies.check(&candidate(&[0; 3])),
+            Err(AdmissionError::CalldataTooLarge { size: 3, limit: 2 })
+        ));
+        assert!(matches!(
+            policies.check(&candidate(&[0; 2])),
+            Err(AdmissionError::SenderDenied(SENDER))
+        ));
+        assert!(AdmissionPolicies::new().check(&candidate(&[0; 3])).is_ok());
+    }
+}
@@ -7,7 +7,10 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        AdmissionCandidate, AdmissionPolicies, AdmissionPolicy, ValidTransaction, ValidationTask,
+        MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -63,7 +66,18 @@ where
         origin: TransactionOrigin,
         transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
-        self.inner.validate_one(origin, transaction)
+        self.inner.validate_one(origin, transaction, false)
+    }
+
+    /// Validates a single transaction that was admitted into the pool before.
+    ///
+    /// See also [`TransactionValidator::validate_readmitted_transaction`]
+    pub fn validate_one_readmitted(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        self.inner.validate_one(origin, transaction, true)
     }
 
     /// Validates all given transactions.
@@ -94,6 +108,14 @@ where
         self.validate_one(origin, transaction)
     }
 
+    async fn validate_readmitted_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_one_readmitted(origin, transaction)
+    }
+
     async fn validate_transactions(
         &self,
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
@@ -148,6 +170,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// Additional policies a transaction must pass to be admitted into the pool.
+    admission_policies: AdmissionPolicies,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -167,10 +191,14 @@ where
     Tx: EthPoolTransaction,
 {
     /// Validates a single transaction.
+    ///
+    /// Readmitted transactions were admitted into the pool before, see
+    /// [`AdmissionCandidate::readmitted`].
     fn validate_one(
         &self,
         origin: TransactionOrigin,
         mut transaction: Tx,
+        readmitted: bool,
     ) -> TransactionValidationOutcome<Tx> {
         // Checks for tx_type
         match transaction.tx_type() {
@@ -273,6 +301,18 @@ where
             )
         }
 
+        // Apply the configured admission policies
+        let mut candidate = AdmissionCandidate::new(origin, &transaction);
+        if readmitted {
+            candidate = candidate.readmitted();
+        }
+        if let Err(err) = self.admission_policies.check(&candidate) {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::Other(Box::new(err)),
+            )
+        }
+
         // Checks for chainid
         if let Some(chain_id) = transaction.chain_id() {
             if chain_id != self.chain_id() {
@@ -518,6 +558,8 @@ pub struct EthTransactionValidatorBuilder {
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
+    /// Additional policies a transaction must pass to be admitted into the pool.
+    admission_policies: AdmissionPolicies,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -538,6 +580,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
+            admission_policies: Default::default(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -570,6 +613,12 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the [`AdmissionPolicies`] transactions must pass to be admitted into the pool.
+    pub fn with_admission_policies(mut self, admission_policies: AdmissionPolicies) -> Self {
+        self.admission_policies = admission_policies;
+        self
+    }
+
     /// Set the Cancun fork.
     pub const fn set_cancun(mut self, cancun: bool) -> Self {
         self.cancun = cancun;
@@ -696,6 +745,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policies,
             ..
         } = self;
 
@@ -719,6 +769,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policies,
             _marker: Default::default(),
         };
 
@@ -832,7 +883,8 @@ mod tests {
     use super::*;
     use crate::{
         blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
+        validate::AddressListPolicy, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+        TransactionPool,
     };
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
@@ -919,4 +971,35 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[tokio::test]
+    async fn invalid_on_admission_policy() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_admission_policies(
+                AdmissionPolicies::new()
+                    .with_policy(AddressListPolicy::default().deny([transaction.sender()])),
+            )
+            .build(provider, blob_store);
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        let TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Other(err)) =
+            outcome
+        else {
+            panic!("expected admission error, got {outcome:?}")
+        };
+        assert_eq!(
+            err.to_string(),
+            format!("sender {} is not allowed", get_transaction().sender())
+        );
+        assert!(!err.is_bad_transaction());
+    }
 }
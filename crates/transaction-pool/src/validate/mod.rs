@@ -12,6 +12,7 @@ use futures_util::future::Either;
 use reth_primitives::{SealedBlock, TransactionSignedEcRecovered};
 use std::{fmt, future::Future, time::Instant};
 
+mod admission;
 mod constants;
 mod eth;
 mod task;
@@ -19,6 +20,13 @@ mod task;
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Node-local rules that decide whether a transaction is admitted into the pool.
+pub use admission::{
+    AddressListPolicy, AdmissionCandidate, AdmissionError, AdmissionPolicies, AdmissionPolicy,
+    MaxCalldataSizePolicy, MinPriorityFeePolicy, Selector, SelectorFilterPolicy,
+    SenderRateLimitPolicy,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -186,6 +194,22 @@ pub trait TransactionValidator: Send + Sync {
         transaction: Self::Transaction,
     ) -> impl Future<Output = TransactionValidationOutcome<Self::Transaction>> + Send;
 
+    /// Validates a transaction that was admitted into the pool before and is added back, e.g. the
+    /// transaction of a reorged block or of a pool snapshot.
+    ///
+    /// The transaction must be validated like any other transaction, but node-local policies that
+    /// limit how many new transactions are accepted don't apply, see
+    /// [`AdmissionCandidate::readmitted`].
+    ///
+    /// By default, this is the same as [`Self::validate_transaction`].
+    fn validate_readmitted_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> impl Future<Output = TransactionValidationOutcome<Self::Transaction>> + Send {
+        self.validate_transaction(origin, transaction)
+    }
+
     /// Validates a batch of transactions.
     ///
     /// Must return all outcomes for the given transactions in the same order.
@@ -227,6 +251,17 @@ where
         }
     }
 
+    async fn validate_readmitted_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        match self {
+            Self::Left(v) => v.validate_readmitted_transaction(origin, transaction).await,
+            Self::Right(v) => v.validate_readmitted_transaction(origin, transaction).await,
+        }
+    }
+
     async fn validate_transactions(
         &self,
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
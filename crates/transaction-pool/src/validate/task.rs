@@ -6,6 +6,7 @@ use crate::{
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
     TransactionValidator,
 };
+use alloy_primitives::TxHash;
 use futures_util::{lock::Mutex, StreamExt};
 use reth_chainspec::ChainSpec;
 use reth_primitives::SealedBlock;
@@ -163,27 +164,28 @@ impl<V> TransactionValidationTaskExecutor<V> {
     }
 }
 
-impl<V> TransactionValidator for TransactionValidationTaskExecutor<V>
+impl<V> TransactionValidationTaskExecutor<V>
 where
     V: TransactionValidator + Clone + 'static,
 {
-    type Transaction = <V as TransactionValidator>::Transaction;
-
-    async fn validate_transaction(
+    /// Runs the validation of the transaction with the given hash on the validation task.
+    async fn validate_on_task<F>(
         &self,
-        origin: TransactionOrigin,
-        transaction: Self::Transaction,
-    ) -> TransactionValidationOutcome<Self::Transaction> {
-        let hash = *transaction.hash();
+        hash: TxHash,
+        validation: impl FnOnce(V) -> F,
+    ) -> TransactionValidationOutcome<V::Transaction>
+    where
+        F: Future<Output = TransactionValidationOutcome<V::Transaction>> + Send + 'static,
+    {
         let (tx, rx) = oneshot::channel();
         {
             let res = {
                 let to_validation_task = self.to_validation_task.clone();
                 let to_validation_task = to_validation_task.lock().await;
-                let validator = self.validator.clone();
+                let validation = validation(self.validator.clone());
                 to_validation_task
                     .send(Box::pin(async move {
-                        let res = validator.validate_transaction(origin, transaction).await;
+                        let res = validation.await;
                         let _ = tx.send(res);
                     }))
                     .await
@@ -204,6 +206,35 @@ where
             ),
         }
     }
+}
+
+impl<V> TransactionValidator for TransactionValidationTaskExecutor<V>
+where
+    V: TransactionValidator + Clone + 'static,
+{
+    type Transaction = <V as TransactionValidator>::Transaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_on_task(*transaction.hash(), |validator| async move {
+            validator.validate_transaction(origin, transaction).await
+        })
+        .await
+    }
+
+    async fn validate_readmitted_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_on_task(*transaction.hash(), |validator| async move {
+            validator.validate_readmitted_transaction(origin, transaction).await
+        })
+        .await
+    }
 
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.validator.on_new_head_block(new_tip_block)
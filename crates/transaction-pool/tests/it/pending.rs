@@ -23,3 +23,22 @@ async fn txpool_new_pending_txs() {
     assert_matches!(added_result, Ok(hash) if hash == *transaction.transaction.get_hash());
     assert_matches!(best_txns.next(), Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_private_txs_are_not_pending() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.transaction.get_hash();
+
+    let mut pending = txpool.pending_transactions_listener();
+    let mut private = txpool.private_pool().new_transactions_listener();
+
+    let added_result = txpool.add_private_transaction(transaction.transaction.clone(), None).await;
+    assert_matches!(added_result, Ok(added) if added == hash);
+
+    assert_matches!(private.try_recv(), Ok(added) if added == hash);
+    assert!(pending.try_recv().is_err());
+    assert!(txpool.get(&hash).is_none());
+    assert_eq!(txpool.private_pool().len(), 1);
+}
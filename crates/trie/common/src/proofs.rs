@@ -1,6 +1,6 @@
 //! Merkle trie proofs.
 
-use crate::{Nibbles, TrieAccount};
+use crate::{Nibbles, TrieAccount, TrieMask};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use alloy_rlp::{encode_fixed_size, Decodable, EMPTY_STRING_CODE};
@@ -21,6 +21,12 @@ use std::collections::{hash_map, HashMap};
 pub struct MultiProof {
     /// State trie multiproof for requested accounts.
     pub account_subtree: ProofNodes,
+    /// The hash masks of the branch nodes in the account proof.
+    /// Only populated if branch node masks were requested during proof calculation.
+    pub branch_node_hash_masks: HashMap<Nibbles, TrieMask>,
+    /// The tree masks of the branch nodes in the account proof.
+    /// Only populated if branch node masks were requested during proof calculation.
+    pub branch_node_tree_masks: HashMap<Nibbles, TrieMask>,
     /// Storage trie multiproofs.
     pub storages: HashMap<B256, StorageMultiProof>,
 }
@@ -81,12 +87,17 @@ impl MultiProof {
     /// proofs.
     pub fn extend(&mut self, other: Self) {
         self.account_subtree.extend_from(other.account_subtree);
+        self.branch_node_hash_masks.extend(other.branch_node_hash_masks);
+        self.branch_node_tree_masks.extend(other.branch_node_tree_masks);
 
         for (hashed_address, storage) in other.storages {
             match self.storages.entry(hashed_address) {
                 hash_map::Entry::Occupied(mut entry) => {
                     debug_assert_eq!(entry.get().root, storage.root);
-                    entry.get_mut().subtree.extend_from(storage.subtree);
+                    let entry = entry.get_mut();
+                    entry.subtree.extend_from(storage.subtree);
+                    entry.branch_node_hash_masks.extend(storage.branch_node_hash_masks);
+                    entry.branch_node_tree_masks.extend(storage.branch_node_tree_masks);
                 }
                 hash_map::Entry::Vacant(entry) => {
                     entry.insert(storage);
@@ -103,6 +114,12 @@ pub struct StorageMultiProof {
     pub root: B256,
     /// Storage multiproof for requested slots.
     pub subtree: ProofNodes,
+    /// The hash masks of the branch nodes in the storage proof.
+    /// Only populated if branch node masks were requested during proof calculation.
+    pub branch_node_hash_masks: HashMap<Nibbles, TrieMask>,
+    /// The tree masks of the branch nodes in the storage proof.
+    /// Only populated if branch node masks were requested during proof calculation.
+    pub branch_node_tree_masks: HashMap<Nibbles, TrieMask>,
 }
 
 impl StorageMultiProof {
@@ -114,6 +131,8 @@ impl StorageMultiProof {
                 Nibbles::default(),
                 Bytes::from([EMPTY_STRING_CODE]),
             )]),
+            branch_node_hash_masks: HashMap::default(),
+            branch_node_tree_masks: HashMap::default(),
         }
     }
 
@@ -314,14 +333,30 @@ mod tests {
             Nibbles::from_nibbles(vec![0]),
             alloy_rlp::encode_fixed_size(&U256::from(42)).to_vec().into(),
         );
-        proof1.storages.insert(addr, StorageMultiProof { root, subtree: subtree1 });
+        proof1.storages.insert(
+            addr,
+            StorageMultiProof {
+                root,
+                subtree: subtree1,
+                branch_node_hash_masks: HashMap::default(),
+                branch_node_tree_masks: HashMap::default(),
+            },
+        );
 
         let mut subtree2 = ProofNodes::default();
         subtree2.insert(
             Nibbles::from_nibbles(vec![1]),
             alloy_rlp::encode_fixed_size(&U256::from(43)).to_vec().into(),
         );
-        proof2.storages.insert(addr, StorageMultiProof { root, subtree: subtree2 });
+        proof2.storages.insert(
+            addr,
+            StorageMultiProof {
+                root,
+                subtree: subtree2,
+                branch_node_hash_masks: HashMap::default(),
+                branch_node_tree_masks: HashMap::default(),
+            },
+        );
 
         proof1.extend(proof2);
 
@@ -31,3 +31,67 @@ impl Compact for StorageTrieEntry {
         (this, buf)
     }
 }
+
+/// Trie node as it was before a block changed it.
+///
+/// If [`TrieChangeSetsEntry::node`] is `None`, the node didn't exist before the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct TrieChangeSetsEntry {
+    /// The nibbles of the intermediate node
+    pub nibbles: StoredNibblesSubKey,
+    /// The node before the change, if it existed.
+    pub node: Option<BranchNodeCompact>,
+}
+
+// NOTE: The subkey is encoded first, like in `StorageTrieEntry`. A missing node is encoded as an
+// empty remainder, because an encoded node is never empty.
+impl Compact for TrieChangeSetsEntry {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let nibbles_len = self.nibbles.to_compact(buf);
+        let node_len = self.node.as_ref().map_or(0, |node| node.to_compact(buf));
+        nibbles_len + node_len
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (nibbles, buf) = StoredNibblesSubKey::from_compact(buf, 65);
+        if len == 65 {
+            return (Self { nibbles, node: None }, buf)
+        }
+
+        let (node, buf) = BranchNodeCompact::from_compact(buf, len - 65);
+        (Self { nibbles, node: Some(node) }, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Nibbles, TrieMask};
+    use alloy_primitives::B256;
+
+    #[test]
+    fn trie_changesets_entry_roundtrip() {
+        let nibbles = StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([0x1, 0x2, 0x3]));
+        let node = BranchNodeCompact::new(
+            TrieMask::new(0b11),
+            TrieMask::new(0),
+            TrieMask::new(0b10),
+            vec![B256::repeat_byte(0x11)],
+            None,
+        );
+
+        for entry in [
+            TrieChangeSetsEntry { nibbles: nibbles.clone(), node: Some(node) },
+            TrieChangeSetsEntry { nibbles, node: None },
+        ] {
+            let mut buf = Vec::new();
+            let len = entry.to_compact(&mut buf);
+            let (decoded, rest) = TrieChangeSetsEntry::from_compact(&buf, len);
+            assert_eq!(decoded, entry);
+            assert!(rest.is_empty());
+        }
+    }
+}
@@ -7,6 +7,7 @@ mod proof;
 mod state;
 mod storage;
 mod trie_cursor;
+mod updates;
 mod witness;
 
 pub use commitment::{MerklePatriciaTrie, StateCommitment};
@@ -20,4 +21,5 @@ pub use storage::{DatabaseHashedStorage, DatabaseStorageRoot};
 pub use trie_cursor::{
     DatabaseAccountTrieCursor, DatabaseStorageTrieCursor, DatabaseTrieCursorFactory,
 };
+pub use updates::DatabaseTrieUpdates;
 pub use witness::DatabaseTrieWitness;
@@ -0,0 +1,70 @@
+use alloy_primitives::{BlockNumber, B256};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO, models::BlockNumberHashedAddress, transaction::DbTx, DatabaseError,
+};
+use reth_trie::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    BranchNodeCompact, Nibbles, TrieChangeSetsEntry,
+};
+use std::collections::HashMap;
+
+/// Extends [`TrieUpdates`] with operations specific for working with a database transaction.
+pub trait DatabaseTrieUpdates<TX>: Sized {
+    /// Initializes [`TrieUpdates`] from trie changesets. Iterates over the trie changesets from the
+    /// specified block up to the current tip and collects the trie nodes as they were before the
+    /// first change.
+    ///
+    /// Applied on top of the current trie, the updates revert it to its state before the specified
+    /// block.
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError>;
+}
+
+impl<TX: DbTx> DatabaseTrieUpdates<TX> for TrieUpdates {
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
+        // Iterate over account trie changesets and record the node before the first change.
+        let mut account_nodes = HashMap::<Nibbles, Option<BranchNodeCompact>>::default();
+        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountsTrieChangeSets>()?;
+        for entry in account_changesets_cursor.walk_range(from..)? {
+            let (_, TrieChangeSetsEntry { nibbles, node }) = entry?;
+            account_nodes.entry(nibbles.0).or_insert(node);
+        }
+
+        // Iterate over storage trie changesets and record the node before the first change.
+        let mut storage_nodes =
+            HashMap::<B256, HashMap<Nibbles, Option<BranchNodeCompact>>>::default();
+        let mut storage_changesets_cursor = tx.cursor_read::<tables::StoragesTrieChangeSets>()?;
+        for entry in
+            storage_changesets_cursor.walk_range(BlockNumberHashedAddress((from, B256::ZERO))..)?
+        {
+            let (
+                BlockNumberHashedAddress((_, hashed_address)),
+                TrieChangeSetsEntry { nibbles, node },
+            ) = entry?;
+            storage_nodes.entry(hashed_address).or_default().entry(nibbles.0).or_insert(node);
+        }
+
+        let (updated_nodes, removed_nodes) = partition_nodes(account_nodes);
+        let storage_tries = storage_nodes.into_iter().map(|(hashed_address, nodes)| {
+            let (updated_nodes, removed_nodes) = partition_nodes(nodes);
+            (hashed_address, StorageTrieUpdates::from_nodes(false, updated_nodes, removed_nodes))
+        });
+
+        Ok(Self::from_nodes(updated_nodes, removed_nodes, storage_tries))
+    }
+}
+
+/// Splits the nodes into the ones that existed and the ones that did not.
+fn partition_nodes(
+    nodes: HashMap<Nibbles, Option<BranchNodeCompact>>,
+) -> (Vec<(Nibbles, BranchNodeCompact)>, Vec<Nibbles>) {
+    let mut updated_nodes = Vec::new();
+    let mut removed_nodes = Vec::new();
+    for (nibbles, node) in nodes {
+        match node {
+            Some(node) => updated_nodes.push((nibbles, node)),
+            None => removed_nodes.push(nibbles),
+        }
+    }
+    (updated_nodes, removed_nodes)
+}
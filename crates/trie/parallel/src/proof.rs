@@ -14,10 +14,12 @@ use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{TrieElement, TrieNodeIter},
     prefix_set::{PrefixSetMut, TriePrefixSetsMut},
-    proof::StorageProof,
+    proof::{branch_node_masks, StorageProof},
     trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
+    updates::TrieUpdatesSorted,
     walker::TrieWalker,
-    HashBuilder, MultiProof, Nibbles, TrieAccount, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    HashBuilder, HashedPostStateSorted, MultiProof, Nibbles, TrieAccount, TrieInput,
+    TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use reth_trie_common::proof::ProofRetainer;
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
@@ -27,13 +29,23 @@ use tracing::{debug, error};
 #[cfg(feature = "metrics")]
 use crate::metrics::ParallelStateRootMetrics;
 
-/// TODO:
+/// Parallel proof calculator.
+///
+/// Storage multiproofs of the changed accounts are generated in parallel, the account multiproof
+/// is then generated on the calling thread. The in-memory trie nodes and hashed state are shared,
+/// so the same overlay can be reused for many proofs without sorting it again.
 #[derive(Debug)]
 pub struct ParallelProof<Factory> {
     /// Consistent view of the database.
     view: ConsistentDbView<Factory>,
-    /// Trie input.
-    input: TrieInput,
+    /// The sorted collection of cached in-memory intermediate trie nodes.
+    nodes_sorted: Arc<TrieUpdatesSorted>,
+    /// The sorted in-memory overlay hashed state.
+    state_sorted: Arc<HashedPostStateSorted>,
+    /// The collection of prefix sets of the in-memory overlay.
+    prefix_sets: Arc<TriePrefixSetsMut>,
+    /// Flag indicating whether to include branch node masks in the proof.
+    collect_branch_node_masks: bool,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -41,14 +53,38 @@ pub struct ParallelProof<Factory> {
 
 impl<Factory> ParallelProof<Factory> {
     /// Create new state proof generator.
-    pub fn new(view: ConsistentDbView<Factory>, input: TrieInput) -> Self {
+    pub fn new(
+        view: ConsistentDbView<Factory>,
+        nodes_sorted: Arc<TrieUpdatesSorted>,
+        state_sorted: Arc<HashedPostStateSorted>,
+        prefix_sets: Arc<TriePrefixSetsMut>,
+    ) -> Self {
         Self {
             view,
-            input,
+            nodes_sorted,
+            state_sorted,
+            prefix_sets,
+            collect_branch_node_masks: false,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Create new state proof generator from the given trie input.
+    pub fn from_input(view: ConsistentDbView<Factory>, input: TrieInput) -> Self {
+        Self::new(
+            view,
+            Arc::new(input.nodes.into_sorted()),
+            Arc::new(input.state.into_sorted()),
+            Arc::new(input.prefix_sets),
+        )
+    }
+
+    /// Set the flag indicating whether to include branch node masks in the proof.
+    pub const fn with_branch_node_masks(mut self, branch_node_masks: bool) -> Self {
+        self.collect_branch_node_masks = branch_node_masks;
+        self
+    }
 }
 
 impl<Factory> ParallelProof<Factory>
@@ -62,11 +98,11 @@ where
     ) -> Result<MultiProof, ParallelStateRootError> {
         let mut tracker = ParallelTrieTracker::default();
 
-        let trie_nodes_sorted = Arc::new(self.input.nodes.into_sorted());
-        let hashed_state_sorted = Arc::new(self.input.state.into_sorted());
+        let trie_nodes_sorted = self.nodes_sorted;
+        let hashed_state_sorted = self.state_sorted;
 
         // Extend prefix sets with targets
-        let mut prefix_sets = self.input.prefix_sets.clone();
+        let mut prefix_sets = (*self.prefix_sets).clone();
         prefix_sets.extend(TriePrefixSetsMut {
             account_prefix_set: PrefixSetMut::from(targets.keys().copied().map(Nibbles::unpack)),
             storage_prefix_sets: targets
@@ -98,6 +134,7 @@ where
 
             let trie_nodes_sorted = trie_nodes_sorted.clone();
             let hashed_state_sorted = hashed_state_sorted.clone();
+            let collect_masks = self.collect_branch_node_masks;
 
             let (tx, rx) = std::sync::mpsc::sync_channel(1);
 
@@ -119,6 +156,7 @@ where
                         hashed_address,
                     )
                     .with_prefix_set_mut(PrefixSetMut::from(prefix_set.iter().cloned()))
+                    .with_branch_node_masks(collect_masks)
                     .storage_multiproof(target_slots)
                     .map_err(|e| {
                         ParallelStateRootError::StorageRoot(StorageRootError::Database(
@@ -152,7 +190,9 @@ where
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
         let retainer: ProofRetainer = targets.keys().map(Nibbles::unpack).collect();
-        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(retainer)
+            .with_updates(self.collect_branch_node_masks);
 
         let mut storages = HashMap::default();
         let mut account_rlp = Vec::with_capacity(TRIE_ACCOUNT_RLP_MAX_SIZE);
@@ -186,6 +226,7 @@ where
                                 hashed_address,
                             )
                             .with_prefix_set_mut(Default::default())
+                            .with_branch_node_masks(self.collect_branch_node_masks)
                             .storage_multiproof(
                                 targets.get(&hashed_address).cloned().unwrap_or_default(),
                             )
@@ -212,7 +253,10 @@ where
         #[cfg(feature = "metrics")]
         self.metrics.record_state_trie(tracker.finish());
 
-        Ok(MultiProof { account_subtree: hash_builder.take_proof_nodes(), storages })
+        let account_subtree = hash_builder.take_proof_nodes();
+        let (branch_node_hash_masks, branch_node_tree_masks) =
+            branch_node_masks(&mut hash_builder, &account_subtree);
+        Ok(MultiProof { account_subtree, branch_node_hash_masks, branch_node_tree_masks, storages })
     }
 }
 
@@ -290,7 +334,7 @@ mod tests {
         let hashed_cursor_factory = DatabaseHashedCursorFactory::new(provider_rw.tx_ref());
 
         assert_eq!(
-            ParallelProof::new(consistent_view, Default::default())
+            ParallelProof::from_input(consistent_view, Default::default())
                 .multiproof(targets.clone())
                 .unwrap(),
             Proof::new(trie_cursor_factory, hashed_cursor_factory).multiproof(targets).unwrap()
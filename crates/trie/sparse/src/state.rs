@@ -1,12 +1,19 @@
 use std::iter::Peekable;
 
-use crate::{SparseStateTrieError, SparseStateTrieResult, SparseTrie};
+use crate::{
+    RevealedSparseTrie, SparseStateTrieError, SparseStateTrieResult, SparseTrie, SparseTrieError,
+};
 use alloy_primitives::{
     map::{HashMap, HashSet},
     Bytes, B256,
 };
-use alloy_rlp::Decodable;
-use reth_trie::{Nibbles, TrieNode};
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives_traits::Account;
+use reth_trie::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    MultiProof, Nibbles, TrieAccount, TrieMask, TrieNode, EMPTY_ROOT_HASH,
+    TRIE_ACCOUNT_RLP_MAX_SIZE,
+};
 
 /// Sparse state trie representing lazy-loaded Ethereum state trie.
 #[derive(Default, Debug)]
@@ -17,6 +24,10 @@ pub struct SparseStateTrie {
     pub(crate) storages: HashMap<B256, SparseTrie>,
     /// Collection of revealed account and storage keys.
     pub(crate) revealed: HashMap<B256, HashSet<B256>>,
+    /// Accounts whose storage tries were wiped.
+    pub(crate) wiped_storages: HashSet<B256>,
+    /// Whether revealed tries retain updated and removed branch nodes.
+    pub(crate) retain_updates: bool,
 }
 
 impl SparseStateTrie {
@@ -25,6 +36,13 @@ impl SparseStateTrie {
         Self { state, ..Default::default() }
     }
 
+    /// Makes the revealed tries retain updated and removed branch nodes, see
+    /// [`Self::take_trie_updates`].
+    pub const fn with_updates(mut self, retain_updates: bool) -> Self {
+        self.retain_updates = retain_updates;
+        self
+    }
+
     /// Returns `true` if account was already revealed.
     pub fn is_account_revealed(&self, account: &B256) -> bool {
         self.revealed.contains_key(account)
@@ -51,7 +69,7 @@ impl SparseStateTrie {
         let Some(root_node) = self.validate_proof(&mut proof)? else { return Ok(()) };
 
         // Reveal root node if it wasn't already.
-        let trie = self.state.reveal_root(root_node)?;
+        let trie = self.state.reveal_root(root_node, self.retain_updates)?;
 
         // Reveal the remaining proof nodes.
         for (path, bytes) in proof {
@@ -82,7 +100,11 @@ impl SparseStateTrie {
         let Some(root_node) = self.validate_proof(&mut proof)? else { return Ok(()) };
 
         // Reveal root node if it wasn't already.
-        let trie = self.storages.entry(account).or_default().reveal_root(root_node)?;
+        let trie = self
+            .storages
+            .entry(account)
+            .or_default()
+            .reveal_root(root_node, self.retain_updates)?;
 
         // Reveal the remaining proof nodes.
         for (path, bytes) in proof {
@@ -96,6 +118,62 @@ impl SparseStateTrie {
         Ok(())
     }
 
+    /// Reveal the nodes of a multiproof generated for the given targets.
+    ///
+    /// Target accounts that have no storage multiproof don't exist in the account trie, so an
+    /// empty storage trie is revealed for them.
+    ///
+    /// The multiproof is expected to include branch node masks, otherwise the retained trie
+    /// updates might not match the database trie.
+    /// NOTE: This method does not extensively validate the proof.
+    pub fn reveal_multiproof(
+        &mut self,
+        targets: HashMap<B256, HashSet<B256>>,
+        multiproof: MultiProof,
+    ) -> SparseStateTrieResult<()> {
+        let mut account_nodes =
+            multiproof.account_subtree.into_nodes_sorted().into_iter().peekable();
+        if let Some(root_node) = self.validate_proof(&mut account_nodes)? {
+            let root_is_branch = matches!(root_node, TrieNode::Branch(_));
+            let trie = self.state.reveal_root(root_node, self.retain_updates)?;
+            reveal_proof_nodes(
+                trie,
+                root_is_branch,
+                account_nodes,
+                &multiproof.branch_node_hash_masks,
+                &multiproof.branch_node_tree_masks,
+            )?;
+        }
+
+        for (account, storage) in multiproof.storages {
+            let mut storage_nodes = storage.subtree.into_nodes_sorted().into_iter().peekable();
+            let Some(root_node) = self.validate_proof(&mut storage_nodes)? else { continue };
+            let root_is_branch = matches!(root_node, TrieNode::Branch(_));
+            let trie = self
+                .storages
+                .entry(account)
+                .or_default()
+                .reveal_root(root_node, self.retain_updates)?;
+            reveal_proof_nodes(
+                trie,
+                root_is_branch,
+                storage_nodes,
+                &storage.branch_node_hash_masks,
+                &storage.branch_node_tree_masks,
+            )?;
+        }
+
+        for (account, slots) in targets {
+            let storage = self.storages.entry(account).or_default();
+            if storage.is_blind() && !slots.is_empty() {
+                storage.reveal_root(TrieNode::EmptyRoot, self.retain_updates)?;
+            }
+            self.revealed.entry(account).or_default().extend(slots);
+        }
+
+        Ok(())
+    }
+
     /// Validates the root node of the proof and returns it if it exists and is valid.
     fn validate_proof<I: Iterator<Item = (Nibbles, Bytes)>>(
         &self,
@@ -124,6 +202,67 @@ impl SparseStateTrie {
         Ok(())
     }
 
+    /// Update the account leaf, using the current root of its storage trie.
+    ///
+    /// If the storage trie of the account was not revealed, the storage root of the existing
+    /// account leaf is kept.
+    pub fn update_account(&mut self, address: B256, account: Account) -> SparseStateTrieResult<()> {
+        let nibbles = Nibbles::unpack(address);
+        let storage_root = if let Some(storage_root) = self.storage_root(address) {
+            storage_root
+        } else if let Some(value) = self.state.get_leaf_value(&nibbles) {
+            TrieAccount::decode(&mut &value[..])?.storage_root
+        } else {
+            EMPTY_ROOT_HASH
+        };
+
+        let mut value = Vec::with_capacity(TRIE_ACCOUNT_RLP_MAX_SIZE);
+        TrieAccount::from((account, storage_root)).encode(&mut value);
+        self.update_leaf(nibbles, value)
+    }
+
+    /// Remove the account leaf if it exists.
+    pub fn remove_account(&mut self, address: B256) -> SparseStateTrieResult<()> {
+        let nibbles = Nibbles::unpack(address);
+        if self.state.get_leaf_value(&nibbles).is_some() {
+            self.state.remove_leaf(&nibbles)?;
+        }
+        Ok(())
+    }
+
+    /// Update the storage leaf of the account. The storage trie must be revealed.
+    pub fn update_storage_leaf(
+        &mut self,
+        address: B256,
+        slot: Nibbles,
+        value: Vec<u8>,
+    ) -> SparseStateTrieResult<()> {
+        self.storages.get_mut(&address).ok_or(SparseTrieError::Blind)?.update_leaf(slot, value)?;
+        Ok(())
+    }
+
+    /// Remove the storage leaf of the account if it exists.
+    pub fn remove_storage_leaf(
+        &mut self,
+        address: B256,
+        slot: &Nibbles,
+    ) -> SparseStateTrieResult<()> {
+        let Some(storage) = self.storages.get_mut(&address) else { return Ok(()) };
+        if storage.get_leaf_value(slot).is_some() {
+            storage.remove_leaf(slot)?;
+        }
+        Ok(())
+    }
+
+    /// Replace the storage trie of the account with an empty one.
+    pub fn wipe_storage(&mut self, address: B256) -> SparseStateTrieResult<()> {
+        let mut storage = SparseTrie::Blind;
+        storage.reveal_root(TrieNode::EmptyRoot, self.retain_updates)?;
+        self.storages.insert(address, storage);
+        self.wiped_storages.insert(address);
+        Ok(())
+    }
+
     /// Returns sparse trie root if the trie has been revealed.
     pub fn root(&mut self) -> Option<B256> {
         self.state.root()
@@ -133,6 +272,70 @@ impl SparseStateTrie {
     pub fn storage_root(&mut self, account: B256) -> Option<B256> {
         self.storages.get_mut(&account).and_then(|trie| trie.root())
     }
+
+    /// Takes the updated and removed branch nodes of all revealed tries.
+    ///
+    /// Returns `None` if the tries were not configured to retain updates, see
+    /// [`Self::with_updates`]. The roots should be calculated before taking the updates.
+    pub fn take_trie_updates(&mut self) -> Option<TrieUpdates> {
+        if !self.retain_updates {
+            return None
+        }
+
+        let account_updates =
+            self.state.as_revealed_mut().map(|trie| trie.take_updates()).unwrap_or_default();
+        let mut wiped_storages = std::mem::take(&mut self.wiped_storages);
+        let mut storage_tries = HashMap::<_, _>::default();
+        for (address, trie) in &mut self.storages {
+            let is_deleted = wiped_storages.remove(address);
+            let updates =
+                trie.as_revealed_mut().map(|trie| trie.take_updates()).unwrap_or_default();
+            let updates = StorageTrieUpdates::from_nodes(
+                is_deleted,
+                updates.updated_nodes,
+                updates.removed_nodes,
+            );
+            if !updates.is_empty() {
+                storage_tries.insert(*address, updates);
+            }
+        }
+        storage_tries.extend(
+            wiped_storages.into_iter().map(|address| (address, StorageTrieUpdates::deleted())),
+        );
+
+        Some(TrieUpdates::from_nodes(
+            account_updates.updated_nodes,
+            account_updates.removed_nodes,
+            storage_tries,
+        ))
+    }
+}
+
+/// Reveals the non-root proof nodes in the trie, together with the masks of the branch nodes.
+fn reveal_proof_nodes(
+    trie: &mut RevealedSparseTrie,
+    root_is_branch: bool,
+    nodes: impl IntoIterator<Item = (Nibbles, Bytes)>,
+    hash_masks: &std::collections::HashMap<Nibbles, TrieMask>,
+    tree_masks: &std::collections::HashMap<Nibbles, TrieMask>,
+) -> SparseStateTrieResult<()> {
+    let reveal_masks = |trie: &mut RevealedSparseTrie, path: Nibbles| {
+        let hash_mask = hash_masks.get(&path).copied().unwrap_or_default();
+        let tree_mask = tree_masks.get(&path).copied().unwrap_or_default();
+        trie.reveal_branch_node_masks(path, hash_mask, tree_mask);
+    };
+
+    if root_is_branch {
+        reveal_masks(trie, Nibbles::default());
+    }
+    for (path, bytes) in nodes {
+        let node = TrieNode::decode(&mut &bytes[..])?;
+        if matches!(node, TrieNode::Branch(_)) {
+            reveal_masks(trie, path.clone());
+        }
+        trie.reveal_node(path, node)?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -50,16 +50,33 @@ impl SparseTrie {
 
     /// Reveals the root node if the trie is blinded.
     ///
+    /// If `retain_updates` is set, the revealed trie keeps track of updated and removed branch
+    /// nodes, see [`RevealedSparseTrie::take_updates`].
+    ///
     /// # Returns
     ///
     /// Mutable reference to [`RevealedSparseTrie`].
-    pub fn reveal_root(&mut self, root: TrieNode) -> SparseTrieResult<&mut RevealedSparseTrie> {
+    pub fn reveal_root(
+        &mut self,
+        root: TrieNode,
+        retain_updates: bool,
+    ) -> SparseTrieResult<&mut RevealedSparseTrie> {
         if self.is_blind() {
-            *self = Self::Revealed(Box::new(RevealedSparseTrie::from_root(root)?))
+            *self = Self::Revealed(Box::new(
+                RevealedSparseTrie::from_root(root)?.with_updates(retain_updates),
+            ))
         }
         Ok(self.as_revealed_mut().unwrap())
     }
 
+    /// Returns the value of the leaf at the given full path if it was revealed.
+    pub fn get_leaf_value(&self, path: &Nibbles) -> Option<&Vec<u8>> {
+        match self {
+            Self::Blind => None,
+            Self::Revealed(revealed) => revealed.get_leaf_value(path),
+        }
+    }
+
     /// Update the leaf node.
     pub fn update_leaf(&mut self, path: Nibbles, value: Vec<u8>) -> SparseTrieResult<()> {
         let revealed = self.as_revealed_mut().ok_or(SparseTrieError::Blind)?;
@@ -67,6 +84,13 @@ impl SparseTrie {
         Ok(())
     }
 
+    /// Remove the leaf node.
+    pub fn remove_leaf(&mut self, path: &Nibbles) -> SparseTrieResult<()> {
+        let revealed = self.as_revealed_mut().ok_or(SparseTrieError::Blind)?;
+        revealed.remove_leaf(path)?;
+        Ok(())
+    }
+
     /// Calculates and returns the trie root if the trie has been revealed.
     pub fn root(&mut self) -> Option<B256> {
         Some(self.as_revealed_mut()?.root())
@@ -87,6 +111,10 @@ pub struct RevealedSparseTrie {
     nodes: HashMap<Nibbles, SparseNode>,
     /// All leaf values.
     values: HashMap<Nibbles, Vec<u8>>,
+    /// Hash masks of the revealed branch nodes, as stored in the database trie.
+    branch_node_hash_masks: HashMap<Nibbles, TrieMask>,
+    /// Tree masks of the revealed branch nodes, as stored in the database trie.
+    branch_node_tree_masks: HashMap<Nibbles, TrieMask>,
     /// Prefix set.
     prefix_set: PrefixSetMut,
     /// Reusable buffer for RLP encoding of nodes.
@@ -99,6 +127,8 @@ impl fmt::Debug for RevealedSparseTrie {
         f.debug_struct("RevealedSparseTrie")
             .field("nodes", &self.nodes)
             .field("values", &self.values)
+            .field("branch_node_hash_masks", &self.branch_node_hash_masks)
+            .field("branch_node_tree_masks", &self.branch_node_tree_masks)
             .field("prefix_set", &self.prefix_set)
             .field("rlp_buf", &hex::encode(&self.rlp_buf))
             .field("updates", &self.updates)
@@ -111,6 +141,8 @@ impl Default for RevealedSparseTrie {
         Self {
             nodes: HashMap::from_iter([(Nibbles::default(), SparseNode::Empty)]),
             values: HashMap::default(),
+            branch_node_hash_masks: HashMap::default(),
+            branch_node_tree_masks: HashMap::default(),
             prefix_set: PrefixSetMut::default(),
             rlp_buf: Vec::new(),
             updates: None,
@@ -124,6 +156,8 @@ impl RevealedSparseTrie {
         let mut this = Self {
             nodes: HashMap::default(),
             values: HashMap::default(),
+            branch_node_hash_masks: HashMap::default(),
+            branch_node_tree_masks: HashMap::default(),
             prefix_set: PrefixSetMut::default(),
             rlp_buf: Vec::new(),
             updates: None,
@@ -150,6 +184,26 @@ impl RevealedSparseTrie {
         self.updates.take().unwrap_or_default()
     }
 
+    /// Returns the value of the leaf at the given full path if it was revealed.
+    pub fn get_leaf_value(&self, path: &Nibbles) -> Option<&Vec<u8>> {
+        self.values.get(path)
+    }
+
+    /// Sets the hash and tree masks of the revealed branch node at the given path, as they are
+    /// stored in the database trie.
+    ///
+    /// The masks are used to set the flags of blinded children when the branch node is updated.
+    /// Without them, blinded children are assumed to be stored in the database trie.
+    pub fn reveal_branch_node_masks(
+        &mut self,
+        path: Nibbles,
+        hash_mask: TrieMask,
+        tree_mask: TrieMask,
+    ) {
+        self.branch_node_hash_masks.insert(path.clone(), hash_mask);
+        self.branch_node_tree_masks.insert(path, tree_mask);
+    }
+
     /// Reveal the trie node only if it was not known already.
     pub fn reveal_node(&mut self, path: Nibbles, node: TrieNode) -> SparseTrieResult<()> {
         // TODO: revise all inserts to not overwrite existing entries
@@ -732,6 +786,9 @@ impl RevealedSparseTrie {
                         continue
                     }
 
+                    let hash_mask = self.branch_node_hash_masks.get(&path).copied();
+                    let tree_mask = self.branch_node_tree_masks.get(&path).copied();
+
                     buffers.branch_child_buf.clear();
                     // Walk children in a reverse order from `f` to `0`, so we pop the `0` first
                     // from the stack and keep walking in the sorted order.
@@ -759,11 +816,23 @@ impl RevealedSparseTrie {
 
                             // Update the masks only if we need to retain trie updates
                             if self.updates.is_some() {
+                                let nibble = child_path.last().unwrap();
+                                let revealed_masks = hash_mask
+                                    .zip(tree_mask)
+                                    .filter(|_| node_type.is_hash())
+                                    .map(|(hash_mask, tree_mask)| {
+                                        (hash_mask.is_bit_set(nibble), tree_mask.is_bit_set(nibble))
+                                    });
+
                                 // Set the trie mask
                                 if node_type.store_in_db_trie() {
                                     // A branch or an extension node explicitly set the
                                     // `store_in_db_trie` flag
                                     tree_mask_values.push(true);
+                                } else if let Some((_, in_db_trie)) = revealed_masks {
+                                    // A blinded node inherits the flag from the revealed masks of
+                                    // its parent branch node
+                                    tree_mask_values.push(in_db_trie);
                                 } else {
                                     // Set the flag according to whether a child node was
                                     // pre-calculated
@@ -775,7 +844,10 @@ impl RevealedSparseTrie {
                                 // Set the hash mask. If a child node has a hash value AND is a
                                 // branch node, set the hash mask
                                 // and save the hash.
-                                let hash = child.as_hash().filter(|_| node_type.is_branch());
+                                let hash = child.as_hash().filter(|_| {
+                                    node_type.is_branch() ||
+                                        revealed_masks.is_some_and(|(is_hashed, _)| is_hashed)
+                                });
                                 hash_mask_values.push(hash.is_some());
                                 if let Some(hash) = hash {
                                     hashes.push(hash);
@@ -822,7 +894,10 @@ impl RevealedSparseTrie {
                         // Store in DB trie if there are either any children that are stored in the
                         // DB trie, or any children represent hashed values
                         let store_in_db_trie = !tree_mask.is_empty() || !hash_mask.is_empty();
-                        if store_in_db_trie {
+                        // Only branches on the path of a changed leaf are reported as updated.
+                        // Revealed branches are recomputed because their hashes are not known
+                        // upfront, but they are already present in the database.
+                        if store_in_db_trie && prefix_set_contains(&path) {
                             hashes.reverse();
                             let branch_node = BranchNodeCompact::new(
                                 *state_mask,
@@ -881,6 +956,10 @@ impl SparseNodeType {
         matches!(self, Self::Branch { .. })
     }
 
+    const fn is_hash(&self) -> bool {
+        matches!(self, Self::Hash)
+    }
+
     const fn store_in_db_trie(&self) -> bool {
         match *self {
             Self::Extension { store_in_db_trie } | Self::Branch { store_in_db_trie } => {
@@ -998,8 +1077,8 @@ impl RlpNodeBuffers {
 /// The aggregation of sparse trie updates.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SparseTrieUpdates {
-    updated_nodes: HashMap<Nibbles, BranchNodeCompact>,
-    removed_nodes: HashSet<Nibbles>,
+    pub(crate) updated_nodes: HashMap<Nibbles, BranchNodeCompact>,
+    pub(crate) removed_nodes: HashSet<Nibbles>,
 }
 
 #[cfg(test)]
@@ -1,4 +1,8 @@
-use crate::{prefix_set::TriePrefixSetsMut, updates::TrieUpdates, HashedPostState};
+use crate::{
+    prefix_set::TriePrefixSetsMut,
+    updates::{TrieUpdates, TrieUpdatesSorted},
+    HashedPostState, HashedPostStateSorted,
+};
 
 /// Inputs for trie-related computations.
 #[derive(Default, Debug)]
@@ -73,3 +77,25 @@ impl TrieInput {
         self.state.extend_ref(state);
     }
 }
+
+/// Sorted [`TrieInput`], that can be used as an overlay of the database for many computations
+/// without sorting the nodes and state again.
+#[derive(Default, Debug, Clone)]
+pub struct TrieInputSorted {
+    /// The sorted cached in-memory intermediate trie nodes.
+    pub nodes: TrieUpdatesSorted,
+    /// The sorted in-memory overlay hashed state.
+    pub state: HashedPostStateSorted,
+    /// The collection of prefix sets for the computation, see [`TrieInput::prefix_sets`].
+    pub prefix_sets: TriePrefixSetsMut,
+}
+
+impl From<TrieInput> for TrieInputSorted {
+    fn from(input: TrieInput) -> Self {
+        Self {
+            nodes: input.nodes.into_sorted(),
+            state: input.state.into_sorted(),
+            prefix_sets: input.prefix_sets,
+        }
+    }
+}
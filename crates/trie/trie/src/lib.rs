@@ -42,7 +42,7 @@ pub use state::*;
 
 /// Input for trie computation.
 mod input;
-pub use input::TrieInput;
+pub use input::{TrieInput, TrieInputSorted};
 
 /// Merkle proof generation.
 pub mod proof;
@@ -4,7 +4,7 @@ use crate::{
     prefix_set::{PrefixSetMut, TriePrefixSetsMut},
     trie_cursor::TrieCursorFactory,
     walker::TrieWalker,
-    HashBuilder, Nibbles, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    HashBuilder, Nibbles, TrieMask, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use alloy_primitives::{
     keccak256,
@@ -14,7 +14,8 @@ use alloy_primitives::{
 use alloy_rlp::{BufMut, Encodable};
 use reth_execution_errors::trie::StateProofError;
 use reth_trie_common::{
-    proof::ProofRetainer, AccountProof, MultiProof, StorageMultiProof, TrieAccount,
+    proof::{ProofNodes, ProofRetainer},
+    AccountProof, MultiProof, StorageMultiProof, TrieAccount,
 };
 
 /// A struct for generating merkle proofs.
@@ -30,6 +31,8 @@ pub struct Proof<T, H> {
     hashed_cursor_factory: H,
     /// A set of prefix sets that have changes.
     prefix_sets: TriePrefixSetsMut,
+    /// Flag indicating whether to include branch node masks in the proof.
+    collect_branch_node_masks: bool,
 }
 
 impl<T, H> Proof<T, H> {
@@ -39,6 +42,7 @@ impl<T, H> Proof<T, H> {
             trie_cursor_factory: t,
             hashed_cursor_factory: h,
             prefix_sets: TriePrefixSetsMut::default(),
+            collect_branch_node_masks: false,
         }
     }
 
@@ -48,6 +52,7 @@ impl<T, H> Proof<T, H> {
             trie_cursor_factory,
             hashed_cursor_factory: self.hashed_cursor_factory,
             prefix_sets: self.prefix_sets,
+            collect_branch_node_masks: self.collect_branch_node_masks,
         }
     }
 
@@ -57,6 +62,7 @@ impl<T, H> Proof<T, H> {
             trie_cursor_factory: self.trie_cursor_factory,
             hashed_cursor_factory,
             prefix_sets: self.prefix_sets,
+            collect_branch_node_masks: self.collect_branch_node_masks,
         }
     }
 
@@ -65,6 +71,12 @@ impl<T, H> Proof<T, H> {
         self.prefix_sets = prefix_sets;
         self
     }
+
+    /// Set the flag indicating whether to include branch node masks in the proof.
+    pub const fn with_branch_node_masks(mut self, branch_node_masks: bool) -> Self {
+        self.collect_branch_node_masks = branch_node_masks;
+        self
+    }
 }
 
 impl<T, H> Proof<T, H>
@@ -101,7 +113,9 @@ where
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
         let retainer = targets.keys().map(Nibbles::unpack).collect();
-        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(retainer)
+            .with_updates(self.collect_branch_node_masks);
 
         let mut storages = HashMap::default();
         let mut account_rlp = Vec::with_capacity(TRIE_ACCOUNT_RLP_MAX_SIZE);
@@ -124,6 +138,7 @@ where
                         hashed_address,
                     )
                     .with_prefix_set_mut(storage_prefix_set)
+                    .with_branch_node_masks(self.collect_branch_node_masks)
                     .storage_multiproof(proof_targets)?;
 
                     // Encode account
@@ -137,7 +152,10 @@ where
             }
         }
         let _ = hash_builder.root();
-        Ok(MultiProof { account_subtree: hash_builder.take_proof_nodes(), storages })
+        let account_subtree = hash_builder.take_proof_nodes();
+        let (branch_node_hash_masks, branch_node_tree_masks) =
+            branch_node_masks(&mut hash_builder, &account_subtree);
+        Ok(MultiProof { account_subtree, branch_node_hash_masks, branch_node_tree_masks, storages })
     }
 }
 
@@ -152,6 +170,8 @@ pub struct StorageProof<T, H> {
     hashed_address: B256,
     /// The set of storage slot prefixes that have changed.
     prefix_set: PrefixSetMut,
+    /// Flag indicating whether to include branch node masks in the proof.
+    collect_branch_node_masks: bool,
 }
 
 impl<T, H> StorageProof<T, H> {
@@ -167,6 +187,7 @@ impl<T, H> StorageProof<T, H> {
             hashed_cursor_factory: h,
             hashed_address,
             prefix_set: PrefixSetMut::default(),
+            collect_branch_node_masks: false,
         }
     }
 
@@ -177,6 +198,7 @@ impl<T, H> StorageProof<T, H> {
             hashed_cursor_factory: self.hashed_cursor_factory,
             hashed_address: self.hashed_address,
             prefix_set: self.prefix_set,
+            collect_branch_node_masks: self.collect_branch_node_masks,
         }
     }
 
@@ -187,6 +209,7 @@ impl<T, H> StorageProof<T, H> {
             hashed_cursor_factory,
             hashed_address: self.hashed_address,
             prefix_set: self.prefix_set,
+            collect_branch_node_masks: self.collect_branch_node_masks,
         }
     }
 
@@ -195,6 +218,12 @@ impl<T, H> StorageProof<T, H> {
         self.prefix_set = prefix_set;
         self
     }
+
+    /// Set the flag indicating whether to include branch node masks in the proof.
+    pub const fn with_branch_node_masks(mut self, branch_node_masks: bool) -> Self {
+        self.collect_branch_node_masks = branch_node_masks;
+        self
+    }
 }
 
 impl<T, H> StorageProof<T, H>
@@ -231,7 +260,9 @@ where
         let walker = TrieWalker::new(trie_cursor, self.prefix_set.freeze());
 
         let retainer = ProofRetainer::from_iter(target_nibbles);
-        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(retainer)
+            .with_updates(self.collect_branch_node_masks);
         let mut storage_node_iter = TrieNodeIter::new(walker, hashed_storage_cursor);
         while let Some(node) = storage_node_iter.try_next()? {
             match node {
@@ -248,6 +279,28 @@ where
         }
 
         let root = hash_builder.root();
-        Ok(StorageMultiProof { root, subtree: hash_builder.take_proof_nodes() })
+        let subtree = hash_builder.take_proof_nodes();
+        let (branch_node_hash_masks, branch_node_tree_masks) =
+            branch_node_masks(&mut hash_builder, &subtree);
+        Ok(StorageMultiProof { root, subtree, branch_node_hash_masks, branch_node_tree_masks })
+    }
+}
+
+/// Takes the branch nodes retained by the hash builder and returns the hash and tree masks of the
+/// ones that are part of the proof.
+///
+/// The hash builder only retains the branch nodes if it was configured with updates.
+pub fn branch_node_masks(
+    hash_builder: &mut HashBuilder,
+    proof_nodes: &ProofNodes,
+) -> (std::collections::HashMap<Nibbles, TrieMask>, std::collections::HashMap<Nibbles, TrieMask>) {
+    let mut hash_masks = std::collections::HashMap::default();
+    let mut tree_masks = std::collections::HashMap::default();
+    for (path, node) in hash_builder.updated_branch_nodes.take().unwrap_or_default() {
+        if proof_nodes.contains_key(&path) {
+            hash_masks.insert(path.clone(), node.hash_mask);
+            tree_masks.insert(path, node.tree_mask);
+        }
     }
+    (hash_masks, tree_masks)
 }
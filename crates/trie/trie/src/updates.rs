@@ -64,6 +64,20 @@ impl TrieUpdates {
         self.account_nodes.retain(|nibbles, _| !other.removed_nodes.contains(nibbles));
     }
 
+    /// Creates trie updates from the updated and removed account trie nodes and the updates of
+    /// the storage tries.
+    pub fn from_nodes(
+        account_nodes: impl IntoIterator<Item = (Nibbles, BranchNodeCompact)>,
+        removed_nodes: impl IntoIterator<Item = Nibbles>,
+        storage_tries: impl IntoIterator<Item = (B256, StorageTrieUpdates)>,
+    ) -> Self {
+        Self {
+            account_nodes: exclude_empty_from_pair(account_nodes).collect(),
+            removed_nodes: exclude_empty(removed_nodes).collect(),
+            storage_tries: storage_tries.into_iter().collect(),
+        }
+    }
+
     /// Insert storage updates for a given hashed address.
     pub fn insert_storage_updates(
         &mut self,
@@ -131,6 +145,19 @@ impl StorageTrieUpdates {
 }
 
 impl StorageTrieUpdates {
+    /// Creates storage trie updates from the updated and removed storage trie nodes.
+    pub fn from_nodes(
+        is_deleted: bool,
+        storage_nodes: impl IntoIterator<Item = (Nibbles, BranchNodeCompact)>,
+        removed_nodes: impl IntoIterator<Item = Nibbles>,
+    ) -> Self {
+        Self {
+            is_deleted,
+            storage_nodes: exclude_empty_from_pair(storage_nodes).collect(),
+            removed_nodes: exclude_empty(removed_nodes).collect(),
+        }
+    }
+
     /// Returns empty storage trie updates with `deleted` set to `true`.
     pub fn deleted() -> Self {
         Self {